@@ -18,7 +18,7 @@ mod bytes;
 mod parse;
 mod serialize;
 
-use crate::{Identifier, U32};
+use crate::{Identifier, Literal, U32};
 use snarkvm_console_network::prelude::*;
 
 /// A helper type for accessing an entry in a register, struct, array, or record.
@@ -43,8 +43,8 @@ impl<N: Network> Access<N> {
         let j_value = match self {
             // Prints the access member, i.e. `.foo`
             Self::Member(identifier) => identifier.to_json(),
-            // Prints the access index, i.e. `[0u32]`
-            Self::Index(index) => json!(index),
+            // Prints the access index as a typed literal, i.e. `0u32`
+            Self::Index(index) => Literal::U32(*index).to_json(),
         };
 
         json!({
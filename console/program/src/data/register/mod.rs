@@ -56,6 +56,7 @@ impl<N: Network> Register<N> {
         json!({
             "type": "Register",
             "vtype": j_vtype,
+            "locator": self.locator(),
             "value": j_value,
         })
     }
@@ -89,7 +90,7 @@ impl<N: Network> PartialOrd for Register<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Identifier, U32};
+    use crate::{Identifier, Literal, U32};
     use snarkvm_console_network::Testnet3;
 
     type CurrentNetwork = Testnet3;
@@ -304,4 +305,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_register_to_json() -> Result<()> {
+        let register = Register::<CurrentNetwork>::Locator(2);
+        assert_eq!(register.to_json(), json!({ "type": "Register", "vtype": "Locator", "locator": 2, "value": 2 }));
+
+        // The access segments are attached to the base register, in order, i.e. `r1[2u32].amount`.
+        let register = Register::<CurrentNetwork>::Access(1, vec![
+            Access::Index(U32::new(2)),
+            Access::Member(Identifier::from_str("amount")?),
+        ]);
+        let j_register = register.to_json();
+        assert_eq!(j_register["vtype"], "Access");
+        assert_eq!(j_register["locator"], 1);
+        assert_eq!(j_register["value"][0]["vtype"], "Index");
+        assert_eq!(j_register["value"][0]["value"], Literal::U32(U32::new(2)).to_json());
+        assert_eq!(j_register["value"][0]["value"]["value"]["integer"], "2");
+        assert_eq!(j_register["value"][1], json!({ "type": "Access", "vtype": "Member", "value": "amount" }));
+
+        Ok(())
+    }
 }
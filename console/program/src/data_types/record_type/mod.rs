@@ -13,7 +13,6 @@
 // limitations under the License.
 
 use serde_json::json;
-use std::collections::HashMap;
 
 mod entry_type;
 pub use entry_type::EntryType;
@@ -44,7 +43,7 @@ pub struct RecordType<N: Network> {
 /// ** Vanguard JSON serialization helper ** ///
 impl<N: Network> RecordType<N> {
     pub fn to_json(&self) -> serde_json::Value {
-        let mut j_entries: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_entries = serde_json::Map::new();
         for (key, val) in &self.entries {
             j_entries.insert(key.to_key(), val.to_json());
         }
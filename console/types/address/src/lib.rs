@@ -49,6 +49,7 @@ impl<E: Environment> Address<E> {
         json!({
             "type": "Address",
             "address": self.address.to_json(),
+            "bech32": self.to_string(),
         })
     }
 }
@@ -117,4 +118,20 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_to_json() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new address.
+            let expected = Address::<CurrentEnvironment>::rand(&mut rng);
+
+            // Check that both the bech32 and the field encodings recover the address.
+            let j_address = expected.to_json();
+            assert_eq!(expected, Address::from_str(j_address["bech32"].as_str().unwrap())?);
+            assert_eq!(j_address["address"], expected.to_group().to_json());
+        }
+        Ok(())
+    }
 }
@@ -69,10 +69,21 @@ pub struct Integer<E: Environment, I: IntegerType> {
 /// ** Vanguard JSON serialization helper ** ///
 impl<E: Environment, I: IntegerType> Integer<E, I> {
     pub fn to_json(&self) -> serde_json::Value {
+        // Prints the two's complement bits of the integer as hex, most significant nibble first, i.e. `-1i8` as `0xff`.
+        let bits = self.integer.to_bits_le();
+        let j_hex: String = bits
+            .chunks(4)
+            .rev()
+            .map(|nibble| format!("{:x}", nibble.iter().rev().fold(0u8, |acc, bit| (acc << 1) | *bit as u8)))
+            .collect();
+
         json!({
             "type": "Integer",
             "vtype": I::type_name(), // FIXME
             "integer": format!("{}", self.integer), // FIXME
+            "hex": format!("0x{j_hex}"),
+            "bits": I::BITS,
+            "signed": I::is_signed(),
         })
     }
 }
@@ -116,3 +127,30 @@ impl<E: Environment, I: IntegerType> Deref for Integer<E, I> {
         &self.integer
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network_environment::Console;
+
+    type CurrentEnvironment = Console;
+
+    #[test]
+    fn test_to_json() {
+        let integer = I8::<CurrentEnvironment>::new(-1);
+        assert_eq!(
+            integer.to_json(),
+            json!({ "type": "Integer", "vtype": "i8", "integer": "-1", "hex": "0xff", "bits": 8, "signed": true })
+        );
+
+        // The values beyond the range of JSON numbers are exact in both the decimal and hex forms.
+        let integer = U128::<CurrentEnvironment>::new(u128::MAX - 1);
+        let j_integer = integer.to_json();
+        assert_eq!(j_integer["integer"], (u128::MAX - 1).to_string());
+        assert_eq!(j_integer["hex"], format!("0x{}e", "f".repeat(31)));
+        assert_eq!((j_integer["bits"].as_u64(), j_integer["signed"].as_bool()), (Some(128), Some(false)));
+
+        let integer = I16::<CurrentEnvironment>::new(i16::MIN);
+        assert_eq!(integer.to_json()["hex"], "0x8000");
+    }
+}
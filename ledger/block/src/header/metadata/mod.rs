@@ -23,6 +23,7 @@ mod verify;
 use console::{network::prelude::*, types::Field};
 
 use core::marker::PhantomData;
+use serde_json::json;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Metadata<N: Network> {
@@ -50,6 +51,25 @@ pub struct Metadata<N: Network> {
     _phantom: PhantomData<N>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Metadata<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "type": "Metadata",
+            "network": self.network,
+            "round": self.round,
+            "height": self.height,
+            "cumulative_weight": format!("{}", self.cumulative_weight),
+            "cumulative_proof_target": format!("{}", self.cumulative_proof_target),
+            "coinbase_target": self.coinbase_target,
+            "proof_target": self.proof_target,
+            "last_coinbase_target": self.last_coinbase_target,
+            "last_coinbase_timestamp": self.last_coinbase_timestamp,
+            "timestamp": self.timestamp,
+        })
+    }
+}
+
 impl<N: Network> Metadata<N> {
     /// Initializes a new metadata with the given inputs.
     #[allow(clippy::too_many_arguments)]
@@ -30,6 +30,8 @@ use console::{
 };
 use synthesizer_program::FinalizeOperation;
 
+use serde_json::json;
+
 /// The header for the block contains metadata that uniquely identifies the block.
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Header<N: Network> {
@@ -49,6 +51,22 @@ pub struct Header<N: Network> {
     metadata: Metadata<N>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Header<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "type": "Header",
+            "previous_state_root": format!("{}", self.previous_state_root),
+            "transactions_root": self.transactions_root.to_json(),
+            "finalize_root": self.finalize_root.to_json(),
+            "ratifications_root": self.ratifications_root.to_json(),
+            "solutions_root": self.solutions_root.to_json(),
+            "subdag_root": self.subdag_root.to_json(),
+            "metadata": self.metadata.to_json(),
+        })
+    }
+}
+
 impl<N: Network> Header<N> {
     /// Initializes a new block header with the given inputs.
     pub fn from(
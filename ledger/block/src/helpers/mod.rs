@@ -12,5 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod report;
+pub use report::*;
+
 mod target;
 pub use target::*;
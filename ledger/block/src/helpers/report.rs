@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::prelude::{bail, Result};
+
+use serde_json::json;
+
+/// The outcome of a single step of block verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockCheckStatus {
+    /// The check was performed and succeeded.
+    Passed,
+    /// The check was performed and failed with the given reason.
+    Failed(String),
+    /// The check was not performed, as a check it depends on failed.
+    Skipped,
+}
+
+/// A named step of block verification and its outcome.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockCheck {
+    /// The name of the check.
+    name: &'static str,
+    /// The outcome of the check.
+    status: BlockCheckStatus,
+}
+
+impl BlockCheck {
+    /// Returns the name of the check.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the outcome of the check.
+    pub const fn status(&self) -> &BlockCheckStatus {
+        &self.status
+    }
+
+    /// Returns `true` if the check was performed and succeeded.
+    pub fn passed(&self) -> bool {
+        self.status == BlockCheckStatus::Passed
+    }
+}
+
+/// The structured result of verifying a block, in the order the checks are performed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockVerificationReport {
+    /// The checks performed on the block.
+    checks: Vec<BlockCheck>,
+}
+
+impl BlockVerificationReport {
+    /// Records the outcome of the check with the given name, returning its value on success.
+    pub(crate) fn record<T>(&mut self, name: &'static str, outcome: Result<T>) -> Option<T> {
+        match outcome {
+            Ok(value) => {
+                self.checks.push(BlockCheck { name, status: BlockCheckStatus::Passed });
+                Some(value)
+            }
+            Err(error) => {
+                self.checks.push(BlockCheck { name, status: BlockCheckStatus::Failed(error.to_string()) });
+                None
+            }
+        }
+    }
+
+    /// Records the check with the given name as skipped.
+    pub(crate) fn skip(&mut self, name: &'static str) {
+        self.checks.push(BlockCheck { name, status: BlockCheckStatus::Skipped });
+    }
+
+    /// Returns the checks, in the order they were performed.
+    pub fn checks(&self) -> &[BlockCheck] {
+        &self.checks
+    }
+
+    /// Returns `true` if every check was performed and succeeded.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(BlockCheck::passed)
+    }
+
+    /// Returns the first failed check, if any.
+    pub fn first_failure(&self) -> Option<&BlockCheck> {
+        self.checks.iter().find(|check| matches!(check.status, BlockCheckStatus::Failed(..)))
+    }
+
+    /// Returns `Ok(())` if the report passed, and otherwise the reason of the first failed check.
+    pub fn into_result(self) -> Result<()> {
+        match self.first_failure() {
+            Some(BlockCheck { status: BlockCheckStatus::Failed(reason), .. }) => bail!("{reason}"),
+            _ => match self.passed() {
+                true => Ok(()),
+                false => bail!("Block verification did not complete"),
+            },
+        }
+    }
+}
+
+/// ** Vanguard JSON serialization helper ** ///
+impl BlockCheck {
+    pub fn to_json(&self) -> serde_json::Value {
+        let (status, reason) = match &self.status {
+            BlockCheckStatus::Passed => ("passed", serde_json::Value::Null),
+            BlockCheckStatus::Failed(reason) => ("failed", json!(reason)),
+            BlockCheckStatus::Skipped => ("skipped", serde_json::Value::Null),
+        };
+
+        json!({
+            "name": self.name,
+            "status": status,
+            "reason": reason,
+        })
+    }
+}
+
+/// ** Vanguard JSON serialization helper ** ///
+impl BlockVerificationReport {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_checks = Vec::new();
+        for val in &self.checks {
+            j_checks.push(val.to_json());
+        }
+
+        json!({
+            "type": "BlockVerificationReport",
+            "passed": self.passed(),
+            "checks": j_checks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::prelude::anyhow;
+
+    #[test]
+    fn test_report_records_checks_in_order() {
+        let mut report = BlockVerificationReport::default();
+        assert_eq!(report.record("hash", Ok(1u32)), Some(1));
+        assert_eq!(report.record::<u32>("authority", Err(anyhow!("Invalid round"))), None);
+        report.skip("header");
+
+        let names = report.checks().iter().map(BlockCheck::name).collect::<Vec<_>>();
+        assert_eq!(names, ["hash", "authority", "header"]);
+        assert!(!report.passed());
+        assert_eq!(report.first_failure().unwrap().name(), "authority");
+
+        let json = report.to_json();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["checks"][1]["status"], "failed");
+        assert_eq!(json["checks"][1]["reason"], "Invalid round");
+        assert_eq!(json["checks"][2]["status"], "skipped");
+
+        assert_eq!(report.into_result().unwrap_err().to_string(), "Invalid round");
+    }
+
+    #[test]
+    fn test_report_passes_when_all_checks_pass() {
+        let mut report = BlockVerificationReport::default();
+        report.record("hash", Ok(()));
+        report.record("transactions", Ok(()));
+        assert!(report.passed());
+        assert!(report.first_failure().is_none());
+        assert!(report.into_result().is_ok());
+    }
+}
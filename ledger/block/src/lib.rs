@@ -56,6 +56,8 @@ use ledger_committee::Committee;
 use ledger_narwhal_subdag::Subdag;
 use ledger_narwhal_transmission_id::TransmissionID;
 
+use serde_json::json;
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Block<N: Network> {
     /// The hash of this block.
@@ -76,6 +78,33 @@ pub struct Block<N: Network> {
     aborted_transaction_ids: Vec<N::TransactionID>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Block<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_solutions = match &self.solutions {
+            Some(solutions) => json!(format!("{}", solutions)),
+            None => serde_json::Value::Null,
+        };
+
+        let mut j_aborted_transaction_ids = Vec::new();
+        for val in &self.aborted_transaction_ids {
+            j_aborted_transaction_ids.push(json!(format!("{}", val)));
+        }
+
+        json!({
+            "type": "Block",
+            "block_hash": format!("{}", self.block_hash),
+            "previous_hash": format!("{}", self.previous_hash),
+            "header": self.header.to_json(),
+            "authority": format!("{}", self.authority),
+            "ratifications": self.ratifications.to_json(),
+            "solutions": j_solutions,
+            "transactions": self.transactions.to_json(),
+            "aborted_transaction_ids": j_aborted_transaction_ids,
+        })
+    }
+}
+
 impl<N: Network> Block<N> {
     /// Initializes a new beacon block from the given previous block hash, block header,
     /// ratifications, solutions, transactions, and aborted transaction IDs.
@@ -24,6 +24,7 @@ use console::{
 };
 
 use indexmap::IndexMap;
+use serde_json::json;
 
 #[cfg(not(feature = "serial"))]
 use rayon::prelude::*;
@@ -34,6 +35,18 @@ pub struct Ratifications<N: Network> {
     ratifications: IndexMap<N::RatificationID, Ratify<N>>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Ratifications<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_ratifications = Vec::new();
+        for val in self.ratifications.values() {
+            j_ratifications.push(val.to_json());
+        }
+
+        json!(j_ratifications)
+    }
+}
+
 impl<N: Network> Ratifications<N> {
     /// Initializes from an iterator of ratifications.
     pub fn try_from_iter<T: IntoIterator<Item = Ratify<N>>>(iter: T) -> Result<Self> {
@@ -20,6 +20,7 @@ use console::{network::prelude::*, types::Address};
 use ledger_committee::Committee;
 
 use indexmap::IndexMap;
+use serde_json::json;
 
 type Variant = u8;
 /// A helper type to represent the public balances.
@@ -35,6 +36,38 @@ pub enum Ratify<N: Network> {
     PuzzleReward(u64),
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Ratify<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_vtype = match self {
+            Self::Genesis(..) => "Genesis",
+            Self::BlockReward(..) => "BlockReward",
+            Self::PuzzleReward(..) => "PuzzleReward",
+        };
+
+        let j_value = match self {
+            Self::Genesis(committee, public_balances) => {
+                let mut j_public_balances = serde_json::Map::new();
+                for (address, amount) in public_balances {
+                    j_public_balances.insert(format!("{}", address), json!(amount));
+                }
+                json!({
+                    "committee": format!("{}", committee),
+                    "public_balances": j_public_balances,
+                })
+            }
+            Self::BlockReward(amount) => json!(amount),
+            Self::PuzzleReward(amount) => json!(amount),
+        };
+
+        json!({
+            "type": "Ratify",
+            "vtype": j_vtype,
+            "value": j_value,
+        })
+    }
+}
+
 impl<N: Network> Ratify<N> {
     /// Returns the ratification ID.
     pub fn to_id(&self) -> Result<N::RatificationID> {
@@ -27,6 +27,8 @@ use console::{
 use synthesizer_program::Program;
 use synthesizer_snark::{Certificate, VerifyingKey};
 
+use serde_json::json;
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Deployment<N: Network> {
     /// The edition.
@@ -37,6 +39,27 @@ pub struct Deployment<N: Network> {
     verifying_keys: Vec<(Identifier<N>, (VerifyingKey<N>, Certificate<N>))>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Deployment<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_verifying_keys = Vec::new();
+        for (function_name, (verifying_key, certificate)) in &self.verifying_keys {
+            j_verifying_keys.push(json!({
+                "function_name": function_name.to_json(),
+                "verifying_key": format!("{}", verifying_key),
+                "certificate": format!("{}", certificate),
+            }));
+        }
+
+        json!({
+            "type": "Deployment",
+            "edition": self.edition,
+            "program": self.program.to_json(),
+            "verifying_keys": j_verifying_keys,
+        })
+    }
+}
+
 impl<N: Network> Deployment<N> {
     /// Initializes a new deployment.
     pub fn new(
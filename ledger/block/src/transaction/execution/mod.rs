@@ -21,6 +21,7 @@ use console::{account::Field, network::prelude::*, program::ProgramID};
 use synthesizer_snark::Proof;
 
 use indexmap::IndexMap;
+use serde_json::json;
 
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Execution<N: Network> {
@@ -32,6 +33,28 @@ pub struct Execution<N: Network> {
     proof: Option<Proof<N>>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Execution<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_transitions = Vec::new();
+        for val in self.transitions.values() {
+            j_transitions.push(val.to_json());
+        }
+
+        let j_proof = match &self.proof {
+            Some(proof) => json!(format!("{}", proof)),
+            None => serde_json::Value::Null,
+        };
+
+        json!({
+            "type": "Execution",
+            "transitions": j_transitions,
+            "global_state_root": format!("{}", self.global_state_root),
+            "proof": j_proof,
+        })
+    }
+}
+
 impl<N: Network> Execution<N> {
     /// Initialize a new `Execution` instance.
     pub fn new() -> Self {
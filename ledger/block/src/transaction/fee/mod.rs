@@ -24,6 +24,8 @@ use console::{
 };
 use synthesizer_snark::Proof;
 
+use serde_json::json;
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Fee<N: Network> {
     /// The transition.
@@ -34,6 +36,23 @@ pub struct Fee<N: Network> {
     proof: Option<Proof<N>>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Fee<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_proof = match &self.proof {
+            Some(proof) => json!(format!("{}", proof)),
+            None => serde_json::Value::Null,
+        };
+
+        json!({
+            "type": "Fee",
+            "transition": self.transition.to_json(),
+            "global_state_root": format!("{}", self.global_state_root),
+            "proof": j_proof,
+        })
+    }
+}
+
 impl<N: Network> Fee<N> {
     /// Initializes a new `Fee` instance with the given transition, global state root, and proof.
     pub fn from(transition: Transition<N>, global_state_root: N::StateRoot, proof: Option<Proof<N>>) -> Result<Self> {
@@ -33,6 +33,8 @@ use console::{
     types::{Field, Group, U64},
 };
 
+use serde_json::json;
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum Transaction<N: Network> {
     /// The deploy transaction publishes an Aleo program to the network.
@@ -43,6 +45,42 @@ pub enum Transaction<N: Network> {
     Fee(N::TransactionID, Fee<N>),
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Transaction<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_vtype = match self {
+            Self::Deploy(..) => "Deploy",
+            Self::Execute(..) => "Execute",
+            Self::Fee(..) => "Fee",
+        };
+
+        let j_value = match self {
+            Self::Deploy(_, owner, deployment, fee) => json!({
+                "owner": format!("{}", owner),
+                "deployment": deployment.to_json(),
+                "fee": fee.to_json(),
+            }),
+            Self::Execute(_, execution, fee) => json!({
+                "execution": execution.to_json(),
+                "fee": match fee {
+                    Some(fee) => fee.to_json(),
+                    None => serde_json::Value::Null,
+                },
+            }),
+            Self::Fee(_, fee) => json!({
+                "fee": fee.to_json(),
+            }),
+        };
+
+        json!({
+            "type": "Transaction",
+            "vtype": j_vtype,
+            "id": format!("{}", self.id()),
+            "value": j_value,
+        })
+    }
+}
+
 impl<N: Network> Transaction<N> {
     /// Initializes a new deployment transaction.
     pub fn from_deployment(owner: ProgramOwner<N>, deployment: Deployment<N>, fee: Fee<N>) -> Result<Self> {
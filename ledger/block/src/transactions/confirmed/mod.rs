@@ -20,6 +20,8 @@ use crate::{rejected::Rejected, Transaction};
 use console::{network::prelude::*, types::Field};
 use synthesizer_program::FinalizeOperation;
 
+use serde_json::json;
+
 pub type NumFinalizeSize = u16;
 
 /// The confirmed transaction.
@@ -180,6 +182,37 @@ impl<N: Network> ConfirmedTransaction<N> {
     }
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> ConfirmedTransaction<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_vtype = match self {
+            Self::AcceptedDeploy(..) => "AcceptedDeploy",
+            Self::AcceptedExecute(..) => "AcceptedExecute",
+            Self::RejectedDeploy(..) => "RejectedDeploy",
+            Self::RejectedExecute(..) => "RejectedExecute",
+        };
+
+        let mut j_finalize = Vec::new();
+        for val in self.finalize_operations() {
+            j_finalize.push(val.to_json());
+        }
+
+        let j_rejected = match self.to_rejected() {
+            Some(rejected) => rejected.to_json(),
+            None => serde_json::Value::Null,
+        };
+
+        json!({
+            "type": "ConfirmedTransaction",
+            "vtype": j_vtype,
+            "index": self.index(),
+            "transaction": self.transaction().to_json(),
+            "rejected": j_rejected,
+            "finalize": j_finalize,
+        })
+    }
+}
+
 impl<N: Network> ConfirmedTransaction<N> {
     /// Returns 'true' if the confirmed transaction is accepted.
     pub const fn is_accepted(&self) -> bool {
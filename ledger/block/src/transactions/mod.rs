@@ -40,6 +40,7 @@ use console::{
 use synthesizer_program::FinalizeOperation;
 
 use indexmap::IndexMap;
+use serde_json::json;
 
 #[cfg(not(feature = "serial"))]
 use rayon::prelude::*;
@@ -50,6 +51,18 @@ pub struct Transactions<N: Network> {
     transactions: IndexMap<N::TransactionID, ConfirmedTransaction<N>>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Transactions<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_transactions = Vec::new();
+        for val in self.transactions.values() {
+            j_transactions.push(val.to_json());
+        }
+
+        json!(j_transactions)
+    }
+}
+
 impl<N: Network> Transactions<N> {
     /// Initializes from a given transactions list.
     pub fn from(transactions: &[ConfirmedTransaction<N>]) -> Self {
@@ -27,6 +27,30 @@ pub enum Rejected<N: Network> {
     Execution(Execution<N>),
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Rejected<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_vtype = match self {
+            Self::Deployment(..) => "Deployment",
+            Self::Execution(..) => "Execution",
+        };
+
+        let j_value = match self {
+            Self::Deployment(program_owner, deployment) => json!({
+                "program_owner": format!("{}", program_owner),
+                "deployment": deployment.to_json(),
+            }),
+            Self::Execution(execution) => execution.to_json(),
+        };
+
+        json!({
+            "type": "Rejected",
+            "vtype": j_vtype,
+            "value": j_value,
+        })
+    }
+}
+
 impl<N: Network> Rejected<N> {
     /// Initializes a rejected deployment.
     pub fn new_deployment(program_owner: ProgramOwner<N>, deployment: Deployment<N>) -> Self {
@@ -22,6 +22,8 @@ use console::{
     types::Field,
 };
 
+use serde_json::json;
+
 type Variant = u8;
 
 /// The transition input.
@@ -39,6 +41,41 @@ pub enum Input<N: Network> {
     ExternalRecord(Field<N>),
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Input<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_vtype = match self {
+            Self::Constant(..) => "Constant",
+            Self::Public(..) => "Public",
+            Self::Private(..) => "Private",
+            Self::Record(..) => "Record",
+            Self::ExternalRecord(..) => "ExternalRecord",
+        };
+
+        let j_value = match self {
+            Self::Constant(_, plaintext) | Self::Public(_, plaintext) => match plaintext {
+                Some(plaintext) => json!(format!("{}", plaintext)),
+                None => serde_json::Value::Null,
+            },
+            Self::Private(_, ciphertext) => match ciphertext {
+                Some(ciphertext) => json!(format!("{}", ciphertext)),
+                None => serde_json::Value::Null,
+            },
+            Self::Record(_, tag) => json!({
+                "tag": tag.to_json(),
+            }),
+            Self::ExternalRecord(_) => serde_json::Value::Null,
+        };
+
+        json!({
+            "type": "Input",
+            "vtype": j_vtype,
+            "id": self.id().to_json(),
+            "value": j_value,
+        })
+    }
+}
+
 impl<N: Network> Input<N> {
     /// Returns the variant of the input.
     pub const fn variant(&self) -> Variant {
@@ -45,6 +45,8 @@ use console::{
     types::{Field, Group},
 };
 
+use serde_json::json;
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct Transition<N: Network> {
     /// The transition ID.
@@ -63,6 +65,32 @@ pub struct Transition<N: Network> {
     tcm: Field<N>,
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Transition<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_inputs = Vec::new();
+        for val in &self.inputs {
+            j_inputs.push(val.to_json());
+        }
+
+        let mut j_outputs = Vec::new();
+        for val in &self.outputs {
+            j_outputs.push(val.to_json());
+        }
+
+        json!({
+            "type": "Transition",
+            "id": format!("{}", self.id),
+            "program_id": self.program_id.to_json(),
+            "function_name": self.function_name.to_json(),
+            "inputs": j_inputs,
+            "outputs": j_outputs,
+            "tpk": self.tpk.to_json(),
+            "tcm": self.tcm.to_json(),
+        })
+    }
+}
+
 impl<N: Network> Transition<N> {
     /// Initializes a new transition.
     #[allow(clippy::too_many_arguments)]
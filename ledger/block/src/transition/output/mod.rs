@@ -22,6 +22,8 @@ use console::{
     types::{Field, Group},
 };
 
+use serde_json::json;
+
 type Variant = u8;
 
 /// The transition output.
@@ -41,6 +43,50 @@ pub enum Output<N: Network> {
     Future(Field<N>, Option<Future<N>>),
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> Output<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_vtype = match self {
+            Self::Constant(..) => "Constant",
+            Self::Public(..) => "Public",
+            Self::Private(..) => "Private",
+            Self::Record(..) => "Record",
+            Self::ExternalRecord(..) => "ExternalRecord",
+            Self::Future(..) => "Future",
+        };
+
+        let j_value = match self {
+            Self::Constant(_, plaintext) | Self::Public(_, plaintext) => match plaintext {
+                Some(plaintext) => json!(format!("{}", plaintext)),
+                None => serde_json::Value::Null,
+            },
+            Self::Private(_, ciphertext) => match ciphertext {
+                Some(ciphertext) => json!(format!("{}", ciphertext)),
+                None => serde_json::Value::Null,
+            },
+            Self::Record(_, checksum, record) => json!({
+                "checksum": checksum.to_json(),
+                "record": match record {
+                    Some(record) => json!(format!("{}", record)),
+                    None => serde_json::Value::Null,
+                },
+            }),
+            Self::ExternalRecord(_) => serde_json::Value::Null,
+            Self::Future(_, future) => match future {
+                Some(future) => json!(format!("{}", future)),
+                None => serde_json::Value::Null,
+            },
+        };
+
+        json!({
+            "type": "Output",
+            "vtype": j_vtype,
+            "id": self.id().to_json(),
+            "value": j_value,
+        })
+    }
+}
+
 impl<N: Network> Output<N> {
     /// Returns the variant of the output.
     pub const fn variant(&self) -> Variant {
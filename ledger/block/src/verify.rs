@@ -94,6 +94,117 @@ impl<N: Network> Block<N> {
             current_timestamp,
         )
     }
+
+    /// Verifies the block, returning the outcome of each check in the order they are performed.
+    /// Checks that depend on the values of a failed check are recorded as skipped.
+    ///
+    /// Unlike `verify`, which stops at the first failure, every check is run, so this is meant for diagnostics
+    /// (i.e. monitoring or exports), not for validating blocks as they are received.
+    pub fn verify_report(
+        &self,
+        previous_block: &Block<N>,
+        current_state_root: N::StateRoot,
+        current_committee: &Committee<N>,
+        current_puzzle: &CoinbasePuzzle<N>,
+        current_epoch_challenge: &EpochChallenge<N>,
+        current_timestamp: i64,
+        ratified_finalize_operations: Vec<FinalizeOperation<N>>,
+    ) -> BlockVerificationReport {
+        let mut report = BlockVerificationReport::default();
+
+        // Ensure the block hash is correct.
+        report.record("hash", self.verify_hash(previous_block.height(), previous_block.hash()));
+
+        // Ensure the block authority is correct.
+        let authority = report.record(
+            "authority",
+            self.verify_authority(previous_block.round(), previous_block.height(), current_committee),
+        );
+
+        // Ensure the block solutions are correct.
+        let solutions =
+            report.record("solutions", self.verify_solutions(previous_block, current_puzzle, current_epoch_challenge));
+
+        // Ensure the block ratifications are correct.
+        match solutions {
+            Some((.., expected_block_reward, expected_puzzle_reward)) => {
+                report
+                    .record("ratifications", self.verify_ratifications(expected_block_reward, expected_puzzle_reward));
+            }
+            None => report.skip("ratifications"),
+        }
+
+        // Ensure the block transactions are correct.
+        report.record("transactions", self.verify_transactions());
+
+        // Set the expected previous state root.
+        let expected_previous_state_root = current_state_root;
+        // Compute the expected transactions root.
+        let expected_transactions_root = report.record("transactions_root", self.compute_transactions_root());
+        // Compute the expected finalize root.
+        let expected_finalize_root =
+            report.record("finalize_root", self.compute_finalize_root(ratified_finalize_operations));
+        // Compute the expected ratifications root.
+        let expected_ratifications_root = report.record("ratifications_root", self.compute_ratifications_root());
+        // Compute the expected solutions root.
+        let expected_solutions_root = report.record("solutions_root", self.compute_solutions_root());
+        // Compute the expected subdag root.
+        let expected_subdag_root = report.record("subdag_root", self.compute_subdag_root());
+
+        // Ensure the block header is correct.
+        match (
+            authority,
+            solutions,
+            expected_transactions_root,
+            expected_finalize_root,
+            expected_ratifications_root,
+            expected_solutions_root,
+            expected_subdag_root,
+        ) {
+            (
+                Some((expected_round, expected_height, expected_timestamp)),
+                Some((
+                    expected_cumulative_weight,
+                    expected_cumulative_proof_target,
+                    expected_coinbase_target,
+                    expected_proof_target,
+                    expected_last_coinbase_target,
+                    expected_last_coinbase_timestamp,
+                    ..,
+                )),
+                Some(expected_transactions_root),
+                Some(expected_finalize_root),
+                Some(expected_ratifications_root),
+                Some(expected_solutions_root),
+                Some(expected_subdag_root),
+            ) => {
+                report.record(
+                    "header",
+                    self.header.verify(
+                        expected_previous_state_root,
+                        expected_transactions_root,
+                        expected_finalize_root,
+                        expected_ratifications_root,
+                        expected_solutions_root,
+                        expected_subdag_root,
+                        expected_round,
+                        expected_height,
+                        expected_cumulative_weight,
+                        expected_cumulative_proof_target,
+                        expected_coinbase_target,
+                        expected_proof_target,
+                        expected_last_coinbase_target,
+                        expected_last_coinbase_timestamp,
+                        expected_timestamp,
+                        current_timestamp,
+                    ),
+                );
+            }
+            _ => report.skip("header"),
+        }
+
+        report
+    }
 }
 
 impl<N: Network> Block<N> {
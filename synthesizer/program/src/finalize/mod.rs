@@ -52,14 +52,25 @@ impl<N: Network, Command: CommandTrait<N>> FinalizeCore<N, Command> {
             j_inputs.push(val.to_json());
         }
 
+        // Each command, and the instruction of an instruction command, is numbered by its index in `commands`.
         let mut j_commands = Vec::new();
-        for val in &self.commands {
-            j_commands.push(val.to_json());
+        for (index, val) in self.commands.iter().enumerate() {
+            let mut j_command = val.to_json();
+            j_command["index"] = json!(index);
+            if j_command["vtype"] == "Instruction" {
+                j_command["value"]["index"] = json!(index);
+            }
+            j_commands.push(j_command);
         }
 
-        let mut j_positions: HashMap<String, serde_json::Value> = HashMap::new();
-        for (key, val) in &self.positions {
+        // The positions, and the labels, are in order of their command index.
+        let mut positions: Vec<_> = self.positions.iter().collect();
+        positions.sort_by_key(|(_, val)| **val);
+        let mut j_positions = serde_json::Map::new();
+        let mut j_labels = serde_json::Map::new();
+        for (key, val) in positions {
             j_positions.insert(key.to_key(), json!(val));
+            j_labels.insert(key.to_key(), json!(val));
         }
 
         json!({
@@ -69,6 +80,7 @@ impl<N: Network, Command: CommandTrait<N>> FinalizeCore<N, Command> {
             "commands": j_commands,
             "num_writes": self.num_writes,
             "positions": j_positions,
+            "labels": j_labels,
         })
     }
 }
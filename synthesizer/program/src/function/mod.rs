@@ -58,8 +58,10 @@ impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> Fun
         }
 
         let mut j_instructions = Vec::new();
-        for val in &self.instructions {
-            j_instructions.push(val.to_json());
+        for (index, val) in self.instructions.iter().enumerate() {
+            let mut j_instruction = val.to_json();
+            j_instruction["index"] = json!(index);
+            j_instructions.push(j_instruction);
         }
 
         let mut j_outputs = Vec::new();
@@ -17,7 +17,6 @@
 #![warn(clippy::cast_possible_truncation)]
 
 use serde_json::json;
-use std::collections::HashMap;
 
 pub type Program<N> = crate::ProgramCore<N, Instruction<N>, Command<N>>;
 pub type Function<N> = crate::FunctionCore<N, Instruction<N>, Command<N>>;
@@ -148,43 +147,43 @@ pub struct ProgramCore<N: Network, Instruction: InstructionTrait<N>, Command: Co
 impl<N: Network, Instruction: InstructionTrait<N>, Command: CommandTrait<N>> ProgramCore<N, Instruction, Command> {
     pub fn to_json(&self) -> serde_json::Value {
         // collect imports
-        let mut j_imports: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_imports = serde_json::Map::new();
         for (key, val) in &self.imports {
             j_imports.insert(key.to_key(), val.to_json());
         }
 
         // collect identifiers
-        let mut j_identifiers: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_identifiers = serde_json::Map::new();
         for (key, val) in &self.identifiers {
             j_identifiers.insert(key.to_key(), val.to_json());
         }
 
         // collect mappings
-        let mut j_mappings: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_mappings = serde_json::Map::new();
         for (key, val) in &self.mappings {
             j_mappings.insert(key.to_key(), val.to_json());
         }
 
         // collect structs
-        let mut j_structs: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_structs = serde_json::Map::new();
         for (key, val) in &self.structs {
             j_structs.insert(key.to_key(), val.to_json());
         }
 
         // collect records
-        let mut j_records: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_records = serde_json::Map::new();
         for (key, val) in &self.records {
             j_records.insert(key.to_key(), val.to_json());
         }
 
         // collect closures
-        let mut j_closures: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_closures = serde_json::Map::new();
         for (key, val) in &self.closures {
             j_closures.insert(key.to_key(), val.to_json());
         }
 
         // collect functions
-        let mut j_functions: HashMap<String, serde_json::Value> = HashMap::new();
+        let mut j_functions = serde_json::Map::new();
         for (key, val) in &self.functions {
             j_functions.insert(key.to_key(), val.to_json());
         }
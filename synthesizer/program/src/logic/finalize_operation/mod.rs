@@ -19,6 +19,8 @@ mod string;
 
 use console::{network::prelude::*, types::Field};
 
+use serde_json::json;
+
 /// Enum to represent the allowed set of Merkle tree operations.
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum FinalizeOperation<N: Network> {
@@ -39,6 +41,53 @@ pub enum FinalizeOperation<N: Network> {
     RemoveMapping(Field<N>),
 }
 
+/// ** Vanguard JSON serialization helper ** ///
+impl<N: Network> FinalizeOperation<N> {
+    pub fn to_json(&self) -> serde_json::Value {
+        let j_vtype = match self {
+            Self::InitializeMapping(..) => "InitializeMapping",
+            Self::InsertKeyValue(..) => "InsertKeyValue",
+            Self::UpdateKeyValue(..) => "UpdateKeyValue",
+            Self::RemoveKeyValue(..) => "RemoveKeyValue",
+            Self::ReplaceMapping(..) => "ReplaceMapping",
+            Self::RemoveMapping(..) => "RemoveMapping",
+        };
+
+        let j_value = match self {
+            Self::InitializeMapping(mapping_id) => json!({
+                "mapping_id": mapping_id.to_json(),
+            }),
+            Self::InsertKeyValue(mapping_id, key_id, value_id) => json!({
+                "mapping_id": mapping_id.to_json(),
+                "key_id": key_id.to_json(),
+                "value_id": value_id.to_json(),
+            }),
+            Self::UpdateKeyValue(mapping_id, index, key_id, value_id) => json!({
+                "mapping_id": mapping_id.to_json(),
+                "index": index,
+                "key_id": key_id.to_json(),
+                "value_id": value_id.to_json(),
+            }),
+            Self::RemoveKeyValue(mapping_id, index) => json!({
+                "mapping_id": mapping_id.to_json(),
+                "index": index,
+            }),
+            Self::ReplaceMapping(mapping_id) => json!({
+                "mapping_id": mapping_id.to_json(),
+            }),
+            Self::RemoveMapping(mapping_id) => json!({
+                "mapping_id": mapping_id.to_json(),
+            }),
+        };
+
+        json!({
+            "type": "FinalizeOperation",
+            "vtype": j_vtype,
+            "value": j_value,
+        })
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test_helpers {
     use super::*;
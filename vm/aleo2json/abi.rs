@@ -0,0 +1,393 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{
+        network::Network,
+        program::{EntryType, FinalizeType, ValueType},
+    },
+    synthesizer::{cost_in_microcredits, Program},
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// A typed value of an ABI: an input or output of a function, a member of a struct or record, or a mapping key or value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiValue {
+    /// The name of the value, i.e. its register or member name, if it has one.
+    pub name: Option<String>,
+    /// The type of the value, without its visibility (i.e. `u64`, `token`, or `credits.aleo/credits`).
+    pub value_type: String,
+    /// The visibility of the value: `constant`, `public`, `private`, `record`, or `future`.
+    /// The members of a struct have none, as they inherit the visibility of the struct.
+    pub visibility: Option<&'static str>,
+    /// The name of the record, if the value is a record (i.e. `credits` for `credits.aleo/credits`).
+    pub record: Option<String>,
+    /// The program defining the record, if the value is a record of another program (i.e. `credits.aleo`).
+    pub program_id: Option<String>,
+}
+
+impl AbiValue {
+    /// Returns the ABI value of the given input or output type.
+    fn of_value_type<N: Network>(name: Option<String>, value_type: &ValueType<N>) -> Self {
+        let (record, program_id) = match value_type {
+            ValueType::Record(record_name) => (Some(record_name.to_string()), None),
+            ValueType::ExternalRecord(locator) => {
+                (Some(locator.resource().to_string()), Some(locator.program_id().to_string()))
+            }
+            _ => (None, None),
+        };
+        let (value_type, visibility) = match value_type {
+            ValueType::Constant(plaintext_type) => (plaintext_type.to_string(), "constant"),
+            ValueType::Public(plaintext_type) => (plaintext_type.to_string(), "public"),
+            ValueType::Private(plaintext_type) => (plaintext_type.to_string(), "private"),
+            ValueType::Record(record_name) => (record_name.to_string(), "record"),
+            ValueType::ExternalRecord(locator) => (locator.to_string(), "record"),
+            ValueType::Future(locator) => (locator.to_string(), "future"),
+        };
+        Self { name, value_type, visibility: Some(visibility), record, program_id }
+    }
+
+    /// Returns the ABI value of the given finalize input type.
+    fn of_finalize_type<N: Network>(name: Option<String>, finalize_type: &FinalizeType<N>) -> Self {
+        let (value_type, visibility) = match finalize_type {
+            FinalizeType::Plaintext(plaintext_type) => (plaintext_type.to_string(), "public"),
+            FinalizeType::Future(locator) => (locator.to_string(), "future"),
+        };
+        Self { name, value_type, visibility: Some(visibility), record: None, program_id: None }
+    }
+
+    /// Returns the ABI value of the given record entry type.
+    fn of_entry_type<N: Network>(name: Option<String>, entry_type: &EntryType<N>) -> Self {
+        let (plaintext_type, visibility) = match entry_type {
+            EntryType::Constant(plaintext_type) => (plaintext_type, "constant"),
+            EntryType::Public(plaintext_type) => (plaintext_type, "public"),
+            EntryType::Private(plaintext_type) => (plaintext_type, "private"),
+        };
+        Self {
+            name,
+            value_type: plaintext_type.to_string(),
+            visibility: Some(visibility),
+            record: None,
+            program_id: None,
+        }
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_value = json!({ "type": self.value_type });
+        if let Some(name) = &self.name {
+            j_value["name"] = json!(name);
+        }
+        if let Some(visibility) = self.visibility {
+            j_value["visibility"] = json!(visibility);
+        }
+        if let Some(record) = &self.record {
+            j_value["record"] = json!(record);
+        }
+        if let Some(program_id) = &self.program_id {
+            j_value["program_id"] = json!(program_id);
+        }
+        j_value
+    }
+}
+
+/// A named list of typed members: a struct, a record, or the entry of a mapping.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiType {
+    /// The name of the type.
+    pub name: String,
+    /// The members of the type, in order.
+    pub members: Vec<AbiValue>,
+}
+
+impl AbiType {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_members = Vec::new();
+        for val in &self.members {
+            j_members.push(val.to_json());
+        }
+
+        json!({
+            "name": self.name,
+            "members": j_members,
+        })
+    }
+}
+
+/// The interface of a function callable from a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiFunction {
+    /// The name of the function.
+    pub name: String,
+    /// The inputs of the function, named by their registers.
+    pub inputs: Vec<AbiValue>,
+    /// The outputs of the function.
+    pub outputs: Vec<AbiValue>,
+    /// The inputs of the finalize block of the function, if it has one.
+    pub finalize: Option<Vec<AbiValue>>,
+    /// The minimum cost of the finalize block in microcredits, paid on top of the storage cost of the execution.
+    pub finalize_cost: u64,
+}
+
+impl AbiFunction {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_inputs = Vec::new();
+        for val in &self.inputs {
+            j_inputs.push(val.to_json());
+        }
+
+        let mut j_outputs = Vec::new();
+        for val in &self.outputs {
+            j_outputs.push(val.to_json());
+        }
+
+        let j_finalize = match &self.finalize {
+            Some(inputs) => {
+                let mut j_finalize_inputs = Vec::new();
+                for val in inputs {
+                    j_finalize_inputs.push(val.to_json());
+                }
+                json!({ "inputs": j_finalize_inputs })
+            }
+            None => Value::Null,
+        };
+
+        json!({
+            "name": self.name,
+            "inputs": j_inputs,
+            "outputs": j_outputs,
+            "has_finalize": self.finalize.is_some(),
+            "finalize": j_finalize,
+            "fee": { "finalize_cost": self.finalize_cost },
+        })
+    }
+}
+
+/// The interface of a program, analogous to a Solidity ABI: what wallets and SDK generators need to call its functions
+/// and decode its records and mappings, without the full program JSON.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramAbi {
+    /// The ID of the program.
+    pub program_id: String,
+    /// The IDs of the imported programs.
+    pub imports: Vec<String>,
+    /// The functions, in declaration order. Closures are omitted, as they cannot be called from a transaction.
+    pub functions: Vec<AbiFunction>,
+    /// The mappings, each with its key and value as members.
+    pub mappings: Vec<AbiType>,
+    /// The structs.
+    pub structs: Vec<AbiType>,
+    /// The records, each with its owner as the first member.
+    pub records: Vec<AbiType>,
+}
+
+impl ProgramAbi {
+    /// Extracts the interface of the given program.
+    pub fn new<N: Network>(program: &Program<N>) -> Result<Self> {
+        let mut functions = Vec::new();
+        for function in program.functions().values() {
+            let inputs = function
+                .inputs()
+                .iter()
+                .map(|input| AbiValue::of_value_type(Some(input.register().to_string()), input.value_type()))
+                .collect();
+            let outputs =
+                function.outputs().iter().map(|output| AbiValue::of_value_type(None, output.value_type())).collect();
+            let (finalize, finalize_cost) = match function.finalize_logic() {
+                Some(finalize) => {
+                    let inputs = finalize
+                        .inputs()
+                        .iter()
+                        .map(|input| {
+                            AbiValue::of_finalize_type(Some(input.register().to_string()), input.finalize_type())
+                        })
+                        .collect();
+                    (Some(inputs), cost_in_microcredits(finalize)?)
+                }
+                None => (None, 0),
+            };
+            functions.push(AbiFunction { name: function.name().to_string(), inputs, outputs, finalize, finalize_cost });
+        }
+
+        let mappings = program
+            .mappings()
+            .iter()
+            .map(|(name, mapping)| {
+                let member = |member: &str, plaintext_type: String| AbiValue {
+                    name: Some(member.to_string()),
+                    value_type: plaintext_type,
+                    visibility: Some("public"),
+                    record: None,
+                    program_id: None,
+                };
+                AbiType {
+                    name: name.to_string(),
+                    members: vec![
+                        member("key", mapping.key().plaintext_type().to_string()),
+                        member("value", mapping.value().plaintext_type().to_string()),
+                    ],
+                }
+            })
+            .collect();
+
+        let structs = program
+            .structs()
+            .iter()
+            .map(|(name, struct_)| AbiType {
+                name: name.to_string(),
+                members: struct_
+                    .members()
+                    .iter()
+                    .map(|(member, plaintext_type)| AbiValue {
+                        name: Some(member.to_string()),
+                        value_type: plaintext_type.to_string(),
+                        visibility: None,
+                        record: None,
+                        program_id: None,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let records = program
+            .records()
+            .iter()
+            .map(|(name, record)| {
+                let owner = AbiValue {
+                    name: Some("owner".to_string()),
+                    value_type: "address".to_string(),
+                    visibility: match record.owner().is_public() {
+                        true => Some("public"),
+                        false => Some("private"),
+                    },
+                    record: None,
+                    program_id: None,
+                };
+                let entries = record
+                    .entries()
+                    .iter()
+                    .map(|(entry, entry_type)| AbiValue::of_entry_type(Some(entry.to_string()), entry_type));
+                AbiType { name: name.to_string(), members: std::iter::once(owner).chain(entries).collect() }
+            })
+            .collect();
+
+        Ok(Self {
+            program_id: program.id().to_string(),
+            imports: program.imports().keys().map(ToString::to_string).collect(),
+            functions,
+            mappings,
+            structs,
+            records,
+        })
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_functions = Vec::new();
+        for val in &self.functions {
+            j_functions.push(val.to_json());
+        }
+
+        let mut j_mappings = Vec::new();
+        for val in &self.mappings {
+            j_mappings.push(val.to_json());
+        }
+
+        let mut j_structs = Vec::new();
+        for val in &self.structs {
+            j_structs.push(val.to_json());
+        }
+
+        let mut j_records = Vec::new();
+        for val in &self.records {
+            j_records.push(val.to_json());
+        }
+
+        json!({
+            "type": "ProgramAbi",
+            "program_id": self.program_id,
+            "imports": self.imports,
+            "functions": j_functions,
+            "mappings": j_mappings,
+            "structs": j_structs,
+            "records": j_records,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_credits_abi() {
+        let program = Program::<CurrentNetwork>::credits().unwrap();
+        let abi = ProgramAbi::new(&program).unwrap();
+        assert_eq!(abi.program_id, "credits.aleo");
+
+        let transfer_public = abi.functions.iter().find(|function| function.name == "transfer_public").unwrap();
+        assert_eq!(transfer_public.inputs[0], AbiValue {
+            name: Some("r0".to_string()),
+            value_type: "address".to_string(),
+            visibility: Some("public"),
+            record: None,
+            program_id: None,
+        });
+        assert!(transfer_public.finalize.is_some());
+        assert!(transfer_public.finalize_cost > 0);
+
+        let transfer_private = abi.functions.iter().find(|function| function.name == "transfer_private").unwrap();
+        assert_eq!(transfer_private.inputs[0].visibility, Some("record"));
+        assert_eq!(transfer_private.inputs[0].record.as_deref(), Some("credits"));
+        assert_eq!(transfer_private.inputs[0].program_id, None);
+        assert_eq!((transfer_private.finalize.is_none(), transfer_private.finalize_cost), (true, 0));
+
+        let credits = abi.records.iter().find(|record| record.name == "credits").unwrap();
+        assert_eq!(credits.members[0].name.as_deref(), Some("owner"));
+        assert_eq!(credits.members[1].value_type, "u64");
+
+        let j_abi = abi.to_json();
+        assert_eq!(j_abi["type"], "ProgramAbi");
+        assert!(j_abi["mappings"].as_array().unwrap().iter().any(|mapping| mapping["name"] == "account"));
+    }
+
+    #[test]
+    fn test_external_record_abi() {
+        let program = Program::<CurrentNetwork>::from_str(
+            r"import credits.aleo;
+program wallet.aleo;
+
+function deposit:
+    input r0 as credits.aleo/credits.record;
+    output r0.microcredits as u64.private;",
+        )
+        .unwrap();
+        let abi = ProgramAbi::new(&program).unwrap();
+
+        let j_input = abi.functions[0].inputs[0].to_json();
+        assert_eq!(j_input["type"], "credits.aleo/credits");
+        assert_eq!(j_input["record"], "credits");
+        assert_eq!(j_input["program_id"], "credits.aleo");
+        assert_eq!(j_input["visibility"], "record");
+    }
+}
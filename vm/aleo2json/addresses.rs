@@ -0,0 +1,179 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::decrypt_transition_records,
+    console::{
+        account::{Address, ViewKey},
+        network::Network,
+        program::{Argument, Literal, Owner, Plaintext},
+    },
+    ledger::block::{Block, Input, Output, Ratify, Transition},
+};
+
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+/// The address appears in a public input of a transition.
+pub const ROLE_PUBLIC_INPUT: &str = "public_input";
+/// The address owns an output record, either public or decrypted with one of the view keys.
+pub const ROLE_RECORD_OWNER: &str = "record_owner";
+/// The address pays the public fee of a transaction.
+pub const ROLE_FEE_PAYER: &str = "fee_payer";
+/// The address is a member of the committee set by the genesis ratification.
+pub const ROLE_COMMITTEE_MEMBER: &str = "committee_member";
+/// The address receives a public balance from the genesis ratification.
+pub const ROLE_RATIFICATION_RECIPIENT: &str = "ratification_recipient";
+
+/// The addresses appearing in a block, each with the roles it played, in order of first appearance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressActivity {
+    /// The appearances of each address, as JSON, by address.
+    pub addresses: IndexMap<String, Vec<Value>>,
+}
+
+impl AddressActivity {
+    /// Lists every address appearing in the given block: in the public inputs of its transitions, as the owners of
+    /// its output records (decrypting the private records owned by the given view keys), as the payers of its public
+    /// fees, and in its ratifications.
+    pub fn of_block<N: Network>(block: &Block<N>, view_keys: &[ViewKey<N>]) -> Self {
+        let mut activity = Self::default();
+        for ratify in block.ratifications().iter() {
+            if let Ratify::Genesis(committee, public_balances) = ratify {
+                for address in committee.members().keys() {
+                    activity.push(address, json!({ "role": ROLE_COMMITTEE_MEMBER }));
+                }
+                for (address, microcredits) in public_balances {
+                    activity
+                        .push(address, json!({ "role": ROLE_RATIFICATION_RECIPIENT, "microcredits": microcredits }));
+                }
+            }
+        }
+        for confirmed in block.transactions().iter() {
+            let transaction_id = confirmed.transaction().id().to_string();
+            for transition in confirmed.transaction().transitions() {
+                activity.add_transition(&transaction_id, transition, view_keys);
+            }
+        }
+        activity
+    }
+
+    /// Records the addresses of the given transition.
+    fn add_transition<N: Network>(
+        &mut self,
+        transaction_id: &str,
+        transition: &Transition<N>,
+        view_keys: &[ViewKey<N>],
+    ) {
+        let appearance = |role: &str, index: usize| {
+            json!({
+                "role": role,
+                "transaction_id": transaction_id,
+                "transition_id": transition.id().to_string(),
+                "index": index,
+            })
+        };
+
+        for (index, input) in transition.inputs().iter().enumerate() {
+            if let Input::Public(_, Some(plaintext)) = input {
+                for address in plaintext_addresses(plaintext) {
+                    self.push(&address, appearance(ROLE_PUBLIC_INPUT, index));
+                }
+            }
+        }
+        for (index, output) in transition.outputs().iter().enumerate() {
+            if let Output::Record(_, _, Some(record)) = output {
+                if let Owner::Public(address) = record.owner() {
+                    self.push(address, appearance(ROLE_RECORD_OWNER, index));
+                }
+            }
+        }
+        for (index, record) in decrypt_transition_records(transition, view_keys) {
+            if record.owner().is_private() {
+                self.push(&**record.owner(), appearance(ROLE_RECORD_OWNER, index));
+            }
+        }
+
+        // The payer of a public fee is the first argument of its future.
+        if transition.program_id().to_string() == "credits.aleo"
+            && transition.function_name().to_string() == "fee_public"
+        {
+            for (index, output) in transition.outputs().iter().enumerate() {
+                if let Output::Future(_, Some(future)) = output {
+                    if let Some(Argument::Plaintext(Plaintext::Literal(Literal::Address(address), _))) =
+                        future.arguments().first()
+                    {
+                        self.push(address, appearance(ROLE_FEE_PAYER, index));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records an appearance of the given address.
+    fn push<N: Network>(&mut self, address: &Address<N>, appearance: Value) {
+        self.addresses.entry(address.to_string()).or_default().push(appearance);
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!(self.addresses)
+    }
+}
+
+/// Returns the addresses in the given plaintext, and in its members and elements.
+fn plaintext_addresses<N: Network>(plaintext: &Plaintext<N>) -> Vec<Address<N>> {
+    match plaintext {
+        Plaintext::Literal(Literal::Address(address), _) => vec![*address],
+        Plaintext::Literal(..) => Vec::new(),
+        Plaintext::Struct(members, _) => members.values().flat_map(plaintext_addresses).collect(),
+        Plaintext::Array(elements, _) => elements.iter().flat_map(plaintext_addresses).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_plaintext_addresses() {
+        let address = "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px";
+        let plaintext =
+            Plaintext::<CurrentNetwork>::from_str(&format!("{{ owner: {address}, amounts: [1u64, 2u64] }}")).unwrap();
+        assert_eq!(plaintext_addresses(&plaintext), [Address::from_str(address).unwrap()]);
+        assert!(plaintext_addresses(&Plaintext::<CurrentNetwork>::from_str("1u64").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_block_address_activity() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let activity = AddressActivity::of_block(&block, &[]);
+
+        // Each member of the genesis committee is listed.
+        for ratify in block.ratifications().iter() {
+            if let Ratify::Genesis(committee, _) = ratify {
+                for address in committee.members().keys() {
+                    let appearances = &activity.addresses[&address.to_string()];
+                    assert!(appearances.iter().any(|appearance| appearance["role"] == ROLE_COMMITTEE_MEMBER));
+                }
+            }
+        }
+        assert_eq!(activity.to_json().as_object().unwrap().len(), activity.addresses.len());
+    }
+}
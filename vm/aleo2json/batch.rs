@@ -0,0 +1,246 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{OutputSink, Progress, ProgressCallback, RawDocument};
+
+use anyhow::{anyhow, ensure, Result};
+use rayon::prelude::*;
+use serde_json::value::RawValue;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+/// The number of files converted per worker thread before the batch is written out.
+const FILES_PER_WORKER: usize = 4;
+
+/// Returns the files with the given extension in the given directory, and in its subdirectories, in sorted order.
+pub fn find_files(root: &Path, extension: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut directories = vec![root.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in std::fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().map_or(false, |file_extension| file_extension == extension) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the output path of each of the given files, mirroring its path relative to `root` under `out_dir`,
+/// with the given extension, i.e. `root/tokens/foo.aleo` to `out_dir/tokens/foo.json`.
+///
+/// The output paths that collide, including those that differ only in case (as they collide on case-insensitive
+/// file systems), are disambiguated with a numeric suffix, i.e. `foo-1.json`.
+pub fn mirror_paths(root: &Path, files: &[PathBuf], out_dir: &Path, extension: &str) -> Result<Vec<PathBuf>> {
+    let mut taken = HashSet::new();
+    let mut outputs = Vec::with_capacity(files.len());
+    for file in files {
+        let relative =
+            file.strip_prefix(root).map_err(|_| anyhow!("'{}' is not in '{}'", file.display(), root.display()))?;
+        let stem = relative.file_stem().ok_or_else(|| anyhow!("'{}' is not a file", file.display()))?;
+        let base = out_dir.join(relative);
+
+        let mut output = base.with_file_name(format!("{}.{extension}", stem.to_string_lossy()));
+        let mut suffix = 0;
+        while !taken.insert(output.to_string_lossy().to_lowercase()) {
+            suffix += 1;
+            output = base.with_file_name(format!("{}-{suffix}.{extension}", stem.to_string_lossy()));
+        }
+        outputs.push(output);
+    }
+    Ok(outputs)
+}
+
+/// Converts each of the given files with `convert`, writing the result to the output path at the same position,
+/// and creating its parent directories.
+///
+/// Files are converted by up to `jobs` threads in parallel, a few files per thread at a time, so only the current
+/// batch is held in memory, and are always written in the given order. Every file is converted, even if an earlier
+/// one fails; the number of files converted is reported to the given callback after each batch.
+/// Returns the files that failed to convert or to be written, each with its error.
+pub fn convert_files<F: Fn(&Path) -> Result<String> + Sync>(
+    files: &[PathBuf],
+    outputs: &[PathBuf],
+    jobs: usize,
+    convert: F,
+    progress: Option<&ProgressCallback>,
+) -> Result<Vec<(PathBuf, String)>> {
+    ensure!(files.len() == outputs.len(), "Found {} output paths for {} files", outputs.len(), files.len());
+    convert_batches(files, jobs, convert, |index, document| write_file(&outputs[index], &document), progress)
+}
+
+/// Converts each of the given files with `convert`, as for `convert_files`, writing the result to the given sink
+/// instead of a file: each document is the path of the file, under `path`, and its converted JSON, under `document`.
+///
+/// The converted JSON is embedded verbatim, as a raw fragment, rather than re-parsed into a `Value` tree.
+/// Returns the files that failed to convert or to be written, each with its error.
+pub fn convert_files_to<F: Fn(&Path) -> Result<String> + Sync, S: OutputSink>(
+    files: &[PathBuf],
+    jobs: usize,
+    convert: F,
+    sink: &mut S,
+    progress: Option<&ProgressCallback>,
+) -> Result<Vec<(PathBuf, String)>> {
+    let write = |index: usize, document: String| -> Result<()> {
+        let mut composed = RawDocument::new();
+        composed
+            .insert("path", &files[index].display().to_string())?
+            .insert_raw("document", RawValue::from_string(document)?);
+        sink.write_raw(&composed.into_raw()?)?;
+        Ok(())
+    };
+    let failures = convert_batches(files, jobs, convert, write, progress)?;
+    sink.flush()?;
+    Ok(failures)
+}
+
+/// Converts the given files in parallel batches, passing the result of each file to `write`, with its position,
+/// in the order of the files. Returns the files that failed to convert or to be written, each with its error.
+fn convert_batches<F: Fn(&Path) -> Result<String> + Sync, W: FnMut(usize, String) -> Result<()>>(
+    files: &[PathBuf],
+    jobs: usize,
+    convert: F,
+    mut write: W,
+    progress: Option<&ProgressCallback>,
+) -> Result<Vec<(PathBuf, String)>> {
+    ensure!(jobs > 0, "The number of jobs must be greater than zero");
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+
+    let mut failures = Vec::new();
+    let mut completed = 0;
+    for batch in files.chunks(jobs * FILES_PER_WORKER) {
+        // Convert the batch in parallel; the collected documents retain the order of the files.
+        let documents: Vec<_> = pool.install(|| batch.par_iter().map(|file| convert(file)).collect());
+        for (offset, (file, document)) in batch.iter().zip(documents).enumerate() {
+            if let Err(error) = document.and_then(|document| write(completed + offset, document)) {
+                failures.push((file.to_path_buf(), error.to_string()));
+            }
+        }
+        completed += batch.len();
+
+        if let Some(progress) = progress {
+            progress(Progress { completed, total: files.len() });
+        }
+    }
+    Ok(failures)
+}
+
+/// Writes the given contents to the given path, creating its parent directories.
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(std::fs::write(path, contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::WriterSink;
+
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_find_files() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(directory.path().join("tokens/nested")).unwrap();
+        for file in ["main.aleo", "README.md", "tokens/foo.aleo", "tokens/nested/bar.aleo"] {
+            std::fs::write(directory.path().join(file), "").unwrap();
+        }
+
+        let files = find_files(directory.path(), "aleo").unwrap();
+        let expected: Vec<_> = ["main.aleo", "tokens/foo.aleo", "tokens/nested/bar.aleo"]
+            .iter()
+            .map(|file| directory.path().join(file))
+            .collect();
+        assert_eq!(files, expected);
+    }
+
+    #[test]
+    fn test_mirror_paths() {
+        let (root, out_dir) = (Path::new("programs"), Path::new("out"));
+        let files: Vec<_> = ["main.aleo", "tokens/foo.aleo", "tokens/Foo.aleo", "tokens/foo.v2.aleo"]
+            .iter()
+            .map(|file| root.join(file))
+            .collect();
+
+        let outputs = mirror_paths(root, &files, out_dir, "json").unwrap();
+        let expected: Vec<_> = ["main.json", "tokens/foo.json", "tokens/Foo-1.json", "tokens/foo.v2.json"]
+            .iter()
+            .map(|file| out_dir.join(file))
+            .collect();
+        assert_eq!(outputs, expected);
+
+        assert!(mirror_paths(root, &[PathBuf::from("elsewhere/main.aleo")], out_dir, "json").is_err());
+    }
+
+    #[test]
+    fn test_convert_files() {
+        let directory = tempfile::tempdir().unwrap();
+        let files: Vec<_> = (0..25).map(|index| PathBuf::from(format!("{index}.aleo"))).collect();
+        let outputs = mirror_paths(Path::new(""), &files, &directory.path().join("out"), "json").unwrap();
+
+        let convert = |file: &Path| match file.to_string_lossy().starts_with('1') {
+            true => Err(anyhow!("Failed")),
+            false => Ok(file.display().to_string()),
+        };
+        let failures = convert_files(&files, &outputs, 3, convert, None).unwrap();
+
+        // The failures are reported in the order of the files, and the other files are written.
+        let failed: Vec<_> = failures.iter().map(|(file, _)| file.display().to_string()).collect();
+        assert_eq!(failed, [
+            "1.aleo", "10.aleo", "11.aleo", "12.aleo", "13.aleo", "14.aleo", "15.aleo", "16.aleo", "17.aleo",
+            "18.aleo", "19.aleo"
+        ]);
+        assert_eq!(std::fs::read_to_string(&outputs[24]).unwrap(), "24.aleo");
+        assert!(!outputs[1].exists());
+
+        assert!(convert_files(&files, &outputs, 0, convert, None).is_err());
+        assert!(convert_files(&files, &outputs[1..], 1, convert, None).is_err());
+    }
+
+    #[test]
+    fn test_convert_files_to() {
+        let files: Vec<_> = (0..10).map(|index| PathBuf::from(format!("{index}.aleo"))).collect();
+        let convert = |file: &Path| match file == Path::new("3.aleo") {
+            true => Err(anyhow!("Failed")),
+            false => Ok(json!({ "file": file.display().to_string() }).to_string()),
+        };
+
+        let mut documents = Vec::<Value>::new();
+        let failures = convert_files_to(&files, 2, convert, &mut documents, None).unwrap();
+        assert_eq!(failures, [(PathBuf::from("3.aleo"), "Failed".to_string())]);
+
+        // The documents are written in the order of the files.
+        assert_eq!(documents.len(), 9);
+        assert_eq!(documents[3], json!({ "path": "4.aleo", "document": { "file": "4.aleo" } }));
+
+        // The documents written as bytes match those built as `Value` trees.
+        let mut sink = WriterSink::new(Vec::new());
+        convert_files_to(&files, 2, convert, &mut sink, None).unwrap();
+        let lines: Vec<Value> = String::from_utf8(sink.into_inner())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, documents);
+    }
+}
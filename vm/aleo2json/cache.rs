@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::console::{network::Network, program::ProgramID};
+
+#[cfg(feature = "aleo2json-ledger")]
+use crate::{
+    console::network::prelude::{FromBytes, ToBytes},
+    ledger::block::Block,
+};
+
+use anyhow::Result;
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// The extension of the cached program sources.
+const PROGRAM_EXTENSION: &str = "aleo";
+
+/// The counter distinguishing the temporary files written by this process.
+static TEMPORARY_FILES: AtomicU64 = AtomicU64::new(0);
+
+/// An on-disk cache of the programs and blocks fetched from a node, reused across runs.
+///
+/// The cache directory is laid out as:
+///  - `programs/<program ID>/<edition>.aleo`, the source of each edition of a deployed program,
+///  - `blocks/<block hash>`, each block, as little-endian bytes,
+///  - `heights/<height>`, the hash of the block at each height.
+///
+/// Deployed editions and accepted blocks never change, so the cached entries are never invalidated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkCache {
+    /// The cache directory.
+    directory: PathBuf,
+}
+
+impl NetworkCache {
+    /// Initializes a cache in the given directory, which is created when the first entry is written.
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Returns the cache directory.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the path of the given edition of the given program.
+    pub fn program_path<N: Network>(&self, program_id: &ProgramID<N>, edition: u16) -> PathBuf {
+        self.directory.join("programs").join(program_id.to_string()).join(format!("{edition}.{PROGRAM_EXTENSION}"))
+    }
+
+    /// Returns the source of the given edition of the given program, if it is cached.
+    pub fn get_program<N: Network>(&self, program_id: &ProgramID<N>, edition: u16) -> Result<Option<String>> {
+        let path = self.program_path(program_id, edition);
+        match path.is_file() {
+            true => Ok(Some(std::fs::read_to_string(path)?)),
+            false => Ok(None),
+        }
+    }
+
+    /// Returns the latest edition of the given program that is cached, if any.
+    pub fn latest_program_edition<N: Network>(&self, program_id: &ProgramID<N>) -> Result<Option<u16>> {
+        let directory = self.directory.join("programs").join(program_id.to_string());
+        if !directory.is_dir() {
+            return Ok(None);
+        }
+        let mut latest = None;
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some(PROGRAM_EXTENSION) {
+                continue;
+            }
+            if let Some(edition) = path.file_stem().and_then(|stem| stem.to_str()?.parse::<u16>().ok()) {
+                latest = latest.max(Some(edition));
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Caches the source of the given edition of the given program.
+    pub fn insert_program<N: Network>(&self, program_id: &ProgramID<N>, edition: u16, source: &str) -> Result<()> {
+        write_atomically(&self.program_path(program_id, edition), source.as_bytes())
+    }
+
+    /// Returns the block with the given hash, if it is cached.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub fn get_block<N: Network>(&self, block_hash: &N::BlockHash) -> Result<Option<Block<N>>> {
+        let path = self.directory.join("blocks").join(block_hash.to_string());
+        match path.is_file() {
+            true => Ok(Some(Block::read_le(&std::fs::read(path)?[..])?)),
+            false => Ok(None),
+        }
+    }
+
+    /// Returns the block at the given height, if it is cached.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub fn get_block_at_height<N: Network>(&self, height: u32) -> Result<Option<Block<N>>> {
+        let path = self.directory.join("heights").join(height.to_string());
+        match path.is_file() {
+            true => self.get_block::<N>(&std::fs::read_to_string(path)?.trim().parse()?),
+            false => Ok(None),
+        }
+    }
+
+    /// Caches the given block, under its hash and its height.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub fn insert_block<N: Network>(&self, block: &Block<N>) -> Result<()> {
+        let block_hash = block.hash().to_string();
+        write_atomically(&self.directory.join("blocks").join(&block_hash), &block.to_bytes_le()?)?;
+        write_atomically(&self.directory.join("heights").join(block.height().to_string()), block_hash.as_bytes())
+    }
+}
+
+/// Writes the given bytes to the given path through a temporary file, so that a concurrent or interrupted run
+/// never reads a partially written entry.
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let counter = TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed);
+    let temporary = path.with_extension(format!("{}.{counter}.tmp", std::process::id()));
+    std::fs::write(&temporary, bytes)?;
+    std::fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_program_cache() {
+        let directory = tempfile::tempdir().unwrap();
+        let cache = NetworkCache::new(directory.path().to_path_buf());
+        let program_id = ProgramID::<CurrentNetwork>::from_str("token.aleo").unwrap();
+
+        assert_eq!(cache.latest_program_edition(&program_id).unwrap(), None);
+        cache.insert_program(&program_id, 0, "program token.aleo;").unwrap();
+        cache.insert_program(&program_id, 2, "program token.aleo;\n").unwrap();
+        assert_eq!(cache.latest_program_edition(&program_id).unwrap(), Some(2));
+        assert_eq!(cache.get_program(&program_id, 0).unwrap().as_deref(), Some("program token.aleo;"));
+        assert_eq!(cache.get_program(&program_id, 1).unwrap(), None);
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_block_cache() {
+        let directory = tempfile::tempdir().unwrap();
+        let cache = NetworkCache::new(directory.path().to_path_buf());
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        assert!(cache.get_block_at_height::<CurrentNetwork>(0).unwrap().is_none());
+        cache.insert_block(&genesis).unwrap();
+        assert_eq!(cache.get_block_at_height::<CurrentNetwork>(0).unwrap(), Some(genesis.clone()));
+        assert_eq!(cache.get_block::<CurrentNetwork>(&genesis.hash()).unwrap(), Some(genesis));
+    }
+}
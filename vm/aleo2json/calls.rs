@@ -0,0 +1,124 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::network::Network,
+    synthesizer::{program::CallOperator, Instruction, Program},
+};
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// A function of another program called by a program.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExternalCall {
+    /// The ID of the called program.
+    pub program_id: String,
+    /// The name of the called function.
+    pub function: String,
+    /// The number of call sites.
+    pub call_sites: usize,
+    /// The functions of the program making the calls, in order of their first call.
+    pub callers: Vec<String>,
+}
+
+impl ExternalCall {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "ExternalCall",
+            "program_id": self.program_id,
+            "function": self.function,
+            "call_sites": self.call_sites,
+            "callers": self.callers,
+        })
+    }
+}
+
+/// Returns the functions of other programs called by the given program, ordered by program ID and function name.
+///
+/// Note: `async` only targets the finalize block of its own function, so every external call is made with `call`.
+pub fn external_calls<N: Network>(program: &Program<N>) -> Vec<ExternalCall> {
+    let mut calls = BTreeMap::<(String, String), ExternalCall>::new();
+    for (name, function) in program.functions() {
+        for instruction in function.instructions() {
+            let locator = match instruction {
+                Instruction::Call(call) => match call.operator() {
+                    CallOperator::Locator(locator) => locator,
+                    CallOperator::Resource(_) => continue,
+                },
+                _ => continue,
+            };
+
+            let (program_id, callee) = (locator.program_id().to_string(), locator.resource().to_string());
+            let entry = calls.entry((program_id.clone(), callee.clone())).or_insert_with(|| ExternalCall {
+                program_id,
+                function: callee,
+                ..Default::default()
+            });
+            entry.call_sites += 1;
+            if !entry.callers.contains(&name.to_string()) {
+                entry.callers.push(name.to_string());
+            }
+        }
+    }
+    calls.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_external_calls() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+
+program vault.aleo;
+
+function deposit:
+    input r0 as u64.public;
+    call credits.aleo/transfer_public_to_private self.caller r0 into r1 r2;
+    call credits.aleo/transfer_public_to_private self.caller r0 into r3 r4;
+    output r1 as credits.aleo/credits.record;
+
+function withdraw:
+    input r0 as credits.aleo/credits.record;
+    input r1 as u64.public;
+    call credits.aleo/split r0 r1 into r2 r3;
+    call credits.aleo/transfer_public_to_private self.caller r1 into r4 r5;
+    output r2 as credits.aleo/credits.record;
+
+function noop:
+    input r0 as u64.public;
+    output r0 as u64.public;",
+        )
+        .unwrap();
+
+        let calls = external_calls(&program);
+        let summary = calls
+            .iter()
+            .map(|call| (call.function.as_str(), call.call_sites, call.callers.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(summary, [
+            ("split", 1, vec!["withdraw".to_string()]),
+            ("transfer_public_to_private", 3, vec!["deposit".to_string(), "withdraw".to_string()]),
+        ]);
+        assert_eq!(calls[0].to_json()["program_id"], "credits.aleo");
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::{Number, Value};
+use std::cmp::Ordering;
+
+/// Serializes the given JSON into its canonical form, following the JSON Canonicalization Scheme (RFC 8785).
+///
+/// Object keys are sorted by their UTF-16 code units, numbers use the ECMAScript encoding,
+/// strings use the minimal escaping, and no insignificant whitespace is emitted. Two documents
+/// with the same content therefore always serialize to the same bytes, and can be hashed directly.
+pub fn to_canonical_string(value: &Value) -> String {
+    let mut output = String::new();
+    write_canonical(&mut output, value);
+    output
+}
+
+/// Appends the canonical form of the given value to the output.
+fn write_canonical(output: &mut String, value: &Value) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(boolean) => output.push_str(if *boolean { "true" } else { "false" }),
+        Value::Number(number) => output.push_str(&canonical_number(number)),
+        Value::String(string) => write_string(output, string),
+        Value::Array(array) => {
+            output.push('[');
+            for (index, element) in array.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_canonical(output, element);
+            }
+            output.push(']');
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| compare_utf16(a, b));
+
+            output.push('{');
+            for (index, (key, element)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_string(output, key);
+                output.push(':');
+                write_canonical(output, element);
+            }
+            output.push('}');
+        }
+    }
+}
+
+/// Appends the given string as a JSON string literal.
+///
+/// serde_json already uses the escaping required by RFC 8785: the short forms for `\b`, `\t`, `\n`,
+/// `\f`, `\r`, `"`, and `\`, lowercase `\u00XX` for the remaining control characters, and nothing else.
+fn write_string(output: &mut String, string: &str) {
+    output.push_str(&Value::from(string).to_string());
+}
+
+/// Compares two strings by their UTF-16 code units, as required for sorting object keys.
+fn compare_utf16(a: &str, b: &str) -> Ordering {
+    a.encode_utf16().cmp(b.encode_utf16())
+}
+
+/// Returns the ECMAScript `Number.prototype.toString` encoding of the given number.
+fn canonical_number(number: &Number) -> String {
+    // Integers that are exactly representable as doubles print as plain digits.
+    if let Some(integer) = number.as_i64().filter(|integer| integer.unsigned_abs() <= (1 << 53)) {
+        return integer.to_string();
+    }
+    if let Some(integer) = number.as_u64().filter(|integer| *integer <= (1 << 53)) {
+        return integer.to_string();
+    }
+    match number.as_f64() {
+        Some(float) => canonical_float(float),
+        None => number.to_string(),
+    }
+}
+
+/// Returns the ECMAScript encoding of the given double.
+fn canonical_float(float: f64) -> String {
+    if float == 0.0 {
+        return "0".to_string();
+    }
+
+    // Rust's exponent notation yields the shortest round-tripping digits, i.e. `1.2345e-7`.
+    let scientific = format!("{:e}", float.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    // The position of the decimal point relative to the start of the digits.
+    let point = exponent + 1;
+    let num_digits = digits.len() as i32;
+
+    let body = if num_digits <= point && point <= 21 {
+        // An integer, padded with zeros, i.e. `123000`.
+        format!("{digits}{}", "0".repeat((point - num_digits) as usize))
+    } else if 0 < point && point <= 21 {
+        // A decimal with the point inside the digits, i.e. `12.3`.
+        format!("{}.{}", &digits[..point as usize], &digits[point as usize..])
+    } else if -6 < point && point <= 0 {
+        // A decimal with leading zeros, i.e. `0.000123`.
+        format!("0.{}{digits}", "0".repeat((-point) as usize))
+    } else {
+        // Exponent notation, i.e. `1.23e+21` or `1e-7`.
+        let sign = if point - 1 < 0 { '-' } else { '+' };
+        match digits.len() {
+            1 => format!("{digits}e{sign}{}", (point - 1).abs()),
+            _ => format!("{}.{}e{sign}{}", &digits[..1], &digits[1..], (point - 1).abs()),
+        }
+    };
+
+    match float.is_sign_negative() {
+        true => format!("-{body}"),
+        false => body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_object() {
+        let value = json!({ "b": [1, { "d": true, "c": null }], "a": "\u{1f}\"\n", "\u{ff61}": 1, "\u{1f600}": 2 });
+        // U+1F600 sorts before U+FF61, as its UTF-16 encoding starts with the surrogate 0xD83D.
+        assert_eq!(
+            to_canonical_string(&value),
+            "{\"a\":\"\\u001f\\\"\\n\",\"b\":[1,{\"c\":null,\"d\":true}],\"\u{1f600}\":2,\"\u{ff61}\":1}"
+        );
+    }
+
+    #[test]
+    fn test_canonical_key_order_independent() {
+        let left = json!({ "x": 1, "y": { "p": 1, "q": 2 } });
+        let right = json!({ "y": { "q": 2, "p": 1 }, "x": 1 });
+        assert_eq!(to_canonical_string(&left), to_canonical_string(&right));
+    }
+
+    #[test]
+    fn test_canonical_numbers() {
+        let cases = [
+            (json!(0), "0"),
+            (json!(-0.0), "0"),
+            (json!(u64::MAX), "18446744073709552000"),
+            (json!(9007199254740992u64), "9007199254740992"),
+            (json!(1.5), "1.5"),
+            (json!(-123.0), "-123"),
+            (json!(0.000001), "0.000001"),
+            (json!(1e-7), "1e-7"),
+            (json!(1e21), "1e+21"),
+            (json!(1.25e22), "1.25e+22"),
+            (json!(1e9 / 3.0), "333333333.3333333"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(to_canonical_string(&value), expected);
+        }
+    }
+}
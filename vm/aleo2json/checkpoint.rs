@@ -0,0 +1,240 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{with_version, write_atomically, ConversionManifest, ExportIndex, Journal, ManifestEntry};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// The progress of a block-range export into a file, saved periodically so an interrupted export can resume
+/// where it stopped, instead of starting over.
+///
+/// The checkpoint file holds only the position of the export; the index and manifest entries are appended to a
+/// journal beside it (i.e. `checkpoint.journal`), so each save writes only the entries added since the previous one.
+#[derive(Clone, Debug)]
+pub struct ExportCheckpoint {
+    /// The height of the first block of the range.
+    pub from_height: u32,
+    /// The height of the last block of the range.
+    pub to_height: u32,
+    /// The height of the last block written, if any.
+    pub last_height: Option<u32>,
+    /// The length of the output file, in bytes, once the last block was written.
+    pub output_length: u64,
+    /// The index of the blocks written so far, if one is built.
+    pub index: Option<ExportIndex>,
+    /// The manifest of the blocks written so far, if one is built.
+    pub manifest: Option<ConversionManifest>,
+    /// The journal holding the saved index and manifest entries, once the checkpoint has been saved or read.
+    journal: Option<Journal>,
+    /// The length of the journal, in bytes, as of the last save.
+    journal_length: u64,
+    /// The number of indexed transactions, indexed transitions, and manifest entries in the journal.
+    saved: (usize, usize, usize),
+}
+
+impl ExportCheckpoint {
+    /// Initializes the checkpoint of an export of the given range that has not started,
+    /// building an index and a manifest if requested.
+    pub fn new(heights: &RangeInclusive<u32>, index: bool, manifest: bool) -> Self {
+        Self {
+            from_height: *heights.start(),
+            to_height: *heights.end(),
+            last_height: None,
+            output_length: 0,
+            index: index.then(ExportIndex::new),
+            manifest: manifest.then(ConversionManifest::new),
+            journal: None,
+            journal_length: 0,
+            saved: (0, 0, 0),
+        }
+    }
+
+    /// Returns the range of heights left to export, or `None` if the export is complete.
+    pub fn remaining(&self) -> Option<RangeInclusive<u32>> {
+        match self.last_height {
+            Some(height) if height >= self.to_height => None,
+            Some(height) => Some(height + 1..=self.to_height),
+            None => Some(self.from_height..=self.to_height),
+        }
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        with_version(json!({
+            "type": "ExportCheckpoint",
+            "from_height": self.from_height,
+            "to_height": self.to_height,
+            "last_height": self.last_height,
+            "output_length": self.output_length,
+            "index": self.index.is_some(),
+            "manifest": self.manifest.is_some(),
+            "journal_length": self.journal_length,
+        }))
+    }
+
+    /// Reads a checkpoint from its JSON, the inverse of `to_json`, without the index and manifest entries
+    /// held in its journal.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let height = |key: &str| -> Result<Option<u32>> { Ok(value[key].as_u64().map(u32::try_from).transpose()?) };
+        Ok(Self {
+            from_height: height("from_height")?
+                .ok_or_else(|| anyhow!("Expected the starting height of a checkpoint"))?,
+            to_height: height("to_height")?.ok_or_else(|| anyhow!("Expected the ending height of a checkpoint"))?,
+            last_height: height("last_height")?,
+            output_length: value["output_length"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("Expected the output length of a checkpoint"))?,
+            index: value["index"].as_bool().unwrap_or_default().then(ExportIndex::new),
+            manifest: value["manifest"].as_bool().unwrap_or_default().then(ConversionManifest::new),
+            journal: None,
+            journal_length: value["journal_length"].as_u64().unwrap_or_default(),
+            saved: (0, 0, 0),
+        })
+    }
+
+    /// Reads the checkpoint at the given path, with the index and manifest entries in its journal.
+    pub fn read(path: &Path) -> Result<Self> {
+        let mut checkpoint = Self::from_json(&serde_json::from_str(&std::fs::read_to_string(path)?)?)?;
+        if checkpoint.journal_length > 0 {
+            let (journal, entries) = Journal::restore(&journal_path(path), checkpoint.journal_length)?;
+            for entry in entries {
+                if let Some(index) = &mut checkpoint.index {
+                    index.extend_from_json(&entry["index"])?;
+                }
+                if let Some(manifest) = &mut checkpoint.manifest {
+                    let j_entries =
+                        entry["entries"].as_array().ok_or_else(|| anyhow!("Expected the manifest entries"))?;
+                    for j_entry in j_entries {
+                        manifest.push(ManifestEntry::from_json(j_entry)?);
+                    }
+                }
+            }
+            checkpoint.journal = Some(journal);
+            checkpoint.saved = checkpoint.num_entries();
+        }
+        Ok(checkpoint)
+    }
+
+    /// Writes the checkpoint to the given path, appending the index and manifest entries added since the last save
+    /// to its journal, and replacing the previous checkpoint only once it is fully written.
+    pub fn write(&mut self, path: &Path) -> Result<()> {
+        if self.index.is_some() || self.manifest.is_some() {
+            let (num_transactions, num_transitions, num_entries) = self.saved;
+            let entry = json!({
+                "index": self.index.as_ref().map(|index| index.delta_json(num_transactions, num_transitions)),
+                "entries": self.manifest.as_ref().map(|manifest| {
+                    manifest.entries()[num_entries..].iter().map(ManifestEntry::to_json).collect::<Vec<_>>()
+                }),
+            });
+            let journal = match self.journal.take() {
+                Some(journal) => journal,
+                None => Journal::create(&journal_path(path))?,
+            };
+            self.journal_length = self.journal.insert(journal).append(&entry)?;
+            self.saved = self.num_entries();
+        }
+        write_atomically(path, serde_json::to_string(&self.to_json())?.as_bytes())
+    }
+
+    /// Returns the number of indexed transactions, indexed transitions, and manifest entries.
+    fn num_entries(&self) -> (usize, usize, usize) {
+        let (num_transactions, num_transitions) =
+            self.index.as_ref().map_or((0, 0), |index| (index.num_transactions(), index.num_transitions()));
+        (num_transactions, num_transitions, self.manifest.as_ref().map_or(0, |manifest| manifest.entries().len()))
+    }
+}
+
+/// Returns the path of the journal of the checkpoint at the given path.
+fn journal_path(path: &Path) -> PathBuf {
+    path.with_extension("journal")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::checksum;
+
+    #[test]
+    fn test_export_checkpoint() {
+        let mut checkpoint = ExportCheckpoint::new(&(3..=9), false, true);
+        assert_eq!(checkpoint.remaining(), Some(3..=9));
+
+        checkpoint.last_height = Some(5);
+        checkpoint.output_length = 1024;
+        checkpoint.manifest.as_mut().unwrap().push(ManifestEntry {
+            output: None,
+            offset: Some(0),
+            input: "ab1hash".to_string(),
+            checksum: checksum(b"block").unwrap(),
+            height: Some(3),
+            edition: None,
+        });
+        assert_eq!(checkpoint.remaining(), Some(6..=9));
+
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("checkpoint.json");
+        checkpoint.write(&path).unwrap();
+        let read = ExportCheckpoint::read(&path).unwrap();
+        assert_eq!(read.to_json(), checkpoint.to_json());
+        assert!(read.index.is_none());
+        assert_eq!(read.manifest, checkpoint.manifest);
+
+        checkpoint.last_height = Some(9);
+        assert_eq!(checkpoint.remaining(), None);
+    }
+
+    #[test]
+    fn test_export_checkpoint_journal() {
+        let entry = |height: u32| ManifestEntry {
+            output: None,
+            offset: Some(height as u64),
+            input: format!("ab1hash{height}"),
+            checksum: checksum(&height.to_le_bytes()).unwrap(),
+            height: Some(height),
+            edition: None,
+        };
+
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("checkpoint.json");
+        let mut checkpoint = ExportCheckpoint::new(&(0..=9), false, true);
+        checkpoint.manifest.as_mut().unwrap().push(entry(0));
+        checkpoint.last_height = Some(0);
+        checkpoint.write(&path).unwrap();
+        let first = std::fs::read(&path).unwrap();
+
+        // Each save appends only the entries added since the previous one.
+        checkpoint.manifest.as_mut().unwrap().push(entry(1));
+        checkpoint.last_height = Some(1);
+        checkpoint.write(&path).unwrap();
+        let journal = std::fs::read_to_string(directory.path().join("checkpoint.journal")).unwrap();
+        let lines = journal.lines().map(|line| serde_json::from_str::<Value>(line).unwrap()).collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["entries"], json!([entry(1).to_json()]));
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("ab1hash"));
+        assert_eq!(ExportCheckpoint::read(&path).unwrap().manifest, checkpoint.manifest);
+
+        // The entries appended after the checkpoint that is read are discarded once it is saved again.
+        std::fs::write(&path, first).unwrap();
+        let mut read = ExportCheckpoint::read(&path).unwrap();
+        assert_eq!(read.manifest.as_ref().unwrap().entries(), &[entry(0)]);
+        read.manifest.as_mut().unwrap().push(entry(2));
+        read.write(&path).unwrap();
+        assert_eq!(ExportCheckpoint::read(&path).unwrap().manifest.unwrap().entries(), &[entry(0), entry(2)]);
+    }
+}
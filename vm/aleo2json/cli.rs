@@ -0,0 +1,215 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::commands::{
+    Abi,
+    CompareSerializations,
+    ConvertProgram,
+    ConvertStream,
+    Corpus,
+    Decompile,
+    ExportBlocks,
+    ExportDeployments,
+    ExportStateDiff,
+    Facts,
+    Fetch,
+    Genesis,
+    LintProgram,
+    Migrate,
+    Opcodes,
+    PatchSource,
+    R1cs,
+    Rpc,
+    Schema,
+    Serve,
+    Similarity,
+    Simulate,
+    Smt,
+    TraceFunction,
+};
+#[cfg(feature = "rocks")]
+use crate::aleo2json::commands::{ExportLedger, Staking};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[clap(name = "aleo2json", author = "The Aleo Team <hello@aleo.org>", args_conflicts_with_subcommands = true)]
+pub struct CLI {
+    /// The path to an Aleo program to convert, if no subcommand is given.
+    pub path: Option<PathBuf>,
+    /// Suppress the progress bars, and the summaries of the commands writing their results to a file (for CI).
+    /// The results written to stdout, and the errors, are still emitted.
+    #[clap(long, short, global = true)]
+    pub quiet: bool,
+    /// Specify a subcommand.
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl CLI {
+    /// Parse the command, falling back to converting the given program path.
+    pub fn parse_command(self) -> Result<String> {
+        match (self.command, self.path) {
+            (Some(mut command), _) => {
+                if !self.quiet {
+                    return command.parse();
+                }
+                command.disable_progress();
+                let is_summary = command.returns_summary();
+                let output = command.parse()?;
+                match is_summary {
+                    true => Ok(String::new()),
+                    false => Ok(output),
+                }
+            }
+            (None, Some(path)) => ConvertProgram::from_path(path).parse(),
+            (None, None) => bail!("No path provided"),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub enum Command {
+    #[clap(name = "abi")]
+    Abi(Abi),
+    #[clap(name = "corpus")]
+    Corpus(Corpus),
+    #[clap(name = "decompile")]
+    Decompile(Decompile),
+    #[clap(name = "differential")]
+    Differential(CompareSerializations),
+    #[clap(name = "blocks")]
+    ExportBlocks(ExportBlocks),
+    #[clap(name = "deployments")]
+    ExportDeployments(ExportDeployments),
+    #[cfg(feature = "rocks")]
+    #[clap(name = "ledger-export")]
+    ExportLedger(ExportLedger),
+    #[clap(name = "facts")]
+    Facts(Facts),
+    #[clap(name = "fetch")]
+    Fetch(Fetch),
+    #[clap(name = "genesis")]
+    Genesis(Genesis),
+    #[clap(name = "lint")]
+    Lint(LintProgram),
+    #[clap(name = "migrate")]
+    Migrate(Migrate),
+    #[clap(name = "opcodes")]
+    Opcodes(Opcodes),
+    #[clap(name = "patch")]
+    Patch(PatchSource),
+    #[clap(name = "program")]
+    Program(ConvertProgram),
+    #[clap(name = "r1cs")]
+    R1cs(R1cs),
+    #[clap(name = "rpc")]
+    Rpc(Rpc),
+    #[clap(name = "schema")]
+    Schema(Schema),
+    #[clap(name = "serve")]
+    Serve(Serve),
+    #[clap(name = "similarity")]
+    Similarity(Similarity),
+    #[clap(name = "simulate")]
+    Simulate(Simulate),
+    #[clap(name = "smt")]
+    Smt(Smt),
+    #[cfg(feature = "rocks")]
+    #[clap(name = "staking")]
+    Staking(Staking),
+    #[clap(name = "state-diff")]
+    StateDiff(ExportStateDiff),
+    #[clap(name = "stream")]
+    Stream(ConvertStream),
+    #[clap(name = "trace")]
+    Trace(TraceFunction),
+}
+
+impl Command {
+    /// Parse the command.
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Abi(command) => command.parse(),
+            Self::Corpus(command) => command.parse(),
+            Self::Decompile(command) => command.parse(),
+            Self::Differential(command) => command.parse(),
+            Self::ExportBlocks(command) => command.parse(),
+            Self::ExportDeployments(command) => command.parse(),
+            #[cfg(feature = "rocks")]
+            Self::ExportLedger(command) => command.parse(),
+            Self::Facts(command) => command.parse(),
+            Self::Fetch(command) => command.parse(),
+            Self::Genesis(command) => command.parse(),
+            Self::Lint(command) => command.parse(),
+            Self::Migrate(command) => command.parse(),
+            Self::Opcodes(command) => command.parse(),
+            Self::Patch(command) => command.parse(),
+            Self::Program(command) => command.parse(),
+            Self::R1cs(command) => command.parse(),
+            Self::Rpc(command) => command.parse(),
+            Self::Schema(command) => command.parse(),
+            Self::Serve(command) => command.parse(),
+            Self::Similarity(command) => command.parse(),
+            Self::Simulate(command) => command.parse(),
+            Self::Smt(command) => command.parse(),
+            #[cfg(feature = "rocks")]
+            Self::Staking(command) => command.parse(),
+            Self::StateDiff(command) => command.parse(),
+            Self::Stream(command) => command.parse(),
+            Self::Trace(command) => command.parse(),
+        }
+    }
+
+    /// Returns `true` if the command returns a summary of its results, rather than the results themselves,
+    /// i.e. when it writes them to a file, or to stdout as they are converted.
+    fn returns_summary(&self) -> bool {
+        match self {
+            Self::ExportBlocks(_) => true,
+            Self::ExportDeployments(command) => command.output.is_some(),
+            #[cfg(feature = "rocks")]
+            Self::ExportLedger(command) => command.socket.is_some(),
+            Self::Facts(command) => command.output.is_some() && command.format == crate::aleo2json::FactFormat::Tsv,
+            Self::Patch(command) => command.output.is_some(),
+            Self::Program(command) => command.out_dir.is_some(),
+            Self::Stream(command) => command.output.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Disables the progress bars of the command, if it has any.
+    fn disable_progress(&mut self) {
+        match self {
+            Self::ExportBlocks(command) => command.progress = false,
+            #[cfg(feature = "rocks")]
+            Self::ExportLedger(command) => command.progress = false,
+            Self::Program(command) => command.progress = false,
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A test case recommended by clap (https://docs.rs/clap/latest/clap/_derive/_tutorial/index.html#testing).
+    #[test]
+    fn verify_cli() {
+        use clap::CommandFactory;
+        CLI::command().debug_assert()
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{Limiter, NetworkCache, Permit},
+    console::{
+        network::{prelude::DeserializeOwned, Network},
+        program::ProgramID,
+    },
+    ledger::block::{Block, Transaction},
+};
+
+use anyhow::{bail, Result};
+use parking_lot::Mutex;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::debug;
+
+/// The statuses of the responses worth retrying: rate limiting, and transient server failures.
+const RETRYABLE_STATUSES: [u16; 5] = [429, 500, 502, 503, 504];
+
+/// The configuration for the REST client.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientConfig {
+    /// The number of times a failed request is retried.
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled before each further retry.
+    pub initial_backoff: Duration,
+    /// The longest delay before a retry, including the delays requested by the node.
+    pub max_backoff: Duration,
+    /// The maximum number of requests sent per second, if limited.
+    pub requests_per_second: Option<f64>,
+    /// The maximum number of requests in flight, if limited.
+    pub max_concurrency: Option<usize>,
+    /// The timeout of each request.
+    pub timeout: Duration,
+    /// The directory caching the fetched blocks across runs, if any.
+    pub cache: Option<PathBuf>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            requests_per_second: None,
+            max_concurrency: None,
+            timeout: Duration::from_secs(30),
+            cache: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Returns the delay before the given retry, counting from zero.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff.saturating_mul(2u32.saturating_pow(retry)).min(self.max_backoff)
+    }
+
+    /// Ensures the configuration is valid, i.e. the rate limit, if set, is positive and finite.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(requests_per_second) = self.requests_per_second {
+            check_rate_limit(requests_per_second)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the given rate limit, in requests per second, if it is positive and finite.
+pub fn check_rate_limit(requests_per_second: f64) -> Result<f64> {
+    match requests_per_second.is_finite() && requests_per_second > 0.0 {
+        true => Ok(requests_per_second),
+        false => bail!("The rate limit must be a positive number of requests per second, not '{requests_per_second}'"),
+    }
+}
+
+/// A client of a node's REST API, retrying failed requests with exponential backoff, and limiting the rate
+/// and concurrency of its requests. Clones of the client share its limits, so it can be used from many threads.
+#[derive(Clone)]
+pub struct RestClient {
+    /// The base URL of the node, i.e. `http://localhost:3030`.
+    endpoint: String,
+    /// The client configuration.
+    config: ClientConfig,
+    /// The HTTP agent.
+    agent: ureq::Agent,
+    /// The limiter on the number of in-flight requests, if limited.
+    limiter: Option<Arc<Limiter>>,
+    /// The earliest time the next request may be sent, if the rate is limited.
+    next_request: Arc<Mutex<Instant>>,
+    /// The cache of the fetched blocks, if any.
+    cache: Option<NetworkCache>,
+}
+
+impl RestClient {
+    /// Initializes a client of the node at the given base URL, with the default configuration.
+    pub fn new(endpoint: &str) -> Self {
+        Self::build(endpoint, ClientConfig::default())
+    }
+
+    /// Initializes a client of the node at the given base URL, with the given configuration,
+    /// failing if the configuration is invalid.
+    pub fn with_config(endpoint: &str, config: ClientConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self::build(endpoint, config))
+    }
+
+    /// Initializes a client of the node at the given base URL, with the given valid configuration.
+    fn build(endpoint: &str, config: ClientConfig) -> Self {
+        let agent = ureq::AgentBuilder::new().timeout(config.timeout).build();
+        let limiter = config.max_concurrency.map(|slots| Arc::new(Limiter::new(slots.max(1))));
+        let cache = config.cache.clone().map(NetworkCache::new);
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            config,
+            agent,
+            limiter,
+            next_request: Arc::new(Mutex::new(Instant::now())),
+            cache,
+        }
+    }
+
+    /// Returns the base URL of the node.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the client configuration.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Performs a GET request to the given path of the node's API, retrying it if it fails transiently.
+    pub fn get(&self, path: &str) -> Result<ureq::Response> {
+        self.get_url(&format!("{}/{}", self.endpoint, path.trim_start_matches('/')))
+    }
+
+    /// Performs a GET request to the given URL, retrying it if it fails transiently, within the limits of the client.
+    pub fn get_url(&self, url: &str) -> Result<ureq::Response> {
+        let mut retry = 0;
+        loop {
+            let result = {
+                let _permit = self.acquire();
+                self.agent.get(url).call()
+            };
+            let error = match result {
+                Ok(response) if response.status() == 200 => return Ok(response),
+                Ok(response) => bail!("Failed to fetch from {url} (status {})", response.status()),
+                Err(error) => error,
+            };
+            // The delay requested by the node, if the failure is worth retrying.
+            let requested_delay = match &error {
+                ureq::Error::Status(status, response) if RETRYABLE_STATUSES.contains(status) => {
+                    Some(response.header("Retry-After").and_then(|seconds| seconds.parse().ok()))
+                }
+                ureq::Error::Status(..) => None,
+                ureq::Error::Transport(_) => Some(None),
+            };
+            match requested_delay {
+                Some(requested_delay) if retry < self.config.max_retries => {
+                    let delay = match requested_delay {
+                        Some(seconds) => Duration::from_secs(seconds).min(self.config.max_backoff),
+                        None => self.config.backoff(retry),
+                    };
+                    debug!("Retrying {url} in {delay:?}: {error}");
+                    std::thread::sleep(delay);
+                    retry += 1;
+                }
+                _ => return Err(error.into()),
+            }
+        }
+    }
+
+    /// Fetches and deserializes the JSON at the given path of the node's API.
+    pub fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        Ok(self.get(path)?.into_json()?)
+    }
+
+    /// Fetches and deserializes the JSON at the given path of the node's API, returning `None` if it is not found.
+    pub fn find_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        match self.get(path) {
+            Ok(response) => Ok(Some(response.into_json()?)),
+            Err(error) => match error.downcast_ref::<ureq::Error>() {
+                Some(ureq::Error::Status(404, _)) => Ok(None),
+                _ => Err(error),
+            },
+        }
+    }
+
+    /// Returns the height of the latest block.
+    pub fn latest_height<N: Network>(&self) -> Result<u32> {
+        self.get_json(&format!("{}/latest/height", network_name::<N>()?))
+    }
+
+    /// Returns the block at the given height, from the cache if it holds the block.
+    pub fn get_block<N: Network>(&self, height: u32) -> Result<Block<N>> {
+        if let Some(cache) = &self.cache {
+            if let Some(block) = cache.get_block_at_height(height)? {
+                return Ok(block);
+            }
+        }
+        let block: Block<N> = self.get_json(&format!("{}/block/{height}", network_name::<N>()?))?;
+        if let Some(cache) = &self.cache {
+            cache.insert_block(&block)?;
+        }
+        Ok(block)
+    }
+
+    /// Returns the transaction with the given ID.
+    pub fn get_transaction<N: Network>(&self, transaction_id: &N::TransactionID) -> Result<Transaction<N>> {
+        self.get_json(&format!("{}/transaction/{transaction_id}", network_name::<N>()?))
+    }
+
+    /// Returns the source of the deployed program with the given ID.
+    pub fn get_program<N: Network>(&self, program_id: &ProgramID<N>) -> Result<String> {
+        self.get_json(&format!("{}/program/{program_id}", network_name::<N>()?))
+    }
+
+    /// Blocks until the limits allow another request, returning the permit to hold while it is in flight.
+    fn acquire(&self) -> Option<Permit> {
+        let permit = self.limiter.as_ref().map(Limiter::acquire);
+        if let Some(requests_per_second) = self.config.requests_per_second {
+            let interval = Duration::from_secs_f64(1.0 / requests_per_second);
+            // Reserve the next slot, then wait for it without holding the lock.
+            let wait = {
+                let mut next_request = self.next_request.lock();
+                let now = Instant::now();
+                let slot = (*next_request).max(now);
+                *next_request = slot + interval;
+                slot - now
+            };
+            std::thread::sleep(wait);
+        }
+        permit
+    }
+}
+
+/// Returns the name of the given network in the paths of the node's API.
+fn network_name<N: Network>() -> Result<&'static str> {
+    match N::ID {
+        3 => Ok("testnet3"),
+        _ => bail!("Unsupported network ID in node query"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let config = ClientConfig::default();
+        assert_eq!(config.backoff(0), Duration::from_millis(500));
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(3), Duration::from_secs(4));
+        assert_eq!(config.backoff(10), Duration::from_secs(10));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_endpoint() {
+        assert_eq!(RestClient::new("http://localhost:3030/").endpoint(), "http://localhost:3030");
+    }
+
+    #[test]
+    fn test_unreachable() {
+        // Nothing listens on port 1, so the request fails, after the configured retries.
+        let config = ClientConfig {
+            max_retries: 1,
+            initial_backoff: Duration::from_millis(1),
+            requests_per_second: Some(1000.0),
+            max_concurrency: Some(1),
+            ..Default::default()
+        };
+        let client = RestClient::with_config("http://127.0.0.1:1", config).unwrap();
+        assert!(client.get("testnet3/latest/height").is_err());
+    }
+
+    #[test]
+    fn test_invalid_rate_limit() {
+        for requests_per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let config = ClientConfig { requests_per_second: Some(requests_per_second), ..Default::default() };
+            assert!(RestClient::with_config("http://127.0.0.1:1", config).is_err());
+        }
+        assert_eq!(check_rate_limit(0.5).unwrap(), 0.5);
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[cfg(feature = "rocks")]
+use crate::ledger::store::{helpers::rocksdb::BlockDB, BlockStore};
+use crate::{
+    aleo2json::{
+        export_block_range_resumable,
+        export_block_range_with_progress,
+        BlockOptions,
+        BlockSource,
+        ConversionManifest,
+        ExportCheckpoint,
+        ExportIndex,
+        OutputSink,
+        Progress,
+        ProgressBar,
+        ProgressCallback,
+        WriterSink,
+    },
+    ledger::store::helpers::memory::BlockMemory,
+};
+
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+/// Converts a contiguous range of blocks into newline-delimited block JSON, in order of height.
+#[derive(Debug, Parser)]
+pub struct ExportBlocks {
+    /// The height of the first block to convert.
+    #[clap(long)]
+    pub from_height: u32,
+    /// The height of the last block to convert (inclusive).
+    #[clap(long)]
+    pub to_height: u32,
+    /// The base URL of a node to fetch the blocks from, i.e. `http://localhost:3030`.
+    #[clap(long)]
+    pub endpoint: Option<String>,
+    /// The development ID of the local ledger to read from, if no endpoint is given.
+    #[cfg(feature = "rocks")]
+    #[clap(long, conflicts_with = "endpoint")]
+    pub dev: Option<u16>,
+    /// The maximum number of blocks fetched in parallel.
+    #[clap(long, alias = "jobs", default_value = "8")]
+    pub concurrency: usize,
+    /// The options configuring the requests to the node.
+    #[clap(flatten)]
+    pub client: ClientOptions,
+    /// Includes the Merkle inclusion paths of each transaction and transition.
+    #[clap(long)]
+    pub merkle_paths: bool,
+    /// Recomputes the block hash, the block roots, and every transaction and transition ID, reporting each check.
+    #[clap(long)]
+    pub verify: bool,
+    /// Pairs the header targets, weights, and rewards with human-readable renderings, and breaks down each fee.
+    #[clap(long)]
+    pub economics: bool,
+    /// Pairs each header timestamp with its ISO-8601 rendering in UTC.
+    #[clap(long)]
+    pub iso_timestamps: bool,
+    /// Includes the Merkle tree of the block header, with its leaves, intermediate nodes, and root.
+    #[clap(long)]
+    pub header_tree: bool,
+    /// Groups the finalize operations of the block by the mapping they update, for state indexing.
+    #[clap(long)]
+    pub mapping_updates: bool,
+    /// Attributes the puzzle reward of each block to its prover solutions, with the reward of each prover.
+    #[clap(long)]
+    pub puzzle_rewards: bool,
+    /// Summarizes the fees, public transfers, and staking operations of each transaction.
+    #[clap(long)]
+    pub value_flow: bool,
+    /// Lists every address appearing in each block with the role it played, for address-indexed lookups.
+    #[clap(long)]
+    pub address_activity: bool,
+    /// Explains each rejected transaction with its rejected ID, its original ID, and the reason where derivable.
+    #[clap(long)]
+    pub rejections: bool,
+    /// Decodes the public transition inputs and outputs against their declared types, with the import options.
+    #[clap(long)]
+    pub typed_values: bool,
+    /// Refers each transition to the node ID of its function in the converted program, with the import options.
+    #[clap(long)]
+    pub function_refs: bool,
+    /// A view key whose output records are decrypted next to their ciphertexts; may be given more than once.
+    #[clap(long = "view-key")]
+    pub view_keys: Vec<String>,
+    /// Exports each output record with its commitment, checksum, nonce, and ciphertext, decrypted with the view keys.
+    #[clap(long)]
+    pub record_outputs: bool,
+    /// The private keys whose output records are checked, deriving their serial numbers.
+    #[clap(flatten)]
+    pub private_keys: PrivateKeyOptions,
+    /// Recomputes the commitment of each output record owned by the given keys, and reports whether it matches.
+    #[clap(long)]
+    pub check_records: bool,
+    /// Describes the verifying key and certificate of each deployed function, next to them.
+    #[clap(long)]
+    pub key_metadata: bool,
+    /// Include the source checksum of the program of each deployment (the SHA3-256 hash of its canonical source),
+    /// next to its edition.
+    #[clap(long)]
+    pub source_checksums: bool,
+    /// The file to write to; if omitted, the blocks are written to stdout.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+    /// The address of a TCP socket to stream the blocks to (i.e. `localhost:9000`), instead of a file or stdout.
+    #[clap(long, conflicts_with = "output")]
+    pub socket: Option<String>,
+    /// The file to write an index of the transactions and transitions to, with the byte offset of each block.
+    #[clap(long, requires = "output")]
+    pub index: Option<PathBuf>,
+    /// The file to write a manifest to, listing each block with its hash, checksum, height, and byte offset.
+    #[clap(long, requires = "output")]
+    pub manifest: Option<PathBuf>,
+    /// The file to save the progress of the export to, so an interrupted export resumes from it when rerun;
+    /// it is removed once the export completes.
+    #[clap(long, requires = "output")]
+    pub checkpoint: Option<PathBuf>,
+    /// The number of blocks written between checkpoints.
+    #[clap(long, default_value = "1000")]
+    pub checkpoint_interval: u32,
+    /// Shows a progress bar on stderr, updated after each batch.
+    #[clap(long)]
+    pub progress: bool,
+    /// The options configuring the JSON output.
+    #[clap(flatten)]
+    pub json: OutputOptions,
+}
+
+impl ExportBlocks {
+    /// Converts the blocks, returning a summary if they were written to a file.
+    pub fn parse(self) -> Result<String> {
+        let heights = self.from_height..=self.to_height;
+        let mut index = self.index.as_ref().map(|_| ExportIndex::new());
+        let mut manifest = self.manifest.as_ref().map(|_| ConversionManifest::new());
+
+        if let Some(address) = &self.socket {
+            let num_blocks = self.export(heights, &mut WriterSink::connect(address.as_str())?, None, None)?;
+            return Ok(format!("Streamed {num_blocks} blocks to {address}"));
+        }
+
+        let num_blocks = match (&self.output, &self.checkpoint) {
+            (Some(path), Some(checkpoint_path)) => {
+                let mut checkpoint = match checkpoint_path.is_file() {
+                    true => ExportCheckpoint::read(checkpoint_path)?,
+                    false => ExportCheckpoint::new(&heights, self.index.is_some(), self.manifest.is_some()),
+                };
+                anyhow::ensure!(
+                    (checkpoint.from_height, checkpoint.to_height) == (self.from_height, self.to_height)
+                        && checkpoint.index.is_some() == self.index.is_some()
+                        && checkpoint.manifest.is_some() == self.manifest.is_some(),
+                    "The checkpoint is of a different export; remove it to start over"
+                );
+                let num_blocks = self.export_resumable(path, &mut checkpoint, checkpoint_path)?;
+                index = checkpoint.index;
+                manifest = checkpoint.manifest;
+                num_blocks
+            }
+            (Some(path), None) => {
+                let mut sink = WriterSink::new(BufWriter::new(File::create(path)?));
+                self.export(heights, &mut sink, index.as_mut(), manifest.as_mut())?
+            }
+            (None, _) => {
+                return self
+                    .export(heights, &mut WriterSink::new(std::io::stdout().lock()), None, None)
+                    .map(|_| String::new())
+            }
+        };
+
+        if let (Some(path), Some(index), Some(output)) = (&self.index, index, &self.output) {
+            let mut j_index = index.to_json();
+            j_index["file"] = serde_json::json!(output.display().to_string());
+            std::fs::write(path, serde_json::to_string(&j_index)?)?;
+        }
+        if let (Some(path), Some(mut manifest), Some(output)) = (&self.manifest, manifest, &self.output) {
+            manifest.set_output(output);
+            manifest.write(path)?;
+        }
+        if let Some(checkpoint_path) = &self.checkpoint {
+            std::fs::remove_file(checkpoint_path)?;
+        }
+
+        Ok(format!("Exported {num_blocks} blocks"))
+    }
+
+    /// Returns the options selecting the sections of each block.
+    fn block_options(&self) -> Result<BlockOptions> {
+        // The private keys are only read if the records are checked.
+        let private_keys = match self.check_records {
+            true => self.private_keys.read()?,
+            false => Vec::new(),
+        };
+        Ok(BlockOptions {
+            merkle_paths: self.merkle_paths,
+            verification: self.verify,
+            economics: self.economics,
+            iso_timestamps: self.iso_timestamps,
+            header_tree: self.header_tree,
+            mapping_updates: self.mapping_updates,
+            puzzle_rewards: self.puzzle_rewards,
+            value_flow: self.value_flow,
+            address_activity: self.address_activity,
+            rejections: self.rejections,
+            typed_values: self.typed_values,
+            function_refs: self.function_refs,
+            view_keys: self.view_keys.clone(),
+            private_keys,
+            record_outputs: self.record_outputs,
+            record_checks: self.check_records,
+            key_metadata: self.key_metadata,
+            source_checksums: self.source_checksums,
+            json: self.json.to_json_options(),
+        })
+    }
+
+    /// Converts the blocks left in the given checkpoint's range from the configured source, saving the checkpoint.
+    fn export_resumable(
+        &self,
+        output: &Path,
+        checkpoint: &mut ExportCheckpoint,
+        checkpoint_path: &Path,
+    ) -> Result<usize> {
+        let options = self.block_options()?;
+        let bar = ProgressBar::new("blocks");
+        let report = |progress: Progress| bar.report(progress);
+        let progress = self.progress.then_some(&report as &ProgressCallback);
+        let interval = self.checkpoint_interval;
+        match &self.endpoint {
+            Some(endpoint) => {
+                let client = self.client.to_client(endpoint, Some(self.concurrency))?;
+                let source = BlockSource::<CurrentNetwork, BlockMemory<_>>::from(client);
+                export_block_range_resumable(
+                    &source,
+                    self.concurrency,
+                    &options,
+                    output,
+                    checkpoint,
+                    checkpoint_path,
+                    interval,
+                    progress,
+                )
+            }
+            #[cfg(feature = "rocks")]
+            None => {
+                let source = BlockSource::from(BlockStore::<CurrentNetwork, BlockDB<_>>::open(self.dev)?);
+                export_block_range_resumable(
+                    &source,
+                    self.concurrency,
+                    &options,
+                    output,
+                    checkpoint,
+                    checkpoint_path,
+                    interval,
+                    progress,
+                )
+            }
+            #[cfg(not(feature = "rocks"))]
+            None => anyhow::bail!("An '--endpoint' is required, as reading local storage requires the 'rocks' feature"),
+        }
+    }
+
+    /// Converts the blocks from the configured source.
+    fn export<S: OutputSink>(
+        &self,
+        heights: std::ops::RangeInclusive<u32>,
+        sink: &mut S,
+        index: Option<&mut ExportIndex>,
+        manifest: Option<&mut ConversionManifest>,
+    ) -> Result<usize> {
+        let options = self.block_options()?;
+        let bar = ProgressBar::new("blocks");
+        let report = |progress: Progress| bar.report(progress);
+        let progress = self.progress.then_some(&report as &ProgressCallback);
+        match &self.endpoint {
+            Some(endpoint) => {
+                let client = self.client.to_client(endpoint, Some(self.concurrency))?;
+                let source = BlockSource::<CurrentNetwork, BlockMemory<_>>::from(client);
+                export_block_range_with_progress(
+                    &source,
+                    heights,
+                    self.concurrency,
+                    &options,
+                    sink,
+                    index,
+                    manifest,
+                    progress,
+                )
+            }
+            #[cfg(feature = "rocks")]
+            None => {
+                let source = BlockSource::from(BlockStore::<CurrentNetwork, BlockDB<_>>::open(self.dev)?);
+                export_block_range_with_progress(
+                    &source,
+                    heights,
+                    self.concurrency,
+                    &options,
+                    sink,
+                    index,
+                    manifest,
+                    progress,
+                )
+            }
+            #[cfg(not(feature = "rocks"))]
+            None => anyhow::bail!("An '--endpoint' is required, as reading local storage requires the 'rocks' feature"),
+        }
+    }
+}
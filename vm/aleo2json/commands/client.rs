@@ -0,0 +1,56 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::aleo2json::{check_rate_limit, ClientConfig, RestClient};
+
+use std::{path::PathBuf, time::Duration};
+
+/// The options configuring the requests to a node, shared by the commands fetching from one.
+#[derive(Debug, Parser)]
+pub struct ClientOptions {
+    /// The number of times a failed request to the node is retried, with exponential backoff.
+    #[clap(long, default_value = "3")]
+    pub max_retries: u32,
+    /// The maximum number of requests sent to the node per second; if omitted, the rate is not limited.
+    #[clap(long, value_parser = parse_rate_limit)]
+    pub rate_limit: Option<f64>,
+    /// The timeout of each request to the node, in seconds.
+    #[clap(long, default_value = "30")]
+    pub timeout: u64,
+    /// The directory to cache the fetched blocks in, reused by later runs.
+    #[clap(long)]
+    pub cache: Option<PathBuf>,
+}
+
+impl ClientOptions {
+    /// Returns a client of the node at the given base URL, with at most the given number of requests in flight.
+    pub fn to_client(&self, endpoint: &str, max_concurrency: Option<usize>) -> Result<RestClient> {
+        let config = ClientConfig {
+            max_retries: self.max_retries,
+            requests_per_second: self.rate_limit,
+            max_concurrency,
+            timeout: Duration::from_secs(self.timeout),
+            cache: self.cache.clone(),
+            ..Default::default()
+        };
+        RestClient::with_config(endpoint, config)
+    }
+}
+
+/// Parses a rate limit, in requests per second, rejecting the values that are not positive and finite.
+fn parse_rate_limit(value: &str) -> Result<f64> {
+    check_rate_limit(value.parse()?)
+}
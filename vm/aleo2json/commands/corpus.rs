@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use anyhow::bail;
+
+/// Downloads recent blocks and the programs most used in them from a node, and converts all of them,
+/// failing on any conversion error or schema violation.
+#[derive(Debug, Parser)]
+pub struct Corpus {
+    /// The base URL of a node to fetch the corpus from, i.e. `http://localhost:3030`.
+    #[clap(long)]
+    pub endpoint: String,
+    /// The number of most recent blocks to convert.
+    #[clap(long, default_value = "100")]
+    pub blocks: u32,
+    /// The number of programs to convert, picked by how many transitions of those blocks call them.
+    #[clap(long, default_value = "20")]
+    pub programs: usize,
+}
+
+impl Corpus {
+    /// Returns the corpus report as a pretty-printed JSON string, or fails if any item of the corpus failed.
+    pub fn parse(self) -> Result<String> {
+        let report = run_corpus::<CurrentNetwork>(&self.endpoint, self.blocks, self.programs)?;
+        let output = serde_json::to_string_pretty(&with_version(report.to_json()))?;
+        match report.passed() {
+            true => Ok(output),
+            false => bail!("{} items of the corpus failed\n{output}", report.failures.len()),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::PathBuf;
+
+/// Renders an Aleo program as Leo-like pseudocode, for reading and auditing.
+#[derive(Debug, Parser)]
+pub struct Decompile {
+    /// The path to the Aleo program.
+    pub path: PathBuf,
+}
+
+impl Decompile {
+    /// Returns the pseudocode of the program.
+    pub fn parse(self) -> Result<String> {
+        let program = parse_program::<CurrentNetwork>(&std::fs::read_to_string(&self.path)?)?;
+        Ok(decompile_program(&program))
+    }
+}
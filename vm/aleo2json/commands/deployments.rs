@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[cfg(feature = "rocks")]
+use crate::ledger::store::{helpers::rocksdb::BlockDB, BlockStore};
+use crate::{
+    aleo2json::{export_deployment_range, BlockSource, OutputSink, WriterSink},
+    ledger::store::helpers::memory::BlockMemory,
+};
+
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+/// Lists the deployments in a contiguous range of blocks as newline-delimited JSON, in order of height,
+/// each with its program ID, edition, deployer, block height, and converted program.
+#[derive(Debug, Parser)]
+pub struct ExportDeployments {
+    /// The height of the first block to scan.
+    #[clap(long)]
+    pub from_height: u32,
+    /// The height of the last block to scan (inclusive).
+    #[clap(long)]
+    pub to_height: u32,
+    /// The base URL of a node to fetch the blocks from, i.e. `http://localhost:3030`.
+    #[clap(long)]
+    pub endpoint: Option<String>,
+    /// The development ID of the local ledger to read from, if no endpoint is given.
+    #[cfg(feature = "rocks")]
+    #[clap(long, conflicts_with = "endpoint")]
+    pub dev: Option<u16>,
+    /// The maximum number of blocks fetched in parallel.
+    #[clap(long, alias = "jobs", default_value = "8")]
+    pub concurrency: usize,
+    /// The options configuring the requests to the node.
+    #[clap(flatten)]
+    pub client: ClientOptions,
+    /// The file to write to; if omitted, the deployments are written to stdout.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+    /// The options configuring the JSON output.
+    #[clap(flatten)]
+    pub json: OutputOptions,
+}
+
+impl ExportDeployments {
+    /// Lists the deployments, returning a summary if they were written to a file.
+    pub fn parse(self) -> Result<String> {
+        let heights = self.from_height..=self.to_height;
+        match &self.output {
+            Some(path) => {
+                let mut sink = WriterSink::new(BufWriter::new(File::create(path)?));
+                let num_deployments = self.export(heights, &mut sink)?;
+                Ok(format!("Exported {num_deployments} deployments"))
+            }
+            None => self.export(heights, &mut WriterSink::new(std::io::stdout().lock())).map(|_| String::new()),
+        }
+    }
+
+    /// Lists the deployments from the configured source.
+    fn export<S: OutputSink>(&self, heights: std::ops::RangeInclusive<u32>, sink: &mut S) -> Result<usize> {
+        let options = self.json.to_json_options();
+        match &self.endpoint {
+            Some(endpoint) => {
+                let client = self.client.to_client(endpoint, Some(self.concurrency))?;
+                let source = BlockSource::<CurrentNetwork, BlockMemory<_>>::from(client);
+                export_deployment_range(&source, heights, self.concurrency, &options, sink)
+            }
+            #[cfg(feature = "rocks")]
+            None => {
+                let source = BlockSource::from(BlockStore::<CurrentNetwork, BlockDB<_>>::open(self.dev)?);
+                export_deployment_range(&source, heights, self.concurrency, &options, sink)
+            }
+            #[cfg(not(feature = "rocks"))]
+            None => anyhow::bail!("An '--endpoint' is required, as reading local storage requires the 'rocks' feature"),
+        }
+    }
+}
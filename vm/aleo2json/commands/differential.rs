@@ -0,0 +1,41 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::ledger::block::{Block, Transaction};
+
+use std::path::PathBuf;
+
+/// Compares the Vanguard JSON of a block or transaction against its snarkVM JSON, reporting structural divergences.
+#[derive(Debug, Parser)]
+pub struct CompareSerializations {
+    /// The path to the block or transaction, encoded as snarkVM JSON (i.e. as returned by a node).
+    pub path: PathBuf,
+    /// Whether the file holds a transaction rather than a block.
+    #[clap(long)]
+    pub transaction: bool,
+}
+
+impl CompareSerializations {
+    /// Returns the divergences between the two serializations as a pretty-printed JSON string.
+    pub fn parse(self) -> Result<String> {
+        let source = std::fs::read_to_string(&self.path)?;
+        let differential = match self.transaction {
+            true => Differential::of(&serde_json::from_str::<Transaction<CurrentNetwork>>(&source)?)?,
+            false => Differential::of(&serde_json::from_str::<Block<CurrentNetwork>>(&source)?)?,
+        };
+        Ok(serde_json::to_string_pretty(&with_version(differential.to_json()))?)
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::{
+    aleo2json::{LedgerExport, ProgressBar, WriterSink, DEFAULT_CHUNK_SIZE},
+    ledger::store::helpers::rocksdb::ConsensusDB,
+};
+
+use std::path::PathBuf;
+
+/// Exports every block, transaction, and committed mapping entry of a ledger as chunked JSON files,
+/// or streams them to a socket.
+#[derive(Debug, Parser)]
+pub struct ExportLedger {
+    /// The directory to write the chunk files and manifest to.
+    #[clap(long, required_unless_present = "socket")]
+    pub output: Option<PathBuf>,
+    /// The address of a TCP socket to stream the entries to (i.e. `localhost:9000`), instead of chunk files.
+    #[clap(long, conflicts_with_all = ["output", "resume"])]
+    pub socket: Option<String>,
+    /// The development ID of the ledger to open; if omitted, the production ledger is opened.
+    #[clap(long)]
+    pub dev: Option<u16>,
+    /// The maximum number of entries in each chunk file.
+    #[clap(long, default_value_t = DEFAULT_CHUNK_SIZE)]
+    pub chunk_size: usize,
+    /// Resumes an interrupted export from the checkpoint in the output directory, rather than starting over.
+    #[clap(long)]
+    pub resume: bool,
+    /// Shows a progress bar on stderr, updated after each block.
+    #[clap(long)]
+    pub progress: bool,
+}
+
+impl ExportLedger {
+    /// Exports the ledger, returning the manifest, or the number of entries streamed to the socket.
+    pub fn parse(self) -> Result<String> {
+        let mut exporter =
+            LedgerExport::<CurrentNetwork, ConsensusDB<CurrentNetwork>>::open(self.dev, self.chunk_size)?
+                .with_resume(self.resume);
+        if self.progress {
+            let bar = ProgressBar::new("blocks");
+            exporter = exporter.with_progress(move |progress| bar.report(progress));
+        }
+        match (&self.output, &self.socket) {
+            (_, Some(address)) => {
+                let num_entries = exporter.export_to(&mut WriterSink::connect(address.as_str())?)?;
+                Ok(format!("Streamed {num_entries} entries to {address}"))
+            }
+            (Some(output), None) => Ok(serde_json::to_string_pretty(&exporter.export(output)?)?),
+            (None, None) => anyhow::bail!("An '--output' directory or a '--socket' is required"),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::PathBuf;
+
+/// Exports the facts of an Aleo program (instructions, operands, definitions, uses, calls, and mapping accesses)
+/// for Datalog engines such as Soufflé.
+#[derive(Debug, Parser)]
+pub struct Facts {
+    /// The path to the Aleo program.
+    pub path: PathBuf,
+    /// The format of the facts (tsv or json).
+    #[clap(long, default_value = "tsv")]
+    pub format: FactFormat,
+    /// The directory to write the `.facts` files and their declarations to, for the tsv format.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+impl Facts {
+    /// Exports the facts, returning them as a JSON string, or the number of facts written.
+    pub fn parse(self) -> Result<String> {
+        let program = parse_program::<CurrentNetwork>(&std::fs::read_to_string(&self.path)?)?;
+        let facts = ProgramFacts::new(&program);
+
+        match (self.format, &self.output) {
+            (FactFormat::Json, _) => Ok(serde_json::to_string_pretty(&facts.to_json())?),
+            (FactFormat::Tsv, Some(output)) => {
+                facts.write_tsv(output)?;
+                Ok(format!("Wrote {} facts to {}", facts.len(), output.display()))
+            }
+            (FactFormat::Tsv, None) => anyhow::bail!("The tsv format requires an output directory (--output)"),
+        }
+    }
+}
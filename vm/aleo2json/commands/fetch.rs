@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::{
+    aleo2json::{attach_block_bytes, attach_transaction_bytes, convert_program_with_options, with_provenance},
+    console::{
+        network::{prelude::ToBytes, Network},
+        program::ProgramID,
+    },
+};
+
+use std::str::FromStr;
+
+/// Fetches a block, transaction, or deployed program from a node, and converts it.
+#[derive(Debug, Parser)]
+#[clap(group(clap::ArgGroup::new("item").required(true).args(["block", "transaction", "program"])))]
+pub struct Fetch {
+    /// The base URL of the node to fetch from, i.e. `http://localhost:3030`.
+    #[clap(long)]
+    pub endpoint: String,
+    /// The height of the block to fetch.
+    #[clap(long)]
+    pub block: Option<u32>,
+    /// The ID of the transaction to fetch.
+    #[clap(long)]
+    pub transaction: Option<String>,
+    /// The ID of the program to fetch.
+    #[clap(long)]
+    pub program: Option<String>,
+    /// The options configuring the requests to the node.
+    #[clap(flatten)]
+    pub client: ClientOptions,
+    /// The options configuring the JSON output.
+    #[clap(flatten)]
+    pub json: OutputOptions,
+}
+
+impl Fetch {
+    /// Fetches and converts the item, returning its JSON.
+    pub fn parse(self) -> Result<String> {
+        let client = self.client.to_client(&self.endpoint, None)?;
+        let options = self.json.to_json_options();
+        let document = match (self.block, &self.transaction, &self.program) {
+            (Some(height), _, _) => {
+                let block = client.get_block::<CurrentNetwork>(height)?;
+                let provenance = options.provenance::<CurrentNetwork>(&block.to_bytes_le()?)?;
+                let mut j_block = block.to_json();
+                if options.include_raw_bytes {
+                    attach_block_bytes(&block, &mut j_block)?;
+                }
+                options.finish(with_provenance(j_block, &provenance))
+            }
+            (_, Some(transaction_id), _) => {
+                let transaction_id = <CurrentNetwork as Network>::TransactionID::from_str(transaction_id)?;
+                let transaction = client.get_transaction::<CurrentNetwork>(&transaction_id)?;
+                let provenance = options.provenance::<CurrentNetwork>(&transaction.to_bytes_le()?)?;
+                let mut j_transaction = transaction.to_json();
+                if options.include_raw_bytes {
+                    attach_transaction_bytes(&transaction, &mut j_transaction)?;
+                }
+                options.finish(with_provenance(j_transaction, &provenance))
+            }
+            (_, _, Some(program_id)) => {
+                let source = client.get_program(&ProgramID::<CurrentNetwork>::from_str(program_id)?)?;
+                convert_program_with_options::<CurrentNetwork>(&source, ConversionMode::default(), &options)?
+            }
+            _ => anyhow::bail!("One of '--block', '--transaction', or '--program' is required"),
+        };
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::console::{account::PrivateKey, network::prelude::ToBytes};
+
+use rand::{rngs::StdRng, SeedableRng};
+use std::{path::PathBuf, str::FromStr};
+
+/// Generates a genesis block and emits it as block JSON, for bootstrapping test networks and generating fixtures.
+#[derive(Debug, Parser)]
+pub struct Genesis {
+    /// The private key of the first validator; if none is given, one is sampled from the RNG.
+    #[clap(flatten)]
+    pub private_key: PrivateKeyOptions,
+    /// The seed for the RNG, for a deterministic genesis block; if omitted, the RNG is seeded from entropy.
+    #[clap(long)]
+    pub seed: Option<u64>,
+    /// The file to write the genesis block to, as little-endian bytes, in addition to emitting its JSON.
+    #[clap(long)]
+    pub bytes: Option<PathBuf>,
+}
+
+impl Genesis {
+    /// Generates the genesis block, returning its block JSON.
+    pub fn parse(self) -> Result<String> {
+        let rng = &mut match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let private_key = match self.private_key.read_one()? {
+            Some(private_key) => PrivateKey::<CurrentNetwork>::from_str(&private_key)?,
+            None => PrivateKey::<CurrentNetwork>::new(rng)?,
+        };
+
+        let block = generate_genesis_block(&private_key, rng)?;
+        if let Some(path) = &self.bytes {
+            std::fs::write(path, block.to_bytes_le()?)?;
+        }
+
+        Ok(format!("{}", with_version(block.to_json())))
+    }
+}
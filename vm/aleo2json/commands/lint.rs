@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::PathBuf;
+
+/// Checks an Aleo program against the built-in lint rules, printing the findings with their rule IDs, severities,
+/// and node IDs.
+#[derive(Debug, Parser)]
+pub struct LintProgram {
+    /// The path to the Aleo program.
+    pub path: PathBuf,
+    /// The minimum severity of the findings to report (info, warning, or error).
+    #[clap(long, default_value = "info")]
+    pub severity: Severity,
+    /// Fail if any finding is at least this severe (info, warning, or error), i.e. for CI.
+    #[clap(long)]
+    pub fail_on: Option<Severity>,
+}
+
+/// The failure of a lint with `--fail-on`, carrying the report, which is still printed to stdout.
+#[derive(Debug, Error)]
+#[error("{num_failures} lint finding(s) of severity '{fail_on}' or above")]
+pub struct LintFailure {
+    /// The lint report, as a pretty-printed JSON string.
+    pub report: String,
+    /// The number of findings at least as severe as `fail_on`.
+    pub num_failures: usize,
+    /// The severity the lint fails on.
+    pub fail_on: Severity,
+}
+
+impl LintProgram {
+    /// Returns the lint report of the program as a pretty-printed JSON string, stamped with the format version,
+    /// or a `LintFailure` carrying it, if a finding is at least as severe as `--fail-on`.
+    pub fn parse(self) -> Result<String> {
+        let program = parse_program::<CurrentNetwork>(&std::fs::read_to_string(&self.path)?)?;
+        let linter = Linter::default();
+        let findings = linter.lint(&program);
+        let reported = findings.iter().filter(|finding| finding.severity >= self.severity).cloned().collect::<Vec<_>>();
+        let output = serde_json::to_string_pretty(&with_version(linter.report_to_json(&program, &reported)))?;
+
+        if let Some(fail_on) = self.fail_on {
+            let num_failures = findings.iter().filter(|finding| finding.severity >= fail_on).count();
+            if num_failures > 0 {
+                return Err(LintFailure { report: output, num_failures, fail_on }.into());
+            }
+        }
+        Ok(output)
+    }
+}
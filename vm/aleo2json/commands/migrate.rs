@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::PathBuf;
+
+/// Upgrades a JSON document produced by an earlier aleo2json release to the current format version.
+#[derive(Debug, Parser)]
+pub struct Migrate {
+    /// The path to the JSON document.
+    pub path: PathBuf,
+    /// Prints the report of the renamed, moved, and replaced fields instead of the migrated document.
+    #[clap(long)]
+    pub report: bool,
+}
+
+impl Migrate {
+    /// Returns the migrated document, or the report of the changes, as a JSON string.
+    pub fn parse(self) -> Result<String> {
+        let document = serde_json::from_str(&std::fs::read_to_string(&self.path)?)?;
+        let (migrated, report) = migrate_document(document)?;
+
+        match self.report {
+            true => Ok(serde_json::to_string_pretty(&report.to_json())?),
+            false => Ok(format!("{migrated}")),
+        }
+    }
+}
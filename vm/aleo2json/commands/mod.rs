@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod abi;
+pub use abi::*;
+
+pub mod blocks;
+pub use blocks::*;
+
+pub mod client;
+pub use client::*;
+
+pub mod corpus;
+pub use corpus::*;
+
+pub mod decompile;
+pub use decompile::*;
+
+pub mod deployments;
+pub use deployments::*;
+
+pub mod differential;
+pub use differential::*;
+
+#[cfg(feature = "rocks")]
+pub mod export;
+#[cfg(feature = "rocks")]
+pub use export::*;
+
+pub mod facts;
+pub use facts::*;
+
+pub mod fetch;
+pub use fetch::*;
+
+pub mod genesis;
+pub use genesis::*;
+
+pub mod lint;
+pub use lint::*;
+
+pub mod migrate;
+pub use migrate::*;
+
+pub mod opcodes;
+pub use opcodes::*;
+
+pub mod output;
+pub use output::*;
+
+pub mod patch;
+pub use patch::*;
+
+pub mod private_key;
+pub use private_key::*;
+
+pub mod program;
+pub use program::*;
+
+pub mod r1cs;
+pub use r1cs::*;
+
+pub mod rpc;
+pub use rpc::*;
+
+pub mod schema;
+pub use schema::*;
+
+pub mod serve;
+pub use serve::*;
+
+pub mod similarity;
+pub use similarity::*;
+
+pub mod simulate;
+pub use simulate::*;
+
+pub mod smt;
+pub use smt::*;
+
+#[cfg(feature = "rocks")]
+pub mod staking;
+#[cfg(feature = "rocks")]
+pub use staking::*;
+
+pub mod state_diff;
+pub use state_diff::*;
+
+pub mod stream;
+pub use stream::*;
+
+pub mod trace;
+pub use trace::*;
+
+use crate::aleo2json::{
+    compare_programs,
+    convert_dependency_closure,
+    convert_files,
+    convert_function_with_options,
+    convert_program_file,
+    convert_program_versioned,
+    convert_state_schema_with_options,
+    decompile_program,
+    find_files,
+    function_to_smtlib,
+    generate_genesis_block,
+    json_schema,
+    mapping_entries_from_json,
+    migrate_document,
+    mirror_paths,
+    opcode_table_to_json,
+    parse_program,
+    run_corpus,
+    simulate_finalize,
+    synthesize_function,
+    to_canonical_string,
+    trace_function,
+    with_version,
+    ConversionMode,
+    Differential,
+    FactFormat,
+    GrammarVersion,
+    ImportResolver,
+    JsonOptions,
+    KeyNaming,
+    Linter,
+    MappingEntries,
+    NamingConvention,
+    NumericEncoding,
+    ProgramAbi,
+    ProgramFacts,
+    Redaction,
+    RpcServer,
+    SchemaKind,
+    Server,
+    ServerConfig,
+    Severity,
+    SmtModel,
+    SmtOptions,
+    SourceMap,
+    Verbosity,
+    NEAR_MATCH_THRESHOLD,
+};
+
+use anyhow::Result;
+use clap::Parser;
+
+pub(crate) type CurrentNetwork = crate::prelude::Testnet3;
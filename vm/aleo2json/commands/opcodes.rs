@@ -0,0 +1,26 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Prints the table describing every opcode: its operands, the type of its result, and its halting behavior.
+#[derive(Debug, Parser)]
+pub struct Opcodes {}
+
+impl Opcodes {
+    /// Returns the opcode table as a pretty-printed JSON string, stamped with the format version.
+    pub fn parse(self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&with_version(opcode_table_to_json::<CurrentNetwork>()))?)
+    }
+}
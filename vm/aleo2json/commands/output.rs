@@ -0,0 +1,224 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::PathBuf;
+
+/// The options configuring the JSON output, shared by the conversion commands.
+#[derive(Debug, Parser)]
+pub struct OutputOptions {
+    /// Omit the fields that are null, or that hold an empty array or object.
+    #[clap(long)]
+    pub compact: bool,
+    /// Omit the source text of each node.
+    #[clap(long)]
+    pub no_text: bool,
+    /// Include the byte encoding of the converted object, in hex.
+    #[clap(long)]
+    pub bytes: bool,
+    /// Include the byte encoding of each block, transaction, and transition, in hex, next to its JSON.
+    #[clap(long)]
+    pub raw_bytes: bool,
+    /// Include the analysis sections of the converted object.
+    #[clap(long)]
+    pub analysis: bool,
+    /// Include the source location of each node, when converting from source text.
+    #[clap(long)]
+    pub spans: bool,
+    /// Include the original source text of each construct, when converting from source text.
+    #[clap(long)]
+    pub source: bool,
+    /// Include the comments of the source text, on the construct following each one, when converting from source text.
+    #[clap(long)]
+    pub comments: bool,
+    /// Include the lossless source map of the program, and the source text of each construct, so edits of the
+    /// source texts can be applied back to the original file (see `aleo2json patch`).
+    #[clap(long)]
+    pub lossless: bool,
+    /// Assign each node a deterministic ID.
+    #[clap(long)]
+    pub node_ids: bool,
+    /// Annotate each node with its JSON pointer and the ID of its parent node.
+    #[clap(long)]
+    pub paths: bool,
+    /// Include the description of each opcode used by a program.
+    #[clap(long)]
+    pub opcodes: bool,
+    /// Include the signature of each function of a program at the top of its document.
+    #[clap(long)]
+    pub interface: bool,
+    /// Include the Leo-like pseudocode of each closure and function of a program.
+    #[clap(long)]
+    pub pseudocode: bool,
+    /// Omit the field element of each address, keeping only its bech32 string.
+    #[clap(long)]
+    pub no_address_fields: bool,
+    /// Include the affine coordinates of each group element.
+    #[clap(long)]
+    pub group_coordinates: bool,
+    /// Include the structural fingerprint of each program, closure, and function, for deduplication.
+    #[clap(long)]
+    pub fingerprints: bool,
+    /// Annotate each instruction and command with whether it can halt, for reachability-of-failure analyses.
+    #[clap(long)]
+    pub can_halt: bool,
+    /// Annotate each wrapping arithmetic, shift, and lossy cast instruction with its operand types, for auditing.
+    #[clap(long)]
+    pub risks: bool,
+    /// Flag whether each closure, struct, record, and mapping is reachable from the functions, and list the dead ones.
+    #[clap(long)]
+    pub reachability: bool,
+    /// Pair each amount in microcredits (i.e. rewards, fees, and transfers) with its decimal value in credits.
+    #[clap(long)]
+    pub credits: bool,
+    /// Compare the source checksum of each deployed program with the one recomputed from its copy found in the imports.
+    #[clap(long)]
+    pub verify_source_checksums: bool,
+    /// Renumber the registers of each closure, function, and finalize canonically, and normalize the literals,
+    /// so code that differs only in register numbering serializes identically.
+    #[clap(long)]
+    pub normalize: bool,
+    /// Record the time of the conversion in the `meta` block of each document (this makes the output irreproducible).
+    #[clap(long)]
+    pub timestamp: bool,
+    /// Resolve each import of a program against the given directory of `<program ID>` files.
+    #[clap(long)]
+    pub imports: Option<PathBuf>,
+    /// Resolve each import of a program against the given cache directory, after the imports directory;
+    /// the programs fetched from the import endpoint are cached there by edition, for later runs.
+    #[clap(long)]
+    pub import_cache: Option<PathBuf>,
+    /// Resolve each import of a program against the deployments on the given node, after the directories.
+    #[clap(long)]
+    pub import_endpoint: Option<String>,
+    /// Redact the private inputs and outputs, the decrypted records, and the serial numbers, so the output can be
+    /// shared (placeholder or hash).
+    #[clap(long)]
+    pub redact: Option<Redaction>,
+    /// The encoding of numbers, and of the values of integers and field elements (native, string, number, or both).
+    #[clap(long, default_value = "native")]
+    pub numbers: NumericEncoding,
+    /// The convention for the field names (snake_case or camelCase).
+    #[clap(long, default_value = "snake_case")]
+    pub naming: NamingConvention,
+    /// The name of the `type` key of each node.
+    #[clap(long, default_value = "type")]
+    pub type_key: String,
+    /// The name of the `vtype` key of each node.
+    #[clap(long, default_value = "vtype")]
+    pub vtype_key: String,
+    /// The name of the `value` key of each node.
+    #[clap(long, default_value = "value")]
+    pub value_key: String,
+}
+
+impl Default for OutputOptions {
+    /// Returns the options that emit exactly the output of the `to_json()` helpers.
+    fn default() -> Self {
+        Self::from(&JsonOptions::default())
+    }
+}
+
+impl From<&JsonOptions> for OutputOptions {
+    /// Returns the command-line options for the given JSON options.
+    fn from(options: &JsonOptions) -> Self {
+        Self {
+            compact: options.verbosity == Verbosity::Compact,
+            no_text: !options.include_text,
+            bytes: options.include_bytes,
+            raw_bytes: options.include_raw_bytes,
+            analysis: options.include_analysis,
+            spans: options.include_spans,
+            source: options.include_source,
+            comments: options.include_comments,
+            lossless: options.lossless,
+            node_ids: options.include_node_ids,
+            paths: options.include_paths,
+            opcodes: options.include_opcodes,
+            interface: options.include_interface,
+            pseudocode: options.include_pseudocode,
+            no_address_fields: !options.include_address_fields,
+            group_coordinates: options.include_group_coordinates,
+            fingerprints: options.include_fingerprints,
+            can_halt: options.include_halting,
+            risks: options.include_risks,
+            reachability: options.include_reachability,
+            credits: options.include_credits,
+            verify_source_checksums: options.verify_source_checksums,
+            normalize: options.normalize,
+            timestamp: options.include_timestamp,
+            imports: options.imports.as_ref().and_then(|resolver| resolver.local.clone()),
+            import_cache: options.imports.as_ref().and_then(|resolver| resolver.cache.clone()),
+            import_endpoint: options.imports.as_ref().and_then(|resolver| resolver.endpoint.clone()),
+            redact: options.redaction,
+            numbers: options.numbers,
+            naming: options.naming.convention,
+            type_key: options.naming.type_key.clone(),
+            vtype_key: options.naming.vtype_key.clone(),
+            value_key: options.naming.value_key.clone(),
+        }
+    }
+}
+
+impl OutputOptions {
+    /// Returns the JSON options selected by the command-line options.
+    pub fn to_json_options(&self) -> JsonOptions {
+        JsonOptions {
+            verbosity: match self.compact {
+                true => Verbosity::Compact,
+                false => Verbosity::Normal,
+            },
+            include_text: !self.no_text,
+            include_bytes: self.bytes,
+            include_raw_bytes: self.raw_bytes,
+            include_analysis: self.analysis,
+            include_spans: self.spans,
+            include_source: self.source,
+            include_comments: self.comments,
+            lossless: self.lossless,
+            include_node_ids: self.node_ids,
+            include_paths: self.paths,
+            include_opcodes: self.opcodes,
+            include_interface: self.interface,
+            include_pseudocode: self.pseudocode,
+            include_address_fields: !self.no_address_fields,
+            include_group_coordinates: self.group_coordinates,
+            include_fingerprints: self.fingerprints,
+            include_halting: self.can_halt,
+            include_risks: self.risks,
+            include_reachability: self.reachability,
+            include_credits: self.credits,
+            verify_source_checksums: self.verify_source_checksums,
+            normalize: self.normalize,
+            include_timestamp: self.timestamp,
+            imports: match self.imports.is_some() || self.import_cache.is_some() || self.import_endpoint.is_some() {
+                true => Some(ImportResolver {
+                    local: self.imports.clone(),
+                    cache: self.import_cache.clone(),
+                    endpoint: self.import_endpoint.clone(),
+                }),
+                false => None,
+            },
+            redaction: self.redact,
+            numbers: self.numbers,
+            naming: KeyNaming {
+                convention: self.naming,
+                type_key: self.type_key.clone(),
+                vtype_key: self.vtype_key.clone(),
+                value_key: self.value_key.clone(),
+            },
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::PathBuf;
+
+/// Applies the edits of a program JSON converted with `--lossless` back to its source text, changing only the text
+/// of the edited constructs, so tools can patch program sources with minimal diffs.
+#[derive(Debug, Parser)]
+pub struct PatchSource {
+    /// The path to the edited program JSON, whose `source` fields hold the edited text of the constructs.
+    pub path: PathBuf,
+    /// The path to write the patched source to, instead of printing it.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+impl PatchSource {
+    /// Returns the patched source, or the path it was written to.
+    pub fn parse(self) -> Result<String> {
+        let document: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&self.path)?)?;
+        let j_source_map = document
+            .get("source_map")
+            .ok_or_else(|| anyhow::anyhow!("The document has no source map (convert the program with --lossless)"))?;
+        let source = SourceMap::from_json(j_source_map)?.apply(&document);
+        // The patched source must still be a valid program.
+        parse_program::<CurrentNetwork>(&source)?;
+
+        match &self.output {
+            Some(output) => {
+                std::fs::write(output, &source)?;
+                Ok(format!("Wrote the patched source to {}", output.display()))
+            }
+            None => Ok(source),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use anyhow::bail;
+use std::path::PathBuf;
+
+/// The environment variable a private key is read from, if no private key file is given.
+pub const PRIVATE_KEY_ENV: &str = "ALEO_PRIVATE_KEY";
+
+/// The options giving private keys to a command, shared by the commands using them.
+///
+/// A private key is never taken as an argument, where it would be kept in the shell history and shown in the process
+/// list: it is read from a file, or from the `ALEO_PRIVATE_KEY` environment variable.
+#[derive(Debug, Parser)]
+pub struct PrivateKeyOptions {
+    /// The file to read the private keys from, one per line; if omitted, the private key is read from the
+    /// `ALEO_PRIVATE_KEY` environment variable, if it is set.
+    #[clap(long)]
+    pub private_key_file: Option<PathBuf>,
+}
+
+impl PrivateKeyOptions {
+    /// Returns the private keys read from the file, or the one in the environment variable, if any.
+    pub fn read(&self) -> Result<Vec<String>> {
+        match &self.private_key_file {
+            Some(path) => Ok(std::fs::read_to_string(path)?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(ToString::to_string)
+                .collect()),
+            None => Ok(std::env::var(PRIVATE_KEY_ENV).ok().into_iter().collect()),
+        }
+    }
+
+    /// Returns the private key read from the file, or from the environment variable, if any.
+    /// Fails if the file holds more than one private key.
+    pub fn read_one(&self) -> Result<Option<String>> {
+        let mut private_keys = self.read()?;
+        match private_keys.len() {
+            0 | 1 => Ok(private_keys.pop()),
+            num_keys => bail!("Expected one private key, found {num_keys}"),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::aleo2json::{ConversionManifest, Progress, ProgressBar, ProgressCallback};
+
+use anyhow::bail;
+use std::path::{Path, PathBuf};
+
+/// Converts an Aleo program file, or a directory of them, into JSON.
+#[derive(Debug, Parser)]
+pub struct ConvertProgram {
+    /// The path to the Aleo program, or to a directory of Aleo programs to convert with `--out-dir`.
+    pub path: PathBuf,
+    /// The directory to write the converted programs to, mirroring the input directory (i.e. `foo.aleo` to `foo.json`).
+    #[clap(long)]
+    pub out_dir: Option<PathBuf>,
+    /// The maximum number of programs converted in parallel, with `--out-dir`.
+    #[clap(long, default_value = "8", requires = "out_dir")]
+    pub jobs: usize,
+    /// Shows a progress bar on stderr, with `--out-dir`.
+    #[clap(long, requires = "out_dir")]
+    pub progress: bool,
+    /// The file to write a manifest to, listing each converted program with its output file and checksum.
+    #[clap(long, requires = "out_dir")]
+    pub manifest: Option<PathBuf>,
+    /// Emit canonical JSON (RFC 8785), with sorted keys and no whitespace, for hashing and byte-for-byte comparison.
+    #[clap(long)]
+    pub canonical: bool,
+    /// Fail on any construct without a structured JSON representation, instead of emitting an "unsupported" node.
+    #[clap(long)]
+    pub strict: bool,
+    /// Convert only the named function or closure, with the definitions it references, into a standalone program JSON.
+    #[clap(long, conflicts_with_all = ["grammar", "out_dir"])]
+    pub function: Option<String>,
+    /// Convert only the state schema: the mappings and records, with the structs of their types.
+    #[clap(long, conflicts_with_all = ["function", "grammar"])]
+    pub state: bool,
+    /// Convert the program and every program it imports, resolved with the import options, with a dependency manifest.
+    #[clap(long, conflicts_with_all = ["function", "state", "grammar", "out_dir"])]
+    pub dependencies: bool,
+    /// The grammar version the program was written against (current, legacy, or auto to detect it).
+    /// If given, the version is included in the output, under `grammar_version`.
+    #[clap(long)]
+    pub grammar: Option<String>,
+    /// The options configuring the JSON output.
+    #[clap(flatten)]
+    pub output: OutputOptions,
+}
+
+impl ConvertProgram {
+    /// Initializes the command for the given path, with the default output options.
+    pub fn from_path(path: PathBuf) -> Self {
+        Self {
+            path,
+            out_dir: None,
+            jobs: 8,
+            progress: false,
+            manifest: None,
+            canonical: false,
+            strict: false,
+            function: None,
+            state: false,
+            dependencies: false,
+            grammar: None,
+            output: OutputOptions::default(),
+        }
+    }
+
+    /// Converts the Aleo program at the given path, or the programs written to the output directory.
+    pub fn parse(self) -> Result<String> {
+        match (&self.out_dir, self.path.is_dir()) {
+            (Some(out_dir), true) => self.convert_directory(&self.path, &find_files(&self.path, "aleo")?, out_dir),
+            (Some(out_dir), false) => {
+                let root = self.path.parent().unwrap_or_else(|| Path::new(""));
+                self.convert_directory(root, &[self.path.clone()], out_dir)
+            }
+            (None, true) => bail!("Converting the directory '{}' requires an '--out-dir'", self.path.display()),
+            (None, false) => self.convert(&self.path),
+        }
+    }
+
+    /// Converts the given Aleo programs in `root`, writing each into the mirrored path under `out_dir`.
+    ///
+    /// Every program is converted, even if an earlier one fails; the failures are reported together.
+    fn convert_directory(&self, root: &Path, files: &[PathBuf], out_dir: &Path) -> Result<String> {
+        let outputs = mirror_paths(root, files, out_dir, "json")?;
+        let bar = ProgressBar::new("programs");
+        let report = |progress: Progress| bar.report(progress);
+        let progress = self.progress.then_some(&report as &ProgressCallback);
+        let failures = convert_files(files, &outputs, self.jobs, |file| self.convert(file), progress)?;
+
+        if let Some(path) = &self.manifest {
+            let mut manifest = ConversionManifest::new();
+            for (file, output) in files.iter().zip(&outputs) {
+                if !failures.iter().any(|(failed, _)| failed == file) {
+                    manifest.push_file(file, output)?;
+                }
+            }
+            manifest.write(path)?;
+        }
+
+        if !failures.is_empty() {
+            let failures: Vec<_> =
+                failures.iter().map(|(file, error)| format!("{}: {error}", file.display())).collect();
+            bail!("Failed to convert {} of {} programs:\n{}", failures.len(), files.len(), failures.join("\n"));
+        }
+        Ok(format!("Converted {} programs into '{}'", files.len(), out_dir.display()))
+    }
+
+    /// Converts the Aleo program at the given path.
+    fn convert(&self, path: &Path) -> Result<String> {
+        // Convert the program.
+        let mode = match self.strict {
+            true => ConversionMode::Strict,
+            false => ConversionMode::Permissive,
+        };
+        let options = self.output.to_json_options();
+        let program = match (&self.function, &self.grammar) {
+            (Some(function), _) => {
+                let source = std::fs::read_to_string(path)?;
+                convert_function_with_options::<CurrentNetwork>(&source, function, mode, &options)?
+            }
+            (None, _) if self.dependencies => {
+                let source = std::fs::read_to_string(path)?;
+                let resolver = options.imports.clone().unwrap_or_default();
+                convert_dependency_closure::<CurrentNetwork>(&source, &resolver, mode, &options)?.to_json()
+            }
+            (None, _) if self.state => {
+                let source = std::fs::read_to_string(path)?;
+                convert_state_schema_with_options::<CurrentNetwork>(&source, mode, &options)?
+            }
+            (None, Some(grammar)) => {
+                let version = match grammar.as_str() {
+                    "auto" => None,
+                    grammar => Some(grammar.parse::<GrammarVersion>()?),
+                };
+                let source = std::fs::read_to_string(path)?;
+                convert_program_versioned::<CurrentNetwork>(&source, version, mode, &options)?
+            }
+            (None, None) => convert_program_file::<CurrentNetwork>(path, mode, &options)?,
+        };
+
+        match self.canonical {
+            true => Ok(to_canonical_string(&program)),
+            false => Ok(format!("{program}")),
+        }
+    }
+}
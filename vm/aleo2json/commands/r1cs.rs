@@ -0,0 +1,62 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::circuit::AleoV0;
+
+use rand::{rngs::StdRng, SeedableRng};
+use serde_json::json;
+use std::path::PathBuf;
+
+/// Synthesizes the circuit of a function of an Aleo program, and prints the statistics of its constraint system,
+/// with one entry per call, the function's own last.
+#[derive(Debug, Parser)]
+pub struct R1cs {
+    /// The path to the Aleo program.
+    pub path: PathBuf,
+    /// The name of the function to synthesize.
+    #[clap(long)]
+    pub function: String,
+    /// Includes the constraints of each circuit, as the linear combinations `a`, `b`, and `c` of `a * b = c`.
+    #[clap(long)]
+    pub constraints: bool,
+    /// The seed for the RNG sampling the inputs; if omitted, the RNG is seeded from entropy.
+    #[clap(long)]
+    pub seed: Option<u64>,
+}
+
+impl R1cs {
+    /// Returns the circuits as a pretty-printed JSON string, stamped with the format version.
+    pub fn parse(self) -> Result<String> {
+        let rng = &mut match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let program = parse_program::<CurrentNetwork>(&std::fs::read_to_string(&self.path)?)?;
+
+        let circuits = synthesize_function::<CurrentNetwork, AleoV0, _>(&program, &self.function, rng)?;
+        let mut j_circuits = Vec::new();
+        for circuit in &circuits {
+            j_circuits.push(circuit.to_json(self.constraints));
+        }
+        let j_document = json!({
+            "type": "FunctionCircuits",
+            "program_id": format!("{}", program.id()),
+            "function": self.function,
+            "circuits": j_circuits,
+        });
+        Ok(serde_json::to_string_pretty(&with_version(j_document))?)
+    }
+}
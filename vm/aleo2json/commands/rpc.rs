@@ -0,0 +1,30 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Serves JSON-RPC requests over stdin and stdout, one request per line.
+#[derive(Debug, Parser)]
+pub struct Rpc;
+
+impl Rpc {
+    /// Serves requests until stdin is closed.
+    pub fn parse(self) -> Result<String> {
+        let stdin = std::io::stdin();
+        let mut stdout = std::io::stdout();
+        RpcServer::<CurrentNetwork>::run(stdin.lock(), &mut stdout)?;
+
+        Ok(String::new())
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Prints the JSON Schema describing the JSON output for the given kind.
+#[derive(Debug, Parser)]
+pub struct Schema {
+    /// The kind of document to describe (program, function, instruction, block, or transaction).
+    pub kind: SchemaKind,
+}
+
+impl Schema {
+    /// Returns the schema as a pretty-printed JSON string.
+    pub fn parse(self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&json_schema(self.kind))?)
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::net::{IpAddr, SocketAddr};
+
+/// Starts an HTTP server that converts programs and blocks on request.
+#[derive(Debug, Parser)]
+pub struct Serve {
+    /// The IP address to bind to.
+    #[clap(long, default_value = "127.0.0.1")]
+    pub ip: IpAddr,
+    /// The port to listen on.
+    #[clap(long, default_value = "8080")]
+    pub port: u16,
+    /// The maximum size of a request body, in bytes.
+    #[clap(long, default_value = "16777216")]
+    pub max_body_size: usize,
+    /// The maximum number of requests handled concurrently.
+    #[clap(long, default_value = "8")]
+    pub max_concurrency: usize,
+}
+
+impl Serve {
+    /// Runs the server until the process is terminated.
+    pub fn parse(self) -> Result<String> {
+        let config = ServerConfig {
+            address: SocketAddr::new(self.ip, self.port),
+            max_body_size: self.max_body_size,
+            max_concurrency: self.max_concurrency,
+        };
+        Server::<CurrentNetwork>::new(config).run()?;
+
+        Ok("Server stopped".to_string())
+    }
+}
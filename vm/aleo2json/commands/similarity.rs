@@ -0,0 +1,45 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::{Path, PathBuf};
+
+/// Compares the functions of two programs, reporting how similar each function is to its closest counterpart.
+#[derive(Debug, Parser)]
+pub struct Similarity {
+    /// The path to the left program, as Aleo source (`.aleo`) or as converted program JSON.
+    pub left: PathBuf,
+    /// The path to the right program, as Aleo source (`.aleo`) or as converted program JSON.
+    pub right: PathBuf,
+    /// The similarity score, from 0 to 1, from which two functions are reported as a near match.
+    #[clap(long, default_value_t = NEAR_MATCH_THRESHOLD)]
+    pub threshold: f64,
+}
+
+impl Similarity {
+    /// Returns the similarity report as a pretty-printed JSON string.
+    pub fn parse(self) -> Result<String> {
+        let report = compare_programs(&load_program(&self.left)?, &load_program(&self.right)?, self.threshold);
+        Ok(serde_json::to_string_pretty(&report.to_json())?)
+    }
+}
+
+/// Returns the program JSON at the given path, converting it first if it is Aleo source.
+fn load_program(path: &Path) -> Result<serde_json::Value> {
+    match path.extension().map_or(false, |extension| extension == "aleo") {
+        true => Ok(convert_program_file::<CurrentNetwork>(path, ConversionMode::Permissive, &JsonOptions::default())?),
+        false => Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+    }
+}
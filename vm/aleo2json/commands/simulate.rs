@@ -0,0 +1,55 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::console::program::Value;
+
+use std::{path::PathBuf, str::FromStr};
+
+/// Simulates the finalize block of a function of an Aleo program against an initial mapping state,
+/// and prints the transcript of every mapping access with its before and after values.
+#[derive(Debug, Parser)]
+pub struct Simulate {
+    /// The path to the Aleo program.
+    pub path: PathBuf,
+    /// The name of the function whose finalize block is simulated.
+    #[clap(long)]
+    pub function: String,
+    /// An input of the finalize block, i.e. `5u64`; given once per input, in order.
+    #[clap(long = "input")]
+    pub inputs: Vec<String>,
+    /// A JSON file with the initial mapping state, i.e. `{ "account": { "aleo1...": "100u64" } }`;
+    /// if omitted, the mappings start empty.
+    #[clap(long)]
+    pub state: Option<PathBuf>,
+    /// The block height to simulate the finalize block at.
+    #[clap(long, default_value = "1")]
+    pub block_height: u32,
+}
+
+impl Simulate {
+    /// Returns the transcript of the simulation as a pretty-printed JSON string, stamped with the format version.
+    pub fn parse(self) -> Result<String> {
+        let program = parse_program::<CurrentNetwork>(&std::fs::read_to_string(&self.path)?)?;
+        let inputs = self.inputs.iter().map(|input| Value::from_str(input)).collect::<Result<Vec<_>>>()?;
+        let state = match &self.state {
+            Some(path) => mapping_entries_from_json(&serde_json::from_str(&std::fs::read_to_string(path)?)?)?,
+            None => MappingEntries::new(),
+        };
+
+        let transcript = simulate_finalize(&program, &self.function, &inputs, &state, self.block_height)?;
+        Ok(serde_json::to_string_pretty(&with_version(transcript.to_json()))?)
+    }
+}
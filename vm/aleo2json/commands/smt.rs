@@ -0,0 +1,42 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::path::PathBuf;
+
+/// Encodes a function of an Aleo program as SMT-LIB2, e.g. to check with Z3 that it never halts on an overflow.
+#[derive(Debug, Parser)]
+pub struct Smt {
+    /// The path to the Aleo program.
+    pub path: PathBuf,
+    /// The name of the function to encode.
+    #[clap(long)]
+    pub function: String,
+    /// The modeling of integers (bitvector or integer).
+    #[clap(long, default_value = "bitvector")]
+    pub model: SmtModel,
+    /// End the encoding with a query for inputs on which the function halts.
+    #[clap(long)]
+    pub check_halts: bool,
+}
+
+impl Smt {
+    /// Returns the SMT-LIB2 encoding of the function.
+    pub fn parse(self) -> Result<String> {
+        let program = parse_program::<CurrentNetwork>(&std::fs::read_to_string(&self.path)?)?;
+        let options = SmtOptions { model: self.model, check_halts: self.check_halts };
+        function_to_smtlib(&program, &self.function, &options)
+    }
+}
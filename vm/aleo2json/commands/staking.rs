@@ -0,0 +1,36 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::{
+    aleo2json::export_staking,
+    ledger::store::{helpers::rocksdb::ConsensusDB, ConsensusStore},
+};
+
+/// Exports the validators, their stake, and their delegators from the `credits.aleo` state of a ledger.
+#[derive(Debug, Parser)]
+pub struct Staking {
+    /// The development ID of the ledger to open; if omitted, the production ledger is opened.
+    #[clap(long)]
+    pub dev: Option<u16>,
+}
+
+impl Staking {
+    /// Exports the staking state, returning it as pretty-printed JSON.
+    pub fn parse(self) -> Result<String> {
+        let store = ConsensusStore::<CurrentNetwork, ConsensusDB<CurrentNetwork>>::open(self.dev)?;
+        Ok(serde_json::to_string_pretty(&export_staking(store.finalize_store())?)?)
+    }
+}
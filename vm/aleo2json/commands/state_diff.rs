@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[cfg(feature = "rocks")]
+use crate::ledger::store::{helpers::rocksdb::BlockDB, BlockStore};
+use crate::{
+    aleo2json::{BlockSource, StateDiff},
+    ledger::{block::Block, store::helpers::memory::BlockMemory},
+};
+
+use std::path::{Path, PathBuf};
+
+/// Exports the net mapping-state delta between two consecutive blocks: the keys added, updated, and removed
+/// in each mapping.
+#[derive(Debug, Parser)]
+pub struct ExportStateDiff {
+    /// The path to the earlier block, encoded as snarkVM JSON (i.e. as returned by a node).
+    #[clap(requires = "next", conflicts_with = "height")]
+    pub previous: Option<PathBuf>,
+    /// The path to the later block, encoded as snarkVM JSON.
+    pub next: Option<PathBuf>,
+    /// The height of the later block, to fetch with the earlier block instead of reading them from files.
+    #[clap(long, required_unless_present = "previous")]
+    pub height: Option<u32>,
+    /// The base URL of a node to fetch the blocks from, i.e. `http://localhost:3030`.
+    #[clap(long, requires = "height")]
+    pub endpoint: Option<String>,
+    /// The development ID of the local ledger to read from, if no endpoint is given.
+    #[cfg(feature = "rocks")]
+    #[clap(long, requires = "height", conflicts_with = "endpoint")]
+    pub dev: Option<u16>,
+    /// The options configuring the requests to the node.
+    #[clap(flatten)]
+    pub client: ClientOptions,
+}
+
+impl ExportStateDiff {
+    /// Returns the state delta between the blocks as pretty-printed JSON.
+    pub fn parse(self) -> Result<String> {
+        let (previous, next) = match (&self.previous, &self.next, self.height) {
+            (Some(previous), Some(next), _) => (read_block(previous)?, read_block(next)?),
+            (_, _, Some(0)) => anyhow::bail!("The genesis block has no earlier block"),
+            (_, _, Some(height)) => self.fetch(height)?,
+            _ => anyhow::bail!("Either the paths to two blocks, or a '--height', are required"),
+        };
+        Ok(serde_json::to_string_pretty(&StateDiff::between(&previous, &next)?.to_json())?)
+    }
+
+    /// Fetches the block at the given height, and the block before it, from the configured source.
+    fn fetch(&self, height: u32) -> Result<(Block<CurrentNetwork>, Block<CurrentNetwork>)> {
+        match &self.endpoint {
+            Some(endpoint) => {
+                let source =
+                    BlockSource::<CurrentNetwork, BlockMemory<_>>::from(self.client.to_client(endpoint, None)?);
+                Ok((source.get_block(height - 1)?, source.get_block(height)?))
+            }
+            #[cfg(feature = "rocks")]
+            None => {
+                let source = BlockSource::from(BlockStore::<CurrentNetwork, BlockDB<_>>::open(self.dev)?);
+                Ok((source.get_block(height - 1)?, source.get_block(height)?))
+            }
+            #[cfg(not(feature = "rocks"))]
+            None => anyhow::bail!("An '--endpoint' is required, as reading local storage requires the 'rocks' feature"),
+        }
+    }
+}
+
+/// Reads the block at the given path, encoded as snarkVM JSON.
+fn read_block(path: &Path) -> Result<Block<CurrentNetwork>> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
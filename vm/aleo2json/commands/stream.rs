@@ -0,0 +1,98 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::aleo2json::{
+    convert_block_stream,
+    convert_transaction_stream,
+    BlockOptions,
+    NodeStreamReader,
+    OutputSink,
+    StreamSummary,
+    WriterSink,
+};
+
+use std::{
+    fs::File,
+    io::{BufWriter, Read},
+    path::PathBuf,
+};
+
+/// Converts a stream of blocks or transactions encoded as snarkVM JSON (i.e. a node's response for a range of
+/// blocks) into newline-delimited JSON, one document at a time, without reading the whole stream into memory.
+#[derive(Debug, Parser)]
+pub struct ConvertStream {
+    /// The file to read the stream from; if omitted, and no URL is given, the stream is read from stdin.
+    #[clap(conflicts_with = "url")]
+    pub input: Option<PathBuf>,
+    /// The URL to stream, i.e. `http://localhost:3030/testnet3/blocks?start=0&end=50`.
+    #[clap(long)]
+    pub url: Option<String>,
+    /// Reads a stream of transactions, instead of blocks.
+    #[clap(long)]
+    pub transactions: bool,
+    /// The options configuring the requests to the URL.
+    #[clap(flatten)]
+    pub client: ClientOptions,
+    /// Skips the documents that do not decode, reporting them, instead of failing.
+    #[clap(long)]
+    pub tolerant: bool,
+    /// The file to write to; if omitted, the documents are written to stdout.
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+    /// The options configuring the JSON output.
+    #[clap(flatten)]
+    pub json: OutputOptions,
+}
+
+impl ConvertStream {
+    /// Converts the stream, returning a summary if the documents were written to a file. The skipped documents are
+    /// reported to stderr, so that they never mix with the documents written to stdout.
+    pub fn parse(self) -> Result<String> {
+        match (&self.input, &self.url) {
+            (_, Some(url)) => self.convert(NodeStreamReader::from_url(&self.client.to_client(url, None)?, url)?),
+            (Some(path), None) => self.convert(NodeStreamReader::new(File::open(path)?)),
+            (None, None) => self.convert(NodeStreamReader::new(std::io::stdin().lock())),
+        }
+    }
+
+    /// Converts the documents read by the given reader to the configured output.
+    fn convert<R: Read>(&self, reader: NodeStreamReader<R>) -> Result<String> {
+        let reader = reader.tolerant(self.tolerant);
+        let summary = match &self.output {
+            Some(path) => self.convert_to(reader, &mut WriterSink::new(BufWriter::new(File::create(path)?)))?,
+            None => self.convert_to(reader, &mut WriterSink::new(std::io::stdout().lock()))?,
+        };
+
+        for (position, error) in &summary.skipped {
+            eprintln!("Skipped the document at position {position}: {error}");
+        }
+        match self.output.is_some() {
+            true => Ok(format!("Converted {} documents", summary.num_documents)),
+            false => Ok(String::new()),
+        }
+    }
+
+    /// Converts the documents read by the given reader to the given sink.
+    fn convert_to<R: Read, S: OutputSink>(&self, reader: NodeStreamReader<R>, sink: &mut S) -> Result<StreamSummary> {
+        match self.transactions {
+            true => convert_transaction_stream::<CurrentNetwork, _, _>(reader, &self.json.to_json_options(), sink),
+            false => {
+                let options = BlockOptions { json: self.json.to_json_options(), ..Default::default() };
+                convert_block_stream::<CurrentNetwork, _, _>(reader, &options, sink)
+            }
+        }
+    }
+}
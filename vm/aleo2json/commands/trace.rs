@@ -0,0 +1,62 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use crate::{
+    circuit::AleoV0,
+    console::{account::PrivateKey, program::Value},
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+use std::{path::PathBuf, str::FromStr};
+
+/// Evaluates a function of an Aleo program on the given inputs, and prints the trace of its evaluation:
+/// each instruction with the concrete values of its operands and destinations.
+#[derive(Debug, Parser)]
+pub struct TraceFunction {
+    /// The path to the Aleo program.
+    pub path: PathBuf,
+    /// The name of the function to evaluate.
+    #[clap(long)]
+    pub function: String,
+    /// An input of the function, i.e. `5u64`; given once per input, in order.
+    #[clap(long = "input")]
+    pub inputs: Vec<String>,
+    /// The private key signing the call; if none is given, one is sampled from the RNG.
+    #[clap(flatten)]
+    pub private_key: PrivateKeyOptions,
+    /// The seed for the RNG, for a deterministic trace; if omitted, the RNG is seeded from entropy.
+    #[clap(long)]
+    pub seed: Option<u64>,
+}
+
+impl TraceFunction {
+    /// Returns the trace of the evaluation as a pretty-printed JSON string, stamped with the format version.
+    pub fn parse(self) -> Result<String> {
+        let rng = &mut match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let private_key = match self.private_key.read_one()? {
+            Some(private_key) => PrivateKey::<CurrentNetwork>::from_str(&private_key)?,
+            None => PrivateKey::<CurrentNetwork>::new(rng)?,
+        };
+        let program = parse_program::<CurrentNetwork>(&std::fs::read_to_string(&self.path)?)?;
+        let inputs = self.inputs.iter().map(|input| Value::from_str(input)).collect::<Result<Vec<_>>>()?;
+
+        let trace = trace_function::<CurrentNetwork, AleoV0, _>(&program, &private_key, &self.function, &inputs, rng)?;
+        Ok(serde_json::to_string_pretty(&with_version(trace.to_json()))?)
+    }
+}
@@ -0,0 +1,767 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "aleo2json-ledger")]
+use crate::{
+    aleo2json::{
+        attach_block_bytes,
+        attach_block_fee_breakdowns,
+        attach_block_function_refs,
+        attach_block_key_metadata,
+        attach_block_record_checks,
+        attach_block_record_outputs,
+        attach_block_records,
+        attach_block_rejections,
+        attach_block_source_checksums,
+        attach_block_typed_values,
+        attach_block_value_flows,
+        attach_header_timestamps,
+        block_to_json_with_paths,
+        header_economics_to_json,
+        header_tree_to_json,
+        mapping_updates_to_json,
+        puzzle_reward_attribution_to_json,
+        AddressActivity,
+        ProgramCatalog,
+        Verification,
+    },
+    console::{
+        account::{PrivateKey, ViewKey},
+        network::prelude::{DeserializeOwned, FromBytes},
+    },
+    ledger::block::{Block, Transaction},
+};
+use crate::{
+    aleo2json::{
+        attach_fingerprints,
+        attach_halting,
+        attach_import_resolutions,
+        attach_interface,
+        attach_pseudocode,
+        attach_reachability,
+        attach_risks,
+        attach_source_locations,
+        check_constructs,
+        check_operands,
+        program_opcodes_to_json,
+        with_provenance,
+        ConversionError,
+        ConversionMode,
+        JsonOptions,
+        ProgramSlice,
+        Provenance,
+        TextLocation,
+    },
+    console::{
+        network::prelude::{FromStr, Network, Parser},
+        program::Identifier,
+    },
+    synthesizer::Program,
+};
+
+use anyhow::Result;
+use std::path::Path;
+use tracing::debug_span;
+
+/// The optional sections included when converting a block.
+#[cfg(feature = "aleo2json-ledger")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockOptions {
+    /// Includes the Merkle inclusion paths of each transaction and transition, under `merkle_paths`.
+    pub merkle_paths: bool,
+    /// Recomputes the block hash, the block roots, and every transaction and transition ID, under `verification`.
+    pub verification: bool,
+    /// Pairs the targets, weights, and rewards with human-readable renderings, under `header.economics`,
+    /// and breaks down the fee of each transaction into its base and priority fees, under `breakdown`.
+    pub economics: bool,
+    /// Pairs each timestamp in the header metadata with its ISO-8601 rendering in UTC, under `timestamp_iso` and
+    /// `last_coinbase_timestamp_iso`.
+    pub iso_timestamps: bool,
+    /// Includes the Merkle tree of the block header, with its leaves and the hashes of each level, under
+    /// `header.merkle_tree`.
+    pub header_tree: bool,
+    /// Groups the finalize operations of every confirmed transaction by the mapping they update, under
+    /// `mapping_updates`.
+    pub mapping_updates: bool,
+    /// Attributes the puzzle reward to the prover solutions of the block, with the address, proof target, and share
+    /// of the combined proof target of each solution, and the reward of each prover, under `puzzle_rewards`.
+    pub puzzle_rewards: bool,
+    /// Summarizes the fees, public transfers, and staking operations of each transaction, decoded from the public
+    /// inputs of its `credits.aleo` transitions, under `value_flow`.
+    pub value_flow: bool,
+    /// Lists every address appearing in the block with the role it played (i.e. public input, record owner, fee payer,
+    /// or ratification recipient), by address, under `address_activity`. The private records owned by the view keys
+    /// are decrypted to list their owners, unless the JSON options redact the output.
+    pub address_activity: bool,
+    /// Adds the context of the rejection of each rejected transaction: its rejected ID, its ID prior to confirmation,
+    /// and a human-readable reason where derivable, under `rejection`.
+    pub rejections: bool,
+    /// Decodes the constant and public inputs and outputs of each transition against the value types declared by its
+    /// function, under `typed_value`. The programs are those deployed in the block, `credits.aleo`, and those resolved
+    /// with the import resolver of the JSON options, if set.
+    pub typed_values: bool,
+    /// Adds the node ID of the function called by each transition, and of the declarations of its inputs and outputs,
+    /// in the converted program documents, under `function_node_id` and `declaration_node_id`. The programs are
+    /// found as for `typed_values`.
+    pub function_refs: bool,
+    /// The view keys, as strings, whose output records are decrypted and included next to their ciphertexts,
+    /// under `plaintext`.
+    pub view_keys: Vec<String>,
+    /// The private keys, as strings, whose output records are decrypted and checked; requires `record_checks`.
+    pub private_keys: Vec<String>,
+    /// Exports each output record with its commitment, checksum, nonce, and ciphertext, and its plaintext if one of the
+    /// view keys owns it, under `record_output`.
+    pub record_outputs: bool,
+    /// Recomputes the commitment of each output record owned by the view keys or private keys, and derives its
+    /// serial number if a private key owns it, under `check`.
+    pub record_checks: bool,
+    /// Describes the verifying key and certificate of each deployed function: their sizes and checksums,
+    /// and the size of the circuit, under `metadata`.
+    pub key_metadata: bool,
+    /// Adds the source checksum of the program of each deployment, next to its edition, under `source_checksum`, and
+    /// compares it with the copy of the program found by the import resolver if the JSON options verify checksums.
+    pub source_checksums: bool,
+    /// The configuration of the JSON output.
+    pub json: JsonOptions,
+}
+
+/// Parses the given Aleo source into a program, ensuring the entire input is consumed.
+pub fn parse_program<N: Network>(source: &str) -> Result<Program<N>, ConversionError> {
+    // Returns the location of the given remainder of the source.
+    let location = |remaining: &str| TextLocation::of(source, source.len() - remaining.len());
+
+    match Program::<N>::parse(source) {
+        Ok((remaining, program)) => match remaining.is_empty() {
+            true => Ok(program),
+            false => Err(ConversionError::Parse {
+                location: Some(location(remaining)),
+                message: format!("Parser did not consume all of the string: '{remaining}'"),
+            }),
+        },
+        Err(error) => {
+            let message = format!("Parser error: {error}");
+            // The first error of the trace is the innermost one, located where the parser failed.
+            let location = match &error {
+                nom::Err::Error(error) | nom::Err::Failure(error) => {
+                    error.errors.first().map(|(remaining, _)| location(*remaining))
+                }
+                nom::Err::Incomplete(_) => None,
+            };
+            Err(ConversionError::Parse { location, message })
+        }
+    }
+}
+
+/// Converts the given Aleo source into its program JSON, stamped with the format version.
+pub fn convert_program<N: Network>(source: &str) -> Result<serde_json::Value, ConversionError> {
+    convert_program_with_mode::<N>(source, ConversionMode::default())
+}
+
+/// Converts the given Aleo source into its program JSON, handling unsupported constructs according to the given mode.
+pub fn convert_program_with_mode<N: Network>(
+    source: &str,
+    mode: ConversionMode,
+) -> Result<serde_json::Value, ConversionError> {
+    convert_program_with_options::<N>(source, mode, &JsonOptions::default())
+}
+
+/// Converts the given Aleo source into its program JSON, handling unsupported constructs according to the given mode,
+/// and configuring the output with the given options.
+pub fn convert_program_with_options<N: Network>(
+    source: &str,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<serde_json::Value, ConversionError> {
+    convert_program_selected::<N>(source, None, mode, options)
+}
+
+/// Converts the given function or closure of the Aleo source into a standalone program JSON, with only
+/// the definitions it references, handling unsupported constructs according to the given mode, and configuring
+/// the output with the given options.
+///
+/// The byte encoding, the analysis sections, and the program fingerprint, if included, still describe
+/// the whole program.
+pub fn convert_function_with_options<N: Network>(
+    source: &str,
+    function: &str,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<serde_json::Value, ConversionError> {
+    convert_program_selected::<N>(source, Some(Selection::Function(function)), mode, options)
+}
+
+/// Converts the state schema of the given Aleo source into a program JSON, with only its mappings and records,
+/// and the structs of their types, handling unsupported constructs according to the given mode, and configuring
+/// the output with the given options.
+///
+/// The byte encoding, the analysis sections, and the program fingerprint, if included, still describe
+/// the whole program.
+pub fn convert_state_schema_with_options<N: Network>(
+    source: &str,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<serde_json::Value, ConversionError> {
+    convert_program_selected::<N>(source, Some(Selection::State), mode, options)
+}
+
+/// The part of a program kept by a conversion.
+enum Selection<'a> {
+    /// The named function or closure, with the definitions it references.
+    Function(&'a str),
+    /// The state schema of the program.
+    State,
+}
+
+/// Converts the given Aleo source into its program JSON, keeping only the selected part of the program, if given.
+fn convert_program_selected<N: Network>(
+    source: &str,
+    selection: Option<Selection>,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<serde_json::Value, ConversionError> {
+    let _span = debug_span!("convert_program", len = source.len()).entered();
+    let program = parse_program::<N>(source)?;
+    let slice = match selection {
+        Some(Selection::Function(function)) => {
+            let name = Identifier::<N>::from_str(function)
+                .map_err(|_| ConversionError::Invalid(format!("Invalid function name '{function}'")))?;
+            Some(ProgramSlice::function(&program, &name)?)
+        }
+        Some(Selection::State) => Some(ProgramSlice::state(&program)),
+        None => None,
+    };
+    let document = program_document(&program, source, slice.as_ref(), mode, options)?;
+    let provenance = input_provenance::<N>(source.as_bytes(), options)?;
+    Ok(options.finish(with_provenance(document, &provenance)))
+}
+
+/// Returns the program JSON of the given program, parsed from the given source, keeping only the given slice of the
+/// program, if any, and handling unsupported constructs according to the given mode. The optional sections selected
+/// by the given options are included, and the source locations refer to the given source.
+///
+/// Note: The document is neither stamped nor finished, so the callers can add their own fields first.
+pub(crate) fn program_document<N: Network>(
+    program: &Program<N>,
+    source: &str,
+    slice: Option<&ProgramSlice>,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<serde_json::Value, ConversionError> {
+    let mut document = check_constructs(program.to_json(), mode)?;
+    check_operands(program, &mut document, mode)?;
+    attach_program_sections(program, &mut document, options)?;
+    attach_source_locations::<N>(source, &mut document, options)?;
+    if let Some(slice) = slice {
+        slice.prune(&mut document);
+    }
+    if options.include_interface {
+        attach_interface(program, &mut document);
+    }
+    options.extend(program, &mut document);
+    Ok(document)
+}
+
+/// Returns the provenance of a document converted from the given input, on the given network, with the given options.
+pub(crate) fn input_provenance<N: Network>(input: &[u8], options: &JsonOptions) -> Result<Provenance, ConversionError> {
+    options.provenance::<N>(input).map_err(|error| ConversionError::Invalid(error.to_string()))
+}
+
+/// Adds the optional sections of the given program selected by the given options (i.e. fingerprints, halting flags,
+/// opcodes, pseudocode, arithmetic risks, reachability, and import resolutions) to its program JSON.
+///
+/// The sections that depend on the source text, or on the kept part of the program, are added by the callers.
+pub fn attach_program_sections<N: Network>(
+    program: &Program<N>,
+    document: &mut serde_json::Value,
+    options: &JsonOptions,
+) -> Result<(), ConversionError> {
+    if options.include_fingerprints {
+        attach_fingerprints(document).map_err(|error| ConversionError::Invalid(error.to_string()))?;
+    }
+    if options.include_halting {
+        attach_halting(document);
+    }
+    if options.include_opcodes {
+        document["opcodes"] = program_opcodes_to_json::<N>(document);
+    }
+    if options.include_pseudocode {
+        attach_pseudocode(program, document);
+    }
+    if options.include_risks {
+        attach_risks(program, document);
+    }
+    if options.include_reachability {
+        attach_reachability(program, document);
+    }
+    if let Some(resolver) = &options.imports {
+        attach_import_resolutions::<N>(document, resolver);
+    }
+    Ok(())
+}
+
+/// Converts the Aleo source at the given path into its program JSON, handling unsupported constructs according to
+/// the given mode, and configuring the output with the given options.
+pub fn convert_program_file<N: Network>(
+    path: &Path,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<serde_json::Value, ConversionError> {
+    let source =
+        std::fs::read_to_string(path).map_err(|source| ConversionError::Io { path: path.to_path_buf(), source })?;
+    convert_program_with_options::<N>(&source, mode, options)
+}
+
+/// Converts the given block into its block JSON, including the optional sections selected by the given options.
+///
+/// Note: The verbosity, numeric encoding, and naming options are not applied, so the caller can still
+/// stamp the document, i.e. with `with_version`, before calling `JsonOptions::finish`.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn block_to_json_with_options<N: Network>(block: &Block<N>, options: &BlockOptions) -> Result<serde_json::Value> {
+    let mut j_block = match options.merkle_paths {
+        true => block_to_json_with_paths(block)?,
+        false => block.to_json(),
+    };
+    if options.json.include_fingerprints {
+        attach_fingerprints(&mut j_block)?;
+    }
+    if options.json.include_halting {
+        attach_halting(&mut j_block);
+    }
+    if options.economics {
+        j_block["header"]["economics"] = header_economics_to_json(block);
+        attach_block_fee_breakdowns(block, &mut j_block)?;
+    }
+    if options.iso_timestamps {
+        attach_header_timestamps(block, &mut j_block);
+    }
+    if options.header_tree {
+        j_block["header"]["merkle_tree"] = header_tree_to_json(block.header())?;
+    }
+    if options.mapping_updates {
+        j_block["mapping_updates"] = mapping_updates_to_json(block);
+    }
+    if options.puzzle_rewards {
+        j_block["puzzle_rewards"] = puzzle_reward_attribution_to_json(block)?;
+    }
+    if options.value_flow {
+        attach_block_value_flows(block, &mut j_block);
+    }
+    if options.rejections {
+        attach_block_rejections(block, &mut j_block)?;
+    }
+    if options.typed_values || options.function_refs {
+        let mut catalog = ProgramCatalog::new(options.json.imports.clone())?;
+        if options.typed_values {
+            attach_block_typed_values(block, &mut catalog, &mut j_block);
+        }
+        if options.function_refs {
+            attach_block_function_refs(block, &mut catalog, &mut j_block);
+        }
+    }
+    if options.verification {
+        j_block["verification"] = Verification::of_block(block).to_json();
+    }
+    let view_keys =
+        options.view_keys.iter().map(|view_key| ViewKey::<N>::from_str(view_key)).collect::<Result<Vec<_>>>()?;
+    attach_block_records(block, &view_keys, &mut j_block);
+    if options.record_outputs {
+        attach_block_record_outputs(block, &view_keys, &mut j_block);
+    }
+    if options.address_activity {
+        // The owners of the private records are only known from the view keys, so they are omitted when redacting.
+        let owner_keys: &[ViewKey<N>] = match options.json.redaction {
+            Some(_) => &[],
+            None => view_keys.as_slice(),
+        };
+        j_block["address_activity"] = AddressActivity::of_block(block, owner_keys).to_json();
+    }
+    if options.record_checks {
+        let private_keys = options
+            .private_keys
+            .iter()
+            .map(|private_key| PrivateKey::<N>::from_str(private_key))
+            .collect::<Result<Vec<_>>>()?;
+        attach_block_record_checks(block, &view_keys, &private_keys, &mut j_block)?;
+    }
+    if options.key_metadata {
+        attach_block_key_metadata(block, &mut j_block)?;
+    }
+    if options.source_checksums {
+        attach_block_source_checksums(block, &mut j_block, &options.json)?;
+    }
+    if options.json.include_raw_bytes {
+        attach_block_bytes(block, &mut j_block)?;
+    }
+    options.json.extend(block, &mut j_block);
+    Ok(j_block)
+}
+
+/// Converts the given block, encoded as snarkVM JSON, into its block JSON, stamped with the format version.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn convert_block_json<N: Network>(block: &str) -> Result<serde_json::Value, ConversionError> {
+    let _span = debug_span!("convert_block_json", len = block.len()).entered();
+    let provenance = input_provenance::<N>(block.as_bytes(), &JsonOptions::default())?;
+    let mut j_block: serde_json::Value = serde_json::from_str(block).map_err(ConversionError::json)?;
+
+    // Recover the block from its fields, rather than deserializing it, to report a mismatching block hash as such.
+    let block_hash: N::BlockHash = take_field(&mut j_block, "block_hash")?;
+    let block = Block::<N>::from(
+        take_field(&mut j_block, "previous_hash")?,
+        take_field(&mut j_block, "header")?,
+        take_field(&mut j_block, "authority")?,
+        take_field(&mut j_block, "ratifications")?,
+        take_field(&mut j_block, "solutions")?,
+        take_field(&mut j_block, "transactions")?,
+        take_field(&mut j_block, "aborted_transaction_ids")?,
+    )
+    .map_err(|error| ConversionError::Invalid(error.to_string()))?;
+
+    // Ensure the block hash matches.
+    match block_hash == block.hash() {
+        true => Ok(with_provenance(block.to_json(), &provenance)),
+        false => Err(ConversionError::HashMismatch {
+            pointer: "/block_hash".to_string(),
+            found: block_hash.to_string(),
+            computed: block.hash().to_string(),
+        }),
+    }
+}
+
+/// Converts the given block, encoded as little-endian bytes, into its block JSON, stamped with the format version.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn convert_block_bytes<N: Network>(block: &[u8]) -> Result<serde_json::Value, ConversionError> {
+    let _span = debug_span!("convert_block_bytes", len = block.len()).entered();
+    let provenance = input_provenance::<N>(block, &JsonOptions::default())?;
+    let block = Block::<N>::read_le(block).map_err(ConversionError::parse)?;
+    Ok(with_provenance(block.to_json(), &provenance))
+}
+
+/// Converts the given transaction, encoded as snarkVM JSON (i.e. as returned by a node), into its transaction JSON,
+/// stamped with the format version.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn convert_transaction_json<N: Network>(transaction: &str) -> Result<serde_json::Value, ConversionError> {
+    let provenance = input_provenance::<N>(transaction.as_bytes(), &JsonOptions::default())?;
+    let transaction: Transaction<N> = serde_json::from_str(transaction).map_err(ConversionError::json)?;
+    Ok(with_provenance(transaction.to_json(), &provenance))
+}
+
+/// Converts the given transaction, encoded as little-endian bytes, into its transaction JSON,
+/// stamped with the format version.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn convert_transaction_bytes<N: Network>(transaction: &[u8]) -> Result<serde_json::Value, ConversionError> {
+    let provenance = input_provenance::<N>(transaction, &JsonOptions::default())?;
+    let transaction = Transaction::<N>::read_le(transaction).map_err(ConversionError::parse)?;
+    Ok(with_provenance(transaction.to_json(), &provenance))
+}
+
+/// Takes the field with the given key out of the given JSON object, and deserializes it.
+/// A missing field is deserialized from `null`.
+#[cfg(feature = "aleo2json-ledger")]
+fn take_field<T: DeserializeOwned>(object: &mut serde_json::Value, key: &str) -> Result<T, ConversionError> {
+    let value = object.get_mut(key).map(serde_json::Value::take).unwrap_or_default();
+    serde_json::from_value(value).map_err(|error| ConversionError::parse(format!("Invalid '{key}': {error}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{NumericEncoding, FORMAT_VERSION},
+        prelude::Testnet3,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_convert_program() {
+        let program = convert_program::<CurrentNetwork>(
+            r"
+program token.aleo;
+
+function compute:
+    input r0 as u32.private;
+    add r0 r0 into r1;
+    output r1 as u32.private;",
+        )
+        .unwrap();
+        assert_eq!(program["format_version"], FORMAT_VERSION);
+        assert_eq!(program["type"], "ProgramCore");
+        assert!(program["functions"].get("compute").is_some());
+
+        // The provenance records the network and the checksum of the source, but not the time of the conversion.
+        assert_eq!(program["meta"]["network"], CurrentNetwork::ID);
+        assert!(program["meta"]["input_checksum"].as_str().unwrap().starts_with("sha3-256:"));
+        assert_eq!(program["meta"]["timestamp"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_convert_program_indices() {
+        let program = convert_program::<CurrentNetwork>(
+            r"
+program token.aleo;
+
+function compute:
+    input r0 as u8.public;
+    add r0 r0 into r1;
+    async compute r1 into r2;
+    output r2 as token.aleo/compute.future;
+
+finalize compute:
+    input r0 as u8.public;
+    branch.eq r0 0u8 to skip;
+    add r0 r0 into r1;
+    position skip;
+    position done;",
+        )
+        .unwrap();
+        let function = &program["functions"]["compute"];
+        assert_eq!(function["instructions"][1]["index"], 1);
+
+        let finalize = &function["finalize_logic"];
+        assert_eq!(finalize["commands"][1]["index"], 1);
+        assert_eq!(finalize["commands"][1]["value"]["index"], 1);
+        // The labels are in order of their command index.
+        assert_eq!(finalize["labels"], serde_json::json!({ "skip": 2, "done": 3 }));
+        assert_eq!(finalize["labels"].as_object().unwrap().keys().collect::<Vec<_>>(), ["skip", "done"]);
+        assert_eq!(finalize["positions"].as_object().unwrap().keys().collect::<Vec<_>>(), ["skip", "done"]);
+    }
+
+    #[test]
+    fn test_convert_program_deterministic() {
+        let source = r"
+import credits.aleo;
+import token.aleo;
+
+program exchange.aleo;
+
+mapping supply:
+    key as field.public;
+    value as u64.public;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+struct pair:
+    quote as u64;
+    base as u64;
+    fee as u8;
+
+record order:
+    owner as address.private;
+    trade as pair.private;
+    amount as u64.private;
+
+closure scale:
+    input r0 as u64;
+    mul r0 2u64 into r1;
+    output r1 as u64;
+
+function swap:
+    input r0 as u64.public;
+    output r0 as u64.public;
+
+function add_liquidity:
+    input r0 as u64.public;
+    output r0 as u64.public;";
+
+        // The serialized documents are identical, including their key order, which `Value` equality ignores.
+        let first = convert_program::<CurrentNetwork>(source).unwrap();
+        let second = convert_program::<CurrentNetwork>(source).unwrap();
+        assert_eq!(first.to_string(), second.to_string());
+
+        // Every collection is in declaration order.
+        let keys = |value: &serde_json::Value| value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(&first["imports"]), ["credits.aleo", "token.aleo"]);
+        assert_eq!(keys(&first["mappings"]), ["supply", "balances"]);
+        assert_eq!(keys(&first["structs"]["pair"]["members"]), ["quote", "base", "fee"]);
+        assert_eq!(keys(&first["records"]["order"]["entries"]), ["trade", "amount"]);
+        assert_eq!(keys(&first["functions"]), ["swap", "add_liquidity"]);
+    }
+
+    #[test]
+    fn test_convert_program_strict() {
+        let source =
+            "program token.aleo; function foo: input r0 as u8.public; add r0 r0 into r1; output r1 as u8.public;";
+        let strict = convert_program_with_mode::<CurrentNetwork>(source, ConversionMode::Strict).unwrap();
+        assert_eq!(strict, convert_program::<CurrentNetwork>(source).unwrap());
+    }
+
+    #[test]
+    fn test_convert_program_with_options() {
+        let source = "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;";
+        let options = JsonOptions {
+            numbers: NumericEncoding::String,
+            include_analysis: true,
+            include_spans: true,
+            ..Default::default()
+        };
+        let program =
+            convert_program_with_options::<CurrentNetwork>(source, ConversionMode::Permissive, &options).unwrap();
+        assert_eq!(program["format_version"], FORMAT_VERSION.to_string());
+        assert_eq!(program["analysis"]["stats"]["num_functions"], "1");
+        assert_eq!(program["functions"]["foo"]["span"]["start"]["line"], "1");
+    }
+
+    #[test]
+    fn test_convert_program_fingerprints() {
+        let options = JsonOptions { include_fingerprints: true, ..Default::default() };
+        let convert = |source: &str| {
+            convert_program_with_options::<CurrentNetwork>(source, ConversionMode::Permissive, &options).unwrap()
+        };
+        let program = convert("program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;");
+        let renumbered = convert("program token.aleo; function foo: input r5 as u8.public; output r5 as u8.public;");
+        assert!(program["fingerprint"].as_str().unwrap().starts_with("sha3-256:"));
+        assert_eq!(program["fingerprint"], renumbered["fingerprint"]);
+        assert_eq!(program["functions"]["foo"]["fingerprint"], renumbered["functions"]["foo"]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_convert_program_halting() {
+        let source = "program token.aleo; function foo: input r0 as u8.public; input r1 as u8.public; \
+                      add.w r0 r1 into r2; div r0 r1 into r3; output r3 as u8.public;";
+        let options = JsonOptions { include_halting: true, ..Default::default() };
+        let program =
+            convert_program_with_options::<CurrentNetwork>(source, ConversionMode::Permissive, &options).unwrap();
+        let j_instructions = &program["functions"]["foo"]["instructions"];
+        assert_eq!(j_instructions[0]["can_halt"], false);
+        assert_eq!(j_instructions[1]["can_halt"], true);
+    }
+
+    #[test]
+    fn test_convert_function_interface() {
+        let source = "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public; \
+                      function bar: input r0 as u8.private;";
+        let options = JsonOptions { include_interface: true, ..Default::default() };
+        let program =
+            convert_function_with_options::<CurrentNetwork>(source, "bar", ConversionMode::Permissive, &options)
+                .unwrap();
+        assert_eq!(
+            program["interface"],
+            serde_json::json!([{ "name": "bar", "inputs": ["u8.private"], "outputs": [] }])
+        );
+    }
+
+    #[test]
+    fn test_convert_program_risks() {
+        let source =
+            "program token.aleo; function foo: input r0 as u8.public; add.w r0 r0 into r1; output r1 as u8.public;";
+        let options = JsonOptions { include_risks: true, include_analysis: true, ..Default::default() };
+        let program =
+            convert_program_with_options::<CurrentNetwork>(source, ConversionMode::Permissive, &options).unwrap();
+        let j_risk = &program["functions"]["foo"]["instructions"][0]["risk"];
+        assert_eq!(j_risk["kind"], "wrapping");
+        assert_eq!(j_risk["operand_types"], serde_json::json!(["u8", "u8"]));
+        assert_eq!(program["analysis"]["risks"][0], *j_risk);
+    }
+
+    #[test]
+    fn test_convert_program_trailing_input() {
+        let error = convert_program::<CurrentNetwork>("program token.aleo; function").unwrap_err();
+        assert!(matches!(error, ConversionError::Parse { location: Some(TextLocation { line: 1, .. }), .. }));
+    }
+
+    #[test]
+    fn test_convert_program_file_missing() {
+        let path = Path::new("/nonexistent/token.aleo");
+        let error = convert_program_file::<CurrentNetwork>(path, ConversionMode::Permissive, &JsonOptions::default())
+            .unwrap_err();
+        assert!(matches!(error, ConversionError::Io { .. }));
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_convert_genesis_block() {
+        let block = convert_block_bytes::<CurrentNetwork>(CurrentNetwork::genesis_bytes()).unwrap();
+        assert_eq!(block["type"], "Block");
+        assert_eq!(block["header"]["metadata"]["height"], 0);
+
+        // A truncated block fails to decode.
+        let error = convert_block_bytes::<CurrentNetwork>(&CurrentNetwork::genesis_bytes()[..64]).unwrap_err();
+        assert!(matches!(error, ConversionError::Parse { location: None, .. }));
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_convert_block_json() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let j_block = convert_block_json::<CurrentNetwork>(&block.to_string()).unwrap();
+        let provenance = Provenance::of::<CurrentNetwork>(block.to_string().as_bytes()).unwrap();
+        assert_eq!(j_block, with_provenance(block.to_json(), &provenance));
+
+        // A block with a mismatching hash is reported as such.
+        let mut tampered: serde_json::Value = serde_json::from_str(&block.to_string()).unwrap();
+        tampered["block_hash"] = tampered["previous_hash"].clone();
+        let error = convert_block_json::<CurrentNetwork>(&tampered.to_string()).unwrap_err();
+        assert_eq!(error.kind(), "hash_mismatch");
+        assert_eq!(error.to_json()["pointer"], "/block_hash");
+
+        // Malformed JSON is reported at its location.
+        let error = convert_block_json::<CurrentNetwork>("{\n  \"block_hash\": }").unwrap_err();
+        assert!(matches!(error, ConversionError::Parse { location: Some(TextLocation { line: 2, .. }), .. }));
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_block_to_json_with_options() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        let plain = block_to_json_with_options(&block, &BlockOptions::default()).unwrap();
+        assert_eq!(plain, block.to_json());
+
+        let options = BlockOptions { verification: true, economics: true, mapping_updates: true, ..Default::default() };
+        let j_block = block_to_json_with_options(&block, &options).unwrap();
+        assert_eq!(j_block["verification"]["passed"], true);
+        assert_eq!(j_block["header"]["economics"]["type"], "Economics");
+        assert_eq!(j_block["mapping_updates"], mapping_updates_to_json(&block));
+        assert!(j_block["transactions"][0].get("value_flow").is_none());
+        assert!(j_block["transactions"][0].get("merkle_paths").is_none());
+
+        // A malformed view key is rejected.
+        let options = BlockOptions { view_keys: vec!["AViewKey1".to_string()], ..Default::default() };
+        assert!(block_to_json_with_options(&block, &options).is_err());
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_address_activity_redacted() {
+        use crate::{
+            aleo2json::{generate_genesis_block, Redaction, ROLE_RECORD_OWNER},
+            console::account::{Address, PrivateKey},
+        };
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let rng = &mut StdRng::seed_from_u64(0);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let owner = Address::try_from(&private_key).unwrap().to_string();
+        let block = generate_genesis_block(&private_key, rng).unwrap();
+
+        // The owner of the private records is listed when they are decrypted with its view key.
+        let options =
+            BlockOptions { address_activity: true, view_keys: vec![view_key.to_string()], ..Default::default() };
+        let j_block = options.json.finish(block_to_json_with_options(&block, &options).unwrap());
+        let appearances = j_block["address_activity"][&owner].as_array().unwrap();
+        assert!(appearances.iter().any(|appearance| appearance["role"] == ROLE_RECORD_OWNER));
+
+        // When redacting, the appearances derived from the view key are omitted.
+        let json = JsonOptions { redaction: Some(Redaction::Placeholder), ..Default::default() };
+        let options = BlockOptions { json, ..options };
+        let j_block = options.json.finish(block_to_json_with_options(&block, &options).unwrap());
+        let appearances = j_block["address_activity"].get(&owner).and_then(|value| value.as_array());
+        assert!(appearances.map_or(true, |appearances| {
+            appearances.iter().all(|appearance| appearance["role"] != ROLE_RECORD_OWNER)
+        }));
+    }
+}
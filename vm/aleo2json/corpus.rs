@@ -0,0 +1,255 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        block_to_json_with_options,
+        convert_program,
+        validate_json,
+        validate_program_json,
+        with_version,
+        BlockOptions,
+        BlockSource,
+        RestClient,
+        SchemaKind,
+        SchemaViolation,
+    },
+    console::{network::Network, program::ProgramID},
+    ledger::{block::Block, store::helpers::memory::BlockMemory},
+};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use tracing::{debug, info_span};
+
+/// The stage of the corpus run at which an item failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CorpusStage {
+    /// The item could not be downloaded from the endpoint.
+    Fetch,
+    /// The item could not be converted.
+    Convert,
+    /// The converted item does not conform to the schema.
+    Schema,
+}
+
+impl CorpusStage {
+    /// Returns the name of the stage.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fetch => "fetch",
+            Self::Convert => "convert",
+            Self::Schema => "schema",
+        }
+    }
+}
+
+/// An item of the corpus that failed, i.e. `block 1024` or `program token.aleo`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorpusFailure {
+    /// The item that failed.
+    pub subject: String,
+    /// The stage at which the item failed.
+    pub stage: CorpusStage,
+    /// The reason the item failed.
+    pub message: String,
+}
+
+impl CorpusFailure {
+    /// Initializes a failure of the given subject at the given stage.
+    fn new(subject: String, stage: CorpusStage, message: impl ToString) -> Self {
+        Self { subject, stage, message: message.to_string() }
+    }
+
+    /// Initializes a schema failure of the given subject from the given violations.
+    fn schema(subject: String, violations: &[SchemaViolation]) -> Self {
+        let messages = violations.iter().map(ToString::to_string).collect::<Vec<_>>();
+        Self::new(subject, CorpusStage::Schema, messages.join("; "))
+    }
+
+    /// Returns the failure as JSON.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "subject": self.subject,
+            "stage": self.stage.as_str(),
+            "message": self.message,
+        })
+    }
+}
+
+/// The outcome of converting a corpus of real-world blocks and programs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CorpusReport {
+    /// The number of blocks checked.
+    pub num_blocks: usize,
+    /// The number of programs checked.
+    pub num_programs: usize,
+    /// The items that failed, in the order they were checked.
+    pub failures: Vec<CorpusFailure>,
+}
+
+impl CorpusReport {
+    /// Returns `true` if every item of the corpus was downloaded, converted, and conforms to its schema.
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the report as JSON.
+    pub fn to_json(&self) -> Value {
+        let mut j_failures = Vec::new();
+        for val in &self.failures {
+            j_failures.push(val.to_json());
+        }
+
+        json!({
+            "type": "CorpusReport",
+            "passed": self.passed(),
+            "num_blocks": self.num_blocks,
+            "num_programs": self.num_programs,
+            "failures": j_failures,
+        })
+    }
+}
+
+/// Converts the given block and checks it against the block schema, returning the failure, if any.
+pub fn check_corpus_block<N: Network>(block: &Block<N>) -> Option<CorpusFailure> {
+    let subject = format!("block {}", block.height());
+    match block_to_json_with_options(block, &BlockOptions::default()) {
+        Ok(document) => match validate_json(SchemaKind::Block, &with_version(document)) {
+            Ok(()) => None,
+            Err(violations) => Some(CorpusFailure::schema(subject, &violations)),
+        },
+        Err(error) => Some(CorpusFailure::new(subject, CorpusStage::Convert, error)),
+    }
+}
+
+/// Converts the given program source and checks it against the program schema, returning the failure, if any.
+pub fn check_corpus_program<N: Network>(program_id: &ProgramID<N>, source: &str) -> Option<CorpusFailure> {
+    let subject = format!("program {program_id}");
+    match convert_program::<N>(source) {
+        Ok(document) => match validate_program_json(&document) {
+            Ok(()) => None,
+            Err(violations) => Some(CorpusFailure::schema(subject, &violations)),
+        },
+        Err(error) => Some(CorpusFailure::new(subject, CorpusStage::Convert, error)),
+    }
+}
+
+/// Returns the programs called by the transitions of the given blocks, ordered by their number of transitions
+/// (the most used first), with ties in order of first use.
+pub fn program_usage<'a, N: Network>(blocks: impl IntoIterator<Item = &'a Block<N>>) -> Vec<(ProgramID<N>, usize)> {
+    let mut usage = IndexMap::<ProgramID<N>, usize>::new();
+    for block in blocks {
+        for transaction in block.transactions().iter() {
+            for transition in transaction.transaction().transitions() {
+                *usage.entry(*transition.program_id()).or_default() += 1;
+            }
+        }
+    }
+    let mut usage = usage.into_iter().collect::<Vec<_>>();
+    // The sort is stable, so programs used equally often remain in order of first use.
+    usage.sort_by(|(_, a), (_, b)| b.cmp(a));
+    usage
+}
+
+/// Downloads the latest `num_blocks` blocks and the `num_programs` programs most used in them from the given node,
+/// converts each of them, and checks them against their schemas.
+///
+/// Items that fail are recorded in the report rather than aborting the run, so that one run surfaces every failure.
+/// Only a failure to fetch the latest height is returned as an error.
+pub fn run_corpus<N: Network>(endpoint: &str, num_blocks: u32, num_programs: usize) -> Result<CorpusReport> {
+    let _span = info_span!("run_corpus", endpoint, num_blocks, num_programs).entered();
+
+    let client = RestClient::new(endpoint);
+    let latest_height = client.latest_height::<N>()?;
+    let heights = latest_height.saturating_sub(num_blocks.saturating_sub(1))..=latest_height;
+
+    let mut report = CorpusReport::default();
+
+    // Check the latest blocks.
+    let source = BlockSource::<N, BlockMemory<N>>::from(client.clone());
+    let mut blocks = Vec::new();
+    for height in heights.take(num_blocks as usize) {
+        match source.get_block(height) {
+            Ok(block) => {
+                report.failures.extend(check_corpus_block(&block));
+                blocks.push(block);
+            }
+            Err(error) => {
+                report.failures.push(CorpusFailure::new(format!("block {height}"), CorpusStage::Fetch, error))
+            }
+        }
+        report.num_blocks += 1;
+        debug!("Checked {} of {num_blocks} blocks", report.num_blocks);
+    }
+
+    // Check the programs most used by those blocks.
+    for (program_id, _) in program_usage(&blocks).into_iter().take(num_programs) {
+        match client.get_program(&program_id) {
+            Ok(source) => report.failures.extend(check_corpus_program(&program_id, &source)),
+            Err(error) => {
+                report.failures.push(CorpusFailure::new(format!("program {program_id}"), CorpusStage::Fetch, error))
+            }
+        }
+        report.num_programs += 1;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_check_corpus_genesis_block() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        assert_eq!(check_corpus_block(&block), None);
+
+        // Every transition of the genesis block calls `credits.aleo`.
+        let usage = program_usage([&block]);
+        let num_transitions: usize = block.transactions().iter().map(|tx| tx.transaction().transitions().count()).sum();
+        assert_eq!(usage, [(ProgramID::from_str("credits.aleo").unwrap(), num_transitions)]);
+    }
+
+    #[test]
+    fn test_check_corpus_program() {
+        let program_id = ProgramID::<CurrentNetwork>::from_str("token.aleo").unwrap();
+        let source =
+            "program token.aleo;\n\nfunction mint:\n    input r0 as u64.public;\n    output r0 as u64.public;\n";
+        assert_eq!(check_corpus_program(&program_id, source), None);
+
+        let failure = check_corpus_program(&program_id, "program token.aleo;\n\nfunction mint:\n    bogus;").unwrap();
+        assert_eq!(failure.subject, "program token.aleo");
+        assert_eq!(failure.stage, CorpusStage::Convert);
+    }
+
+    #[test]
+    fn test_corpus_report_to_json() {
+        let mut report = CorpusReport { num_blocks: 2, num_programs: 1, failures: Vec::new() };
+        assert!(report.passed());
+
+        report.failures.push(CorpusFailure::new("block 1".to_string(), CorpusStage::Fetch, "Failed to fetch"));
+        let json = report.to_json();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["failures"][0]["stage"], "fetch");
+        assert_eq!(json["failures"][0]["subject"], "block 1");
+    }
+}
@@ -0,0 +1,375 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{network::Network, program::Register},
+    synthesizer::{
+        program::{Command, CommandTrait, InstructionTrait, Operand},
+        Instruction,
+        Program,
+    },
+};
+
+use anyhow::{bail, Error, Result};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, fmt, path::Path, str::FromStr};
+
+/// The relations of the program facts, with the name and Soufflé type of each column.
+pub const FACT_RELATIONS: &[(&str, &[(&str, &str)])] = &[
+    ("scope", &[("scope", "symbol"), ("kind", "symbol")]),
+    ("scope_input", &[("scope", "symbol"), ("index", "number"), ("register", "symbol"), ("type", "symbol")]),
+    ("scope_output", &[("scope", "symbol"), ("index", "number"), ("operand", "symbol"), ("type", "symbol")]),
+    ("instruction", &[("id", "symbol"), ("scope", "symbol"), ("index", "number"), ("opcode", "symbol")]),
+    ("operand", &[("id", "symbol"), ("position", "number"), ("operand", "symbol")]),
+    ("def", &[("id", "symbol"), ("register", "symbol")]),
+    ("use", &[("id", "symbol"), ("register", "symbol")]),
+    ("call", &[("id", "symbol"), ("callee", "symbol")]),
+    ("mapping", &[("mapping", "symbol"), ("key_type", "symbol"), ("value_type", "symbol")]),
+    ("mapping_access", &[("id", "symbol"), ("mapping", "symbol"), ("kind", "symbol")]),
+];
+
+/// The file format of the program facts.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FactFormat {
+    /// One tab-separated `<relation>.facts` file per relation, as read by Soufflé.
+    #[default]
+    Tsv,
+    /// A single JSON document holding every relation.
+    Json,
+}
+
+impl FromStr for FactFormat {
+    type Err = Error;
+
+    /// Parses a fact format from its name.
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "tsv" => Ok(Self::Tsv),
+            "json" => Ok(Self::Json),
+            _ => bail!("Unknown fact format '{format}' (expected one of: tsv, json)"),
+        }
+    }
+}
+
+impl fmt::Display for FactFormat {
+    /// Prints the name of the fact format.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Tsv => write!(f, "tsv"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// The facts of a program, as relations for Datalog engines.
+///
+/// Each closure, function, and finalize block is a scope, named after it (`<function>/finalize` for a finalize block).
+/// Each instruction and command is identified by `<scope>/<index>`, and registers by their locator (i.e. `r0`),
+/// so an access such as `r0.owner` is a use of `r0`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramFacts {
+    /// The facts of each relation of `FACT_RELATIONS`, by relation name.
+    pub relations: BTreeMap<&'static str, Vec<Vec<String>>>,
+}
+
+impl ProgramFacts {
+    /// Computes the facts of the given program.
+    pub fn new<N: Network>(program: &Program<N>) -> Self {
+        let mut facts =
+            Self { relations: FACT_RELATIONS.iter().map(|(relation, _)| (*relation, Vec::new())).collect() };
+
+        for (name, mapping) in program.mappings() {
+            facts.add("mapping", [
+                name.to_string(),
+                mapping.key().plaintext_type().to_string(),
+                mapping.value().plaintext_type().to_string(),
+            ]);
+        }
+
+        for closure in program.closures().values() {
+            let scope = closure.name().to_string();
+            facts.add("scope", [scope.clone(), "closure".to_string()]);
+            for (index, input) in closure.inputs().iter().enumerate() {
+                let fact =
+                    [scope.clone(), index.to_string(), input.register().to_string(), input.register_type().to_string()];
+                facts.add("scope_input", fact);
+            }
+            for (index, instruction) in closure.instructions().iter().enumerate() {
+                facts.add_instruction(&scope, index, instruction);
+            }
+            for (index, output) in closure.outputs().iter().enumerate() {
+                let fact = [
+                    scope.clone(),
+                    index.to_string(),
+                    output.operand().to_string(),
+                    output.register_type().to_string(),
+                ];
+                facts.add("scope_output", fact);
+            }
+        }
+
+        for function in program.functions().values() {
+            let scope = function.name().to_string();
+            facts.add("scope", [scope.clone(), "function".to_string()]);
+            for (index, input) in function.inputs().iter().enumerate() {
+                let fact =
+                    [scope.clone(), index.to_string(), input.register().to_string(), input.value_type().to_string()];
+                facts.add("scope_input", fact);
+            }
+            for (index, instruction) in function.instructions().iter().enumerate() {
+                facts.add_instruction(&scope, index, instruction);
+            }
+            for (index, output) in function.outputs().iter().enumerate() {
+                let fact =
+                    [scope.clone(), index.to_string(), output.operand().to_string(), output.value_type().to_string()];
+                facts.add("scope_output", fact);
+            }
+
+            if let Some(finalize) = function.finalize_logic() {
+                let scope = format!("{scope}/finalize");
+                facts.add("scope", [scope.clone(), "finalize".to_string()]);
+                for (index, input) in finalize.inputs().iter().enumerate() {
+                    let fact = [
+                        scope.clone(),
+                        index.to_string(),
+                        input.register().to_string(),
+                        input.finalize_type().to_string(),
+                    ];
+                    facts.add("scope_input", fact);
+                }
+                for (index, command) in finalize.commands().iter().enumerate() {
+                    facts.add_command(&scope, index, command);
+                }
+            }
+        }
+
+        facts
+    }
+
+    /// Returns the total number of facts, across all relations.
+    pub fn len(&self) -> usize {
+        self.relations.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if there are no facts.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the contents of the tab-separated `.facts` file of each relation, by file name.
+    pub fn to_tsv(&self) -> BTreeMap<String, String> {
+        let mut files = BTreeMap::new();
+        for (relation, facts) in &self.relations {
+            let mut contents = String::new();
+            for fact in facts {
+                // Tabs and newlines would split the fact, so they are replaced with spaces.
+                let fields = fact.iter().map(|field| field.replace(['\t', '\n'], " ")).collect::<Vec<_>>();
+                contents.push_str(&fields.join("\t"));
+                contents.push('\n');
+            }
+            files.insert(format!("{relation}.facts"), contents);
+        }
+        files
+    }
+
+    /// Writes the `.facts` file of each relation, and the Soufflé declarations in `facts.dl`, to the given directory.
+    pub fn write_tsv(&self, directory: &Path) -> Result<()> {
+        std::fs::create_dir_all(directory)?;
+        for (file_name, contents) in self.to_tsv() {
+            std::fs::write(directory.join(file_name), contents)?;
+        }
+        std::fs::write(directory.join("facts.dl"), fact_declarations())?;
+        Ok(())
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_relations = serde_json::Map::new();
+        for (relation, columns) in FACT_RELATIONS {
+            let mut j_facts = Vec::new();
+            for fact in self.relations.get(relation).into_iter().flatten() {
+                let mut j_fact = serde_json::Map::new();
+                for ((column, column_type), field) in columns.iter().zip(fact) {
+                    let j_field = match (*column_type, field.parse::<u64>()) {
+                        ("number", Ok(number)) => json!(number),
+                        _ => json!(field),
+                    };
+                    j_fact.insert(column.to_string(), j_field);
+                }
+                j_facts.push(Value::Object(j_fact));
+            }
+            j_relations.insert(relation.to_string(), Value::Array(j_facts));
+        }
+
+        json!({
+            "type": "ProgramFacts",
+            "relations": j_relations,
+        })
+    }
+
+    /// Adds a fact to the given relation.
+    fn add<const K: usize>(&mut self, relation: &'static str, fact: [String; K]) {
+        self.relations.entry(relation).or_default().push(fact.to_vec());
+    }
+
+    /// Adds the facts of an instruction or command, returning its identifier.
+    fn add_statement<N: Network>(
+        &mut self,
+        scope: &str,
+        index: usize,
+        opcode: &str,
+        operands: &[Operand<N>],
+        destinations: Vec<Register<N>>,
+    ) -> String {
+        let id = format!("{scope}/{index}");
+        self.add("instruction", [id.clone(), scope.to_string(), index.to_string(), opcode.to_string()]);
+        for (position, operand) in operands.iter().enumerate() {
+            self.add("operand", [id.clone(), position.to_string(), operand.to_string()]);
+            if let Operand::Register(register) = operand {
+                self.add("use", [id.clone(), base_register(register)]);
+            }
+        }
+        for destination in &destinations {
+            self.add("def", [id.clone(), base_register(destination)]);
+        }
+        id
+    }
+
+    /// Adds the facts of an instruction.
+    fn add_instruction<N: Network>(&mut self, scope: &str, index: usize, instruction: &Instruction<N>) {
+        let opcode = instruction.opcode().to_string();
+        let id = self.add_statement(scope, index, &opcode, instruction.operands(), instruction.destinations());
+        if let Instruction::Call(call) = instruction {
+            self.add("call", [id, call.operator().to_string()]);
+        }
+    }
+
+    /// Adds the facts of a finalize command.
+    fn add_command<N: Network>(&mut self, scope: &str, index: usize, command: &Command<N>) {
+        let (operands, mapping) = match command {
+            Command::Instruction(instruction) => return self.add_instruction(scope, index, instruction),
+            Command::Await(await_) => (vec![Operand::Register(await_.register().clone())], None),
+            Command::Contains(contains) => (contains.operands(), Some(contains.mapping_name().to_string())),
+            Command::Get(get) => (get.operands(), Some(get.mapping().to_string())),
+            Command::GetOrUse(get_or_use) => (get_or_use.operands(), Some(get_or_use.mapping().to_string())),
+            Command::RandChaCha(rand_chacha) => (rand_chacha.operands(), None),
+            Command::Remove(remove) => (remove.operands(), Some(remove.mapping_name().to_string())),
+            Command::Set(set) => (set.operands(), Some(set.mapping_name().to_string())),
+            Command::BranchEq(branch) => (vec![branch.first().clone(), branch.second().clone()], None),
+            Command::BranchNeq(branch) => (vec![branch.first().clone(), branch.second().clone()], None),
+            Command::Position(_) => (vec![], None),
+        };
+        // The opcode of a command is the first token of its string representation.
+        let command_string = command.to_string();
+        let opcode = command_string.split_whitespace().next().unwrap_or_default();
+
+        let id = self.add_statement(scope, index, opcode, &operands, command.destinations());
+        if let Some(mapping) = mapping {
+            self.add("mapping_access", [id, mapping, opcode.to_string()]);
+        }
+    }
+}
+
+/// Returns the locator of the given register, i.e. `r0` for `r0.owner`.
+fn base_register<N: Network>(register: &Register<N>) -> String {
+    match register {
+        Register::Locator(locator) | Register::Access(locator, _) => format!("r{locator}"),
+    }
+}
+
+/// Returns the Soufflé declarations of the fact relations, each read from its `.facts` file.
+pub fn fact_declarations() -> String {
+    let mut declarations = String::new();
+    for (relation, columns) in FACT_RELATIONS {
+        let columns =
+            columns.iter().map(|(column, column_type)| format!("{column}: {column_type}")).collect::<Vec<_>>();
+        declarations.push_str(&format!(".decl {relation}({})\n.input {relation}\n", columns.join(", ")));
+    }
+    declarations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"program facts.aleo;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+closure double:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function deposit:
+    input r0 as u64.public;
+    call double r0 into r1;
+    async deposit self.caller r1 into r2;
+    output r2 as facts.aleo/deposit.future;
+
+finalize deposit:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use balances[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into balances[r0];
+";
+
+    #[test]
+    fn test_program_facts() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let facts = ProgramFacts::new(&program);
+        let relation = |name: &str| facts.relations[name].iter().map(|fact| fact.join(" ")).collect::<Vec<_>>();
+
+        assert_eq!(relation("scope"), ["double closure", "deposit function", "deposit/finalize finalize"]);
+        assert_eq!(relation("mapping"), ["balances address u64"]);
+        assert!(relation("instruction").contains(&"double/0 double 0 add".to_string()));
+        assert!(relation("def").contains(&"double/0 r1".to_string()));
+        assert_eq!(relation("call"), ["deposit/0 double"]);
+        assert!(relation("use").contains(&"deposit/finalize/1 r2".to_string()));
+        assert_eq!(relation("mapping_access"), [
+            "deposit/finalize/0 balances get.or_use",
+            "deposit/finalize/2 balances set"
+        ]);
+        assert_eq!(facts.to_tsv()["call.facts"], "deposit/0\tdouble\n");
+    }
+
+    #[test]
+    fn test_program_facts_to_json() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let j_facts = ProgramFacts::new(&program).to_json();
+        assert_eq!(j_facts["type"], "ProgramFacts");
+        assert_eq!(
+            j_facts["relations"]["instruction"][0],
+            json!({
+                "id": "double/0",
+                "scope": "double",
+                "index": 0,
+                "opcode": "add",
+            })
+        );
+    }
+
+    #[test]
+    fn test_fact_declarations() {
+        let declarations = fact_declarations();
+        assert!(declarations.contains(".decl def(id: symbol, register: symbol)\n.input def\n"));
+        assert_eq!(declarations.matches(".decl").count(), FACT_RELATIONS.len());
+    }
+}
@@ -0,0 +1,540 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{network::Network, program::PlaintextType},
+    synthesizer::{
+        program::{CastType, Closure, Command, Function, InstructionTrait},
+        Instruction,
+        Program,
+    },
+};
+
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// The indentation of each nested block of pseudocode.
+const INDENT: &str = "    ";
+
+/// Renders the given program as Leo-like pseudocode (best effort).
+///
+/// The pseudocode is meant to be read, not compiled: registers keep their names, operations are rendered as operators
+/// or method calls, casts into structs and records as literals, and the forward branches of finalize blocks as
+/// `if`/`else` blocks. Branches that do not nest are rendered as `goto`s.
+pub fn decompile_program<N: Network>(program: &Program<N>) -> String {
+    let mut lines = Vec::new();
+    for import in program.imports().keys() {
+        lines.push(format!("import {import};"));
+    }
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+
+    let mut sections = Vec::new();
+    for (name, mapping) in program.mappings() {
+        sections.push(format!(
+            "mapping {name}: {} => {};",
+            mapping.key().plaintext_type(),
+            mapping.value().plaintext_type()
+        ));
+    }
+    for (name, struct_) in program.structs() {
+        let members = struct_.members().iter().map(|(member, member_type)| format!("{member}: {member_type}"));
+        sections.push(format!("struct {name} {{ {} }}", members.collect::<Vec<_>>().join(", ")));
+    }
+    for (name, record) in program.records() {
+        let entries = record.entries().iter().map(|(entry, entry_type)| format!("{entry}: {entry_type}"));
+        let members = std::iter::once(format!("owner: address.{}", record.owner())).chain(entries);
+        sections.push(format!("record {name} {{ {} }}", members.collect::<Vec<_>>().join(", ")));
+    }
+    for closure in program.closures().values() {
+        sections.push(decompile_closure(program, closure));
+    }
+    for function in program.functions().values() {
+        sections.push(decompile_function(program, function));
+    }
+
+    lines.push(format!("program {} {{", program.id()));
+    for (index, section) in sections.iter().enumerate() {
+        if index > 0 && (section.contains('\n') || sections[index - 1].contains('\n')) {
+            lines.push(String::new());
+        }
+        lines.extend(section.lines().map(|line| indent(1, line)));
+    }
+    lines.push("}".to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Renders the given closure as Leo-like pseudocode.
+pub fn decompile_closure<N: Network>(program: &Program<N>, closure: &Closure<N>) -> String {
+    let inputs = closure.inputs().iter().map(|input| format!("{}: {}", input.register(), input.register_type()));
+    let outputs =
+        closure.outputs().iter().map(|output| (output.operand().to_string(), output.register_type().to_string()));
+
+    let mut writer = Writer::default();
+    writer.line(
+        0,
+        signature("closure", &closure.name().to_string(), inputs.collect(), &outputs.clone().collect::<Vec<_>>()),
+    );
+    for instruction in closure.instructions() {
+        writer.line(1, instruction_statement(program, instruction));
+    }
+    writer.line_if_some(1, return_statement(outputs.map(|(operand, _)| operand).collect()));
+    writer.line(0, "}");
+    writer.finish()
+}
+
+/// Renders the given function, followed by its finalize block, if any, as Leo-like pseudocode.
+pub fn decompile_function<N: Network>(program: &Program<N>, function: &Function<N>) -> String {
+    let name = function.name().to_string();
+    let inputs = function.inputs().iter().map(|input| format!("{}: {}", input.register(), input.value_type()));
+    let outputs =
+        function.outputs().iter().map(|output| (output.operand().to_string(), output.value_type().to_string()));
+
+    let mut writer = Writer::default();
+    writer.line(0, signature("function", &name, inputs.collect(), &outputs.clone().collect::<Vec<_>>()));
+    for instruction in function.instructions() {
+        writer.line(1, instruction_statement(program, instruction));
+    }
+    writer.line_if_some(1, return_statement(outputs.map(|(operand, _)| operand).collect()));
+    writer.line(0, "}");
+
+    if let Some(finalize) = function.finalize_logic() {
+        let inputs = finalize.inputs().iter().map(|input| format!("{}: {}", input.register(), input.finalize_type()));
+        writer.line(0, "");
+        writer.line(0, signature("finalize", &name, inputs.collect(), &[]));
+        // The index of each position, by name.
+        let labels = finalize
+            .commands()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| match command {
+                Command::Position(position) => Some((position.name().to_string(), index)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        writer.commands(program, finalize.commands(), &labels, 0, finalize.commands().len(), 1);
+        writer.line(0, "}");
+    }
+    writer.finish()
+}
+
+/// Adds the pseudocode of each closure and function of the given program to its JSON, under `pseudocode`.
+pub fn attach_pseudocode<N: Network>(program: &Program<N>, document: &mut Value) {
+    for (name, closure) in program.closures() {
+        if let Some(Value::Object(j_closure)) = document.get_mut("closures").and_then(|j| j.get_mut(name.to_string())) {
+            j_closure.insert("pseudocode".to_string(), Value::String(decompile_closure(program, closure)));
+        }
+    }
+    for (name, function) in program.functions() {
+        if let Some(Value::Object(j_function)) = document.get_mut("functions").and_then(|j| j.get_mut(name.to_string()))
+        {
+            j_function.insert("pseudocode".to_string(), Value::String(decompile_function(program, function)));
+        }
+    }
+}
+
+/// Returns the given line, indented to the given depth.
+fn indent(depth: usize, line: &str) -> String {
+    match line.is_empty() {
+        true => String::new(),
+        false => format!("{}{line}", INDENT.repeat(depth)),
+    }
+}
+
+/// Returns the opening line of a closure, function, or finalize block.
+fn signature(keyword: &str, name: &str, inputs: Vec<String>, outputs: &[(String, String)]) -> String {
+    let output_types = outputs.iter().map(|(_, output_type)| output_type.as_str()).collect::<Vec<_>>();
+    let returns = match output_types.as_slice() {
+        [] => String::new(),
+        [output_type] => format!(" -> {output_type}"),
+        output_types => format!(" -> ({})", output_types.join(", ")),
+    };
+    format!("{keyword} {name}({}){returns} {{", inputs.join(", "))
+}
+
+/// Returns the statement returning the given operands, if any.
+fn return_statement(operands: Vec<String>) -> Option<String> {
+    match operands.as_slice() {
+        [] => None,
+        [operand] => Some(format!("return {operand};")),
+        operands => Some(format!("return ({});", operands.join(", "))),
+    }
+}
+
+/// Returns the statement assigning the given expression to the given destinations.
+fn assignment(destinations: &[String], expression: String) -> String {
+    match destinations {
+        [] => format!("{expression};"),
+        [destination] => format!("let {destination} = {expression};"),
+        destinations => format!("let ({}) = {expression};", destinations.join(", ")),
+    }
+}
+
+/// Returns the infix operator of the given opcode, if it has one.
+fn infix_operator(opcode: &str) -> Option<&'static str> {
+    Some(match opcode {
+        "add" => "+",
+        "sub" => "-",
+        "mul" => "*",
+        "div" => "/",
+        "rem" => "%",
+        "pow" => "**",
+        "shl" => "<<",
+        "shr" => ">>",
+        "and" => "&",
+        "or" => "|",
+        "xor" => "^",
+        "is.eq" => "==",
+        "is.neq" => "!=",
+        "gt" => ">",
+        "gte" => ">=",
+        "lt" => "<",
+        "lte" => "<=",
+        _ => return None,
+    })
+}
+
+/// Returns the statement rendering the given instruction.
+fn instruction_statement<N: Network>(program: &Program<N>, instruction: &Instruction<N>) -> String {
+    let opcode = *instruction.opcode();
+    let operands = instruction.operands().iter().map(ToString::to_string).collect::<Vec<_>>();
+    let destinations = instruction.destinations().iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    let expression = match (instruction, infix_operator(opcode), operands.as_slice()) {
+        (Instruction::AssertEq(_), _, [a, b]) => return format!("assert_eq({a}, {b});"),
+        (Instruction::AssertNeq(_), _, [a, b]) => return format!("assert_neq({a}, {b});"),
+        (Instruction::Call(call), _, _) => format!("{}({})", call.operator(), operands.join(", ")),
+        (Instruction::Async(async_), _, _) => format!("async {}({})", async_.function_name(), operands.join(", ")),
+        (Instruction::Cast(cast), _, _) => cast_expression(program, cast.cast_type(), &operands),
+        (Instruction::CastLossy(cast), _, _) => format!("cast_lossy({}) as {}", operands.join(", "), cast.cast_type()),
+        (Instruction::Ternary(_), _, [condition, a, b]) => format!("{condition} ? {a} : {b}"),
+        (Instruction::Not(_), _, [a]) => format!("!{a}"),
+        (Instruction::Neg(_), _, [a]) => format!("-{a}"),
+        (_, Some(operator), [a, b]) => format!("{a} {operator} {b}"),
+        // Single-word and wrapped operations are rendered as methods of their first operand, i.e. `r0.add_wrapped(r1)`.
+        (_, _, [receiver, arguments @ ..]) if !opcode.contains('.') || opcode.ends_with(".w") => {
+            let method = match opcode.strip_suffix(".w") {
+                Some(operation) => format!("{operation}_wrapped"),
+                None => opcode.to_string(),
+            };
+            format!("{receiver}.{method}({})", arguments.join(", "))
+        }
+        _ => format!("{opcode}({})", operands.join(", ")),
+    };
+    assignment(&destinations, expression)
+}
+
+/// Returns the expression casting the given operands into the given type, as a struct or record literal if possible.
+fn cast_expression<N: Network>(program: &Program<N>, cast_type: &CastType<N>, operands: &[String]) -> String {
+    let literal = |name: String, members: Vec<String>| {
+        let fields = members.iter().zip(operands).map(|(member, operand)| format!("{member}: {operand}"));
+        format!("{name} {{ {} }}", fields.collect::<Vec<_>>().join(", "))
+    };
+    match cast_type {
+        CastType::Plaintext(PlaintextType::Struct(name)) => match program.get_struct(name) {
+            Ok(struct_) => literal(name.to_string(), struct_.members().keys().map(ToString::to_string).collect()),
+            Err(_) => format!("{name}({})", operands.join(", ")),
+        },
+        CastType::Plaintext(PlaintextType::Array(_)) => format!("[{}]", operands.join(", ")),
+        CastType::Record(name) => match program.get_record(name) {
+            Ok(record) => {
+                let entries = record.entries().keys().map(ToString::to_string);
+                literal(name.to_string(), std::iter::once("owner".to_string()).chain(entries).collect())
+            }
+            Err(_) => format!("{name}({})", operands.join(", ")),
+        },
+        CastType::ExternalRecord(locator) => format!("{locator}({})", operands.join(", ")),
+        _ => format!("{} as {cast_type}", operands.join(", ")),
+    }
+}
+
+/// Returns the statement rendering the given finalize command, other than a branch or position.
+fn command_statement<N: Network>(program: &Program<N>, command: &Command<N>) -> String {
+    match command {
+        Command::Instruction(instruction) => instruction_statement(program, instruction),
+        Command::Await(await_) => format!("{}.await();", await_.register()),
+        Command::Contains(contains) => {
+            let expression = format!("{}.contains({})", contains.mapping_name(), contains.key());
+            assignment(&[contains.destination().to_string()], expression)
+        }
+        Command::Get(get) => {
+            assignment(&[get.destination().to_string()], format!("{}.get({})", get.mapping(), get.key()))
+        }
+        Command::GetOrUse(get_or_use) => {
+            let expression =
+                format!("{}.get_or_use({}, {})", get_or_use.mapping(), get_or_use.key(), get_or_use.default());
+            assignment(&[get_or_use.destination().to_string()], expression)
+        }
+        Command::RandChaCha(rand_chacha) => {
+            let operands = rand_chacha.operands().iter().map(ToString::to_string).collect::<Vec<_>>();
+            let expression = format!("ChaCha::rand_{}({})", rand_chacha.destination_type(), operands.join(", "));
+            assignment(&[rand_chacha.destination().to_string()], expression)
+        }
+        Command::Remove(remove) => format!("{}.remove({});", remove.mapping_name(), remove.key()),
+        Command::Set(set) => format!("{}.set({}, {});", set.mapping_name(), set.key(), set.value()),
+        command => command.to_string(),
+    }
+}
+
+/// A branch: its operands, whether it jumps if they are equal (`branch.eq`) or not (`branch.neq`), and its target.
+struct Branch {
+    first: String,
+    second: String,
+    jumps_if_equal: bool,
+    label: String,
+}
+
+impl Branch {
+    /// Returns the branch of the given command, if it is one.
+    fn of<N: Network>(command: &Command<N>) -> Option<Self> {
+        let (first, second, jumps_if_equal, label) = match command {
+            Command::BranchEq(branch) => (branch.first(), branch.second(), true, branch.position()),
+            Command::BranchNeq(branch) => (branch.first(), branch.second(), false, branch.position()),
+            _ => return None,
+        };
+        Some(Self { first: first.to_string(), second: second.to_string(), jumps_if_equal, label: label.to_string() })
+    }
+
+    /// Returns `true` if the branch is always taken.
+    fn is_unconditional(&self) -> bool {
+        self.jumps_if_equal && self.first == self.second
+    }
+
+    /// Returns the condition comparing the operands for equality, or for inequality.
+    fn condition(&self, equal: bool) -> String {
+        let (first, second) = (&self.first, &self.second);
+        match (second.as_str(), equal) {
+            ("true", true) | ("false", false) => first.clone(),
+            ("true", false) | ("false", true) => format!("!{first}"),
+            (_, true) => format!("{first} == {second}"),
+            (_, false) => format!("{first} != {second}"),
+        }
+    }
+}
+
+/// The lines of pseudocode rendered so far.
+#[derive(Default)]
+struct Writer {
+    lines: Vec<String>,
+    /// The positions targeted by a `goto`, which are rendered as labels.
+    gotos: HashSet<String>,
+}
+
+impl Writer {
+    /// Adds the given line, indented to the given depth.
+    fn line(&mut self, depth: usize, line: impl AsRef<str>) {
+        self.lines.push(indent(depth, line.as_ref()));
+    }
+
+    /// Adds the given line, if any, indented to the given depth.
+    fn line_if_some(&mut self, depth: usize, line: Option<String>) {
+        if let Some(line) = line {
+            self.line(depth, line);
+        }
+    }
+
+    /// Renders `commands[start..end]`, reconstructing `if`/`else` blocks from the forward branches to a position.
+    ///
+    /// A branch to the position at `target` guards the commands up to it, and if these end with an unconditional branch
+    /// to a later position, the commands between the two positions are the `else` block.
+    fn commands<N: Network>(
+        &mut self,
+        program: &Program<N>,
+        commands: &[Command<N>],
+        labels: &HashMap<String, usize>,
+        start: usize,
+        end: usize,
+        depth: usize,
+    ) {
+        let mut index = start;
+        while index < end {
+            let command = &commands[index];
+            let branch = match Branch::of(command) {
+                Some(branch) => branch,
+                None => {
+                    match command {
+                        Command::Position(position) => {
+                            let label = position.name().to_string();
+                            if self.gotos.contains(&label) {
+                                self.line(depth.saturating_sub(1), format!("{label}:"));
+                            }
+                        }
+                        command => self.line(depth, command_statement(program, command)),
+                    }
+                    index += 1;
+                    continue;
+                }
+            };
+
+            match labels.get(&branch.label).copied().filter(|target| index < *target && *target <= end) {
+                Some(target) if !branch.is_unconditional() => {
+                    // The block is executed if the branch is not taken.
+                    self.line(depth, format!("if {} {{", branch.condition(!branch.jumps_if_equal)));
+                    let else_branch = (target > index + 1).then(|| Branch::of(&commands[target - 1])).flatten();
+                    let else_target = else_branch
+                        .filter(Branch::is_unconditional)
+                        .and_then(|else_branch| labels.get(&else_branch.label).copied())
+                        .filter(|else_target| target < *else_target && *else_target <= end);
+                    match else_target {
+                        Some(else_target) => {
+                            self.commands(program, commands, labels, index + 1, target - 1, depth + 1);
+                            self.line(depth, "} else {");
+                            self.commands(program, commands, labels, target + 1, else_target, depth + 1);
+                            self.line(depth, "}");
+                            index = else_target;
+                        }
+                        None => {
+                            self.commands(program, commands, labels, index + 1, target, depth + 1);
+                            self.line(depth, "}");
+                            index = target;
+                        }
+                    }
+                }
+                _ => {
+                    match branch.is_unconditional() {
+                        true => self.line(depth, format!("goto {};", branch.label)),
+                        false => {
+                            let condition = branch.condition(branch.jumps_if_equal);
+                            self.line(depth, format!("if {condition} {{ goto {}; }}", branch.label))
+                        }
+                    }
+                    self.gotos.insert(branch.label);
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    /// Returns the rendered pseudocode.
+    fn finish(self) -> String {
+        self.lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"program vault.aleo;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+struct point:
+    x as field;
+    y as field;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+closure offset:
+    input r0 as field;
+    input r1 as field;
+    add r0 r1 into r2;
+    add.w r0 r1 into r3;
+    cast r2 r3 into r4 as point;
+    output r4 as point;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.private;
+    assert.neq r1 0u64;
+    cast r0 r1 into r2 as token.record;
+    async mint r0 r1 into r3;
+    output r2 as token.record;
+    output r3 as vault.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use balances[r0] 0u64 into r2;
+    gt r1 100u64 into r3;
+    branch.eq r3 false to small;
+    add r2 r1 into r4;
+    set r4 into balances[r0];
+    branch.eq true true to end;
+    position small;
+    remove balances[r0];
+    position end;
+";
+
+    #[test]
+    fn test_decompile_closure() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let closure = program.closures().values().next().unwrap();
+        assert_eq!(
+            decompile_closure(&program, closure),
+            "closure offset(r0: field, r1: field) -> point {
+    let r2 = r0 + r1;
+    let r3 = r0.add_wrapped(r1);
+    let r4 = point { x: r2, y: r3 };
+    return r4;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_decompile_function() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let function = program.functions().values().next().unwrap();
+        assert_eq!(
+            decompile_function(&program, function),
+            "function mint(r0: address.private, r1: u64.private) -> (token.record, vault.aleo/mint.future) {
+    assert_neq(r1, 0u64);
+    let r2 = token { owner: r0, amount: r1 };
+    let r3 = async mint(r0, r1);
+    return (r2, r3);
+}
+
+finalize mint(r0: address.public, r1: u64.public) {
+    let r2 = balances.get_or_use(r0, 0u64);
+    let r3 = r1 > 100u64;
+    if r3 {
+        let r4 = r2 + r1;
+        balances.set(r0, r4);
+    } else {
+        balances.remove(r0);
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_decompile_program() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let pseudocode = decompile_program(&program);
+        assert!(pseudocode.starts_with(
+            "program vault.aleo {
+    mapping balances: address => u64;
+    struct point { x: field, y: field }
+    record token { owner: address.private, amount: u64.private }
+
+    closure offset(r0: field, r1: field) -> point {"
+        ));
+        assert!(pseudocode.ends_with("    }\n}\n"));
+
+        let mut document = program.to_json();
+        attach_pseudocode(&program, &mut document);
+        assert_eq!(document["closures"]["offset"]["pseudocode"], decompile_closure(&program, &program.closures()[0]));
+        assert!(document["functions"]["mint"]["pseudocode"].as_str().unwrap().starts_with("function mint("));
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        convert_program_with_options,
+        parse_program,
+        ConversionError,
+        ConversionMode,
+        ImportResolution,
+        ImportResolver,
+        JsonOptions,
+    },
+    console::{network::Network, program::ProgramID},
+    synthesizer::Program,
+};
+
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// A program of a dependency closure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dependency {
+    /// The ID of the program.
+    pub program_id: String,
+    /// Where the program was resolved from, or `None` for the converted program itself.
+    pub resolution: Option<ImportResolution>,
+    /// The IDs of the programs it imports.
+    pub imports: Vec<String>,
+    /// The program JSON.
+    pub document: Value,
+}
+
+/// A program, and every program it imports, directly or through other imports.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DependencyClosure {
+    /// The programs in dependency order, so each program follows the programs it imports,
+    /// ending with the converted program.
+    pub programs: Vec<Dependency>,
+    /// The imports that could not be resolved, or converted, each with the reason, if any.
+    pub unresolved: Vec<(String, Option<String>)>,
+}
+
+impl DependencyClosure {
+    /// Returns `true` if every import was resolved and converted.
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+
+    /// Returns the manifest of the closure: the dependency order, and the imports and resolution of each program.
+    pub fn manifest(&self) -> Value {
+        let j_order: Vec<_> = self.programs.iter().map(|program| program.program_id.clone()).collect();
+
+        let mut j_programs = serde_json::Map::new();
+        for program in &self.programs {
+            let resolution = program.resolution.as_ref();
+            j_programs.insert(
+                program.program_id.clone(),
+                json!({
+                    "imports": program.imports,
+                    "source": resolution.map(|resolution| resolution.source.as_str()),
+                    "location": resolution.map(|resolution| resolution.location.clone()),
+                    "edition": resolution.and_then(|resolution| resolution.edition),
+                }),
+            );
+        }
+
+        let mut j_unresolved = Vec::new();
+        for (program_id, error) in &self.unresolved {
+            j_unresolved.push(json!({ "program_id": program_id, "error": error }));
+        }
+
+        json!({
+            "type": "DependencyManifest",
+            "root": j_order.last(),
+            "order": j_order,
+            "programs": j_programs,
+            "unresolved": j_unresolved,
+            "complete": self.is_complete(),
+        })
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_programs = serde_json::Map::new();
+        for program in &self.programs {
+            j_programs.insert(program.program_id.clone(), program.document.clone());
+        }
+
+        json!({
+            "type": "DependencyClosure",
+            "manifest": self.manifest(),
+            "programs": j_programs,
+        })
+    }
+
+    /// Returns `true` if the closure has visited the given program.
+    fn contains(&self, program_id: &str) -> bool {
+        self.programs.iter().any(|program| program.program_id == program_id)
+            || self.unresolved.iter().any(|(unresolved, _)| unresolved == program_id)
+    }
+
+    /// Converts the given program after the programs it imports, resolving each with the given resolver.
+    ///
+    /// A program resolved for an import must have the ID of the import; the resolved source is rejected otherwise.
+    fn add_program<N: Network>(
+        &mut self,
+        source: &str,
+        resolution: Option<(&ProgramID<N>, ImportResolution)>,
+        context: &ClosureContext,
+        visiting: &mut HashSet<String>,
+    ) -> Result<(), ConversionError> {
+        let program = parse_program::<N>(source)?;
+        let resolution = match resolution {
+            Some((import, _)) if program.id() != import => {
+                return Err(ConversionError::Invalid(format!(
+                    "The program resolved for '{import}' is '{}'",
+                    program.id()
+                )));
+            }
+            Some((_, resolution)) => Some(resolution),
+            None => None,
+        };
+        let program_id = program.id().to_string();
+        visiting.insert(program_id.clone());
+
+        for import in program.imports().keys() {
+            let key = import.to_string();
+            // The imports of a deployed program are acyclic, but a local program may not be.
+            if visiting.contains(&key) || self.contains(&key) {
+                continue;
+            }
+            let resolved = context.resolver.resolve(import).and_then(|resolution| match resolution {
+                Some(resolution) => Ok(Some((context.resolver.source(import, &resolution)?, resolution))),
+                None => Ok(None),
+            });
+            let outcome = match resolved {
+                Ok(Some((Some(source), resolution))) => {
+                    self.add_program::<N>(&source, Some((import, resolution)), context, visiting)
+                }
+                Ok(Some((None, resolution))) => match Program::<N>::credits() {
+                    Ok(credits) => {
+                        self.add_program::<N>(&credits.to_string(), Some((import, resolution)), context, visiting)
+                    }
+                    Err(error) => Err(ConversionError::Invalid(error.to_string())),
+                },
+                Ok(None) => {
+                    self.unresolved.push((key, None));
+                    Ok(())
+                }
+                Err(error) => Err(ConversionError::Invalid(error.to_string())),
+            };
+            if let Err(error) = outcome {
+                self.unresolved.push((import.to_string(), Some(error.to_string())));
+            }
+        }
+
+        let document = convert_program_with_options::<N>(source, context.mode, context.options)?;
+        let imports = program.imports().keys().map(|import| import.to_string()).collect();
+        self.programs.push(Dependency { program_id, resolution, imports, document });
+        Ok(())
+    }
+}
+
+/// The settings shared by the conversions of a dependency closure.
+struct ClosureContext<'a> {
+    resolver: &'a ImportResolver,
+    mode: ConversionMode,
+    options: &'a JsonOptions,
+}
+
+/// Converts the given Aleo source, and every program it imports, directly or through other imports,
+/// resolving the imports with the given resolver (and the built-in programs from the VM).
+///
+/// Each program is converted with the given mode and options, except for their import resolver, as the closure
+/// resolves the imports itself. The imports that fail to resolve or convert, or that resolve to another program,
+/// are listed in the manifest, and do not fail the conversion; only the given program must convert.
+pub fn convert_dependency_closure<N: Network>(
+    source: &str,
+    resolver: &ImportResolver,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<DependencyClosure, ConversionError> {
+    let mut closure = DependencyClosure::default();
+    let options = JsonOptions { imports: None, ..options.clone() };
+    let context = ClosureContext { resolver, mode, options: &options };
+    closure.add_program::<N>(source, None, &context, &mut HashSet::new())?;
+    Ok(closure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::ImportSource, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    const TOKEN: &str = r"program token.aleo;
+
+function mint:
+    input r0 as u64.public;
+    output r0 as u64.public;
+";
+
+    const SWAP: &str = r"import token.aleo;
+program swap.aleo;
+
+function swap:
+    input r0 as u64.public;
+    output r0 as u64.public;
+";
+
+    const ROUTER: &str = r"import credits.aleo;
+import swap.aleo;
+import token.aleo;
+import missing.aleo;
+program router.aleo;
+
+function route:
+    input r0 as u64.public;
+    output r0 as u64.public;
+";
+
+    #[test]
+    fn test_convert_dependency_closure() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("token.aleo"), TOKEN).unwrap();
+        std::fs::write(directory.path().join("swap.aleo"), SWAP).unwrap();
+        let resolver = ImportResolver { local: Some(directory.path().to_path_buf()), ..Default::default() };
+
+        let (mode, options) = (ConversionMode::default(), JsonOptions::default());
+        let closure = convert_dependency_closure::<CurrentNetwork>(ROUTER, &resolver, mode, &options).unwrap();
+
+        // The imports precede the programs that import them, and each program is converted once.
+        let order: Vec<_> = closure.programs.iter().map(|program| program.program_id.as_str()).collect();
+        assert_eq!(order, ["credits.aleo", "token.aleo", "swap.aleo", "router.aleo"]);
+        assert_eq!(closure.unresolved, [("missing.aleo".to_string(), None)]);
+        assert!(!closure.is_complete());
+
+        let manifest = closure.manifest();
+        assert_eq!(manifest["root"], "router.aleo");
+        assert_eq!(manifest["programs"]["credits.aleo"]["source"], ImportSource::Builtin.as_str());
+        assert_eq!(manifest["programs"]["swap.aleo"]["imports"], json!(["token.aleo"]));
+        assert_eq!(manifest["programs"]["router.aleo"]["source"], Value::Null);
+
+        let j_closure = closure.to_json();
+        assert_eq!(j_closure["programs"]["token.aleo"]["type"], "ProgramCore");
+    }
+
+    #[test]
+    fn test_convert_dependency_closure_mismatched_program() {
+        // The file resolved for `token.aleo` holds another program.
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("token.aleo"), TOKEN.replace("token.aleo", "other.aleo")).unwrap();
+        let resolver = ImportResolver { local: Some(directory.path().to_path_buf()), ..Default::default() };
+
+        let options = JsonOptions { imports: Some(resolver.clone()), ..Default::default() };
+        let closure =
+            convert_dependency_closure::<CurrentNetwork>(SWAP, &resolver, ConversionMode::default(), &options).unwrap();
+        let order: Vec<_> = closure.programs.iter().map(|program| program.program_id.as_str()).collect();
+        assert_eq!(order, ["swap.aleo"]);
+        let (program_id, error) = &closure.unresolved[0];
+        assert_eq!(program_id, "token.aleo");
+        assert!(error.as_ref().unwrap().contains("other.aleo"));
+        // The imports are not resolved again by the conversion of each program.
+        assert!(closure.programs[0].document["imports"]["token.aleo"].get("resolution").is_none());
+    }
+}
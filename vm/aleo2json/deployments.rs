@@ -0,0 +1,345 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        attach_interface,
+        attach_program_sections,
+        checksum,
+        parse_program,
+        ImportResolution,
+        ImportResolver,
+        JsonOptions,
+        ToJson,
+    },
+    console::{
+        network::Network,
+        program::{ProgramID, ProgramOwner},
+    },
+    ledger::block::{Block, Deployment},
+    synthesizer::Program,
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// Returns the listing of the given deployment, accepted in the transaction with the given ID at the given height:
+/// its program ID, edition, deployer, and block height, with the converted program.
+///
+/// The program includes the optional sections selected by the given options, and the listing includes the source
+/// checksum of the program, as for `attach_deployment_source_checksum`.
+pub fn deployment_listing_to_json<N: Network>(
+    block_height: u32,
+    transaction_id: &N::TransactionID,
+    owner: &ProgramOwner<N>,
+    deployment: &Deployment<N>,
+    options: &JsonOptions,
+) -> Result<Value> {
+    let program = deployment.program();
+    let mut j_program = program.to_json();
+    attach_program_sections(program, &mut j_program, options)?;
+    if options.include_interface {
+        attach_interface(program, &mut j_program);
+    }
+    options.extend(program, &mut j_program);
+    let mut listing = json!({
+        "type": "DeploymentListing",
+        "program_id": deployment.program_id().to_string(),
+        "edition": deployment.edition(),
+        "deployer": owner.address().to_string(),
+        "block_height": block_height,
+        "transaction_id": transaction_id.to_string(),
+        "program": j_program,
+    });
+    attach_deployment_source_checksum(deployment, &mut listing, options)?;
+    Ok(listing)
+}
+
+/// Returns the source checksum of the given program: the SHA3-256 hash of the bits of its canonical source
+/// (i.e. `program.to_string()`), prefixed by `sha3-256:`, so copies of a program that differ only in formatting have
+/// the same checksum.
+///
+/// Note: The checksum is defined by aleo2json, to compare a deployed program with its copies; it is not a checksum
+/// recorded by the network, and is not part of the deployment.
+pub fn source_checksum<N: Network>(program: &Program<N>) -> Result<String> {
+    checksum(program.to_string().as_bytes())
+}
+
+/// Returns the resolution of the copy of the given program found by the given resolver, with its source checksum,
+/// or `None` if no source has the program.
+fn resolve_source_checksum<N: Network>(
+    program_id: &ProgramID<N>,
+    resolver: &ImportResolver,
+) -> Result<Option<(ImportResolution, String)>> {
+    let Some(resolution) = resolver.resolve(program_id)? else {
+        return Ok(None);
+    };
+    let program = match resolver.source(program_id, &resolution)? {
+        Some(source) => parse_program::<N>(&source)?,
+        // The built-in program has no source to read.
+        None => Program::credits()?,
+    };
+    Ok(Some((resolution, source_checksum(&program)?)))
+}
+
+/// Returns the comparison of the source checksum of the given deployed program with the one recomputed from its copy
+/// found by the given resolver: where the copy was resolved from, its checksum, and whether the two checksums match.
+///
+/// A copy that is not found, or fails to resolve or to parse, is marked unresolved, with the reason under `error`.
+pub fn source_checksum_check<N: Network>(program: &Program<N>, resolver: Option<&ImportResolver>) -> Result<Value> {
+    let deployed = source_checksum(program)?;
+    let outcome = match resolver {
+        Some(resolver) => resolve_source_checksum(program.id(), resolver),
+        None => Ok(None),
+    };
+    Ok(match outcome {
+        Ok(Some((resolution, recomputed))) => json!({
+            "resolved": true,
+            "source": resolution.source.as_str(),
+            "location": resolution.location,
+            "checksum": recomputed,
+            "matches": recomputed == deployed,
+            "error": null,
+        }),
+        Ok(None) => json!({
+            "resolved": false,
+            "source": null,
+            "location": null,
+            "checksum": null,
+            "matches": null,
+            "error": null,
+        }),
+        Err(error) => json!({
+            "resolved": false,
+            "source": null,
+            "location": null,
+            "checksum": null,
+            "matches": null,
+            "error": error.to_string(),
+        }),
+    })
+}
+
+/// Adds the source checksum of the program of the given deployment to the given deployment JSON,
+/// under `source_checksum`, next to its `edition`.
+///
+/// If the options verify checksums, the checksum is also compared with the one recomputed from the copy of the program
+/// found by the import resolver of the options, under `checksum_check`.
+pub fn attach_deployment_source_checksum<N: Network>(
+    deployment: &Deployment<N>,
+    j_deployment: &mut Value,
+    options: &JsonOptions,
+) -> Result<()> {
+    j_deployment["edition"] = json!(deployment.edition());
+    j_deployment["source_checksum"] = json!(source_checksum(deployment.program())?);
+    if options.verify_source_checksums {
+        j_deployment["checksum_check"] = source_checksum_check(deployment.program(), options.imports.as_ref())?;
+    }
+    Ok(())
+}
+
+/// Adds the source checksum of the program of each deployment accepted in the given block to the given block JSON,
+/// as for `attach_deployment_source_checksum`.
+///
+/// Only accepted deployments are described; a rejected deployment is left as is.
+pub fn attach_block_source_checksums<N: Network>(
+    block: &Block<N>,
+    j_block: &mut Value,
+    options: &JsonOptions,
+) -> Result<()> {
+    for (index, confirmed) in block.transactions().iter().enumerate() {
+        if let Some(deployment) = confirmed.transaction().deployment() {
+            let j_deployment = &mut j_block["transactions"][index]["transaction"]["value"]["deployment"];
+            attach_deployment_source_checksum(deployment, j_deployment, options)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the listings of the deployments accepted in the given block, in order of their transactions.
+///
+/// Rejected deployments are confirmed as fee transactions, so they are not listed.
+pub fn block_deployments_to_json<N: Network>(block: &Block<N>, options: &JsonOptions) -> Result<Vec<Value>> {
+    let mut listings = Vec::new();
+    for confirmed in block.transactions().iter() {
+        let transaction = confirmed.transaction();
+        if let (Some(owner), Some(deployment)) = (transaction.owner(), transaction.deployment()) {
+            listings.push(deployment_listing_to_json(block.height(), &transaction.id(), owner, deployment, options)?);
+        }
+    }
+    Ok(listings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuit::AleoV0,
+        console::{account::PrivateKey, network::prelude::FromBytes, types::Field},
+        ledger::{
+            block::{ConfirmedTransaction, Transaction, Transactions},
+            query::Query,
+            store::{helpers::memory::BlockMemory, BlockStore},
+        },
+        prelude::Testnet3,
+        synthesizer::{program::FinalizeOperation, Process, Program},
+    };
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_deployment_listing_to_json() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program registry.aleo;
+
+function double:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;
+",
+        )
+        .unwrap();
+        let process = Process::<CurrentNetwork>::load().unwrap();
+        let deployment = process.deploy::<AleoV0, _>(&program, rng).unwrap();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let owner = ProgramOwner::new(&private_key, deployment.to_deployment_id().unwrap(), rng).unwrap();
+        let transaction_id = <CurrentNetwork as Network>::TransactionID::from(Field::from_u64(1));
+
+        let options = JsonOptions { include_interface: true, ..Default::default() };
+        let listing = deployment_listing_to_json(7, &transaction_id, &owner, &deployment, &options).unwrap();
+        assert_eq!(listing["type"], "DeploymentListing");
+        assert_eq!(listing["program_id"], "registry.aleo");
+        assert_eq!(listing["edition"], deployment.edition());
+        assert_eq!(listing["deployer"], owner.address().to_string());
+        assert_eq!(listing["block_height"], 7);
+        assert_eq!(listing["program"]["interface"][0]["name"], "double");
+        assert_eq!(listing["source_checksum"], source_checksum(&program).unwrap());
+        assert!(listing.get("checksum_check").is_none());
+    }
+
+    #[test]
+    fn test_source_checksum_check() {
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program registry.aleo;
+
+function double:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;
+",
+        )
+        .unwrap();
+        // The checksum is that of the canonical source, regardless of formatting.
+        let reformatted = Program::<CurrentNetwork>::from_str(&program.to_string().replace('\n', "\n\n")).unwrap();
+        assert_eq!(source_checksum(&program).unwrap(), source_checksum(&reformatted).unwrap());
+
+        let check = source_checksum_check(&program, None).unwrap();
+        assert_eq!(check["resolved"], false);
+        assert_eq!(check["matches"], Value::Null);
+
+        let directory = tempfile::tempdir().unwrap();
+        let resolver = ImportResolver { local: Some(directory.path().to_path_buf()), ..Default::default() };
+        std::fs::write(directory.path().join("registry.aleo"), program.to_string()).unwrap();
+        let check = source_checksum_check(&program, Some(&resolver)).unwrap();
+        assert_eq!(check["resolved"], true);
+        assert_eq!(check["source"], "local");
+        assert_eq!(check["matches"], true);
+
+        std::fs::write(directory.path().join("registry.aleo"), program.to_string().replace("add", "mul")).unwrap();
+        let check = source_checksum_check(&program, Some(&resolver)).unwrap();
+        assert_eq!(check["matches"], false);
+        assert_ne!(check["checksum"], source_checksum(&program).unwrap());
+
+        std::fs::write(directory.path().join("registry.aleo"), "program registry.aleo").unwrap();
+        let check = source_checksum_check(&program, Some(&resolver)).unwrap();
+        assert_eq!(check["resolved"], false);
+        assert!(check["error"].is_string());
+    }
+
+    #[test]
+    fn test_attach_block_source_checksums() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program registry.aleo;
+
+function double:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;
+",
+        )
+        .unwrap();
+        let process = Process::<CurrentNetwork>::load().unwrap();
+        let deployment = process.deploy::<AleoV0, _>(&program, rng).unwrap();
+        let deployment_id = deployment.to_deployment_id().unwrap();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let owner = ProgramOwner::new(&private_key, deployment_id, rng).unwrap();
+
+        // Prove the fee of the deployment against the genesis block.
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let authorization =
+            process.authorize_fee_public::<AleoV0, _>(&private_key, 10_000_000, 0, deployment_id, rng).unwrap();
+        let (_, mut trace) = process.execute::<AleoV0, _>(authorization, rng).unwrap();
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        block_store.insert(&genesis).unwrap();
+        trace.prepare(Query::from(block_store)).unwrap();
+        let fee = trace.prove_fee::<AleoV0, _>(rng).unwrap();
+
+        // Append the deployment, accepted, to the transactions of the genesis block.
+        let transaction = Transaction::from_deployment(owner, deployment, fee).unwrap();
+        let index = genesis.transactions().len();
+        let operation =
+            FinalizeOperation::UpdateKeyValue(Field::from_u64(0), 0, Field::from_u64(1), Field::from_u64(2));
+        let confirmed = ConfirmedTransaction::accepted_deploy(index as u32, transaction, vec![operation]).unwrap();
+        let transactions = genesis.transactions().iter().cloned().chain([confirmed]).collect::<Transactions<_>>();
+        let block = Block::from_unchecked(
+            genesis.hash(),
+            genesis.previous_hash(),
+            *genesis.header(),
+            genesis.authority().clone(),
+            genesis.ratifications().clone(),
+            genesis.solutions().cloned(),
+            transactions,
+            vec![],
+        )
+        .unwrap();
+
+        let mut j_block = block.to_json();
+        attach_block_source_checksums(&block, &mut j_block, &JsonOptions::default()).unwrap();
+        let j_transactions = j_block["transactions"].as_array().unwrap();
+        let j_deployment = &j_transactions[index]["transaction"]["value"]["deployment"];
+        assert_eq!(j_deployment["source_checksum"], source_checksum(&program).unwrap());
+        assert!(j_deployment.get("checksum_check").is_none());
+        // The other transactions are left as is.
+        for j_transaction in &j_transactions[..index] {
+            assert!(j_transaction["transaction"]["value"].get("deployment").is_none());
+        }
+    }
+
+    #[test]
+    fn test_block_deployments_to_json() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let listings = block_deployments_to_json(&genesis, &JsonOptions::default()).unwrap();
+        let num_deployments =
+            genesis.transactions().iter().filter(|confirmed| confirmed.transaction().deployment().is_some()).count();
+        assert_eq!(listings.len(), num_deployments);
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::{json, Value};
+
+/// The kind of a difference between two JSON documents.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    /// The value is only present in the right document.
+    Added,
+    /// The value is only present in the left document.
+    Removed,
+    /// The value is present in both documents, but differs.
+    Changed,
+}
+
+impl DiffKind {
+    /// Returns the name of the difference kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+        }
+    }
+}
+
+/// A single difference between two JSON documents, located by its JSON Pointer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonDiff {
+    /// The JSON Pointer (RFC 6901) of the differing value.
+    pub pointer: String,
+    /// The kind of difference.
+    pub kind: DiffKind,
+    /// The value in the left document, if present.
+    pub left: Option<Value>,
+    /// The value in the right document, if present.
+    pub right: Option<Value>,
+}
+
+impl JsonDiff {
+    /// Returns the difference as JSON.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "pointer": self.pointer,
+            "kind": self.kind.as_str(),
+            "left": self.left,
+            "right": self.right,
+        })
+    }
+}
+
+/// Returns the structural differences between the two given JSON documents.
+///
+/// Objects are compared key by key, and arrays are compared index by index,
+/// so the result is reported at the deepest differing location.
+pub fn diff_json(left: &Value, right: &Value) -> Vec<JsonDiff> {
+    let mut diffs = Vec::new();
+    diff_at(&mut String::new(), left, right, &mut diffs);
+    diffs
+}
+
+/// Appends the differences between `left` and `right`, located at `pointer`, to `diffs`.
+fn diff_at(pointer: &mut String, left: &Value, right: &Value, diffs: &mut Vec<JsonDiff>) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            for (key, left_value) in left {
+                let length = push_token(pointer, key);
+                match right.get(key) {
+                    Some(right_value) => diff_at(pointer, left_value, right_value, diffs),
+                    None => diffs.push(JsonDiff {
+                        pointer: pointer.clone(),
+                        kind: DiffKind::Removed,
+                        left: Some(left_value.clone()),
+                        right: None,
+                    }),
+                }
+                pointer.truncate(length);
+            }
+            for (key, right_value) in right.iter().filter(|(key, _)| !left.contains_key(*key)) {
+                let length = push_token(pointer, key);
+                diffs.push(JsonDiff {
+                    pointer: pointer.clone(),
+                    kind: DiffKind::Added,
+                    left: None,
+                    right: Some(right_value.clone()),
+                });
+                pointer.truncate(length);
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let length = push_token(pointer, &index.to_string());
+                match (left.get(index), right.get(index)) {
+                    (Some(left_value), Some(right_value)) => diff_at(pointer, left_value, right_value, diffs),
+                    (left_value, right_value) => diffs.push(JsonDiff {
+                        pointer: pointer.clone(),
+                        kind: match left_value.is_some() {
+                            true => DiffKind::Removed,
+                            false => DiffKind::Added,
+                        },
+                        left: left_value.cloned(),
+                        right: right_value.cloned(),
+                    }),
+                }
+                pointer.truncate(length);
+            }
+        }
+        (left, right) if left != right => diffs.push(JsonDiff {
+            pointer: pointer.clone(),
+            kind: DiffKind::Changed,
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+        _ => (),
+    }
+}
+
+/// Appends the escaped reference token to the pointer, returning the length of the pointer beforehand.
+pub(crate) fn push_token(pointer: &mut String, token: &str) -> usize {
+    let length = pointer.len();
+    pointer.push('/');
+    pointer.push_str(&token.replace('~', "~0").replace('/', "~1"));
+    length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_json() {
+        let left = json!({ "a": 1, "b": [1, 2], "c/d": { "e": true } });
+        let right = json!({ "a": 2, "b": [1], "c/d": { "e": true }, "f": null });
+
+        let diffs = diff_json(&left, &right);
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(diffs[0], JsonDiff {
+            pointer: "/a".to_string(),
+            kind: DiffKind::Changed,
+            left: Some(json!(1)),
+            right: Some(json!(2))
+        });
+        assert_eq!(diffs[1].pointer, "/b/1");
+        assert_eq!(diffs[1].kind, DiffKind::Removed);
+        assert_eq!(diffs[2].pointer, "/f");
+        assert_eq!(diffs[2].kind, DiffKind::Added);
+    }
+
+    #[test]
+    fn test_diff_json_identical() {
+        let document = json!({ "a": [{ "b": "c" }] });
+        assert!(diff_json(&document, &document).is_empty());
+    }
+}
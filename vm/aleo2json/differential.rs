@@ -0,0 +1,236 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{push_token, ToJson},
+    console::network::prelude::Serialize,
+};
+
+use serde_json::{json, Value};
+
+/// The keys that tag a representation with its type, rather than carrying data, in either serialization.
+const TAG_KEYS: [&str; 2] = ["type", "vtype"];
+
+/// The kind of a structural divergence between the Vanguard JSON and the serde JSON of an object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The field is only present in the serde JSON, i.e. `to_json` does not export it.
+    MissingInToJson,
+    /// The field is only present in the Vanguard JSON.
+    MissingInSerde,
+    /// The arrays have a different number of elements.
+    LengthMismatch,
+    /// The serde JSON is structured (an object or an array), but the Vanguard JSON is not, or vice versa.
+    ShapeMismatch,
+}
+
+impl DivergenceKind {
+    /// Returns the name of the divergence kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingInToJson => "missing_in_to_json",
+            Self::MissingInSerde => "missing_in_serde",
+            Self::LengthMismatch => "length_mismatch",
+            Self::ShapeMismatch => "shape_mismatch",
+        }
+    }
+}
+
+/// A structural divergence between the two serializations, located by its JSON Pointer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The JSON Pointer (RFC 6901) of the divergence, in the serde JSON if the location exists there.
+    pub pointer: String,
+    /// The kind of divergence.
+    pub kind: DivergenceKind,
+    /// The value in the Vanguard JSON, if present.
+    pub to_json: Option<Value>,
+    /// The value in the serde JSON, if present.
+    pub serde: Option<Value>,
+}
+
+impl Divergence {
+    /// Returns the divergence as JSON.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "pointer": self.pointer,
+            "kind": self.kind.as_str(),
+            "to_json": self.to_json,
+            "serde": self.serde,
+        })
+    }
+}
+
+/// The structural divergences between the Vanguard JSON and the serde (human-readable) JSON of an object.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Differential {
+    /// The divergences, in document order.
+    divergences: Vec<Divergence>,
+}
+
+impl Differential {
+    /// Serializes the given object through both paths, and compares the results.
+    pub fn of<T: ToJson + Serialize>(object: &T) -> Result<Self, serde_json::Error> {
+        Ok(Self::between(&object.to_json(), &serde_json::to_value(object)?))
+    }
+
+    /// Compares the given Vanguard JSON against the given serde JSON.
+    ///
+    /// Only the structure is compared: objects are compared by their keys (ignoring the type tags),
+    /// and arrays by their length. A scalar in the serde JSON matches any value in the Vanguard JSON,
+    /// as `to_json` may expand scalars into tagged objects.
+    pub fn between(to_json: &Value, serde: &Value) -> Self {
+        let mut divergences = Vec::new();
+        diverge_at(&mut String::new(), to_json, serde, &mut divergences);
+        Self { divergences }
+    }
+
+    /// Returns the divergences, in document order.
+    pub fn divergences(&self) -> &[Divergence] {
+        &self.divergences
+    }
+
+    /// Returns `true` if the two serializations have the same structure.
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Returns the differential as JSON.
+    pub fn to_json(&self) -> Value {
+        let mut j_divergences = Vec::new();
+        for val in &self.divergences {
+            j_divergences.push(val.to_json());
+        }
+
+        json!({
+            "type": "Differential",
+            "consistent": self.is_consistent(),
+            "divergences": j_divergences,
+        })
+    }
+}
+
+/// Appends the divergences between `to_json` and `serde`, located at `pointer`, to `divergences`.
+fn diverge_at(pointer: &mut String, to_json: &Value, serde: &Value, divergences: &mut Vec<Divergence>) {
+    match (to_json, serde) {
+        (Value::Object(to_json), Value::Object(serde)) => {
+            for (key, serde_value) in serde.iter().filter(|(key, _)| !TAG_KEYS.contains(&key.as_str())) {
+                let length = push_token(pointer, key);
+                match to_json.get(key) {
+                    Some(to_json_value) => diverge_at(pointer, to_json_value, serde_value, divergences),
+                    None => divergences.push(Divergence {
+                        pointer: pointer.clone(),
+                        kind: DivergenceKind::MissingInToJson,
+                        to_json: None,
+                        serde: Some(serde_value.clone()),
+                    }),
+                }
+                pointer.truncate(length);
+            }
+            for (key, to_json_value) in
+                to_json.iter().filter(|(key, _)| !TAG_KEYS.contains(&key.as_str()) && !serde.contains_key(*key))
+            {
+                let length = push_token(pointer, key);
+                divergences.push(Divergence {
+                    pointer: pointer.clone(),
+                    kind: DivergenceKind::MissingInSerde,
+                    to_json: Some(to_json_value.clone()),
+                    serde: None,
+                });
+                pointer.truncate(length);
+            }
+        }
+        (Value::Array(to_json_values), Value::Array(serde_values)) => {
+            match to_json_values.len() == serde_values.len() {
+                true => {
+                    for (index, (to_json_value, serde_value)) in to_json_values.iter().zip(serde_values).enumerate() {
+                        let length = push_token(pointer, &index.to_string());
+                        diverge_at(pointer, to_json_value, serde_value, divergences);
+                        pointer.truncate(length);
+                    }
+                }
+                false => divergences.push(Divergence {
+                    pointer: pointer.clone(),
+                    kind: DivergenceKind::LengthMismatch,
+                    to_json: Some(to_json.clone()),
+                    serde: Some(serde.clone()),
+                }),
+            }
+        }
+        // A structured value in the serde JSON must be structured alike in the Vanguard JSON.
+        (_, Value::Object(..) | Value::Array(..)) => divergences.push(Divergence {
+            pointer: pointer.clone(),
+            kind: DivergenceKind::ShapeMismatch,
+            to_json: Some(to_json.clone()),
+            serde: Some(serde.clone()),
+        }),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_differential_between() {
+        let to_json = json!({
+            "type": "Transition",
+            "id": "au1",
+            "program_id": { "type": "ProgramID", "name": "credits" },
+            "inputs": [{ "type": "Input", "vtype": "Public", "id": { "type": "Field", "field": "1field" } }],
+            "tpk": "1group",
+        });
+        let serde = json!({
+            "id": "au1",
+            "program": "credits.aleo",
+            "inputs": [{ "type": "public", "id": "1field", "value": "1u64" }],
+            "tpk": { "x": "1field", "y": "2field" },
+        });
+
+        let differential = Differential::between(&to_json, &serde);
+        let divergences = differential
+            .divergences()
+            .iter()
+            .map(|divergence| (divergence.pointer.as_str(), divergence.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(divergences, [
+            ("/program", DivergenceKind::MissingInToJson),
+            ("/inputs/0/value", DivergenceKind::MissingInToJson),
+            ("/tpk", DivergenceKind::ShapeMismatch),
+            ("/program_id", DivergenceKind::MissingInSerde),
+        ]);
+        assert!(!differential.is_consistent());
+        assert_eq!(differential.to_json()["divergences"][0]["kind"], "missing_in_to_json");
+    }
+
+    #[test]
+    fn test_differential_length_mismatch() {
+        let differential = Differential::between(&json!({ "outputs": [1] }), &json!({ "outputs": [1, 2] }));
+        assert_eq!(differential.divergences().len(), 1);
+        assert_eq!(differential.divergences()[0].kind, DivergenceKind::LengthMismatch);
+        assert_eq!(differential.divergences()[0].pointer, "/outputs");
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_differential_of_genesis_header() {
+        use crate::{console::network::prelude::FromBytes, ledger::block::Block, prelude::Testnet3};
+
+        let block = Block::<Testnet3>::read_le(Testnet3::genesis_bytes()).unwrap();
+        // The header exports every field that serde does, so adding a field to one path alone fails here.
+        let differential = Differential::of(block.header()).unwrap();
+        assert!(differential.is_consistent(), "{:?}", differential.divergences());
+    }
+}
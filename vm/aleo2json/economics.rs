@@ -0,0 +1,305 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::network::Network,
+    ledger::block::{Block, Fee, Ratify, Transaction},
+};
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+/// The number of microcredits in one credit.
+pub const MICROCREDITS_PER_CREDIT: u64 = 1_000_000;
+
+/// Renders the given amount of microcredits as credits, with all six decimal places, i.e. `1.500000`.
+pub fn format_credits(microcredits: u64) -> String {
+    format!("{}.{:06}", microcredits / MICROCREDITS_PER_CREDIT, microcredits % MICROCREDITS_PER_CREDIT)
+}
+
+/// Renders the given integer in scientific notation, keeping every significant digit, i.e. `1.25e9`.
+pub fn format_scientific(value: u128) -> String {
+    format!("{:e}", value)
+}
+
+/// Renders the given Unix timestamp, in seconds, as an ISO-8601 date and time in UTC, i.e. `2023-09-01T12:00:00Z`.
+pub fn format_timestamp(timestamp: i64) -> String {
+    let (days, seconds) = (timestamp.div_euclid(86_400), timestamp.rem_euclid(86_400));
+    // Convert the days since the epoch into a date of the proleptic Gregorian calendar, in eras of 400 years,
+    // with each year starting on March 1st so the leap day comes last.
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = era * 400 + year_of_era + (month <= 2) as i64;
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", seconds / 3_600, seconds / 60 % 60, seconds % 60)
+}
+
+/// The keys holding an amount in microcredits, each with the key of its rendering in credits. The keys other than
+/// `microcredits` are only amounts within a value-flow summary, as programs other than `credits.aleo` reuse them.
+const AMOUNT_KEYS: &[(&str, &str)] = &[
+    ("microcredits", "credits"),
+    ("amount", "credits"),
+    ("base_fee", "base_fee_credits"),
+    ("priority_fee", "priority_fee_credits"),
+];
+
+/// Adds the rendering in credits of each amount in microcredits in the given document, next to the raw amount:
+/// the rewards of the ratifications, under `credits`, the genesis balances, under `credits` next to
+/// `public_balances`, and the amounts of the value-flow summaries and address activities.
+pub fn attach_credits(document: &mut Value) {
+    attach_credits_in(document, false)
+}
+
+/// Adds the rendering in credits of each amount in the given value, and in its children, which are part of a
+/// value-flow summary if `in_value_flow` is set.
+fn attach_credits_in(value: &mut Value, in_value_flow: bool) {
+    let object = match value {
+        Value::Object(object) => object,
+        Value::Array(array) => return array.iter_mut().for_each(|val| attach_credits_in(val, in_value_flow)),
+        _ => return,
+    };
+    let node_type = object.get("type").and_then(Value::as_str).unwrap_or_default().to_string();
+    let vtype = object.get("vtype").and_then(Value::as_str).unwrap_or_default().to_string();
+    match (node_type.as_str(), vtype.as_str()) {
+        ("Ratify", "BlockReward" | "PuzzleReward") => {
+            if let Some(amount) = object.get("value").and_then(Value::as_u64) {
+                object.insert("credits".to_string(), json!(format_credits(amount)));
+            }
+        }
+        ("Ratify", "Genesis") => {
+            if let Some(Value::Object(j_genesis)) = object.get_mut("value") {
+                let j_credits = match j_genesis.get("public_balances") {
+                    Some(Value::Object(balances)) => balances
+                        .iter()
+                        .filter_map(|(address, amount)| {
+                            Some((address.clone(), json!(format_credits(amount.as_u64()?))))
+                        })
+                        .collect::<Map<_, _>>(),
+                    _ => Map::new(),
+                };
+                j_genesis.insert("credits".to_string(), Value::Object(j_credits));
+            }
+        }
+        _ => {
+            let in_value_flow = in_value_flow || node_type == "ValueFlow";
+            let mut j_renderings = Vec::new();
+            for (key, credits_key) in AMOUNT_KEYS {
+                if *key == "microcredits" || in_value_flow {
+                    if let Some(amount) = object.get(*key).and_then(Value::as_u64) {
+                        j_renderings.push((credits_key.to_string(), json!(format_credits(amount))));
+                    }
+                }
+            }
+            object.values_mut().for_each(|val| attach_credits_in(val, in_value_flow));
+            object.extend(j_renderings);
+        }
+    }
+}
+
+/// Returns the JSON pairing the given raw value with its human-readable rendering.
+fn rendered(raw: Value, display: String) -> Value {
+    json!({ "raw": raw, "display": display })
+}
+
+/// Returns the economics of the given block, pairing each target, weight, and reward with a human-readable
+/// rendering: targets and weights in scientific notation, and rewards in credits. The raw values are
+/// represented as in the header metadata, and a reward is `null` if the block does not ratify one.
+pub fn header_economics_to_json<N: Network>(block: &Block<N>) -> Value {
+    let metadata = block.header().metadata();
+
+    let mut j_block_reward = Value::Null;
+    let mut j_puzzle_reward = Value::Null;
+    for ratify in block.ratifications().iter() {
+        match ratify {
+            Ratify::BlockReward(amount) => j_block_reward = rendered(json!(amount), format_credits(*amount)),
+            Ratify::PuzzleReward(amount) => j_puzzle_reward = rendered(json!(amount), format_credits(*amount)),
+            Ratify::Genesis(..) => (),
+        }
+    }
+
+    json!({
+        "type": "Economics",
+        "cumulative_weight": rendered(
+            json!(format!("{}", metadata.cumulative_weight())),
+            format_scientific(metadata.cumulative_weight()),
+        ),
+        "cumulative_proof_target": rendered(
+            json!(format!("{}", metadata.cumulative_proof_target())),
+            format_scientific(metadata.cumulative_proof_target()),
+        ),
+        "coinbase_target": rendered(
+            json!(metadata.coinbase_target()),
+            format_scientific(metadata.coinbase_target() as u128),
+        ),
+        "proof_target": rendered(json!(metadata.proof_target()), format_scientific(metadata.proof_target() as u128)),
+        "last_coinbase_target": rendered(
+            json!(metadata.last_coinbase_target()),
+            format_scientific(metadata.last_coinbase_target() as u128),
+        ),
+        "block_reward": j_block_reward,
+        "puzzle_reward": j_puzzle_reward,
+    })
+}
+
+/// Pairs each timestamp in the header metadata of the given block with its ISO-8601 rendering in UTC, next to the
+/// raw seconds, under `timestamp_iso` and `last_coinbase_timestamp_iso`.
+pub fn attach_header_timestamps<N: Network>(block: &Block<N>, j_block: &mut Value) {
+    let metadata = block.header().metadata();
+    let j_metadata = &mut j_block["header"]["metadata"];
+    j_metadata["timestamp_iso"] = json!(format_timestamp(metadata.timestamp()));
+    j_metadata["last_coinbase_timestamp_iso"] = json!(format_timestamp(metadata.last_coinbase_timestamp()));
+}
+
+/// Returns the breakdown of the given fee, pairing its base fee, priority fee, and total, in microcredits,
+/// with their renderings in credits.
+pub fn fee_breakdown_to_json<N: Network>(fee: &Fee<N>) -> Result<Value> {
+    let base_fee = *fee.base_amount()?;
+    let priority_fee = *fee.priority_amount()?;
+    // Note: The sum cannot exceed a u64, as the fee transition checks it.
+    let total = base_fee.saturating_add(priority_fee);
+    Ok(json!({
+        "type": "FeeBreakdown",
+        "base_fee": rendered(json!(base_fee), format_credits(base_fee)),
+        "priority_fee": rendered(json!(priority_fee), format_credits(priority_fee)),
+        "total": rendered(json!(total), format_credits(total)),
+    }))
+}
+
+/// Adds the breakdown of the fee of the given transaction, if it pays one, to its fee JSON, under `breakdown`.
+pub fn attach_fee_breakdown<N: Network>(transaction: &Transaction<N>, j_transaction: &mut Value) -> Result<()> {
+    if let Some(fee) = transaction.fee_transition() {
+        j_transaction["value"]["fee"]["breakdown"] = fee_breakdown_to_json(&fee)?;
+    }
+    Ok(())
+}
+
+/// Adds the breakdown of the fee of each transaction of the given block to its block JSON.
+pub fn attach_block_fee_breakdowns<N: Network>(block: &Block<N>, j_block: &mut Value) -> Result<()> {
+    for (index, confirmed) in block.transactions().iter().enumerate() {
+        attach_fee_breakdown(confirmed.transaction(), &mut j_block["transactions"][index]["transaction"])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_format_credits() {
+        assert_eq!(format_credits(0), "0.000000");
+        assert_eq!(format_credits(1), "0.000001");
+        assert_eq!(format_credits(1_500_000), "1.500000");
+        assert_eq!(format_credits(u64::MAX), "18446744073709.551615");
+    }
+
+    #[test]
+    fn test_format_scientific() {
+        assert_eq!(format_scientific(0), "0e0");
+        assert_eq!(format_scientific(1_250_000_000), "1.25e9");
+        assert_eq!(format_scientific(123_456_789), "1.23456789e8");
+    }
+
+    #[test]
+    fn test_genesis_economics() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let j_economics = header_economics_to_json(&block);
+
+        let metadata = block.header().metadata();
+        assert_eq!(j_economics["coinbase_target"]["raw"], metadata.coinbase_target());
+        assert_eq!(j_economics["coinbase_target"]["display"], format_scientific(metadata.coinbase_target() as u128));
+        assert_eq!(j_economics["cumulative_weight"]["raw"], format!("{}", metadata.cumulative_weight()));
+        // The genesis block ratifies no rewards.
+        assert_eq!(j_economics["block_reward"], Value::Null);
+        assert_eq!(j_economics["puzzle_reward"], Value::Null);
+    }
+
+    #[test]
+    fn test_attach_credits() {
+        let mut document = json!({
+            "ratifications": [
+                { "type": "Ratify", "vtype": "BlockReward", "value": 1_500_000 },
+                { "type": "Ratify", "vtype": "Genesis", "value": { "public_balances": { "aleo1": 2_000_000 } } },
+            ],
+            "value_flow": {
+                "type": "ValueFlow",
+                "fees": [{ "function": "fee_public", "base_fee": 1, "priority_fee": 0 }],
+                "transfers": [{ "function": "transfer_public", "amount": 5_000_000 }],
+            },
+            "address_activity": { "aleo1": [{ "role": "ratification_recipient", "microcredits": 10 }] },
+            "typed_value": { "amount": 7 },
+        });
+        attach_credits(&mut document);
+
+        assert_eq!(document["ratifications"][0]["credits"], "1.500000");
+        assert_eq!(document["ratifications"][1]["value"]["credits"], json!({ "aleo1": "2.000000" }));
+        assert_eq!(document["value_flow"]["fees"][0]["base_fee_credits"], "0.000001");
+        assert_eq!(document["value_flow"]["fees"][0]["priority_fee_credits"], "0.000000");
+        assert_eq!(document["value_flow"]["transfers"][0]["credits"], "5.000000");
+        assert_eq!(document["address_activity"]["aleo1"][0]["credits"], "0.000010");
+        // An amount outside of a value-flow summary may not be in microcredits, so it is left as is.
+        assert_eq!(document["typed_value"], json!({ "amount": 7 }));
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_timestamp(1_693_569_600), "2023-09-01T12:00:00Z");
+        assert_eq!(format_timestamp(-1), "1969-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_genesis_header_timestamps() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut j_block = block.to_json();
+        attach_header_timestamps(&block, &mut j_block);
+
+        let metadata = block.header().metadata();
+        let j_metadata = &j_block["header"]["metadata"];
+        assert_eq!(j_metadata["timestamp"], metadata.timestamp());
+        assert_eq!(j_metadata["timestamp_iso"], format_timestamp(metadata.timestamp()));
+        assert_eq!(j_metadata["last_coinbase_timestamp_iso"], format_timestamp(metadata.last_coinbase_timestamp()));
+    }
+
+    #[test]
+    fn test_genesis_fee_breakdowns() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut j_block = block.to_json();
+        attach_block_fee_breakdowns(&block, &mut j_block).unwrap();
+
+        for (index, confirmed) in block.transactions().iter().enumerate() {
+            let j_fee = &j_block["transactions"][index]["transaction"]["value"]["fee"];
+            match confirmed.transaction().fee_transition() {
+                Some(fee) => {
+                    let j_breakdown = &j_fee["breakdown"];
+                    assert_eq!(j_breakdown["type"], "FeeBreakdown");
+                    assert_eq!(j_breakdown["base_fee"]["raw"], *fee.base_amount().unwrap());
+                    assert_eq!(j_breakdown["priority_fee"]["raw"], *fee.priority_amount().unwrap());
+                    assert_eq!(j_breakdown["total"]["raw"], *fee.amount().unwrap());
+                    assert_eq!(j_breakdown["total"]["display"], format_credits(*fee.amount().unwrap()));
+                }
+                None => assert!(j_fee.get("breakdown").is_none()),
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::to_hex,
+    console::network::{prelude::ToBytes, Network},
+    ledger::block::{Block, Transaction, Transition},
+};
+
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Adds the little-endian byte encoding of the given block, in hex, to the given block JSON, under `bytes`,
+/// and that of each of its transactions and transitions next to their JSON, including the transitions of
+/// rejected executions, so consumers can re-verify the hashes without a separate export.
+pub fn attach_block_bytes<N: Network>(block: &Block<N>, j_block: &mut Value) -> Result<()> {
+    let mut encodings = HashMap::new();
+    for confirmed in block.transactions().iter() {
+        insert_transaction_bytes(confirmed.transaction(), &mut encodings)?;
+        if let Some(execution) = confirmed.to_rejected().and_then(|rejected| rejected.execution()) {
+            for transition in execution.transitions() {
+                insert_transition_bytes(transition, &mut encodings)?;
+            }
+        }
+    }
+    attach_encodings(j_block, &encodings);
+    j_block["bytes"] = Value::String(to_hex(&block.to_bytes_le()?));
+    Ok(())
+}
+
+/// Adds the little-endian byte encoding of the given transaction, in hex, to the given transaction JSON,
+/// under `bytes`, and that of each of its transitions next to their JSON.
+pub fn attach_transaction_bytes<N: Network>(transaction: &Transaction<N>, j_transaction: &mut Value) -> Result<()> {
+    let mut encodings = HashMap::new();
+    insert_transaction_bytes(transaction, &mut encodings)?;
+    attach_encodings(j_transaction, &encodings);
+    Ok(())
+}
+
+/// Records the encoding of the given transaction, and of each of its transitions, by ID.
+fn insert_transaction_bytes<N: Network>(
+    transaction: &Transaction<N>,
+    encodings: &mut HashMap<String, String>,
+) -> Result<()> {
+    encodings.insert(transaction.id().to_string(), to_hex(&transaction.to_bytes_le()?));
+    for transition in transaction.transitions() {
+        insert_transition_bytes(transition, encodings)?;
+    }
+    Ok(())
+}
+
+/// Records the encoding of the given transition, by ID.
+fn insert_transition_bytes<N: Network>(
+    transition: &Transition<N>,
+    encodings: &mut HashMap<String, String>,
+) -> Result<()> {
+    encodings.insert(transition.id().to_string(), to_hex(&transition.to_bytes_le()?));
+    Ok(())
+}
+
+/// Adds the given encodings to the transactions and transitions found in the given value, and in its children.
+///
+/// The transaction and transition IDs have distinct prefixes, so they can share the map.
+fn attach_encodings(value: &mut Value, encodings: &HashMap<String, String>) {
+    match value {
+        Value::Object(object) => {
+            let encoding = match object.get("type").and_then(Value::as_str) {
+                Some("Transaction" | "Transition") => {
+                    object.get("id").and_then(Value::as_str).and_then(|id| encodings.get(id)).cloned()
+                }
+                _ => None,
+            };
+            object.values_mut().for_each(|val| attach_encodings(val, encodings));
+            if let Some(encoding) = encoding {
+                object.insert("bytes".to_string(), Value::String(encoding));
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(|val| attach_encodings(val, encodings)),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_attach_block_bytes() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut j_block = block.to_json();
+        attach_block_bytes(&block, &mut j_block).unwrap();
+        assert_eq!(j_block["bytes"], to_hex(CurrentNetwork::genesis_bytes()));
+
+        for (index, confirmed) in block.transactions().iter().enumerate() {
+            let transaction = confirmed.transaction();
+            let j_transaction = &j_block["transactions"][index]["transaction"];
+            assert_eq!(j_transaction["bytes"], to_hex(&transaction.to_bytes_le().unwrap()));
+
+            // Each transition of the genesis executions carries its own encoding.
+            let execution = transaction.execution().unwrap();
+            for (j_transition, transition) in j_transaction["value"]["execution"]["transitions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .zip(execution.transitions())
+            {
+                assert_eq!(j_transition["bytes"], to_hex(&transition.to_bytes_le().unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_attach_transaction_bytes() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let transaction = block.transactions().iter().next().unwrap().transaction();
+        let mut j_transaction = transaction.to_json();
+        attach_transaction_bytes(transaction, &mut j_transaction).unwrap();
+        assert_eq!(j_transaction["bytes"], to_hex(&transaction.to_bytes_le().unwrap()));
+
+        let transition = transaction.transitions().next().unwrap();
+        let j_transition = &j_transaction["value"]["execution"]["transitions"][0];
+        assert_eq!(j_transition["id"], transition.id().to_string());
+        assert_eq!(j_transition["bytes"], to_hex(&transition.to_bytes_le().unwrap()));
+    }
+}
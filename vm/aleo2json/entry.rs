@@ -0,0 +1,99 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! One-call entry points, converting raw inputs on the default network, without the network type parameter.
+//! To select the network at runtime, see `NetworkId`.
+
+#[cfg(feature = "aleo2json-ledger")]
+use crate::aleo2json::{convert_block_bytes, convert_block_json, convert_transaction_bytes, convert_transaction_json};
+use crate::{
+    aleo2json::{convert_program, convert_program_file, ConversionError, ConversionMode, JsonOptions},
+    prelude::Testnet3,
+};
+
+use serde_json::Value;
+use std::path::Path;
+
+/// The network of the one-call entry points.
+pub type DefaultNetwork = Testnet3;
+
+/// Converts the given Aleo program source into its program JSON.
+pub fn program_str_to_json(source: &str) -> Result<Value, ConversionError> {
+    convert_program::<DefaultNetwork>(source)
+}
+
+/// Converts the Aleo program source at the given path into its program JSON.
+pub fn program_file_to_json<P: AsRef<Path>>(path: P) -> Result<Value, ConversionError> {
+    convert_program_file::<DefaultNetwork>(path.as_ref(), ConversionMode::default(), &JsonOptions::default())
+}
+
+/// Converts the given block, encoded as little-endian bytes, into its block JSON.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn block_bytes_to_json(bytes: &[u8]) -> Result<Value, ConversionError> {
+    convert_block_bytes::<DefaultNetwork>(bytes)
+}
+
+/// Converts the given block, encoded as snarkVM JSON, into its block JSON.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn block_str_to_json(block: &str) -> Result<Value, ConversionError> {
+    convert_block_json::<DefaultNetwork>(block)
+}
+
+/// Converts the given transaction, encoded as little-endian bytes, into its transaction JSON.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn transaction_bytes_to_json(bytes: &[u8]) -> Result<Value, ConversionError> {
+    convert_transaction_bytes::<DefaultNetwork>(bytes)
+}
+
+/// Converts the given transaction, encoded as snarkVM JSON (i.e. as returned by a node), into its transaction JSON.
+#[cfg(feature = "aleo2json-ledger")]
+pub fn transaction_str_to_json(transaction: &str) -> Result<Value, ConversionError> {
+    convert_transaction_json::<DefaultNetwork>(transaction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "aleo2json-ledger")]
+    use crate::{
+        console::network::{
+            prelude::{FromBytes, ToBytes},
+            Network,
+        },
+        ledger::block::Block,
+    };
+
+    #[test]
+    fn test_program_str_to_json() {
+        let program =
+            program_str_to_json("program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;")
+                .unwrap();
+        assert_eq!(program["type"], "ProgramCore");
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_block_and_transaction_to_json() {
+        let block = block_bytes_to_json(DefaultNetwork::genesis_bytes()).unwrap();
+        assert_eq!(block["header"]["metadata"]["height"], 0);
+
+        let genesis = Block::<DefaultNetwork>::read_le(DefaultNetwork::genesis_bytes()).unwrap();
+        assert_eq!(block_str_to_json(&genesis.to_string()).unwrap(), block);
+
+        let transaction = genesis.transactions().iter().next().unwrap().transaction();
+        let j_transaction = transaction_str_to_json(&serde_json::to_string(transaction).unwrap()).unwrap();
+        assert_eq!(j_transaction["id"], block["transactions"][0]["transaction"]["id"]);
+        assert_eq!(transaction_bytes_to_json(&transaction.to_bytes_le().unwrap()).unwrap(), j_transaction);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::{json, Value};
+use std::{fmt, io, path::PathBuf};
+
+/// The line and column of a location in a text input, both starting at 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TextLocation {
+    /// The line number.
+    pub line: usize,
+    /// The column number, in characters.
+    pub column: usize,
+}
+
+impl TextLocation {
+    /// Returns the location of the given byte offset in the given text.
+    pub fn of(text: &str, offset: usize) -> Self {
+        let before = &text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+        Self { line, column }
+    }
+
+    /// Returns the JSON representation of the location.
+    pub fn to_json(&self) -> Value {
+        json!({ "line": self.line, "column": self.column })
+    }
+}
+
+impl fmt::Display for TextLocation {
+    /// Prints the location as `line {line}, column {column}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// An error raised while converting an input into JSON.
+#[derive(Debug, Error)]
+pub enum ConversionError {
+    /// The input could not be parsed or decoded. The location is known for text inputs.
+    #[error("Failed to parse the input{}: {message}", .location.map(|location| format!(" at {location}")).unwrap_or_default())]
+    Parse { location: Option<TextLocation>, message: String },
+
+    /// The input holds a construct without a structured JSON representation, in strict mode.
+    #[error("Unsupported {construct} '{vtype}' at '{pointer}': {text}")]
+    Unsupported { construct: String, vtype: String, pointer: String, text: String },
+
+    /// An instruction's operands violate the signature of its opcode, in strict mode.
+    #[error("Invalid operands for '{opcode}' at '{pointer}': {message}")]
+    InvalidOperands { opcode: String, pointer: String, message: String },
+
+    /// A hash or ID in the input does not match the one recomputed from its contents.
+    #[error("Mismatching hash at '{pointer}': found {found}, computed {computed}")]
+    HashMismatch { pointer: String, found: String, computed: String },
+
+    /// The input is well-formed, but is not a valid object (i.e. a block with an invalid signature).
+    #[error("Invalid input: {0}")]
+    Invalid(String),
+
+    /// The input could not be read.
+    #[error("Failed to read '{}': {source}", .path.display())]
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl ConversionError {
+    /// Initializes a new parse error without a location, i.e. for a binary input.
+    pub fn parse(message: impl ToString) -> Self {
+        Self::Parse { location: None, message: message.to_string() }
+    }
+
+    /// Initializes a new parse error for the given JSON error, at its location.
+    pub fn json(error: serde_json::Error) -> Self {
+        // The line is 0 if the error is not located in the input, i.e. for a semantic error.
+        let location = match error.line() {
+            0 => None,
+            line => Some(TextLocation { line, column: error.column() }),
+        };
+        Self::Parse { location, message: error.to_string() }
+    }
+
+    /// Returns the name of the kind of error.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Parse { .. } => "parse",
+            Self::Unsupported { .. } => "unsupported",
+            Self::InvalidOperands { .. } => "invalid_operands",
+            Self::HashMismatch { .. } => "hash_mismatch",
+            Self::Invalid(..) => "invalid",
+            Self::Io { .. } => "io",
+        }
+    }
+
+    /// Returns the JSON representation of the error, with its kind, message, and location.
+    pub fn to_json(&self) -> Value {
+        let mut j_error = json!({
+            "type": "ConversionError",
+            "kind": self.kind(),
+            "message": self.to_string(),
+        });
+        match self {
+            Self::Parse { location: Some(location), .. } => j_error["location"] = location.to_json(),
+            Self::Unsupported { pointer, .. }
+            | Self::InvalidOperands { pointer, .. }
+            | Self::HashMismatch { pointer, .. } => j_error["pointer"] = json!(pointer),
+            Self::Io { path, .. } => j_error["path"] = json!(path.display().to_string()),
+            _ => (),
+        }
+        j_error
+    }
+}
+
+impl From<std::str::Utf8Error> for ConversionError {
+    /// Returns a parse error for an input that is not valid UTF-8.
+    fn from(error: std::str::Utf8Error) -> Self {
+        Self::parse(format!("The input is not valid UTF-8: {error}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_location() {
+        let text = "program token.aleo;\nfunction foo:\n    ädd";
+        assert_eq!(TextLocation::of(text, 0), TextLocation { line: 1, column: 1 });
+        assert_eq!(TextLocation::of(text, 20), TextLocation { line: 2, column: 1 });
+        assert_eq!(TextLocation::of(text, text.len()), TextLocation { line: 3, column: 8 });
+    }
+
+    #[test]
+    fn test_conversion_error_to_json() {
+        let error = ConversionError::json(serde_json::from_str::<Value>("{\n  \"a\": }").unwrap_err());
+        let j_error = error.to_json();
+        assert_eq!(j_error["kind"], "parse");
+        assert_eq!(j_error["location"]["line"], 2);
+        assert!(error.to_string().starts_with("Failed to parse the input at line 2"));
+
+        let error =
+            ConversionError::HashMismatch { pointer: "/block_hash".into(), found: "a".into(), computed: "b".into() };
+        assert_eq!(error.to_json()["pointer"], "/block_hash");
+        assert_eq!(error.kind(), "hash_mismatch");
+    }
+}
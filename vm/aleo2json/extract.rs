@@ -0,0 +1,481 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::ConversionError,
+    console::{
+        network::Network,
+        program::{FinalizeType, Identifier, PlaintextType, RegisterType, ValueType},
+    },
+    synthesizer::{
+        program::{CallOperator, CastType, Command, MappingLocator},
+        Instruction,
+        Program,
+    },
+};
+
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+/// The sections of a program document holding its definitions, keyed by name.
+const DEFINITION_SECTIONS: &[&str] = &["closures", "functions", "identifiers", "mappings", "records", "structs"];
+
+/// A part of a program, as the names of the definitions it keeps, closed under the references between them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgramSlice {
+    /// The functions.
+    pub functions: BTreeSet<String>,
+    /// The closures, including those called by the functions and by other closures.
+    pub closures: BTreeSet<String>,
+    /// The structs, including those nested in other structs and in records.
+    pub structs: BTreeSet<String>,
+    /// The records.
+    pub records: BTreeSet<String>,
+    /// The mappings, including those accessed by the finalize logic of the functions.
+    pub mappings: BTreeSet<String>,
+}
+
+impl ProgramSlice {
+    /// Returns the slice of the given function or closure of the program, with the definitions it references.
+    pub fn function<N: Network>(program: &Program<N>, name: &Identifier<N>) -> Result<Self, ConversionError> {
+        let mut slice = Self::default();
+        if program.contains_function(name) {
+            slice.add_function(program, name);
+        } else if program.contains_closure(name) {
+            slice.add_closure(program, name);
+        } else {
+            return Err(ConversionError::Invalid(format!(
+                "'{name}' is not a function or closure of '{}'",
+                program.id()
+            )));
+        }
+        Ok(slice)
+    }
+
+    /// Returns the slice of the state schema of the given program: its mappings and records,
+    /// with the structs of their types.
+    pub fn state<N: Network>(program: &Program<N>) -> Self {
+        let mut slice = Self::default();
+        for name in program.mappings().keys() {
+            slice.add_mapping(program, name);
+        }
+        for name in program.records().keys() {
+            slice.add_record(program, name);
+        }
+        slice
+    }
+
+    /// Returns the slice of the definitions reachable from the entry points of the given program, i.e. its functions,
+    /// which are all externally callable.
+    pub fn reachable<N: Network>(program: &Program<N>) -> Self {
+        let mut slice = Self::default();
+        for name in program.functions().keys() {
+            slice.add_function(program, name);
+        }
+        slice
+    }
+
+    /// Returns `true` if the slice keeps the definition with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        [&self.functions, &self.closures, &self.structs, &self.records, &self.mappings]
+            .iter()
+            .any(|names| names.contains(name))
+    }
+
+    /// Removes the definitions that the slice does not keep from the given program document.
+    /// The imports are kept, as the external types may refer to them.
+    pub fn prune(&self, document: &mut Value) {
+        for section in DEFINITION_SECTIONS {
+            if let Some(Value::Object(definitions)) = document.get_mut(*section) {
+                definitions.retain(|name, _| self.contains(name));
+            }
+        }
+    }
+
+    /// Adds the given function, and its references.
+    fn add_function<N: Network>(&mut self, program: &Program<N>, name: &Identifier<N>) {
+        self.functions.insert(name.to_string());
+        let Ok(function) = program.get_function_ref(name) else { return };
+        for value_type in function.input_types().iter().chain(&function.output_types()) {
+            self.add_value_type(program, value_type);
+        }
+        for instruction in function.instructions() {
+            self.add_instruction(program, instruction);
+        }
+        if let Some(finalize) = function.finalize_logic() {
+            for finalize_type in finalize.input_types() {
+                if let FinalizeType::Plaintext(plaintext_type) = finalize_type {
+                    self.add_plaintext_type(program, &plaintext_type);
+                }
+            }
+            for command in finalize.commands() {
+                self.add_command(program, command);
+            }
+        }
+    }
+
+    /// Adds the given closure, and its references.
+    fn add_closure<N: Network>(&mut self, program: &Program<N>, name: &Identifier<N>) {
+        // A closure may be called more than once, but is only visited once.
+        if !self.closures.insert(name.to_string()) {
+            return;
+        }
+        let Ok(closure) = program.get_closure(name) else { return };
+        for input in closure.inputs() {
+            self.add_register_type(program, input.register_type());
+        }
+        for output in closure.outputs() {
+            self.add_register_type(program, output.register_type());
+        }
+        for instruction in closure.instructions() {
+            self.add_instruction(program, instruction);
+        }
+    }
+
+    /// Adds the references of the given instruction: the closure it calls, or the type it casts into.
+    fn add_instruction<N: Network>(&mut self, program: &Program<N>, instruction: &Instruction<N>) {
+        match instruction {
+            Instruction::Call(call) => {
+                if let CallOperator::Resource(callee) = call.operator() {
+                    if program.contains_closure(callee) {
+                        self.add_closure(program, callee);
+                    }
+                }
+            }
+            Instruction::Cast(cast) => self.add_cast_type(program, cast.cast_type()),
+            Instruction::CastLossy(cast) => self.add_cast_type(program, cast.cast_type()),
+            _ => (),
+        }
+    }
+
+    /// Adds the references of the given finalize command: the mapping it accesses, or those of its instruction.
+    fn add_command<N: Network>(&mut self, program: &Program<N>, command: &Command<N>) {
+        let mapping = match command {
+            Command::Instruction(instruction) => return self.add_instruction(program, instruction),
+            Command::Contains(contains) => Some(contains.mapping_name()),
+            Command::Get(get) => match get.mapping() {
+                MappingLocator::Resource(mapping_name) => Some(mapping_name),
+                MappingLocator::Locator(_) => None,
+            },
+            Command::GetOrUse(get_or_use) => match get_or_use.mapping() {
+                MappingLocator::Resource(mapping_name) => Some(mapping_name),
+                MappingLocator::Locator(_) => None,
+            },
+            Command::Remove(remove) => Some(remove.mapping_name()),
+            Command::Set(set) => Some(set.mapping_name()),
+            _ => None,
+        };
+        if let Some(mapping_name) = mapping {
+            self.add_mapping(program, mapping_name);
+        }
+    }
+
+    /// Adds the given mapping, and the structs of its key and value types.
+    fn add_mapping<N: Network>(&mut self, program: &Program<N>, mapping_name: &Identifier<N>) {
+        if self.mappings.insert(mapping_name.to_string()) {
+            if let Ok(mapping) = program.get_mapping(mapping_name) {
+                self.add_plaintext_type(program, mapping.key().plaintext_type());
+                self.add_plaintext_type(program, mapping.value().plaintext_type());
+            }
+        }
+    }
+
+    /// Adds the struct or record of the given value type.
+    fn add_value_type<N: Network>(&mut self, program: &Program<N>, value_type: &ValueType<N>) {
+        match value_type {
+            ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type) => self.add_plaintext_type(program, plaintext_type),
+            ValueType::Record(record_name) => self.add_record(program, record_name),
+            ValueType::ExternalRecord(_) | ValueType::Future(_) => (),
+        }
+    }
+
+    /// Adds the struct or record of the given register type.
+    fn add_register_type<N: Network>(&mut self, program: &Program<N>, register_type: &RegisterType<N>) {
+        match register_type {
+            RegisterType::Plaintext(plaintext_type) => self.add_plaintext_type(program, plaintext_type),
+            RegisterType::Record(record_name) => self.add_record(program, record_name),
+            RegisterType::ExternalRecord(_) | RegisterType::Future(_) => (),
+        }
+    }
+
+    /// Adds the struct or record of the given cast type.
+    fn add_cast_type<N: Network>(&mut self, program: &Program<N>, cast_type: &CastType<N>) {
+        match cast_type {
+            CastType::Plaintext(plaintext_type) => self.add_plaintext_type(program, plaintext_type),
+            CastType::Record(record_name) => self.add_record(program, record_name),
+            CastType::GroupXCoordinate | CastType::GroupYCoordinate | CastType::ExternalRecord(_) => (),
+        }
+    }
+
+    /// Adds the struct of the given plaintext type, or of its elements, and the structs nested in it.
+    fn add_plaintext_type<N: Network>(&mut self, program: &Program<N>, plaintext_type: &PlaintextType<N>) {
+        match plaintext_type {
+            PlaintextType::Struct(struct_name) => {
+                if self.structs.insert(struct_name.to_string()) {
+                    if let Ok(struct_) = program.get_struct(struct_name) {
+                        for member_type in struct_.members().values() {
+                            self.add_plaintext_type(program, member_type);
+                        }
+                    }
+                }
+            }
+            PlaintextType::Array(array_type) => self.add_plaintext_type(program, array_type.next_element_type()),
+            PlaintextType::Literal(_) => (),
+        }
+    }
+
+    /// Adds the given record, and the structs of its entries.
+    fn add_record<N: Network>(&mut self, program: &Program<N>, record_name: &Identifier<N>) {
+        if self.records.insert(record_name.to_string()) {
+            if let Ok(record) = program.get_record(record_name) {
+                for entry_type in record.entries().values() {
+                    self.add_plaintext_type(program, entry_type.plaintext_type());
+                }
+            }
+        }
+    }
+}
+
+/// Returns the definitions of the given program that are unreachable from its functions, as their section
+/// (i.e. `mappings`) and name, in order of the sections of the program document, then of declaration.
+pub fn dead_definitions<N: Network>(program: &Program<N>) -> Vec<(&'static str, String)> {
+    let reachable = ProgramSlice::reachable(program);
+    let sections = [
+        ("mappings", program.mappings().keys().map(ToString::to_string).collect::<Vec<_>>(), &reachable.mappings),
+        ("structs", program.structs().keys().map(ToString::to_string).collect(), &reachable.structs),
+        ("records", program.records().keys().map(ToString::to_string).collect(), &reachable.records),
+        ("closures", program.closures().keys().map(ToString::to_string).collect(), &reachable.closures),
+    ];
+    let mut dead = Vec::new();
+    for (section, names, reachable) in sections {
+        dead.extend(names.into_iter().filter(|name| !reachable.contains(name)).map(|name| (section, name)));
+    }
+    dead
+}
+
+/// Annotates each closure, struct, record, and mapping of the given program JSON with whether it is reachable from
+/// the functions, under `reachable`, and lists the unreachable ones under `dead_definitions`, for minimizing programs
+/// and scoping reviews.
+pub fn attach_reachability<N: Network>(program: &Program<N>, document: &mut Value) {
+    let dead = dead_definitions(program);
+    for section in ["closures", "structs", "records", "mappings"] {
+        if let Some(Value::Object(definitions)) = document.get_mut(section) {
+            for (name, j_definition) in definitions.iter_mut() {
+                let reachable =
+                    !dead.iter().any(|(dead_section, dead_name)| *dead_section == section && dead_name == name);
+                if let Value::Object(j_definition) = j_definition {
+                    j_definition.insert("reachable".to_string(), json!(reachable));
+                }
+            }
+        }
+    }
+    let j_dead = dead.iter().map(|(section, name)| json!({ "section": section, "name": name })).collect::<Vec<_>>();
+    document["dead_definitions"] = json!(j_dead);
+}
+
+/// Returns the JSON of the given function or closure of the program, as a standalone program document
+/// that includes only the definitions it references, for focused review and sharing.
+pub fn extract_function<N: Network>(program: &Program<N>, name: &Identifier<N>) -> Result<Value, ConversionError> {
+    let mut document = program.to_json();
+    ProgramSlice::function(program, name)?.prune(&mut document);
+    Ok(document)
+}
+
+/// Returns the JSON of the state schema of the given program, as a program document that includes only
+/// its mappings, with their key and value types, and its records, with the structs those types depend on.
+pub fn extract_state_schema<N: Network>(program: &Program<N>) -> Value {
+    let mut document = program.to_json();
+    ProgramSlice::state(program).prune(&mut document);
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"program vault.aleo;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+struct coordinate:
+    raw as field;
+
+struct point:
+    x as coordinate;
+    y as coordinate;
+
+struct unused:
+    raw as u8;
+
+mapping points:
+    key as field.public;
+    value as point.public;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+closure wrap:
+    input r0 as field;
+    cast r0 into r1 as coordinate;
+    output r1 as coordinate;
+
+closure make_point:
+    input r0 as field;
+    call wrap r0 into r1;
+    cast r1 r1 into r2 as point;
+    output r2 as point;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.private;
+    cast r0 r1 into r2 as token.record;
+    async mint r0 r1 into r3;
+    output r2 as token.record;
+    output r3 as vault.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use balances[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into balances[r0];
+
+function locate:
+    input r0 as field.private;
+    call make_point r0 into r1;
+    output r1 as point.private;
+";
+
+    const ARRAYS: &str = r"program shapes.aleo;
+
+struct point:
+    x as field;
+    y as field;
+
+struct corner:
+    at as point;
+
+mapping corners:
+    key as u8.public;
+    value as [corner; 4u32].public;
+
+function mirror:
+    input r0 as [[point; 2u32]; 2u32].private;
+    output r0 as [[point; 2u32]; 2u32].private;
+";
+
+    fn extract(name: &str) -> Result<Value, ConversionError> {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        extract_function(&program, &Identifier::from_str(name).unwrap())
+    }
+
+    fn keys(document: &Value, section: &str) -> Vec<String> {
+        let mut keys: Vec<_> = document[section].as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_extract_function() {
+        let document = extract("mint").unwrap();
+        assert_eq!(keys(&document, "functions"), ["mint"]);
+        assert!(keys(&document, "closures").is_empty());
+        assert_eq!(keys(&document, "records"), ["token"]);
+        assert_eq!(keys(&document, "mappings"), ["balances"]);
+        assert!(keys(&document, "structs").is_empty());
+        assert_eq!(keys(&document, "identifiers"), ["balances", "mint", "token"]);
+
+        // The closures are called transitively, and the structs are nested.
+        let document = extract("locate").unwrap();
+        assert_eq!(keys(&document, "functions"), ["locate"]);
+        assert_eq!(keys(&document, "closures"), ["make_point", "wrap"]);
+        assert_eq!(keys(&document, "structs"), ["coordinate", "point"]);
+        assert!(keys(&document, "mappings").is_empty());
+    }
+
+    #[test]
+    fn test_extract_function_arrays() {
+        // A struct used only as the element of an array is kept.
+        let program = parse_program::<CurrentNetwork>(ARRAYS).unwrap();
+        let document = extract_function(&program, &Identifier::from_str("mirror").unwrap()).unwrap();
+        assert_eq!(keys(&document, "structs"), ["point"]);
+        assert!(keys(&document, "mappings").is_empty());
+    }
+
+    #[test]
+    fn test_extract_closure() {
+        let document = extract("wrap").unwrap();
+        assert!(keys(&document, "functions").is_empty());
+        assert_eq!(keys(&document, "closures"), ["wrap"]);
+        assert_eq!(keys(&document, "structs"), ["coordinate"]);
+
+        assert!(extract("unused").is_err());
+        assert!(extract("missing").is_err());
+    }
+
+    #[test]
+    fn test_reachability() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let reachable = ProgramSlice::reachable(&program);
+        assert_eq!(reachable.closures.iter().collect::<Vec<_>>(), ["make_point", "wrap"]);
+        assert_eq!(reachable.mappings.iter().collect::<Vec<_>>(), ["balances"]);
+        assert_eq!(dead_definitions(&program), [("mappings", "points".to_string()), ("structs", "unused".to_string())]);
+
+        let mut document = program.to_json();
+        attach_reachability(&program, &mut document);
+        assert_eq!(document["structs"]["point"]["reachable"], true);
+        assert_eq!(document["structs"]["unused"]["reachable"], false);
+        assert_eq!(document["closures"]["wrap"]["reachable"], true);
+        assert_eq!(document["dead_definitions"][0], json!({ "section": "mappings", "name": "points" }));
+
+        // A struct reachable only as the element of an array is not dead.
+        let program = parse_program::<CurrentNetwork>(ARRAYS).unwrap();
+        assert_eq!(dead_definitions(&program), [
+            ("mappings", "corners".to_string()),
+            ("structs", "corner".to_string())
+        ]);
+    }
+
+    #[test]
+    fn test_extract_state_schema() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let document = extract_state_schema(&program);
+        assert_eq!(keys(&document, "mappings"), ["balances", "points"]);
+        assert_eq!(keys(&document, "records"), ["token"]);
+        assert_eq!(keys(&document, "structs"), ["coordinate", "point"]);
+        assert!(keys(&document, "functions").is_empty());
+        assert!(keys(&document, "closures").is_empty());
+        assert_eq!(keys(&document, "identifiers"), ["balances", "coordinate", "point", "points", "token"]);
+    }
+
+    #[test]
+    fn test_extract_state_schema_arrays() {
+        // The structs of a mapping keyed or valued by an array are kept.
+        let program = parse_program::<CurrentNetwork>(ARRAYS).unwrap();
+        let document = extract_state_schema(&program);
+        assert_eq!(keys(&document, "mappings"), ["corners"]);
+        assert_eq!(keys(&document, "structs"), ["corner", "point"]);
+        assert!(keys(&document, "functions").is_empty());
+    }
+}
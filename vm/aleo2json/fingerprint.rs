@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{checksum, normalize, to_canonical_string};
+
+use anyhow::Result;
+use serde_json::Value;
+
+/// The sections of a program whose definitions are each fingerprinted.
+const FINGERPRINTED_SECTIONS: &[&str] = &["closures", "functions"];
+
+/// Returns the structural fingerprint of the given JSON: the SHA3-256 hash of its canonical form, once normalized,
+/// in hex, prefixed by `sha3-256:`.
+///
+/// As the registers are renumbered canonically, two closures or functions that differ only in register numbering
+/// have the same fingerprint.
+pub fn fingerprint(value: &Value) -> Result<String> {
+    let mut normalized = value.clone();
+    normalize(&mut normalized);
+    checksum(to_canonical_string(&normalized).as_bytes())
+}
+
+/// Adds the fingerprint of each program in the given document, and of each of its closures and functions,
+/// under `fingerprint`.
+///
+/// This must be called before any other section is added, so the fingerprints only cover the program itself.
+pub fn attach_fingerprints(document: &mut Value) -> Result<()> {
+    let is_program = document.get("type").and_then(Value::as_str) == Some("ProgramCore");
+    match document {
+        Value::Object(_) if is_program => {
+            let j_fingerprint = Value::String(fingerprint(document)?);
+            for section in FINGERPRINTED_SECTIONS {
+                if let Some(Value::Object(definitions)) = document.get_mut(*section) {
+                    for definition in definitions.values_mut() {
+                        definition["fingerprint"] = Value::String(fingerprint(definition)?);
+                    }
+                }
+            }
+            document["fingerprint"] = j_fingerprint;
+        }
+        Value::Object(object) => object.values_mut().try_for_each(attach_fingerprints)?,
+        Value::Array(array) => array.iter_mut().try_for_each(attach_fingerprints)?,
+        _ => (),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    /// Returns a program JSON with the given function, adding its inputs into its output.
+    fn program(name: &str, registers: [u64; 3]) -> Value {
+        let register =
+            |locator: u64| json!({ "type": "Register", "vtype": "Locator", "locator": locator, "value": locator });
+        json!({
+            "type": "ProgramCore",
+            "id": { "type": "ProgramID", "name": "token", "network": "aleo" },
+            "closures": {},
+            "functions": {
+                name: {
+                    "type": "FunctionCore",
+                    "name": name,
+                    "inputs": [register(registers[0]), register(registers[1])],
+                    "instructions": [{
+                        "type": "Instruction",
+                        "vtype": "Add",
+                        "value": { "operands": [register(registers[0]), register(registers[1])] },
+                        "destination": register(registers[2]),
+                        "index": 0,
+                    }],
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn test_attach_fingerprints() {
+        let mut document = json!({ "type": "Deployment", "edition": 1, "program": program("sum", [0, 1, 2]) });
+        attach_fingerprints(&mut document).unwrap();
+
+        let j_program = &document["program"];
+        assert!(j_program["fingerprint"].as_str().unwrap().starts_with("sha3-256:"));
+        assert!(j_program["functions"]["sum"]["fingerprint"].as_str().unwrap().starts_with("sha3-256:"));
+
+        // The fingerprint of the program does not cover the fingerprints of its functions.
+        let mut j_program = j_program.clone();
+        let j_fingerprint = j_program.as_object_mut().unwrap().remove("fingerprint").unwrap();
+        j_program["functions"]["sum"].as_object_mut().unwrap().remove("fingerprint");
+        assert_eq!(j_fingerprint, fingerprint(&j_program).unwrap());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let sum = fingerprint(&program("sum", [0, 1, 2])).unwrap();
+        // The fingerprint does not depend on the register numbering, or on the order of the keys.
+        assert_eq!(sum, fingerprint(&program("sum", [3, 5, 8])).unwrap());
+        let mut reordered = serde_json::Map::new();
+        for (key, val) in program("sum", [0, 1, 2]).as_object().unwrap().iter().rev() {
+            reordered.insert(key.clone(), val.clone());
+        }
+        assert_eq!(sum, fingerprint(&Value::Object(reordered)).unwrap());
+        // The fingerprint depends on the structure.
+        assert_ne!(sum, fingerprint(&program("total", [0, 1, 2])).unwrap());
+        assert_ne!(sum, fingerprint(&program("sum", [0, 1, 0])).unwrap());
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{network::Network, program::ValueType},
+    synthesizer::Program,
+};
+
+use serde_json::{json, Map, Value};
+
+/// A record type consumed or produced by a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordUse {
+    /// The register or operand holding the record, i.e. `r0`.
+    pub register: String,
+    /// The record type, as a locator (i.e. `token.aleo/token`).
+    pub record: String,
+    /// Whether the record is defined by another program.
+    pub external: bool,
+}
+
+impl RecordUse {
+    /// Returns the record use of the given value type, if it is a record.
+    fn of<N: Network>(program: &Program<N>, register: String, value_type: &ValueType<N>) -> Option<Self> {
+        match value_type {
+            ValueType::Record(name) => {
+                Some(Self { register, record: format!("{}/{name}", program.id()), external: false })
+            }
+            ValueType::ExternalRecord(locator) => Some(Self { register, record: locator.to_string(), external: true }),
+            _ => None,
+        }
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({ "register": self.register, "record": self.record, "external": self.external })
+    }
+}
+
+/// The records consumed and produced by a function: the token-flow skeleton of the function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordFlow {
+    /// The name of the function.
+    pub function: String,
+    /// The records consumed as inputs, in order of the inputs.
+    pub consumed: Vec<RecordUse>,
+    /// The records produced as outputs, in order of the outputs.
+    pub produced: Vec<RecordUse>,
+}
+
+impl RecordFlow {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "RecordFlow",
+            "function": self.function,
+            "consumed": self.consumed.iter().map(RecordUse::to_json).collect::<Vec<_>>(),
+            "produced": self.produced.iter().map(RecordUse::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Returns the records consumed and produced by each function of the given program, in order of the functions.
+pub fn program_record_flows<N: Network>(program: &Program<N>) -> Vec<RecordFlow> {
+    let mut flows = Vec::new();
+    for (name, function) in program.functions() {
+        let consumed = function
+            .inputs()
+            .iter()
+            .filter_map(|input| RecordUse::of(program, input.register().to_string(), input.value_type()))
+            .collect();
+        let produced = function
+            .outputs()
+            .iter()
+            .filter_map(|output| RecordUse::of(program, output.operand().to_string(), output.value_type()))
+            .collect();
+        flows.push(RecordFlow { function: name.to_string(), consumed, produced });
+    }
+    flows
+}
+
+/// Returns the record flows of the given program as JSON, keyed by function name.
+pub fn program_record_flows_to_json<N: Network>(program: &Program<N>) -> Value {
+    let mut j_flows = Map::new();
+    for flow in program_record_flows(program) {
+        j_flows.insert(flow.function.clone(), flow.to_json());
+    }
+    Value::Object(j_flows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_program_record_flows() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+
+program swap.aleo;
+
+record ticket:
+    owner as address.private;
+    amount as u64.private;
+
+function buy:
+    input r0 as credits.aleo/credits.record;
+    input r1 as u64.public;
+    cast r0.owner r1 into r2 as ticket.record;
+    output r2 as ticket.record;
+
+function redeem:
+    input r0 as ticket.record;
+    input r1 as credits.aleo/credits.record;
+    output r1 as credits.aleo/credits.record;
+
+function quote:
+    input r0 as u64.public;
+    output r0 as u64.public;",
+        )
+        .unwrap();
+
+        let flows = program_record_flows(&program);
+        let summary = flows
+            .iter()
+            .map(|flow| {
+                let records = |uses: &[RecordUse]| uses.iter().map(|use_| use_.record.clone()).collect::<Vec<_>>();
+                (flow.function.as_str(), records(&flow.consumed), records(&flow.produced))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(summary, [
+            ("buy", vec!["credits.aleo/credits".to_string()], vec!["swap.aleo/ticket".to_string()]),
+            ("redeem", vec!["swap.aleo/ticket".to_string(), "credits.aleo/credits".to_string()], vec![
+                "credits.aleo/credits".to_string()
+            ]),
+            ("quote", vec![], vec![]),
+        ]);
+        assert!(flows[0].consumed[0].external);
+        assert!(!flows[0].produced[0].external);
+
+        let j_flows = program_record_flows_to_json(&program);
+        assert_eq!(
+            j_flows["redeem"]["consumed"][0],
+            json!({ "register": "r0", "record": "swap.aleo/ticket", "external": false })
+        );
+        assert_eq!(j_flows["redeem"]["produced"][0]["register"], "r1");
+    }
+}
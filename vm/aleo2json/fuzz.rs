@@ -0,0 +1,240 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{
+        network::Network,
+        program::{Identifier, Literal, LiteralType, PlaintextType, ValueType},
+    },
+    synthesizer::Instruction,
+};
+
+use proptest::{
+    collection::vec,
+    prelude::{any, prop_oneof, Strategy},
+    sample::select,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::str::FromStr;
+
+/// The literal types, in the order of their type IDs.
+const LITERAL_TYPES: [LiteralType; 17] = [
+    LiteralType::Address,
+    LiteralType::Boolean,
+    LiteralType::Field,
+    LiteralType::Group,
+    LiteralType::I8,
+    LiteralType::I16,
+    LiteralType::I32,
+    LiteralType::I64,
+    LiteralType::I128,
+    LiteralType::U8,
+    LiteralType::U16,
+    LiteralType::U32,
+    LiteralType::U64,
+    LiteralType::U128,
+    LiteralType::Scalar,
+    LiteralType::Signature,
+    LiteralType::String,
+];
+
+/// The integer types, which every opcode in `ARITHMETIC_OPCODES` is defined on.
+const INTEGER_TYPES: [LiteralType; 10] = [
+    LiteralType::I8,
+    LiteralType::I16,
+    LiteralType::I32,
+    LiteralType::I64,
+    LiteralType::I128,
+    LiteralType::U8,
+    LiteralType::U16,
+    LiteralType::U32,
+    LiteralType::U64,
+    LiteralType::U128,
+];
+
+/// The binary opcodes that take two integers of the same type and return an integer of that type.
+const ARITHMETIC_OPCODES: [&str; 6] = ["add.w", "sub.w", "mul.w", "and", "or", "xor"];
+
+/// The opcodes that the instruction strategy draws from, with their number of operands.
+const OPCODES: [(&str, usize); 16] = [
+    ("abs", 1),
+    ("add", 2),
+    ("add.w", 2),
+    ("and", 2),
+    ("div", 2),
+    ("gt", 2),
+    ("is.eq", 2),
+    ("lt", 2),
+    ("mul", 2),
+    ("neg", 1),
+    ("not", 1),
+    ("or", 2),
+    ("pow", 2),
+    ("square", 1),
+    ("sub", 2),
+    ("ternary", 3),
+];
+
+/// Returns a strategy for identifiers, including ones that collide with keywords of the language.
+pub fn arbitrary_identifier<N: Network>() -> impl Strategy<Value = Identifier<N>> {
+    "[a-z][a-z0-9_]{0,15}".prop_filter_map("reserved or invalid identifier", |name| Identifier::from_str(&name).ok())
+}
+
+/// Returns a strategy for literal types.
+pub fn arbitrary_literal_type() -> impl Strategy<Value = LiteralType> {
+    select(LITERAL_TYPES.to_vec())
+}
+
+/// Returns a strategy for literals of every literal type.
+pub fn arbitrary_literal<N: Network>() -> impl Strategy<Value = Literal<N>> {
+    (arbitrary_literal_type(), any::<u64>())
+        .prop_map(|(literal_type, seed)| Literal::sample(literal_type, &mut StdRng::seed_from_u64(seed)))
+}
+
+/// Returns a strategy for plaintext types, i.e. literal types and structs with an arbitrary name.
+pub fn arbitrary_plaintext_type<N: Network>() -> impl Strategy<Value = PlaintextType<N>> {
+    prop_oneof![
+        arbitrary_literal_type().prop_map(PlaintextType::Literal),
+        arbitrary_identifier::<N>().prop_map(PlaintextType::Struct),
+    ]
+}
+
+/// Returns a strategy for value types of literals, in every visibility.
+pub fn arbitrary_value_type<N: Network>() -> impl Strategy<Value = ValueType<N>> {
+    (arbitrary_literal_type(), 0..3u8).prop_map(|(literal_type, visibility)| {
+        let plaintext_type = PlaintextType::Literal(literal_type);
+        match visibility {
+            0 => ValueType::Constant(plaintext_type),
+            1 => ValueType::Public(plaintext_type),
+            _ => ValueType::Private(plaintext_type),
+        }
+    })
+}
+
+/// Returns a strategy for instructions over registers and literal operands.
+/// The instructions are well-formed, but are not necessarily well-typed.
+pub fn arbitrary_instruction<N: Network>() -> impl Strategy<Value = Instruction<N>> {
+    let operand = prop_oneof![
+        (0..16u64).prop_map(|locator| format!("r{locator}")),
+        arbitrary_literal::<N>().prop_map(|literal| literal.to_string()),
+    ];
+    (select(OPCODES.to_vec()), vec(operand, 3), 16..32u64).prop_filter_map(
+        "unparseable instruction",
+        |((opcode, num_operands), operands, destination)| {
+            let operands = operands[..num_operands].join(" ");
+            Instruction::from_str(&format!("{opcode} {operands} into r{destination};")).ok()
+        },
+    )
+}
+
+/// Returns a strategy for the source of small programs that are both well-formed and well-typed.
+///
+/// Each function takes two integers of the same type, chains arithmetic over its registers, and outputs the last one.
+pub fn arbitrary_program_source() -> impl Strategy<Value = String> {
+    let function = (select(INTEGER_TYPES.to_vec()), vec((select(ARITHMETIC_OPCODES.to_vec()), 0..8usize), 1..8));
+    ("[a-z][a-z0-9_]{0,8}", vec(function, 1..4)).prop_map(|(name, functions)| {
+        let mut lines = vec![format!("program fuzz_{name}.aleo;")];
+        for (index, (integer_type, instructions)) in functions.iter().enumerate() {
+            lines.push(String::new());
+            lines.push(format!("function f{index}:"));
+            lines.push(format!("    input r0 as {integer_type}.public;"));
+            lines.push(format!("    input r1 as {integer_type}.private;"));
+            for (destination, (opcode, operand)) in (2..).zip(instructions) {
+                // Reads the previous register and any register that has been assigned so far.
+                lines.push(format!(
+                    "    {opcode} r{} r{} into r{destination};",
+                    destination - 1,
+                    operand % destination
+                ));
+            }
+            lines.push(format!("    output r{} as {integer_type}.private;", instructions.len() + 1));
+        }
+        lines.join("\n")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{
+            convert_program,
+            convert_program_with_options,
+            decompile_program,
+            parse_program,
+            validate_program_json,
+            ConversionMode,
+            JsonOptions,
+            SourceMap,
+        },
+        prelude::Testnet3,
+    };
+
+    use proptest::proptest;
+
+    type CurrentNetwork = Testnet3;
+
+    proptest! {
+        #[test]
+        fn test_identifier_round_trip(identifier in arbitrary_identifier::<CurrentNetwork>()) {
+            assert_eq!(Identifier::from_str(&identifier.to_string()).unwrap(), identifier);
+        }
+
+        #[test]
+        fn test_literal_round_trip(literal in arbitrary_literal::<CurrentNetwork>()) {
+            assert_eq!(Literal::from_str(&literal.to_string()).unwrap(), literal);
+
+            // The JSON names the type of the literal, and is determined by its value.
+            let json = literal.to_json();
+            assert_eq!(json["type"], "Literal");
+            assert_eq!(json["vtype"].as_str().unwrap().to_lowercase(), literal.to_type().to_string());
+            assert_eq!(Literal::from_str(&literal.to_string()).unwrap().to_json(), json);
+        }
+
+        #[test]
+        fn test_type_round_trip(plaintext_type in arbitrary_plaintext_type::<CurrentNetwork>(), value_type in arbitrary_value_type::<CurrentNetwork>()) {
+            assert_eq!(PlaintextType::from_str(&plaintext_type.to_string()).unwrap(), plaintext_type);
+            assert_eq!(ValueType::from_str(&value_type.to_string()).unwrap(), value_type);
+        }
+
+        #[test]
+        fn test_instruction_round_trip(instruction in arbitrary_instruction::<CurrentNetwork>()) {
+            assert_eq!(Instruction::from_str(&instruction.to_string()).unwrap(), instruction);
+        }
+
+        #[test]
+        fn test_program_conversion(source in arbitrary_program_source()) {
+            let json = convert_program::<CurrentNetwork>(&source).unwrap();
+            assert_eq!(validate_program_json(&json), Ok(()));
+
+            // The source is reconstructed from the lossless JSON, and converts back to the same JSON.
+            let options = JsonOptions { lossless: true, ..Default::default() };
+            let lossless =
+                convert_program_with_options::<CurrentNetwork>(&source, ConversionMode::default(), &options).unwrap();
+            let reconstructed = SourceMap::from_json(&lossless["source_map"]).unwrap().apply(&lossless);
+            assert_eq!(reconstructed, source);
+            assert_eq!(convert_program::<CurrentNetwork>(&reconstructed).unwrap(), json);
+
+            let program = parse_program::<CurrentNetwork>(&source).unwrap();
+            assert_eq!(program.to_string(), parse_program::<CurrentNetwork>(&program.to_string()).unwrap().to_string());
+            assert!(!decompile_program(&program).is_empty());
+        }
+
+        #[test]
+        fn test_parser_robustness(source in "\\PC{0,256}") {
+            // Arbitrary input must be rejected with an error rather than a panic.
+            let _ = parse_program::<CurrentNetwork>(&source);
+        }
+    }
+}
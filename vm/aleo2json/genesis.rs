@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::with_version,
+    console::{account::PrivateKey, network::Network},
+    ledger::{
+        block::Block,
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    },
+    synthesizer::VM,
+};
+
+use anyhow::Result;
+use rand::{CryptoRng, Rng};
+
+/// Generates a genesis block for a beacon chain, whose first validator is the given private key.
+///
+/// The genesis block is derived entirely from the private key and the given RNG, so a seeded RNG yields a
+/// deterministic block, suitable as a fixture.
+pub fn generate_genesis_block<N: Network, R: Rng + CryptoRng>(
+    private_key: &PrivateKey<N>,
+    rng: &mut R,
+) -> Result<Block<N>> {
+    // Initialize the VM on an empty, in-memory store.
+    let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(None)?)?;
+    // Generate the genesis block.
+    vm.genesis_beacon(private_key, rng)
+}
+
+/// Generates a genesis block for a beacon chain, whose first validator is the given private key,
+/// and converts it into its block JSON, stamped with the format version.
+pub fn generate_genesis_json<N: Network, R: Rng + CryptoRng>(
+    private_key: &PrivateKey<N>,
+    rng: &mut R,
+) -> Result<serde_json::Value> {
+    Ok(with_version(generate_genesis_block(private_key, rng)?.to_json()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_generate_genesis_block() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+
+        let block = generate_genesis_block(&private_key, rng).unwrap();
+        assert!(block.is_genesis());
+
+        let j_block = with_version(block.to_json());
+        assert_eq!(j_block["type"], "Block");
+        assert_eq!(j_block["block_hash"], format!("{}", block.hash()));
+        assert_eq!(j_block["header"]["metadata"]["height"], 0);
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Golden-file tests for the converters.
+//!
+//! Each program in `tests/programs`, and each block in `tests/blocks` (as snarkVM JSON in `.json` files,
+//! or as little-endian bytes in `.bin` files), is converted and compared against its expectation file in
+//! `tests/expectations`. Documents are compared in their canonical form, so key order does not matter.
+//!
+//! When the `REWRITE_EXPECTATIONS` environment variable is set, the expectation files are rewritten.
+//! A corpus entry without an expectation file fails, so the expectation of a new entry must be recorded with
+//! `REWRITE_EXPECTATIONS=1` and committed with it.
+
+use crate::{
+    aleo2json::{convert_block_bytes, convert_block_json, convert_program, diff_json, to_canonical_string},
+    prelude::Testnet3,
+};
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+type CurrentNetwork = Testnet3;
+
+/// The maximum number of differences reported for a single mismatching document.
+const MAX_REPORTED_DIFFS: usize = 20;
+
+/// Returns the directory of the golden-file corpus.
+fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("vm").join("aleo2json").join("tests")
+}
+
+/// Returns the files in the given corpus subdirectory, in a stable order.
+fn load_corpus(subdirectory: &str) -> Vec<PathBuf> {
+    let directory = corpus_dir().join(subdirectory);
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&directory)
+        .unwrap_or_else(|error| panic!("Failed to read '{}': {error}", directory.display()))
+        .map(|entry| entry.expect("Failed to read corpus entry").path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Returns the path to the expectation file for the given corpus entry, i.e. `hello.aleo.json`.
+fn expectation_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().expect("Corpus entries are files").to_string_lossy();
+    corpus_dir().join("expectations").join(format!("{file_name}.json"))
+}
+
+/// Returns the given document as pretty-printed JSON, with its keys in canonical order.
+fn normalize(document: &Value) -> String {
+    let canonical: Value = serde_json::from_str(&to_canonical_string(document)).expect("Canonical JSON is valid JSON");
+    let mut output = serde_json::to_string_pretty(&canonical).expect("JSON values always serialize");
+    output.push('\n');
+    output
+}
+
+/// Compares the converted document against the expectation for the given corpus entry.
+fn check(path: &Path, document: &Value) -> Result<()> {
+    let output = normalize(document);
+    let expectation_path = expectation_path(path);
+
+    // Record the expectation if requested.
+    if std::env::var("REWRITE_EXPECTATIONS").is_ok() {
+        std::fs::create_dir_all(corpus_dir().join("expectations"))?;
+        std::fs::write(&expectation_path, output)?;
+        return Ok(());
+    }
+    if !expectation_path.exists() {
+        bail!(
+            "'{}' has no expectation '{}'.\nRun with REWRITE_EXPECTATIONS=1 to record it.",
+            path.display(),
+            expectation_path.display()
+        )
+    }
+
+    let expected = std::fs::read_to_string(&expectation_path)?;
+    if expected != output {
+        let diffs = diff_json(&serde_json::from_str(&expected)?, document);
+        let mut message = format!("'{}' does not match '{}':", path.display(), expectation_path.display());
+        for diff in diffs.iter().take(MAX_REPORTED_DIFFS) {
+            message.push_str(&format!("\n  {} {}", diff.kind.as_str(), diff.pointer));
+        }
+        if diffs.len() > MAX_REPORTED_DIFFS {
+            message.push_str(&format!("\n  ... and {} more", diffs.len() - MAX_REPORTED_DIFFS));
+        }
+        bail!("{message}\nRun with REWRITE_EXPECTATIONS=1 to accept the new output.")
+    }
+    Ok(())
+}
+
+/// Runs the given converter over the corpus subdirectory, panicking with every failure found.
+fn run_corpus(subdirectory: &str, convert: impl Fn(&Path) -> Result<Value>) {
+    let paths = load_corpus(subdirectory);
+    assert!(!paths.is_empty(), "The '{subdirectory}' corpus is empty");
+
+    let failures: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            convert(path)
+                .and_then(|document| check(path, &document))
+                .err()
+                .map(|error| format!("{}: {error}", path.display()))
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn test_golden_programs() {
+    run_corpus("programs", |path| Ok(convert_program::<CurrentNetwork>(&std::fs::read_to_string(path)?)?));
+}
+
+#[test]
+fn test_golden_blocks() {
+    run_corpus("blocks", |path| match path.extension().and_then(|extension| extension.to_str()) {
+        Some("bin") => Ok(convert_block_bytes::<CurrentNetwork>(&std::fs::read(path)?)?),
+        Some("json") => Ok(convert_block_json::<CurrentNetwork>(&std::fs::read_to_string(path)?)?),
+        _ => bail!("Unknown block encoding"),
+    });
+}
@@ -0,0 +1,289 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        input_provenance,
+        parse_program,
+        program_document,
+        with_provenance,
+        ConversionError,
+        ConversionMode,
+        JsonOptions,
+    },
+    console::network::Network,
+    synthesizer::Program,
+};
+
+use anyhow::{bail, Error, Result};
+use std::{borrow::Cow, fmt, str::FromStr};
+
+/// The version of the `.aleo` grammar a program was written against.
+///
+/// Programs deployed by earlier snarkVM releases may use syntax the current parser rejects.
+/// They are converted by rewriting the source into the current grammar, then parsing it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GrammarVersion {
+    /// The grammar of earlier snarkVM releases, which declares structs with `interface`, names the key and value
+    /// of each mapping (i.e. `key owner as address.public;`), and omits the output type of hashes.
+    Legacy,
+    /// The grammar of the current snarkVM release.
+    #[default]
+    Current,
+}
+
+impl GrammarVersion {
+    /// The supported grammar versions, from the most recent.
+    pub const ALL: &'static [GrammarVersion] = &[GrammarVersion::Current, GrammarVersion::Legacy];
+
+    /// Rewrites the given source, written against this grammar version, into the current grammar.
+    pub fn upgrade<'a>(&self, source: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Current => Cow::Borrowed(source),
+            Self::Legacy => Cow::Owned(
+                statements(source)
+                    .into_iter()
+                    .map(|(prefix, statement)| format!("{prefix}{}", upgrade_legacy_statement(statement)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl FromStr for GrammarVersion {
+    type Err = Error;
+
+    /// Parses a grammar version from its name.
+    fn from_str(version: &str) -> Result<Self> {
+        match version {
+            "legacy" => Ok(Self::Legacy),
+            "current" => Ok(Self::Current),
+            _ => bail!("Unknown grammar version '{version}' (expected one of: current, legacy)"),
+        }
+    }
+}
+
+impl fmt::Display for GrammarVersion {
+    /// Prints the name of the grammar version.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "legacy"),
+            Self::Current => write!(f, "current"),
+        }
+    }
+}
+
+/// Parses the given Aleo source, written against the given grammar version, into a program.
+///
+/// If no version is given, it is detected: each version is tried from the most recent, and the first one
+/// that parses the source is returned. If none does, the error of the current grammar is returned.
+pub fn parse_program_versioned<N: Network>(
+    source: &str,
+    version: Option<GrammarVersion>,
+) -> Result<(Program<N>, GrammarVersion), ConversionError> {
+    match version {
+        Some(version) => Ok((parse_program::<N>(&version.upgrade(source))?, version)),
+        None => {
+            let mut first_error = None;
+            for version in GrammarVersion::ALL {
+                match parse_program::<N>(&version.upgrade(source)) {
+                    Ok(program) => return Ok((program, *version)),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                }
+            }
+            Err(first_error.unwrap_or_else(|| ConversionError::parse("No grammar version is supported")))
+        }
+    }
+}
+
+/// Converts the given Aleo source, written against the given grammar version (or detected, if none is given),
+/// into its program JSON, stamped with the format version and with the grammar version, under `grammar_version`.
+///
+/// Note: The source locations, if included, refer to the source rewritten into the current grammar. A lossless
+/// conversion, whose source map must reproduce the original source, is refused if the source was rewritten.
+pub fn convert_program_versioned<N: Network>(
+    source: &str,
+    version: Option<GrammarVersion>,
+    mode: ConversionMode,
+    options: &JsonOptions,
+) -> Result<serde_json::Value, ConversionError> {
+    let (program, version) = parse_program_versioned::<N>(source, version)?;
+    let upgraded = version.upgrade(source);
+    if options.lossless && upgraded != source {
+        return Err(ConversionError::Invalid(format!(
+            "A lossless conversion requires a source in the current grammar, not the {version} grammar"
+        )));
+    }
+    let mut document = program_document(&program, &upgraded, None, mode, options)?;
+    document["grammar_version"] = serde_json::json!(version.to_string());
+    let provenance = input_provenance::<N>(source.as_bytes(), options)?;
+    Ok(options.finish(with_provenance(document, &provenance)))
+}
+
+/// Splits the given source into its statements and headers, each ending with `;` or `:`, and each paired with the
+/// whitespace and comments preceding it. The remainder of the source, if any, is returned as the last statement.
+fn statements(source: &str) -> Vec<(&str, &str)> {
+    let bytes = source.as_bytes();
+    let mut statements = Vec::new();
+    let (mut start, mut body) = (0, None);
+    let mut index = 0;
+    while index < bytes.len() {
+        match (bytes[index], bytes.get(index + 1)) {
+            (b'/', Some(b'/')) => {
+                index = source[index..].find('\n').map_or(bytes.len(), |end| index + end + 1);
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                index = source[index + 2..].find("*/").map_or(bytes.len(), |end| index + 2 + end + 2);
+                continue;
+            }
+            (byte, _) if byte.is_ascii_whitespace() => (),
+            (byte, _) => {
+                let body_start = *body.get_or_insert(index);
+                if byte == b';' || byte == b':' {
+                    statements.push((&source[start..body_start], &source[body_start..=index]));
+                    start = index + 1;
+                    body = None;
+                }
+            }
+        }
+        index += 1;
+    }
+    if start < bytes.len() {
+        let body_start = body.unwrap_or(bytes.len());
+        statements.push((&source[start..body_start], &source[body_start..]));
+    }
+    statements
+}
+
+/// Rewrites the given statement or header, written against the legacy grammar, into the current grammar.
+fn upgrade_legacy_statement(statement: &str) -> Cow<str> {
+    let tokens: Vec<&str> = statement.trim_end_matches([';', ':']).split_whitespace().collect();
+    match tokens.as_slice() {
+        // `interface name:` declares a struct.
+        ["interface", name] if statement.ends_with(':') => Cow::Owned(format!("struct {name}:")),
+        // `key name as type;` and `value name as type;` name the key and value of a mapping.
+        [keyword @ ("key" | "value"), _, "as", type_] if statement.ends_with(';') => {
+            Cow::Owned(format!("{keyword} as {type_};"))
+        }
+        // `hash.* operand into destination;` omits the output type, which was always a field.
+        [opcode, .., "into", _] if opcode.starts_with("hash.") && statement.ends_with(';') => {
+            Cow::Owned(format!("{} as field;", statement.trim_end_matches(';')))
+        }
+        _ => Cow::Borrowed(statement),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const LEGACY: &str = r"program legacy.aleo;
+
+// A point in the plane.
+interface point:
+    x as field;
+    y as field;
+
+mapping account:
+    key owner as address.public;
+    value amount as u64.public;
+
+function digest:
+    input r0 as point.private;
+    hash.bhp256 r0 into r1;
+    output r1 as field.private;
+";
+
+    #[test]
+    fn test_upgrade_legacy() {
+        let upgraded = GrammarVersion::Legacy.upgrade(LEGACY);
+        assert!(upgraded.contains("// A point in the plane.\nstruct point:"));
+        assert!(upgraded.contains("key as address.public;"));
+        assert!(upgraded.contains("value as u64.public;"));
+        assert!(upgraded.contains("hash.bhp256 r0 into r1 as field;"));
+        // The current grammar is left as is.
+        assert_eq!(GrammarVersion::Current.upgrade(LEGACY), LEGACY);
+    }
+
+    #[test]
+    fn test_detect_grammar_version() {
+        assert!(parse_program::<CurrentNetwork>(LEGACY).is_err());
+
+        let (program, version) = parse_program_versioned::<CurrentNetwork>(LEGACY, None).unwrap();
+        assert_eq!(version, GrammarVersion::Legacy);
+        assert_eq!(program.id().to_string(), "legacy.aleo");
+
+        let current = GrammarVersion::Legacy.upgrade(LEGACY);
+        let (_, version) = parse_program_versioned::<CurrentNetwork>(&current, None).unwrap();
+        assert_eq!(version, GrammarVersion::Current);
+
+        // An explicit version is not detected.
+        assert!(parse_program_versioned::<CurrentNetwork>(LEGACY, Some(GrammarVersion::Current)).is_err());
+    }
+
+    #[test]
+    fn test_convert_program_versioned() {
+        let document = convert_program_versioned::<CurrentNetwork>(
+            LEGACY,
+            None,
+            ConversionMode::default(),
+            &JsonOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(document["grammar_version"], "legacy");
+        assert_eq!(document["type"], "ProgramCore");
+    }
+
+    #[test]
+    fn test_convert_versioned_sections() {
+        // A source in the current grammar converts as without a grammar version, with the same sections.
+        let current = GrammarVersion::Legacy.upgrade(LEGACY);
+        let options = JsonOptions {
+            include_fingerprints: true,
+            include_halting: true,
+            include_opcodes: true,
+            include_reachability: true,
+            ..Default::default()
+        };
+        let mode = ConversionMode::default();
+        let mut versioned =
+            convert_program_versioned::<CurrentNetwork>(&current, Some(GrammarVersion::Current), mode, &options)
+                .unwrap();
+        assert!(versioned["fingerprint"].is_string());
+        assert_eq!(versioned.as_object_mut().unwrap().remove("grammar_version").unwrap(), "current");
+        let document =
+            crate::aleo2json::convert_program_with_options::<CurrentNetwork>(&current, mode, &options).unwrap();
+        assert_eq!(versioned, document);
+    }
+
+    #[test]
+    fn test_convert_lossless_versioned() {
+        let options = JsonOptions { lossless: true, ..Default::default() };
+        // The source map of a rewritten source would not reproduce the original source.
+        let error = convert_program_versioned::<CurrentNetwork>(LEGACY, None, ConversionMode::default(), &options);
+        assert!(matches!(error, Err(ConversionError::Invalid(_))));
+
+        let current = GrammarVersion::Legacy.upgrade(LEGACY);
+        let document =
+            convert_program_versioned::<CurrentNetwork>(&current, None, ConversionMode::default(), &options).unwrap();
+        let map = crate::aleo2json::SourceMap::from_json(&document["source_map"]).unwrap();
+        assert_eq!(map.render(), current);
+    }
+}
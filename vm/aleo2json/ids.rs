@@ -0,0 +1,148 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::KEYED_PROPERTIES;
+
+use serde_json::Value;
+
+/// Assigns every node of the given document, i.e. every object with a `type`, a deterministic ID under `node_id`.
+///
+/// The ID of a node is the given root ID, followed by `#` and the JSON pointer of the node in the document,
+/// i.e. `token.aleo#/functions/mint/instructions/0`. The pointers are taken on the default field names,
+/// so the IDs of an object are the same across conversions, regardless of the naming options.
+///
+/// Note: The key is `node_id` rather than `id`, as several nodes (i.e. programs and transactions) already have an `id`.
+pub fn assign_node_ids(document: &mut Value, root: &str) {
+    annotate_nodes(document, root, true, false);
+}
+
+/// Annotates every node of the given document with its JSON pointer, under `pointer`,
+/// and with the ID of its closest enclosing node, under `parent_id` (`null` for the root).
+///
+/// The parent IDs follow the scheme of `assign_node_ids`, so they match the `node_id` of the parents, if assigned.
+pub fn assign_node_paths(document: &mut Value, root: &str) {
+    annotate_nodes(document, root, false, true);
+}
+
+/// Annotates every node of the given document with its ID and/or with its JSON pointer and parent ID.
+pub fn annotate_nodes(document: &mut Value, root: &str, with_ids: bool, with_paths: bool) {
+    if with_ids || with_paths {
+        let annotator = Annotator { root, with_ids, with_paths };
+        annotator.annotate(document, &mut String::new(), None, false);
+    }
+}
+
+/// A helper to annotate the nodes of a document.
+struct Annotator<'a> {
+    /// The ID prefixing the node IDs.
+    root: &'a str,
+    /// Whether to add the node IDs.
+    with_ids: bool,
+    /// Whether to add the JSON pointers and the parent IDs.
+    with_paths: bool,
+}
+
+impl Annotator<'_> {
+    /// Annotates the given value, and its children, where `pointer` is the JSON pointer of the value,
+    /// and `parent` is the ID of its closest enclosing node.
+    fn annotate(&self, value: &mut Value, pointer: &mut String, parent: Option<&str>, is_keyed: bool) {
+        match value {
+            Value::Object(object) => {
+                let is_node = !is_keyed && object.contains_key("type");
+                let id = format!("{}#{pointer}", self.root);
+                let parent_of_children = match is_node {
+                    true => Some(id.as_str()),
+                    false => parent,
+                };
+                for (key, val) in object.iter_mut() {
+                    let is_keyed_child = !is_keyed && KEYED_PROPERTIES.contains(&key.as_str());
+                    let length = pointer.len();
+                    pointer.push('/');
+                    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                    self.annotate(val, pointer, parent_of_children, is_keyed_child);
+                    pointer.truncate(length);
+                }
+                if is_node {
+                    if self.with_paths {
+                        object.insert("pointer".to_string(), Value::String(pointer.clone()));
+                        object.insert("parent_id".to_string(), parent.map_or(Value::Null, |parent| parent.into()));
+                    }
+                    if self.with_ids {
+                        object.insert("node_id".to_string(), Value::String(id));
+                    }
+                }
+            }
+            Value::Array(array) => {
+                for (index, val) in array.iter_mut().enumerate() {
+                    let length = pointer.len();
+                    pointer.push_str(&format!("/{index}"));
+                    self.annotate(val, pointer, parent, false);
+                    pointer.truncate(length);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn sample_document() -> Value {
+        json!({
+            "type": "ProgramCore",
+            "id": { "type": "ProgramID", "name": "token" },
+            "functions": {
+                "mint": { "type": "FunctionCore", "instructions": [{ "type": "Instruction" }, { "type": "Instruction" }] },
+                "a/b": { "type": "FunctionCore" },
+            },
+        })
+    }
+
+    #[test]
+    fn test_assign_node_ids() {
+        let mut document = sample_document();
+        assign_node_ids(&mut document, "token.aleo");
+
+        assert_eq!(document["node_id"], "token.aleo#");
+        assert_eq!(document["id"]["node_id"], "token.aleo#/id");
+        // The functions are keyed by name, so the keyed object itself is not a node.
+        assert!(document["functions"].get("node_id").is_none());
+        assert_eq!(document["functions"]["mint"]["node_id"], "token.aleo#/functions/mint");
+        assert_eq!(
+            document["functions"]["mint"]["instructions"][1]["node_id"],
+            "token.aleo#/functions/mint/instructions/1"
+        );
+        assert_eq!(document["functions"]["a/b"]["node_id"], "token.aleo#/functions/a~1b");
+        assert!(document.get("pointer").is_none());
+    }
+
+    #[test]
+    fn test_assign_node_paths() {
+        let mut document = sample_document();
+        assign_node_paths(&mut document, "token.aleo");
+
+        assert_eq!(document["pointer"], "");
+        assert_eq!(document["parent_id"], Value::Null);
+        let instruction = &document["functions"]["mint"]["instructions"][0];
+        assert_eq!(instruction["pointer"], "/functions/mint/instructions/0");
+        assert_eq!(instruction["parent_id"], "token.aleo#/functions/mint");
+        // The keyed functions object is not a node, so the functions are children of the program.
+        assert_eq!(document["functions"]["mint"]["parent_id"], "token.aleo#");
+        assert!(document.get("node_id").is_none());
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::NetworkCache,
+    console::{network::Network, program::ProgramID},
+};
+
+#[cfg(feature = "aleo2json-ledger")]
+use crate::{aleo2json::RestClient, ledger::block::Transaction};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::{path::PathBuf, str::FromStr};
+
+/// The program that every network provides, without a deployment.
+const BUILTIN_PROGRAM: &str = "credits.aleo";
+
+/// Where an import was resolved from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImportSource {
+    /// The program is built into the network (i.e. `credits.aleo`).
+    Builtin,
+    /// The program is a local file, i.e. in the `imports` directory of a project.
+    Local,
+    /// The program is a previously downloaded copy, in the cache directory.
+    Cache,
+    /// The program is deployed on the network, as reported by a node.
+    Network,
+}
+
+impl ImportSource {
+    /// Returns the name of the import source.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::Local => "local",
+            Self::Cache => "cache",
+            Self::Network => "network",
+        }
+    }
+}
+
+/// The outcome of resolving an import.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportResolution {
+    /// Where the import was resolved from.
+    pub source: ImportSource,
+    /// The path or URL the import was resolved at.
+    pub location: String,
+    /// The edition of the deployed program, if known (only for imports resolved from the network).
+    pub edition: Option<u16>,
+}
+
+/// Resolves the imports of a program against local directories and, optionally, a node.
+///
+/// The sources are tried in order: built-in programs, the local directory, the cache directory, and then the node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportResolver {
+    /// The directory holding the imported programs, as `<program ID>` files (i.e. `token.aleo`).
+    pub local: Option<PathBuf>,
+    /// The directory caching the programs downloaded from the node, laid out as a `NetworkCache`;
+    /// programs laid out as the local directory are also found.
+    pub cache: Option<PathBuf>,
+    /// The base URL of a node to look up deployed programs from, i.e. `http://localhost:3030`.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub endpoint: Option<String>,
+}
+
+impl ImportResolver {
+    /// Resolves the given program ID, returning `None` if no source has the program.
+    pub fn resolve<N: Network>(&self, program_id: &ProgramID<N>) -> Result<Option<ImportResolution>> {
+        if program_id.to_string() == BUILTIN_PROGRAM {
+            return Ok(Some(ImportResolution {
+                source: ImportSource::Builtin,
+                location: BUILTIN_PROGRAM.to_string(),
+                edition: None,
+            }));
+        }
+        for (source, directory) in [(ImportSource::Local, &self.local), (ImportSource::Cache, &self.cache)] {
+            if let Some(directory) = directory {
+                // The latest edition cached from the node takes precedence in the cache directory.
+                if source == ImportSource::Cache {
+                    let cache = NetworkCache::new(directory.clone());
+                    if let Some(edition) = cache.latest_program_edition(program_id)? {
+                        let location = cache.program_path(program_id, edition).display().to_string();
+                        return Ok(Some(ImportResolution { source, location, edition: Some(edition) }));
+                    }
+                }
+                let path = directory.join(program_id.to_string());
+                if path.is_file() {
+                    return Ok(Some(ImportResolution { source, location: path.display().to_string(), edition: None }));
+                }
+            }
+        }
+        #[cfg(feature = "aleo2json-ledger")]
+        if let Some(endpoint) = &self.endpoint {
+            return resolve_deployment(endpoint, program_id);
+        }
+        Ok(None)
+    }
+
+    /// Returns the source of the given program, as resolved by `resolve`, or `None` for a built-in program.
+    ///
+    /// The programs fetched from the node are saved to the cache directory under their edition, if one is set.
+    pub fn source<N: Network>(
+        &self,
+        program_id: &ProgramID<N>,
+        resolution: &ImportResolution,
+    ) -> Result<Option<String>> {
+        match resolution.source {
+            ImportSource::Builtin => Ok(None),
+            ImportSource::Local | ImportSource::Cache => Ok(Some(std::fs::read_to_string(&resolution.location)?)),
+            ImportSource::Network => {
+                let source = self.fetch(program_id)?;
+                if let (Some(cache), Some(edition)) = (&self.cache, resolution.edition) {
+                    NetworkCache::new(cache.clone()).insert_program(program_id, edition, &source)?;
+                }
+                Ok(Some(source))
+            }
+        }
+    }
+
+    /// Fetches the source of the given deployed program from the node.
+    #[cfg(feature = "aleo2json-ledger")]
+    fn fetch<N: Network>(&self, program_id: &ProgramID<N>) -> Result<String> {
+        match &self.endpoint {
+            Some(endpoint) => RestClient::new(endpoint).get_program(program_id),
+            None => anyhow::bail!("No endpoint to fetch '{program_id}' from"),
+        }
+    }
+
+    /// Fetches the source of the given deployed program from the node.
+    #[cfg(not(feature = "aleo2json-ledger"))]
+    fn fetch<N: Network>(&self, program_id: &ProgramID<N>) -> Result<String> {
+        anyhow::bail!("Fetching '{program_id}' from a node requires the 'aleo2json-ledger' feature")
+    }
+}
+
+/// Looks up the deployment of the given program on the given node, returning `None` if it is not deployed.
+#[cfg(feature = "aleo2json-ledger")]
+fn resolve_deployment<N: Network>(endpoint: &str, program_id: &ProgramID<N>) -> Result<Option<ImportResolution>> {
+    let network = match N::ID {
+        3 => "testnet3",
+        _ => anyhow::bail!("Unsupported network ID in import query"),
+    };
+    let client = RestClient::new(endpoint);
+
+    let path = format!("{network}/find/transactionID/deployment/{program_id}");
+    let location = format!("{}/{path}", client.endpoint());
+    let transaction_id: N::TransactionID = match client.find_json(&path)? {
+        Some(transaction_id) => transaction_id,
+        None => return Ok(None),
+    };
+    let transaction: Transaction<N> = client.get_transaction(&transaction_id)?;
+    match transaction.deployment() {
+        Some(deployment) => {
+            Ok(Some(ImportResolution { source: ImportSource::Network, location, edition: Some(deployment.edition()) }))
+        }
+        None => anyhow::bail!("Transaction '{transaction_id}' does not deploy '{program_id}'"),
+    }
+}
+
+/// Adds the resolution of each import of the given program JSON, under `imports.<program ID>.resolution`.
+///
+/// An import that fails to resolve (i.e. as the node is unreachable) is marked unresolved, with the reason under `error`.
+pub fn attach_import_resolutions<N: Network>(document: &mut Value, resolver: &ImportResolver) {
+    if let Some(imports) = document.get_mut("imports").and_then(Value::as_object_mut) {
+        for (key, import) in imports.iter_mut() {
+            let outcome = ProgramID::<N>::from_str(key).and_then(|program_id| resolver.resolve(&program_id));
+            import["resolution"] = match outcome {
+                Ok(Some(resolution)) => json!({
+                    "resolved": true,
+                    "source": resolution.source.as_str(),
+                    "location": resolution.location,
+                    "edition": resolution.edition,
+                    "error": null,
+                }),
+                Ok(None) => {
+                    json!({ "resolved": false, "source": null, "location": null, "edition": null, "error": null })
+                }
+                Err(error) => json!({
+                    "resolved": false,
+                    "source": null,
+                    "location": null,
+                    "edition": null,
+                    "error": error.to_string(),
+                }),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_resolve_imports() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("token.aleo"), "program token.aleo;").unwrap();
+        let resolver = ImportResolver { local: Some(directory.path().to_path_buf()), ..Default::default() };
+
+        let resolve = |program_id: &str| {
+            resolver.resolve(&ProgramID::<CurrentNetwork>::from_str(program_id).unwrap()).unwrap().map(|r| r.source)
+        };
+        assert_eq!(resolve("credits.aleo"), Some(ImportSource::Builtin));
+        assert_eq!(resolve("token.aleo"), Some(ImportSource::Local));
+        assert_eq!(resolve("swap.aleo"), None);
+    }
+
+    #[test]
+    fn test_import_source() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("token.aleo"), "program token.aleo;").unwrap();
+        let resolver = ImportResolver { local: Some(directory.path().to_path_buf()), ..Default::default() };
+
+        let source = |program_id: &str| {
+            let program_id = ProgramID::<CurrentNetwork>::from_str(program_id).unwrap();
+            let resolution = resolver.resolve(&program_id).unwrap().unwrap();
+            resolver.source(&program_id, &resolution).unwrap()
+        };
+        assert_eq!(source("token.aleo").as_deref(), Some("program token.aleo;"));
+        assert_eq!(source("credits.aleo"), None);
+    }
+
+    #[test]
+    fn test_resolve_cached_edition() {
+        let directory = tempfile::tempdir().unwrap();
+        let program_id = ProgramID::<CurrentNetwork>::from_str("token.aleo").unwrap();
+        let cache = NetworkCache::new(directory.path().to_path_buf());
+        cache.insert_program(&program_id, 1, "program token.aleo;").unwrap();
+        let resolver = ImportResolver { cache: Some(directory.path().to_path_buf()), ..Default::default() };
+
+        let resolution = resolver.resolve(&program_id).unwrap().unwrap();
+        assert_eq!((resolution.source, resolution.edition), (ImportSource::Cache, Some(1)));
+        assert_eq!(resolver.source(&program_id, &resolution).unwrap().as_deref(), Some("program token.aleo;"));
+    }
+
+    #[test]
+    fn test_attach_import_resolutions() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("token.aleo"), "program token.aleo;").unwrap();
+        let resolver = ImportResolver { cache: Some(directory.path().to_path_buf()), ..Default::default() };
+
+        let mut document = json!({
+            "imports": {
+                "token.aleo": { "type": "Import" },
+                "swap.aleo": { "type": "Import" },
+            },
+        });
+        attach_import_resolutions::<CurrentNetwork>(&mut document, &resolver);
+
+        let token = &document["imports"]["token.aleo"]["resolution"];
+        assert_eq!(token["resolved"], true);
+        assert_eq!(token["source"], "cache");
+        assert_eq!(token["location"], directory.path().join("token.aleo").display().to_string());
+        assert_eq!(document["imports"]["swap.aleo"]["resolution"]["resolved"], false);
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{aleo2json::with_version, console::network::Network, ledger::block::ConfirmedTransaction};
+
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+/// The location of an exported object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexLocation {
+    /// The height of the block containing the object.
+    pub block_height: u32,
+    /// The exported file holding the object, if it was written to a file.
+    pub file: Option<String>,
+    /// The byte offset into the file at which the JSON holding the object begins.
+    pub offset: u64,
+}
+
+impl IndexLocation {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "block_height": self.block_height,
+            "file": self.file,
+            "offset": self.offset,
+        })
+    }
+
+    /// Reads a location from its JSON, the inverse of `to_json`.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let block_height =
+            value["block_height"].as_u64().ok_or_else(|| anyhow!("Expected the height of a location"))?;
+        Ok(Self {
+            block_height: u32::try_from(block_height)?,
+            file: value["file"].as_str().map(str::to_string),
+            offset: value["offset"].as_u64().ok_or_else(|| anyhow!("Expected the offset of a location"))?,
+        })
+    }
+}
+
+/// An index from transaction IDs and transition IDs to their locations in exported files,
+/// so consumers can seek directly to an object without scanning.
+#[derive(Clone, Debug, Default)]
+pub struct ExportIndex {
+    /// The locations of the transactions, keyed by transaction ID.
+    transactions: IndexMap<String, IndexLocation>,
+    /// The transitions, keyed by transition ID, with the ID of their transaction and its location.
+    transitions: IndexMap<String, (String, IndexLocation)>,
+}
+
+impl ExportIndex {
+    /// Initializes an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the given transaction, and each of its transitions, at the given location.
+    pub fn insert<N: Network>(&mut self, transaction: &ConfirmedTransaction<N>, location: &IndexLocation) {
+        let transaction_id = format!("{}", transaction.id());
+        for transition_id in transaction.transaction().transition_ids() {
+            self.transitions.insert(format!("{}", transition_id), (transaction_id.clone(), location.clone()));
+        }
+        self.transactions.insert(transaction_id, location.clone());
+    }
+
+    /// Returns the location of the transaction with the given ID.
+    pub fn get_transaction(&self, transaction_id: &str) -> Option<&IndexLocation> {
+        self.transactions.get(transaction_id)
+    }
+
+    /// Returns the ID of the transaction containing the transition with the given ID, and its location.
+    pub fn get_transition(&self, transition_id: &str) -> Option<(&str, &IndexLocation)> {
+        self.transitions.get(transition_id).map(|(transaction_id, location)| (transaction_id.as_str(), location))
+    }
+
+    /// Returns the number of indexed transactions.
+    pub fn num_transactions(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns the number of indexed transitions.
+    pub fn num_transitions(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let j_delta = self.delta_json(0, 0);
+        with_version(json!({
+            "type": "ExportIndex",
+            "transactions": j_delta["transactions"],
+            "transitions": j_delta["transitions"],
+        }))
+    }
+
+    /// Reads an index from its JSON, the inverse of `to_json`.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let mut index = Self::new();
+        index.extend_from_json(value)?;
+        Ok(index)
+    }
+
+    /// Returns the JSON of the transactions and transitions recorded after the first `num_transactions`
+    /// and `num_transitions`, so an index can be saved incrementally.
+    pub(crate) fn delta_json(&self, num_transactions: usize, num_transitions: usize) -> Value {
+        let mut j_transactions = serde_json::Map::new();
+        for (key, val) in self.transactions.iter().skip(num_transactions) {
+            j_transactions.insert(key.clone(), val.to_json());
+        }
+
+        let mut j_transitions = serde_json::Map::new();
+        for (key, (transaction_id, location)) in self.transitions.iter().skip(num_transitions) {
+            let mut j_location = location.to_json();
+            j_location["transaction_id"] = json!(transaction_id);
+            j_transitions.insert(key.clone(), j_location);
+        }
+
+        json!({
+            "transactions": j_transactions,
+            "transitions": j_transitions,
+        })
+    }
+
+    /// Records the transactions and transitions in the given JSON, as returned by `to_json` or `delta_json`.
+    pub(crate) fn extend_from_json(&mut self, value: &Value) -> Result<()> {
+        let j_transactions =
+            value["transactions"].as_object().ok_or_else(|| anyhow!("Expected the transactions of an index"))?;
+        for (transaction_id, location) in j_transactions {
+            self.transactions.insert(transaction_id.clone(), IndexLocation::from_json(location)?);
+        }
+        let j_transitions =
+            value["transitions"].as_object().ok_or_else(|| anyhow!("Expected the transitions of an index"))?;
+        for (transition_id, location) in j_transitions {
+            let transaction_id = location["transaction_id"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Expected the transaction of transition '{transition_id}'"))?;
+            self.transitions
+                .insert(transition_id.clone(), (transaction_id.to_string(), IndexLocation::from_json(location)?));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, ledger::block::Block, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_export_index() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        let mut index = ExportIndex::new();
+        let location = IndexLocation { block_height: 0, file: Some("blocks.ndjson".to_string()), offset: 42 };
+        for transaction in genesis.transactions().iter() {
+            index.insert(transaction, &location);
+        }
+        assert_eq!(index.num_transactions(), genesis.transactions().len());
+        assert_eq!(index.num_transitions(), genesis.transitions().count());
+
+        let transaction = genesis.transactions().iter().next().unwrap();
+        let transaction_id = format!("{}", transaction.id());
+        assert_eq!(index.get_transaction(&transaction_id), Some(&location));
+
+        let transition_id = format!("{}", transaction.transaction().transition_ids().next().unwrap());
+        assert_eq!(index.get_transition(&transition_id), Some((transaction_id.as_str(), &location)));
+
+        let j_index = index.to_json();
+        assert_eq!(j_index["transactions"][&transaction_id]["offset"], 42);
+        assert_eq!(j_index["transitions"][&transition_id]["transaction_id"], transaction_id);
+
+        // Ensure the index reads back from its JSON.
+        assert_eq!(ExportIndex::from_json(&j_index).unwrap().to_json(), j_index);
+
+        // Ensure the index reads back from its deltas, i.e. of the first transaction, then of the others.
+        let num_transitions = transaction.transaction().transition_ids().count();
+        let j_delta = index.delta_json(1, num_transitions);
+        assert!(j_delta["transactions"].get(&transaction_id).is_none());
+        let mut restored = ExportIndex::new();
+        restored.insert(transaction, &location);
+        restored.extend_from_json(&j_delta).unwrap();
+        assert_eq!(restored.to_json(), j_index);
+    }
+}
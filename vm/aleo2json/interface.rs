@@ -0,0 +1,101 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{console::network::Network, synthesizer::Program};
+
+use serde_json::{json, Map, Value};
+
+/// Returns the interface of the given program: the name of each function, and the type of each of its inputs and
+/// outputs (i.e. `u64.public` or `token.record`), in order of the functions.
+pub fn program_interface_to_json<N: Network>(program: &Program<N>) -> Value {
+    let mut j_interface = Vec::new();
+    for (name, function) in program.functions() {
+        let j_inputs: Vec<_> = function.inputs().iter().map(|input| input.value_type().to_string()).collect();
+        let j_outputs: Vec<_> = function.outputs().iter().map(|output| output.value_type().to_string()).collect();
+        j_interface.push(json!({ "name": name.to_string(), "inputs": j_inputs, "outputs": j_outputs }));
+    }
+    Value::Array(j_interface)
+}
+
+/// Inserts the interface of the given program at the top of its program JSON, under `interface`.
+///
+/// Only the functions kept in the document are listed, so the interface of a slice matches its functions.
+pub fn attach_interface<N: Network>(program: &Program<N>, document: &mut Value) {
+    let object = match document {
+        Value::Object(object) => object,
+        _ => return,
+    };
+    let functions = object.get("functions").and_then(Value::as_object);
+    let j_interface = match program_interface_to_json(program) {
+        Value::Array(j_interface) => j_interface
+            .into_iter()
+            .filter(|j_function| {
+                let name = j_function["name"].as_str().unwrap_or_default();
+                functions.map_or(false, |functions| functions.contains_key(name))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut j_document = Map::with_capacity(object.len() + 1);
+    j_document.insert("interface".to_string(), Value::Array(j_interface));
+    j_document.extend(std::mem::take(object));
+    *object = j_document;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_program_interface() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program token.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.public;
+    cast r0 r1 into r2 as token.record;
+    output r2 as token.record;
+
+function burn:
+    input r0 as token.record;",
+        )
+        .unwrap();
+
+        assert_eq!(
+            program_interface_to_json(&program),
+            json!([
+                { "name": "mint", "inputs": ["address.private", "u64.public"], "outputs": ["token.record"] },
+                { "name": "burn", "inputs": ["token.record"], "outputs": [] },
+            ])
+        );
+
+        let mut document = program.to_json();
+        document["functions"].as_object_mut().unwrap().remove("burn");
+        attach_interface(&program, &mut document);
+        // The interface is the first field, and only lists the functions kept in the document.
+        assert_eq!(document.as_object().unwrap().keys().next().unwrap(), "interface");
+        assert_eq!(document["interface"].as_array().unwrap().len(), 1);
+        assert_eq!(document["interface"][0]["name"], "mint");
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use serde_json::Value;
+use std::{
+    fs::{File, OpenOptions},
+    io::{Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// An append-only file of JSON lines, holding what each checkpoint of an export added since the previous one,
+/// so saving a checkpoint writes only its changes, instead of everything exported so far.
+///
+/// The checkpoint records the length of the journal once its changes are appended; anything after that length
+/// was appended by a checkpoint that was interrupted before it was saved, and is discarded.
+#[derive(Clone, Debug)]
+pub(crate) struct Journal {
+    /// The path of the journal file.
+    path: PathBuf,
+    /// The length of the journal file, in bytes, as of the last entry appended.
+    length: u64,
+}
+
+impl Journal {
+    /// Starts an empty journal at the given path, replacing any previous one.
+    pub(crate) fn create(path: &Path) -> Result<Self> {
+        File::create(path)?;
+        Ok(Self { path: path.to_path_buf(), length: 0 })
+    }
+
+    /// Reopens the journal at the given path, returning it with the entries in its first `length` bytes.
+    pub(crate) fn restore(path: &Path, length: u64) -> Result<(Self, Vec<Value>)> {
+        let mut bytes = std::fs::read(path)?;
+        ensure!(bytes.len() as u64 >= length, "The journal '{}' is shorter than its checkpoint", path.display());
+        bytes.truncate(length as usize);
+        let entries = bytes
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(serde_json::from_slice)
+            .collect::<Result<Vec<Value>, _>>()?;
+        Ok((Self { path: path.to_path_buf(), length }, entries))
+    }
+
+    /// Appends the given entry, returning the length of the journal once the entry is durably written.
+    pub(crate) fn append(&mut self, entry: &Value) -> Result<u64> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        let mut file = OpenOptions::new().write(true).open(&self.path)?;
+        // Discard anything appended by an interrupted checkpoint.
+        file.set_len(self.length)?;
+        file.seek(SeekFrom::End(0))?;
+        file.write_all(&line)?;
+        file.sync_data()?;
+
+        self.length += line.len() as u64;
+        Ok(self.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_journal() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("checkpoint.journal");
+
+        let mut journal = Journal::create(&path).unwrap();
+        journal.append(&json!({ "entries": [1] })).unwrap();
+        let length = journal.append(&json!({ "entries": [2, 3] })).unwrap();
+        assert_eq!(length, std::fs::metadata(&path).unwrap().len());
+
+        // An entry appended after the checkpoint was saved is discarded on restoration.
+        journal.append(&json!({ "entries": [4] })).unwrap();
+        let (mut journal, entries) = Journal::restore(&path, length).unwrap();
+        assert_eq!(entries, vec![json!({ "entries": [1] }), json!({ "entries": [2, 3] })]);
+        journal.append(&json!({ "entries": [5] })).unwrap();
+        let (_, entries) = Journal::restore(&path, std::fs::metadata(&path).unwrap().len()).unwrap();
+        assert_eq!(entries[2], json!({ "entries": [5] }));
+
+        // A journal shorter than its checkpoint cannot be restored.
+        assert!(Journal::restore(&path, length * 10).is_err());
+    }
+}
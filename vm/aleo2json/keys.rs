@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::checksum,
+    console::network::{prelude::ToBytes, Network},
+    ledger::block::{Block, Deployment},
+    synthesizer::snark::{Certificate, VerifyingKey},
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// The metadata of the verifying key deployed for a function, and of its certificate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKeyMetadata {
+    /// The size of the verifying key, in bytes.
+    pub size_in_bytes: usize,
+    /// The SHA3-256 hash of the verifying key bytes, in hex.
+    pub checksum: String,
+    /// The ID of the circuit, as committed to by the verifying key, in hex.
+    pub circuit_id: String,
+    /// The number of public inputs of the circuit, after padding.
+    pub num_public_inputs: usize,
+    /// The number of variables of the circuit.
+    pub num_variables: usize,
+    /// The number of constraints of the circuit.
+    pub num_constraints: usize,
+    /// The number of nonzero entries of the `A`, `B`, and `C` matrices of the circuit.
+    pub num_non_zero: (usize, usize, usize),
+    /// The size of the certificate, in bytes.
+    pub certificate_size_in_bytes: usize,
+    /// The SHA3-256 hash of the certificate bytes, in hex.
+    pub certificate_checksum: String,
+}
+
+impl VerifyingKeyMetadata {
+    /// Returns the metadata of the given verifying key and certificate.
+    pub fn new<N: Network>(verifying_key: &VerifyingKey<N>, certificate: &Certificate<N>) -> Result<Self> {
+        let verifying_key_bytes = verifying_key.to_bytes_le()?;
+        let certificate_bytes = certificate.to_bytes_le()?;
+        let circuit_info = &verifying_key.circuit_info;
+        Ok(Self {
+            size_in_bytes: verifying_key_bytes.len(),
+            checksum: checksum(&verifying_key_bytes)?,
+            circuit_id: format!("{}", verifying_key.id),
+            num_public_inputs: circuit_info.num_public_inputs,
+            num_variables: circuit_info.num_variables,
+            num_constraints: circuit_info.num_constraints,
+            num_non_zero: (circuit_info.num_non_zero_a, circuit_info.num_non_zero_b, circuit_info.num_non_zero_c),
+            certificate_size_in_bytes: certificate_bytes.len(),
+            certificate_checksum: checksum(&certificate_bytes)?,
+        })
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "VerifyingKeyMetadata",
+            "size_in_bytes": self.size_in_bytes,
+            "checksum": self.checksum,
+            "circuit_id": self.circuit_id,
+            "num_public_inputs": self.num_public_inputs,
+            "num_variables": self.num_variables,
+            "num_constraints": self.num_constraints,
+            "num_non_zero": {
+                "a": self.num_non_zero.0,
+                "b": self.num_non_zero.1,
+                "c": self.num_non_zero.2,
+            },
+            "certificate": {
+                "size_in_bytes": self.certificate_size_in_bytes,
+                "checksum": self.certificate_checksum,
+            },
+        })
+    }
+}
+
+/// Adds the metadata of each verifying key of the given deployment to the given deployment JSON,
+/// under `metadata`, next to the `verifying_key` and `certificate` of each function.
+pub fn attach_deployment_key_metadata<N: Network>(deployment: &Deployment<N>, j_deployment: &mut Value) -> Result<()> {
+    for (index, (_, (verifying_key, certificate))) in deployment.verifying_keys().iter().enumerate() {
+        if let Some(j_verifying_key) = j_deployment["verifying_keys"].get_mut(index) {
+            j_verifying_key["metadata"] = VerifyingKeyMetadata::new(verifying_key, certificate)?.to_json();
+        }
+    }
+    Ok(())
+}
+
+/// Adds the metadata of each verifying key deployed in the given block to the given block JSON,
+/// under `metadata`, next to the `verifying_key` and `certificate` of each function.
+///
+/// Only accepted deployments are described; a rejected deployment is left as is.
+pub fn attach_block_key_metadata<N: Network>(block: &Block<N>, j_block: &mut Value) -> Result<()> {
+    for (index, confirmed) in block.transactions().iter().enumerate() {
+        if let Some(deployment) = confirmed.transaction().deployment() {
+            let j_deployment = &mut j_block["transactions"][index]["transaction"]["value"]["deployment"];
+            attach_deployment_key_metadata(deployment, j_deployment)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuit::AleoV0,
+        prelude::Testnet3,
+        synthesizer::{Process, Program},
+    };
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_attach_deployment_key_metadata() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program keys.aleo;
+
+function double:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;
+",
+        )
+        .unwrap();
+        let process = Process::<CurrentNetwork>::load().unwrap();
+        let deployment = process.deploy::<AleoV0, _>(&program, rng).unwrap();
+
+        let (_, (verifying_key, certificate)) = &deployment.verifying_keys()[0];
+        let metadata = VerifyingKeyMetadata::new(verifying_key, certificate).unwrap();
+        assert_eq!(metadata.size_in_bytes, verifying_key.to_bytes_le().unwrap().len());
+        assert!(metadata.checksum.starts_with("sha3-256:"));
+        assert!(metadata.num_constraints > 0);
+        assert_eq!(metadata, VerifyingKeyMetadata::new(verifying_key, certificate).unwrap());
+
+        let mut j_deployment = deployment.to_json();
+        attach_deployment_key_metadata(&deployment, &mut j_deployment).unwrap();
+        let j_metadata = &j_deployment["verifying_keys"][0]["metadata"];
+        assert_eq!(j_metadata["type"], "VerifyingKeyMetadata");
+        assert_eq!(j_metadata["num_constraints"], metadata.num_constraints);
+        assert_eq!(j_metadata["certificate"]["checksum"], metadata.certificate_checksum);
+    }
+}
@@ -0,0 +1,683 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        with_version,
+        write_atomically,
+        ExportIndex,
+        IndexLocation,
+        Journal,
+        OutputSink,
+        Progress,
+        ProgressCallback,
+    },
+    console::{
+        network::{prelude::FromStr, Network},
+        program::ProgramID,
+    },
+    ledger::{
+        block::{Block, ConfirmedTransaction},
+        store::{ConsensusStorage, ConsensusStore},
+    },
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use indexmap::IndexSet;
+use serde_json::{json, Value};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+use tracing::{debug, info_span};
+
+/// The default number of entries written to each chunk file.
+pub const DEFAULT_CHUNK_SIZE: usize = 1000;
+
+/// The name of the manifest file written alongside the chunk files.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// The name of the index file, which locates each transaction and transition in the chunk files.
+pub const INDEX_FILE_NAME: &str = "index.json";
+
+/// The name of the checkpoint file, which records the progress of an export until it completes.
+pub const CHECKPOINT_FILE_NAME: &str = "checkpoint.json";
+
+/// The name of the checkpoint journal file, which holds the chunk files and index entries of each checkpoint.
+pub const CHECKPOINT_JOURNAL_FILE_NAME: &str = "checkpoint.journal";
+
+/// Exports the contents of a ledger as JSON.
+///
+/// Every block, every confirmed transaction, and every committed mapping entry is streamed out into
+/// chunked files (i.e. `blocks-000000.json`), each holding a JSON array of at most `chunk_size` entries.
+/// A `manifest.json` lists the chunk files, so consumers can locate and process them independently,
+/// and an `index.json` maps each transaction ID and transition ID to its entry in the transaction chunk files.
+///
+/// After every `chunk_size` blocks, a `checkpoint.json` records the last block exported, and the chunk files completed
+/// and the index entries added since the previous checkpoint are appended to a `checkpoint.journal`, with the state
+/// of the current chunk files, so an interrupted export can resume from it, instead of starting over.
+/// The checkpoint and its journal are removed once the export completes.
+pub struct LedgerExport<N: Network, C: ConsensusStorage<N>> {
+    /// The ledger storage.
+    store: ConsensusStore<N, C>,
+    /// The maximum number of entries in each chunk file.
+    chunk_size: usize,
+    /// The callback receiving the number of blocks exported, if any.
+    progress: Option<Box<ProgressCallback>>,
+    /// Whether the export resumes from the checkpoint in the output directory, if there is one.
+    resume: bool,
+}
+
+impl<N: Network, C: ConsensusStorage<N>> LedgerExport<N, C> {
+    /// Initializes a new exporter for the given ledger storage.
+    pub fn new(store: ConsensusStore<N, C>, chunk_size: usize) -> Result<Self> {
+        ensure!(chunk_size > 0, "The chunk size must be greater than zero");
+        Ok(Self { store, chunk_size, progress: None, resume: false })
+    }
+
+    /// Opens the ledger storage for the given (optional) development ID, and initializes a new exporter for it.
+    pub fn open(dev: Option<u16>, chunk_size: usize) -> Result<Self> {
+        Self::new(ConsensusStore::open(dev)?, chunk_size)
+    }
+
+    /// Sets the callback receiving the number of blocks exported, after each block.
+    pub fn with_progress<F: Fn(Progress) + Send + Sync + 'static>(mut self, progress: F) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Sets whether the export resumes from the checkpoint in the output directory, if there is one,
+    /// rather than starting over.
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Exports the ledger into the given directory, returning the manifest.
+    pub fn export(&self, output: &Path) -> Result<Value> {
+        let _span = info_span!("ledger_export", output = %output.display()).entered();
+        std::fs::create_dir_all(output)?;
+
+        let block_store = self.store.block_store();
+        let latest_height = block_store.heights().map(|height| *height).max();
+        let num_blocks = latest_height.map_or(0, |latest| latest as usize + 1);
+
+        let (checkpoint_path, journal_path) =
+            (output.join(CHECKPOINT_FILE_NAME), output.join(CHECKPOINT_JOURNAL_FILE_NAME));
+        let resume = self.resume && checkpoint_path.is_file();
+        let (mut blocks, mut transactions, mut index, mut journal, start_height) = match resume {
+            true => self.restore_checkpoint(output, &checkpoint_path)?,
+            false => (
+                ChunkWriter::new(output, "blocks", self.chunk_size),
+                ChunkWriter::new(output, "transactions", self.chunk_size),
+                ExportIndex::new(),
+                CheckpointJournal { journal: Journal::create(&journal_path)?, num_transactions: 0, num_transitions: 0 },
+                0,
+            ),
+        };
+        if start_height > 0 {
+            ensure!(start_height as usize <= num_blocks, "The checkpoint is ahead of the ledger");
+            debug!("Resuming the export at height {start_height}");
+        }
+
+        self.for_each_block(start_height, |block| {
+            let height = block.height();
+            Self::export_block(block, &mut blocks, &mut transactions, &mut index)?;
+            if (height as usize + 1) % self.chunk_size == 0 {
+                self.save_checkpoint(&checkpoint_path, height, &mut blocks, &mut transactions, &index, &mut journal)?;
+            }
+            Ok(())
+        })?;
+        debug!("Exported {num_blocks} blocks");
+
+        let mut mappings = ChunkWriter::new(output, "mappings", self.chunk_size);
+        self.for_each_mapping_entry(|j_entry| mappings.push(&j_entry, None).map(|_| ()))?;
+
+        let manifest = with_version(json!({
+            "type": "LedgerExport",
+            "network": N::ID,
+            "latest_height": latest_height,
+            "chunk_size": self.chunk_size,
+            "blocks": blocks.finish()?,
+            "transactions": transactions.finish()?,
+            "mappings": mappings.finish()?,
+            "index": INDEX_FILE_NAME,
+        }));
+        std::fs::write(output.join(INDEX_FILE_NAME), serde_json::to_string(&index.to_json())?)?;
+        std::fs::write(output.join(MANIFEST_FILE_NAME), serde_json::to_string_pretty(&manifest)?)?;
+        for path in [&checkpoint_path, &journal_path] {
+            if path.is_file() {
+                std::fs::remove_file(path)?;
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Exports the ledger to the given sink, instead of chunk files, returning the number of documents written.
+    ///
+    /// Each document is an entry of the chunk files, under `entry`, with the name of its section (`blocks`,
+    /// `transactions`, or `mappings`) under `section`, in the order of the chunk files. The sink has no manifest,
+    /// index, or checkpoint, so the export cannot be resumed.
+    pub fn export_to<S: OutputSink>(&self, sink: &mut S) -> Result<usize> {
+        let _span = info_span!("ledger_export_to_sink").entered();
+        let mut num_documents = 0;
+        let mut write = |section: &str, entry: Value| -> Result<()> {
+            sink.write_document(&json!({ "section": section, "entry": entry }))?;
+            num_documents += 1;
+            Ok(())
+        };
+
+        self.for_each_block(0, |block| {
+            write("blocks", block.to_json())?;
+            for transaction in block.transactions().iter() {
+                write("transactions", transaction_entry(block, transaction))?;
+            }
+            Ok(())
+        })?;
+        self.for_each_mapping_entry(|j_entry| write("mappings", j_entry))?;
+
+        sink.flush()?;
+        Ok(num_documents)
+    }
+
+    /// Passes each block of the ledger, from the given height, to the given callback, in order,
+    /// reporting the number of blocks exported after each.
+    fn for_each_block(&self, start_height: u32, mut callback: impl FnMut(&Block<N>) -> Result<()>) -> Result<()> {
+        let block_store = self.store.block_store();
+        let latest_height = block_store.heights().map(|height| *height).max();
+        let num_blocks = latest_height.map_or(0, |latest| latest as usize + 1);
+
+        for height in latest_height.map(|latest| start_height..=latest).into_iter().flatten() {
+            let block = match block_store.get_block_hash(height)? {
+                Some(hash) => block_store.get_block(&hash)?,
+                None => None,
+            };
+            let block = match block {
+                Some(block) => block,
+                None => bail!("The ledger is missing the block at height {height}"),
+            };
+
+            callback(&block)?;
+
+            if let Some(progress) = &self.progress {
+                progress(Progress { completed: height as usize + 1, total: num_blocks });
+            }
+        }
+        Ok(())
+    }
+
+    /// Passes each committed mapping entry of the ledger to the given callback, in order.
+    ///
+    /// The programs with committed state are `credits.aleo`, and every deployed program.
+    /// The storage iterates in an order that differs between backends, so the deployed programs are sorted
+    /// by ID, and the entries of each mapping by key, for the export to be reproducible. The mapping names
+    /// are stored in their declaration order, and are emitted as-is.
+    fn for_each_mapping_entry(&self, mut callback: impl FnMut(Value) -> Result<()>) -> Result<()> {
+        let mut deployed_ids: Vec<_> = self.store.transaction_store().program_ids().map(|id| *id).collect();
+        deployed_ids.sort_by_cached_key(|program_id| program_id.to_string());
+        let mut program_ids = IndexSet::new();
+        program_ids.insert(ProgramID::<N>::from_str("credits.aleo")?);
+        program_ids.extend(deployed_ids);
+
+        let finalize_store = self.store.finalize_store();
+        for program_id in program_ids {
+            for mapping_name in finalize_store.get_mapping_names_confirmed(&program_id)?.unwrap_or_default() {
+                let mut entries: Vec<_> = finalize_store
+                    .get_mapping_confirmed(program_id, mapping_name)?
+                    .into_iter()
+                    .map(|(key, value)| (format!("{}", key), format!("{}", value)))
+                    .collect();
+                entries.sort();
+                for (key, value) in entries {
+                    callback(json!({
+                        "program_id": program_id.to_json(),
+                        "mapping": mapping_name.to_json(),
+                        "key": key,
+                        "value": value,
+                    }))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the given block, and each of its transactions, to the chunk files,
+    /// recording the transactions in the index.
+    fn export_block(
+        block: &Block<N>,
+        blocks: &mut ChunkWriter,
+        transactions: &mut ChunkWriter,
+        index: &mut ExportIndex,
+    ) -> Result<()> {
+        let height = block.height();
+        blocks.push(&block.to_json(), Some(height))?;
+        for transaction in block.transactions().iter() {
+            let (file, offset) = transactions.push(&transaction_entry(block, transaction), Some(height))?;
+            index.insert(transaction, &IndexLocation { block_height: height, file: Some(file), offset });
+        }
+        Ok(())
+    }
+
+    /// Saves a checkpoint of the export, once the block at the given height is written,
+    /// appending the chunk files and index entries added since the previous checkpoint to the journal.
+    fn save_checkpoint(
+        &self,
+        path: &Path,
+        height: u32,
+        blocks: &mut ChunkWriter,
+        transactions: &mut ChunkWriter,
+        index: &ExportIndex,
+        journal: &mut CheckpointJournal,
+    ) -> Result<()> {
+        let journal_length = journal.journal.append(&json!({
+            "last_height": height,
+            "blocks": blocks.checkpoint()?,
+            "transactions": transactions.checkpoint()?,
+            "index": index.delta_json(journal.num_transactions, journal.num_transitions),
+        }))?;
+        (journal.num_transactions, journal.num_transitions) = (index.num_transactions(), index.num_transitions());
+
+        let checkpoint = with_version(json!({
+            "type": "LedgerExportCheckpoint",
+            "chunk_size": self.chunk_size,
+            "last_height": height,
+            "journal_length": journal_length,
+        }));
+        write_atomically(path, serde_json::to_string(&checkpoint)?.as_bytes())?;
+        debug!("Saved a checkpoint at height {height}");
+        Ok(())
+    }
+
+    /// Restores the chunk writers, the index, and the journal from the checkpoint at the given path,
+    /// returning them with the height to resume the export at.
+    fn restore_checkpoint(
+        &self,
+        output: &Path,
+        path: &Path,
+    ) -> Result<(ChunkWriter, ChunkWriter, ExportIndex, CheckpointJournal, u32)> {
+        let checkpoint: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        ensure!(
+            checkpoint["chunk_size"].as_u64() == Some(self.chunk_size as u64),
+            "The checkpoint was saved with a different chunk size"
+        );
+        let last_height = checkpoint["last_height"].as_u64().ok_or_else(|| anyhow!("Expected the last height"))?;
+        let journal_length =
+            checkpoint["journal_length"].as_u64().ok_or_else(|| anyhow!("Expected the length of the journal"))?;
+
+        let (journal, entries) = Journal::restore(&output.join(CHECKPOINT_JOURNAL_FILE_NAME), journal_length)?;
+        let mut index = ExportIndex::new();
+        for entry in &entries {
+            index.extend_from_json(&entry["index"])?;
+        }
+        let journal = CheckpointJournal {
+            journal,
+            num_transactions: index.num_transactions(),
+            num_transitions: index.num_transitions(),
+        };
+        let states = |section: &str| entries.iter().map(|entry| &entry[section]).collect::<Vec<_>>();
+        Ok((
+            ChunkWriter::restore(output, "blocks", self.chunk_size, &states("blocks"))?,
+            ChunkWriter::restore(output, "transactions", self.chunk_size, &states("transactions"))?,
+            index,
+            journal,
+            u32::try_from(last_height + 1)?,
+        ))
+    }
+}
+
+/// Returns the entry of the given transaction of the given block, as written to the transaction chunk files.
+fn transaction_entry<N: Network>(block: &Block<N>, transaction: &ConfirmedTransaction<N>) -> Value {
+    json!({
+        "block_height": block.height(),
+        "block_hash": format!("{}", block.hash()),
+        "transaction": transaction.to_json(),
+    })
+}
+
+/// The journal of an export's checkpoints, with the number of indexed transactions and transitions it holds.
+struct CheckpointJournal {
+    /// The journal file.
+    journal: Journal,
+    /// The number of indexed transactions in the journal.
+    num_transactions: usize,
+    /// The number of indexed transitions in the journal.
+    num_transitions: usize,
+}
+
+/// Streams JSON entries into a sequence of chunk files, each holding a JSON array.
+struct ChunkWriter {
+    /// The directory the chunk files are written to.
+    directory: PathBuf,
+    /// The prefix of the chunk file names.
+    prefix: &'static str,
+    /// The maximum number of entries in each chunk file.
+    chunk_size: usize,
+    /// The chunk file currently being written.
+    current: Option<Chunk>,
+    /// The manifest entries of the completed chunk files.
+    chunks: Vec<Value>,
+    /// The number of completed chunk files returned by the previous checkpoints.
+    num_saved: usize,
+}
+
+/// A chunk file being written.
+struct Chunk {
+    /// The writer for the chunk file.
+    writer: BufWriter<File>,
+    /// The number of bytes written so far.
+    length: u64,
+    /// The number of entries written so far.
+    count: usize,
+    /// The range of block heights of the entries written so far.
+    heights: Option<(u32, u32)>,
+}
+
+impl ChunkWriter {
+    /// Initializes a new chunk writer.
+    fn new(directory: &Path, prefix: &'static str, chunk_size: usize) -> Self {
+        Self { directory: directory.to_path_buf(), prefix, chunk_size, current: None, chunks: Vec::new(), num_saved: 0 }
+    }
+
+    /// Restores a chunk writer to the states of the given checkpoints, as returned by `checkpoint`, in order,
+    /// truncating the current chunk file to the entries the last state covers.
+    fn restore(directory: &Path, prefix: &'static str, chunk_size: usize, states: &[&Value]) -> Result<Self> {
+        let mut writer = Self::new(directory, prefix, chunk_size);
+        for state in states {
+            let chunks = state["chunks"].as_array().ok_or_else(|| anyhow!("Expected the '{prefix}' chunks"))?;
+            writer.chunks.extend(chunks.iter().cloned());
+        }
+        writer.num_saved = writer.chunks.len();
+
+        if let Some(current) = states.last().map(|state| &state["current"]).filter(|current| !current.is_null()) {
+            let number =
+                |value: &Value| value.as_u64().ok_or_else(|| anyhow!("Expected a number in the '{prefix}' state"));
+            let length = number(&current["length"])?;
+            let heights = match current["heights"].as_array() {
+                Some(heights) if heights.len() == 2 => {
+                    Some((u32::try_from(number(&heights[0])?)?, u32::try_from(number(&heights[1])?)?))
+                }
+                _ => None,
+            };
+
+            let mut file =
+                OpenOptions::new().write(true).open(directory.join(writer.file_name(writer.chunks.len())))?;
+            ensure!(
+                file.metadata()?.len() >= length,
+                "The current '{prefix}' chunk file is shorter than its checkpoint"
+            );
+            file.set_len(length)?;
+            file.seek(SeekFrom::End(0))?;
+            let count = number(&current["count"])? as usize;
+            writer.current = Some(Chunk { writer: BufWriter::new(file), length, count, heights });
+        }
+        Ok(writer)
+    }
+
+    /// Returns the state of the chunk writer, with the chunk files completed since the previous checkpoint,
+    /// flushing the current chunk file, so it holds every entry written.
+    fn checkpoint(&mut self) -> Result<Value> {
+        let current = match &mut self.current {
+            Some(chunk) => {
+                chunk.writer.flush()?;
+                json!({
+                    "length": chunk.length,
+                    "count": chunk.count,
+                    "heights": chunk.heights.map(|(first, last)| [first, last]),
+                })
+            }
+            None => Value::Null,
+        };
+        let chunks = &self.chunks[self.num_saved..];
+        let state = json!({ "chunks": chunks, "current": current });
+        self.num_saved = self.chunks.len();
+        Ok(state)
+    }
+
+    /// Returns the name of the chunk file with the given index.
+    fn file_name(&self, index: usize) -> String {
+        format!("{}-{index:06}.json", self.prefix)
+    }
+
+    /// Appends the given entry, associated with the given (optional) block height,
+    /// returning the chunk file it was written to and its byte offset in that file.
+    fn push(&mut self, entry: &Value, height: Option<u32>) -> Result<(String, u64)> {
+        let file_name = self.file_name(self.chunks.len());
+        let mut chunk = match self.current.take() {
+            Some(chunk) => chunk,
+            None => {
+                let mut writer = BufWriter::new(File::create(self.directory.join(&file_name))?);
+                writer.write_all(b"[")?;
+                Chunk { writer, length: 1, count: 0, heights: None }
+            }
+        };
+
+        let separator: &[u8] = if chunk.count > 0 { b",\n" } else { b"\n" };
+        chunk.writer.write_all(separator)?;
+        let offset = chunk.length + separator.len() as u64;
+
+        let bytes = serde_json::to_vec(entry)?;
+        chunk.writer.write_all(&bytes)?;
+        chunk.length = offset + bytes.len() as u64;
+        chunk.count += 1;
+        if let Some(height) = height {
+            chunk.heights = Some(chunk.heights.map_or((height, height), |(first, _)| (first, height)));
+        }
+
+        match chunk.count >= self.chunk_size {
+            true => self.complete(chunk)?,
+            false => self.current = Some(chunk),
+        }
+        Ok((file_name, offset))
+    }
+
+    /// Completes the given chunk file, and records it in the manifest.
+    fn complete(&mut self, mut chunk: Chunk) -> Result<()> {
+        chunk.writer.write_all(b"\n]\n")?;
+        chunk.writer.flush()?;
+
+        let mut j_chunk = json!({ "file": self.file_name(self.chunks.len()), "count": chunk.count });
+        if let Some((first, last)) = chunk.heights {
+            j_chunk["first_height"] = json!(first);
+            j_chunk["last_height"] = json!(last);
+        }
+        self.chunks.push(j_chunk);
+        Ok(())
+    }
+
+    /// Completes the last chunk file, if any, returning the manifest entries of every chunk file.
+    fn finish(mut self) -> Result<Vec<Value>> {
+        if let Some(chunk) = self.current.take() {
+            self.complete(chunk)?;
+        }
+        Ok(self.chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        console::network::prelude::FromBytes,
+        ledger::{block::Block, store::helpers::memory::ConsensusMemory},
+        prelude::Testnet3,
+        synthesizer::VM,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_export_genesis() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap();
+        store.block_store().insert(&genesis).unwrap();
+
+        let directory = tempfile::tempdir().unwrap();
+        let reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let exporter =
+            LedgerExport::new(store, 2).unwrap().with_progress(move |progress| sink.lock().unwrap().push(progress));
+        let manifest = exporter.export(directory.path()).unwrap();
+        assert_eq!(manifest["latest_height"], 0);
+        assert_eq!(*reports.lock().unwrap(), vec![Progress { completed: 1, total: 1 }]);
+        assert_eq!(
+            manifest["blocks"],
+            json!([{ "file": "blocks-000000.json", "count": 1, "first_height": 0, "last_height": 0 }])
+        );
+
+        // Ensure the transactions are chunked.
+        let num_transactions = genesis.transactions().len();
+        let chunks = manifest["transactions"].as_array().unwrap();
+        assert_eq!(chunks.len(), (num_transactions + 1) / 2);
+        assert_eq!(
+            chunks.iter().map(|chunk| chunk["count"].as_u64().unwrap() as usize).sum::<usize>(),
+            num_transactions
+        );
+
+        // Ensure the chunk files and the manifest are valid JSON.
+        let blocks: Vec<Value> =
+            serde_json::from_str(&std::fs::read_to_string(directory.path().join("blocks-000000.json")).unwrap())
+                .unwrap();
+        assert_eq!(blocks, vec![genesis.to_json()]);
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(directory.path().join(MANIFEST_FILE_NAME)).unwrap()).unwrap();
+        assert_eq!(written, manifest);
+
+        // Ensure the index locates each transaction in the transaction chunk files.
+        let index: Value =
+            serde_json::from_str(&std::fs::read_to_string(directory.path().join(INDEX_FILE_NAME)).unwrap()).unwrap();
+        for transaction in genesis.transactions().iter() {
+            let location = &index["transactions"][format!("{}", transaction.id())];
+            let contents = std::fs::read(directory.path().join(location["file"].as_str().unwrap())).unwrap();
+            let offset = location["offset"].as_u64().unwrap() as usize;
+            // Each entry is written on its own line.
+            let length = contents[offset..].iter().position(|byte| *byte == b'\n').unwrap();
+            let entry: Value = serde_json::from_slice(&contents[offset..offset + length]).unwrap();
+            assert_eq!(entry["transaction"], transaction.to_json());
+        }
+    }
+
+    #[test]
+    fn test_export_mappings_sorted() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap();
+        VM::from(store.clone()).unwrap().add_next_block(&genesis).unwrap();
+
+        let directory = tempfile::tempdir().unwrap();
+        LedgerExport::new(store, 1000).unwrap().export(directory.path()).unwrap();
+        let entries: Vec<Value> =
+            serde_json::from_str(&std::fs::read_to_string(directory.path().join("mappings-000000.json")).unwrap())
+                .unwrap();
+        assert!(!entries.is_empty());
+
+        // Ensure the entries of each mapping are sorted by key, regardless of the storage order.
+        for pair in entries.windows(2) {
+            if pair[0]["program_id"] == pair[1]["program_id"] && pair[0]["mapping"] == pair[1]["mapping"] {
+                assert!(pair[0]["key"].as_str().unwrap() < pair[1]["key"].as_str().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_export_to_sink() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap();
+        VM::from(store.clone()).unwrap().add_next_block(&genesis).unwrap();
+        let exporter = LedgerExport::new(store, 2).unwrap();
+
+        // The sink receives the entries of the chunk files, in order.
+        let directory = tempfile::tempdir().unwrap();
+        exporter.export(directory.path()).unwrap();
+        let mut documents = Vec::<Value>::new();
+        let num_documents = exporter.export_to(&mut documents).unwrap();
+        assert_eq!(num_documents, documents.len());
+
+        let section = |name: &str| -> Vec<Value> {
+            let documents = documents.iter().filter(|document| document["section"] == name);
+            documents.map(|document| document["entry"].clone()).collect()
+        };
+        assert_eq!(section("blocks"), vec![genesis.to_json()]);
+        assert_eq!(section("transactions").len(), genesis.transactions().len());
+        let mappings: Vec<Value> =
+            serde_json::from_str(&std::fs::read_to_string(directory.path().join("mappings-000000.json")).unwrap())
+                .unwrap();
+        assert_eq!(section("mappings")[..mappings.len()], mappings);
+    }
+
+    #[test]
+    fn test_resume_export() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let open_store = || {
+            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap();
+            store.block_store().insert(&genesis).unwrap();
+            store
+        };
+
+        // Export the ledger without interruption.
+        let expected = tempfile::tempdir().unwrap();
+        let expected_manifest = LedgerExport::new(open_store(), 2).unwrap().export(expected.path()).unwrap();
+
+        // Save a checkpoint once the genesis block is written, then write a partial entry, as if interrupted.
+        let directory = tempfile::tempdir().unwrap();
+        let exporter = LedgerExport::new(open_store(), 2).unwrap();
+        let mut blocks = ChunkWriter::new(directory.path(), "blocks", 2);
+        let mut transactions = ChunkWriter::new(directory.path(), "transactions", 2);
+        let mut index = ExportIndex::new();
+        LedgerExport::<CurrentNetwork, ConsensusMemory<_>>::export_block(
+            &genesis,
+            &mut blocks,
+            &mut transactions,
+            &mut index,
+        )
+        .unwrap();
+        let checkpoint_path = directory.path().join(CHECKPOINT_FILE_NAME);
+        let journal_path = directory.path().join(CHECKPOINT_JOURNAL_FILE_NAME);
+        let mut journal = CheckpointJournal {
+            journal: Journal::create(&journal_path).unwrap(),
+            num_transactions: 0,
+            num_transitions: 0,
+        };
+        exporter.save_checkpoint(&checkpoint_path, 0, &mut blocks, &mut transactions, &index, &mut journal).unwrap();
+        // The completed chunk files and the index entries are in the journal, not the checkpoint.
+        let checkpoint: Value = serde_json::from_str(&std::fs::read_to_string(&checkpoint_path).unwrap()).unwrap();
+        assert!(checkpoint.get("index").is_none() && checkpoint.get("transactions").is_none());
+        // A second checkpoint holds only what was added since the first.
+        exporter.save_checkpoint(&checkpoint_path, 0, &mut blocks, &mut transactions, &index, &mut journal).unwrap();
+        let entries = Journal::restore(&journal_path, std::fs::metadata(&journal_path).unwrap().len()).unwrap().1;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["transactions"]["chunks"].as_array().unwrap().len(), genesis.transactions().len() / 2);
+        assert_eq!(entries[1]["transactions"]["chunks"], json!([]));
+        assert_eq!(entries[1]["index"]["transactions"], json!({}));
+        blocks.push(&json!({ "partial": true }), Some(1)).unwrap();
+        drop((blocks, transactions));
+
+        // Resume the export, which matches the uninterrupted export.
+        let manifest = exporter.with_resume(true).export(directory.path()).unwrap();
+        assert_eq!(manifest, expected_manifest);
+        for file in ["blocks-000000.json", INDEX_FILE_NAME] {
+            assert_eq!(
+                std::fs::read_to_string(directory.path().join(file)).unwrap(),
+                std::fs::read_to_string(expected.path().join(file)).unwrap()
+            );
+        }
+        assert!(!checkpoint_path.exists() && !journal_path.exists());
+    }
+
+    #[test]
+    fn test_chunk_size() {
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap();
+        assert!(LedgerExport::new(store, 0).is_err());
+    }
+}
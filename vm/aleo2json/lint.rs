@@ -0,0 +1,524 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{program_risks, RiskKind},
+    console::{
+        network::Network,
+        program::{LiteralType, Register, ValueType},
+    },
+    synthesizer::{
+        program::{Command, CommandTrait, InstructionTrait, Operand},
+        Instruction,
+        Program,
+    },
+};
+
+use anyhow::{bail, Error, Result};
+use serde_json::{json, Value};
+use std::{collections::HashSet, fmt, str::FromStr};
+
+/// The severity of a lint finding, from the least to the most severe.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A pattern worth reviewing, which is often intended.
+    #[default]
+    Info,
+    /// A pattern that is likely a mistake.
+    Warning,
+    /// A pattern that breaks a guarantee of the program, i.e. by revealing private data.
+    Error,
+}
+
+impl Severity {
+    /// Returns the name of the severity.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = Error;
+
+    /// Parses a severity from its name.
+    fn from_str(severity: &str) -> Result<Self> {
+        match severity {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            _ => bail!("Unknown severity '{severity}' (expected one of: info, warning, error)"),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    /// Prints the name of the severity.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The program a lint rule checks: the program, and its converted JSON.
+pub struct LintContext<'a, N: Network> {
+    /// The program.
+    pub program: &'a Program<N>,
+    /// The JSON of the program, as emitted by `Program::to_json`, which the pointers of the matches refer to.
+    pub document: &'a Value,
+}
+
+/// A node of the program JSON matched by a lint rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintMatch {
+    /// The JSON pointer to the node, in the program JSON.
+    pub pointer: String,
+    /// The explanation of the match.
+    pub message: String,
+}
+
+/// A lint rule over programs.
+///
+/// The built-in rules are listed in `Linter::default`. Downstream crates add their own rules by implementing this
+/// trait and registering them with `Linter::with_rule`; their IDs should not collide with the `A2J` prefix.
+pub trait LintRule<N: Network> {
+    /// Returns the stable ID of the rule, i.e. `A2J001`.
+    fn id(&self) -> &'static str;
+
+    /// Returns the name of the rule, i.e. `mapping-write-without-caller-check`.
+    fn name(&self) -> &'static str;
+
+    /// Returns the severity of the findings of the rule.
+    fn severity(&self) -> Severity;
+
+    /// Returns the nodes of the given program matched by the rule, in order of the program.
+    fn check(&self, context: &LintContext<N>) -> Vec<LintMatch>;
+}
+
+/// A finding of a lint rule, referencing the node it concerns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LintFinding {
+    /// The ID of the rule.
+    pub rule_id: String,
+    /// The name of the rule.
+    pub rule: String,
+    /// The severity of the finding.
+    pub severity: Severity,
+    /// The explanation of the finding.
+    pub message: String,
+    /// The JSON pointer to the node, in the program JSON.
+    pub pointer: String,
+    /// The ID of the node, following the scheme of `assign_node_ids`, i.e. `token.aleo#/functions/mint`.
+    pub node_id: String,
+}
+
+impl LintFinding {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "LintFinding",
+            "rule_id": self.rule_id,
+            "rule": self.rule,
+            "severity": self.severity.as_str(),
+            "message": self.message,
+            "pointer": self.pointer,
+            "node_id": self.node_id,
+        })
+    }
+}
+
+/// Checks programs against a set of lint rules.
+pub struct Linter<N: Network> {
+    /// The rules, in order of registration.
+    rules: Vec<Box<dyn LintRule<N>>>,
+}
+
+impl<N: Network> Default for Linter<N> {
+    /// Initializes a linter with the built-in rules.
+    fn default() -> Self {
+        Self::new()
+            .with_rule(MappingWriteWithoutCallerCheck)
+            .with_rule(UncheckedNarrowingCast)
+            .with_rule(PrivateMappingKey)
+    }
+}
+
+impl<N: Network> Linter<N> {
+    /// Initializes a linter without any rule.
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Registers the given rule.
+    pub fn with_rule<R: LintRule<N> + 'static>(mut self, rule: R) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Returns the IDs of the registered rules, in order of registration.
+    pub fn rule_ids(&self) -> Vec<&'static str> {
+        self.rules.iter().map(|rule| rule.id()).collect()
+    }
+
+    /// Returns the findings of every rule on the given program, in order of the rules.
+    pub fn lint(&self, program: &Program<N>) -> Vec<LintFinding> {
+        let document = program.to_json();
+        let context = LintContext { program, document: &document };
+        let mut findings = Vec::new();
+        for rule in &self.rules {
+            for lint_match in rule.check(&context) {
+                findings.push(LintFinding {
+                    rule_id: rule.id().to_string(),
+                    rule: rule.name().to_string(),
+                    severity: rule.severity(),
+                    node_id: format!("{}#{}", program.id(), lint_match.pointer),
+                    message: lint_match.message,
+                    pointer: lint_match.pointer,
+                });
+            }
+        }
+        findings
+    }
+
+    /// Returns the lint report of the given program: the findings of every rule, with their number by severity.
+    pub fn lint_to_json(&self, program: &Program<N>) -> Value {
+        self.report_to_json(program, &self.lint(program))
+    }
+
+    /// Returns the lint report of the given program with the given findings, i.e. a subset of its findings.
+    pub fn report_to_json(&self, program: &Program<N>, findings: &[LintFinding]) -> Value {
+        let count = |severity: Severity| findings.iter().filter(|finding| finding.severity == severity).count();
+        json!({
+            "type": "LintReport",
+            "program": program.id().to_string(),
+            "rules": self.rule_ids(),
+            "counts": {
+                "info": count(Severity::Info),
+                "warning": count(Severity::Warning),
+                "error": count(Severity::Error),
+            },
+            "findings": findings.iter().map(LintFinding::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// `A2J001`: A finalize block writes to a mapping, but its function never references `self.caller` or
+/// `self.signer`, so anyone may trigger the write. The rule does not check how the caller is used.
+pub struct MappingWriteWithoutCallerCheck;
+
+impl<N: Network> LintRule<N> for MappingWriteWithoutCallerCheck {
+    fn id(&self) -> &'static str {
+        "A2J001"
+    }
+
+    fn name(&self) -> &'static str {
+        "mapping-write-without-caller-check"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &LintContext<N>) -> Vec<LintMatch> {
+        let mut matches = Vec::new();
+        for (name, function) in context.program.functions() {
+            let references_caller = function
+                .instructions()
+                .iter()
+                .flat_map(|instruction| instruction.operands())
+                .any(|operand| matches!(operand, Operand::Caller | Operand::Signer));
+            let finalize = match function.finalize_logic() {
+                Some(finalize) if !references_caller => finalize,
+                _ => continue,
+            };
+            for (index, command) in finalize.commands().iter().enumerate() {
+                let mapping_name = match command {
+                    Command::Set(set) => set.mapping_name(),
+                    Command::Remove(remove) => remove.mapping_name(),
+                    _ => continue,
+                };
+                matches.push(LintMatch {
+                    pointer: format!("/functions/{name}/finalize_logic/commands/{index}"),
+                    message: format!(
+                        "'{name}' writes to mapping '{mapping_name}' without referencing 'self.caller' or 'self.signer'"
+                    ),
+                });
+            }
+        }
+        matches
+    }
+}
+
+/// `A2J002`: A `cast.lossy` truncates an integer to a narrower integer type, silently discarding its high bits,
+/// where a `cast` would halt on overflow.
+pub struct UncheckedNarrowingCast;
+
+impl<N: Network> LintRule<N> for UncheckedNarrowingCast {
+    fn id(&self) -> &'static str {
+        "A2J002"
+    }
+
+    fn name(&self) -> &'static str {
+        "unchecked-narrowing-cast"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, context: &LintContext<N>) -> Vec<LintMatch> {
+        let mut matches = Vec::new();
+        for risk in program_risks(context.program).into_iter().filter(|risk| risk.kind == RiskKind::LossyCast) {
+            let (from, to) = match (risk.operand_types.first().copied().flatten(), risk.destination_type) {
+                (Some(from), Some(to)) => (from, to),
+                _ => continue,
+            };
+            if let (Some(from_bits), Some(to_bits)) = (integer_bits(from), integer_bits(to)) {
+                if from_bits > to_bits {
+                    let message = format!("'cast.lossy' truncates {} to {}", from.type_name(), to.type_name());
+                    matches.push(LintMatch { pointer: risk.pointer, message });
+                }
+            }
+        }
+        matches
+    }
+}
+
+/// `A2J003`: A finalize block accesses a mapping with a key derived from a private input (or a record) of its
+/// function. The finalize inputs, and so the key, are public, revealing the private input on chain.
+pub struct PrivateMappingKey;
+
+impl<N: Network> LintRule<N> for PrivateMappingKey {
+    fn id(&self) -> &'static str {
+        "A2J003"
+    }
+
+    fn name(&self) -> &'static str {
+        "private-mapping-key"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &LintContext<N>) -> Vec<LintMatch> {
+        let mut matches = Vec::new();
+        for (name, function) in context.program.functions() {
+            let finalize = match function.finalize_logic() {
+                Some(finalize) => finalize,
+                None => continue,
+            };
+
+            // Track the registers derived from the private inputs, up to the arguments of the finalize block.
+            let mut tainted = HashSet::new();
+            for input in function.inputs() {
+                if let ValueType::Private(_) | ValueType::Record(_) | ValueType::ExternalRecord(_) = input.value_type()
+                {
+                    tainted.insert(base_locator(input.register()));
+                }
+            }
+            let mut tainted_arguments = HashSet::new();
+            for instruction in function.instructions() {
+                let reads_tainted = instruction.operands().iter().any(|operand| is_tainted(&tainted, operand));
+                if reads_tainted && matches!(instruction, Instruction::Async(_)) {
+                    for (position, operand) in instruction.operands().iter().enumerate() {
+                        if is_tainted(&tainted, operand) {
+                            tainted_arguments.insert(position);
+                        }
+                    }
+                }
+                if reads_tainted {
+                    tainted.extend(instruction.destinations().iter().map(base_locator));
+                }
+            }
+
+            let mut tainted = finalize
+                .inputs()
+                .iter()
+                .enumerate()
+                .filter(|(position, _)| tainted_arguments.contains(position))
+                .map(|(_, input)| base_locator(input.register()))
+                .collect::<HashSet<_>>();
+            for (index, command) in finalize.commands().iter().enumerate() {
+                let (opcode, mapping_name, key) = match command {
+                    Command::Instruction(instruction) => {
+                        if instruction.operands().iter().any(|operand| is_tainted(&tainted, operand)) {
+                            tainted.extend(instruction.destinations().iter().map(base_locator));
+                        }
+                        continue;
+                    }
+                    Command::Contains(contains) => ("contains", contains.mapping_name().to_string(), contains.key()),
+                    Command::Get(get) => ("get", get.mapping().to_string(), get.key()),
+                    Command::GetOrUse(get_or_use) => ("get.or_use", get_or_use.mapping().to_string(), get_or_use.key()),
+                    Command::Remove(remove) => ("remove", remove.mapping_name().to_string(), remove.key()),
+                    Command::Set(set) => ("set", set.mapping_name().to_string(), set.key()),
+                    _ => continue,
+                };
+                if is_tainted(&tainted, key) {
+                    matches.push(LintMatch {
+                        pointer: format!("/functions/{name}/finalize_logic/commands/{index}"),
+                        message: format!(
+                            "'{opcode}' on mapping '{mapping_name}' uses a key derived from a private input of '{name}'"
+                        ),
+                    });
+                    tainted.extend(command.destinations().iter().map(base_locator));
+                }
+            }
+        }
+        matches
+    }
+}
+
+/// Returns the number of bits of the given literal type, if it is an integer type.
+fn integer_bits(literal_type: LiteralType) -> Option<u16> {
+    match literal_type {
+        LiteralType::I8 | LiteralType::U8 => Some(8),
+        LiteralType::I16 | LiteralType::U16 => Some(16),
+        LiteralType::I32 | LiteralType::U32 => Some(32),
+        LiteralType::I64 | LiteralType::U64 => Some(64),
+        LiteralType::I128 | LiteralType::U128 => Some(128),
+        _ => None,
+    }
+}
+
+/// Returns the locator of the given register, i.e. `0` for `r0.owner`.
+fn base_locator<N: Network>(register: &Register<N>) -> u64 {
+    match register {
+        Register::Locator(locator) | Register::Access(locator, _) => *locator,
+    }
+}
+
+/// Returns `true` if the given operand reads one of the given registers.
+fn is_tainted<N: Network>(tainted: &HashSet<u64>, operand: &Operand<N>) -> bool {
+    match operand {
+        Operand::Register(register) => tainted.contains(&base_locator(register)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"
+program token.aleo;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+mapping revealed:
+    key as field.public;
+    value as boolean.public;
+
+function mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    cast.lossy r1 into r2 as u8;
+    async mint r0 r1 into r3;
+    output r3 as token.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into account[r0];
+
+function reveal:
+    input r0 as field.private;
+    input r1 as address.public;
+    assert.eq self.caller r1;
+    hash.bhp256 r0 into r2 as field;
+    async reveal r2 into r3;
+    output r3 as token.aleo/reveal.future;
+
+finalize reveal:
+    input r0 as field.public;
+    contains revealed[r0] into r1;
+    set r1 into revealed[r0];";
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(Severity::from_str("warning").unwrap(), Severity::Warning);
+        assert!(Severity::from_str("fatal").is_err());
+        assert!(Severity::Error > Severity::Warning);
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let findings = Linter::default().lint(&program);
+        let summary = findings
+            .iter()
+            .map(|finding| (finding.rule_id.as_str(), finding.severity, finding.pointer.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(summary, [
+            ("A2J001", Severity::Warning, "/functions/mint/finalize_logic/commands/2"),
+            ("A2J002", Severity::Warning, "/functions/mint/instructions/0"),
+            ("A2J003", Severity::Error, "/functions/reveal/finalize_logic/commands/0"),
+            ("A2J003", Severity::Error, "/functions/reveal/finalize_logic/commands/1"),
+        ]);
+        assert_eq!(findings[1].message, "'cast.lossy' truncates u64 to u8");
+        assert_eq!(findings[2].node_id, "token.aleo#/functions/reveal/finalize_logic/commands/0");
+
+        // Each pointer references a node of the program JSON.
+        let document = program.to_json();
+        assert!(findings.iter().all(|finding| document.pointer(&finding.pointer).is_some()));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        /// Flags every function without outputs.
+        struct NoOutputs;
+
+        impl<N: Network> LintRule<N> for NoOutputs {
+            fn id(&self) -> &'static str {
+                "X001"
+            }
+
+            fn name(&self) -> &'static str {
+                "no-outputs"
+            }
+
+            fn severity(&self) -> Severity {
+                Severity::Info
+            }
+
+            fn check(&self, context: &LintContext<N>) -> Vec<LintMatch> {
+                let functions = context.document["functions"].as_object().into_iter().flatten();
+                functions
+                    .filter(|(_, j_function)| {
+                        j_function["outputs"].as_array().map_or(true, |outputs| outputs.is_empty())
+                    })
+                    .map(|(name, _)| LintMatch { pointer: format!("/functions/{name}"), message: String::new() })
+                    .collect()
+            }
+        }
+
+        let program =
+            parse_program::<CurrentNetwork>("program noop.aleo; function noop: input r0 as u8.public;").unwrap();
+        let linter = Linter::new().with_rule(NoOutputs);
+        assert_eq!(linter.rule_ids(), ["X001"]);
+
+        let j_report = linter.lint_to_json(&program);
+        assert_eq!(j_report["counts"]["info"], 1);
+        assert_eq!(j_report["findings"][0]["node_id"], "noop.aleo#/functions/noop");
+    }
+}
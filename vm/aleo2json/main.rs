@@ -12,43 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkvm::{console::network::Testnet3, prelude::Parser, synthesizer::Program};
-type CurrentNetwork = Testnet3;
-
-fn parse(rawp: &str) -> Program<CurrentNetwork> {
-    match Program::<CurrentNetwork>::parse(rawp) {
-        Ok((remain, program)) => {
-            if remain.is_empty() {
-                // println!("Good");
-                program
-            } else {
-                panic!("Parser did not consume all of the string: '{remain}'");
+use snarkvm::aleo2json::{LintFailure, CLI};
+
+use clap::Parser;
+
+fn main() -> anyhow::Result<()> {
+    // Parse the given arguments.
+    let cli = CLI::parse();
+    // Run the CLI.
+    let output = match cli.parse_command() {
+        Ok(output) => output,
+        Err(error) => {
+            // A failed lint still prints its report, so the exit code and the findings are reported separately.
+            if let Some(failure) = error.downcast_ref::<LintFailure>() {
+                println!("{}\n", failure.report);
             }
+            return Err(error);
         }
-        Err(e) => {
-            panic!("Parser error: {}", e);
-        }
+    };
+    if !output.is_empty() {
+        println!("{output}\n");
     }
-}
-
-fn main() {
-    let path = std::env::args().nth(1).expect("No path provided");
-    let path = std::path::Path::new(&path);
-
-    // read
-    // let file_name = path.file_name().unwrap().to_str().unwrap();
-    // let file = std::fs::File::open(&path).expect("Could not open file");
-    let rawp = std::fs::read_to_string(path).expect("Read error");
-
-    // println!("Read:\n{rawp}");
-
-    let program = parse(&rawp);
-    // println!("Program:\n{program}");
-
-    // let j = serde_json::to_string(&program).expect("Json error");
-    // println!("Json:\n{j}");
-
-    let jj = program.to_json();
-    println!("{jj}\n");
-    // println!("Json:\n{jj}");
+    Ok(())
 }
@@ -0,0 +1,192 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{to_hex_le, with_version},
+    console::{
+        algorithms::{Hash, Sha3_256},
+        network::prelude::ToBits,
+    },
+};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Returns the SHA3-256 hash of the given bytes, in hex, prefixed by `sha3-256:`.
+pub fn checksum(bytes: &[u8]) -> Result<String> {
+    Ok(format!("sha3-256:{}", to_hex_le(&Sha3_256::default().hash(&bytes.to_bits_le())?)))
+}
+
+/// An output file of a conversion, with the input it was converted from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The output file, if it is known.
+    pub output: Option<String>,
+    /// The byte offset into the output file at which the converted input begins, if it shares the file.
+    pub offset: Option<u64>,
+    /// The input, i.e. the path of a program, or the hash of a block.
+    pub input: String,
+    /// The checksum of the input.
+    pub checksum: String,
+    /// The height of the block, if the input is a block.
+    pub height: Option<u32>,
+    /// The edition of the program, if the input is a deployed program.
+    pub edition: Option<u16>,
+}
+
+impl ManifestEntry {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "output": self.output,
+            "offset": self.offset,
+            "input": self.input,
+            "checksum": self.checksum,
+            "height": self.height,
+            "edition": self.edition,
+        })
+    }
+
+    /// Reads an entry from its JSON, the inverse of `to_json`.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let input = value["input"].as_str().ok_or_else(|| anyhow!("Expected the input of a manifest entry"))?;
+        let checksum = value["checksum"].as_str().ok_or_else(|| anyhow!("Expected the checksum of '{input}'"))?;
+        Ok(Self {
+            output: value["output"].as_str().map(str::to_string),
+            offset: value["offset"].as_u64(),
+            input: input.to_string(),
+            checksum: checksum.to_string(),
+            height: value["height"].as_u64().map(u32::try_from).transpose()?,
+            edition: value["edition"].as_u64().map(u16::try_from).transpose()?,
+        })
+    }
+}
+
+/// A manifest of the files written by a batch or range conversion, so large exports can be audited,
+/// and resumed by skipping the inputs whose checksums are unchanged.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConversionManifest {
+    /// The entries, in the order the inputs were converted.
+    entries: Vec<ManifestEntry>,
+}
+
+impl ConversionManifest {
+    /// Initializes an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the entries, in the order the inputs were converted.
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    /// Records the given entry.
+    pub fn push(&mut self, entry: ManifestEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Records the given program file, converted into the given output file.
+    pub fn push_file(&mut self, input: &Path, output: &Path) -> Result<()> {
+        self.push(ManifestEntry {
+            output: Some(output.display().to_string()),
+            offset: None,
+            input: input.display().to_string(),
+            checksum: checksum(&std::fs::read(input)?)?,
+            height: None,
+            edition: None,
+        });
+        Ok(())
+    }
+
+    /// Sets the output file of every entry without one, i.e. once the blocks written to a writer are saved.
+    pub fn set_output(&mut self, output: &Path) {
+        for entry in self.entries.iter_mut().filter(|entry| entry.output.is_none()) {
+            entry.output = Some(output.display().to_string());
+        }
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        with_version(json!({
+            "type": "ConversionManifest",
+            "entries": self.entries.iter().map(ManifestEntry::to_json).collect::<Vec<_>>(),
+        }))
+    }
+
+    /// Reads a manifest from its JSON, the inverse of `to_json`.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let entries = value["entries"].as_array().ok_or_else(|| anyhow!("Expected the entries of a manifest"))?;
+        Ok(Self { entries: entries.iter().map(ManifestEntry::from_json).collect::<Result<_>>()? })
+    }
+
+    /// Writes the manifest to the given path.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(&self.to_json())?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::{FORMAT_VERSION, SNARKVM_VERSION};
+
+    #[test]
+    fn test_checksum() {
+        let hash = checksum(b"program foo.aleo;").unwrap();
+        assert!(hash.starts_with("sha3-256:"));
+        assert_eq!(hash.len(), "sha3-256:".len() + 64);
+        assert_eq!(hash, checksum(b"program foo.aleo;").unwrap());
+        assert_ne!(hash, checksum(b"program bar.aleo;").unwrap());
+    }
+
+    #[test]
+    fn test_conversion_manifest() {
+        let directory = tempfile::tempdir().unwrap();
+        let (input, output) = (directory.path().join("foo.aleo"), directory.path().join("foo.json"));
+        std::fs::write(&input, "program foo.aleo;").unwrap();
+
+        let mut manifest = ConversionManifest::new();
+        manifest.push_file(&input, &output).unwrap();
+        manifest.push(ManifestEntry {
+            output: None,
+            offset: Some(0),
+            input: "ab1hash".to_string(),
+            checksum: checksum(b"block").unwrap(),
+            height: Some(7),
+            edition: None,
+        });
+        manifest.set_output(Path::new("blocks.jsonl"));
+
+        let j_manifest = manifest.to_json();
+        assert_eq!(j_manifest["format_version"], FORMAT_VERSION);
+        assert_eq!(j_manifest["snarkvm_version"], SNARKVM_VERSION);
+        assert_eq!(j_manifest["type"], "ConversionManifest");
+
+        let entries = j_manifest["entries"].as_array().unwrap();
+        assert_eq!(entries[0]["output"], output.display().to_string());
+        assert_eq!(entries[0]["checksum"], checksum(b"program foo.aleo;").unwrap());
+        assert!(entries[0]["height"].is_null());
+        assert_eq!(entries[1]["output"], "blocks.jsonl");
+        assert_eq!(entries[1]["height"], 7);
+
+        // Ensure the written manifest is the same.
+        let path = directory.path().join("manifest.json");
+        manifest.write(&path).unwrap();
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(written, j_manifest);
+        assert_eq!(ConversionManifest::from_json(&written).unwrap(), manifest);
+    }
+}
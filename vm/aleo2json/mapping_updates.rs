@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{network::Network, types::Field},
+    ledger::block::Block,
+    synthesizer::program::FinalizeOperation,
+};
+
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+/// Returns the ID of the mapping updated by the given finalize operation.
+pub(crate) fn mapping_id<N: Network>(operation: &FinalizeOperation<N>) -> Field<N> {
+    match operation {
+        FinalizeOperation::InitializeMapping(mapping_id)
+        | FinalizeOperation::InsertKeyValue(mapping_id, ..)
+        | FinalizeOperation::UpdateKeyValue(mapping_id, ..)
+        | FinalizeOperation::RemoveKeyValue(mapping_id, ..)
+        | FinalizeOperation::ReplaceMapping(mapping_id)
+        | FinalizeOperation::RemoveMapping(mapping_id) => *mapping_id,
+    }
+}
+
+/// Returns the finalize operations of every confirmed transaction of the given block, grouped by the ID of the mapping
+/// they update, so the updates of a mapping can be applied without walking every transaction.
+///
+/// The mappings are in order of their first update, and the operations of each mapping in order of execution,
+/// each with the ID of its transaction and its index among the finalize operations of the transaction.
+pub fn mapping_updates_to_json<N: Network>(block: &Block<N>) -> Value {
+    let mut updates = IndexMap::<String, Vec<Value>>::new();
+    for confirmed in block.transactions().iter() {
+        let transaction_id = confirmed.transaction().id().to_string();
+        for (index, operation) in confirmed.finalize_operations().iter().enumerate() {
+            updates.entry(mapping_id(operation).to_string()).or_default().push(json!({
+                "transaction_id": transaction_id,
+                "index": index,
+                "operation": operation.to_json(),
+            }));
+        }
+    }
+    json!(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_mapping_updates_to_json() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let j_updates = mapping_updates_to_json(&block);
+
+        // Every finalize operation is indexed once, under the mapping it updates.
+        let mut num_operations = 0;
+        for confirmed in block.transactions().iter() {
+            for (index, operation) in confirmed.finalize_operations().iter().enumerate() {
+                let j_operations = j_updates[mapping_id(operation).to_string()].as_array().unwrap();
+                let j_operation = json!({
+                    "transaction_id": confirmed.transaction().id().to_string(),
+                    "index": index,
+                    "operation": operation.to_json(),
+                });
+                assert!(j_operations.contains(&j_operation));
+                num_operations += 1;
+            }
+        }
+        let j_mappings = j_updates.as_object().unwrap();
+        assert_eq!(
+            j_mappings.values().map(|j_operations| j_operations.as_array().unwrap().len()).sum::<usize>(),
+            num_operations
+        );
+    }
+}
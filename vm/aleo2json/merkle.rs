@@ -0,0 +1,176 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{
+        collections::merkle_tree::MerklePath,
+        network::{
+            prelude::{ToBits, Zero},
+            Environment,
+            Network,
+        },
+        program::HEADER_DEPTH,
+        types::Field,
+    },
+    ledger::block::{Block, Header, Transaction},
+};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+/// ** Vanguard JSON serialization helper ** ///
+pub fn merkle_path_to_json<E: Environment, const DEPTH: u8>(path: &MerklePath<E, DEPTH>, root: &Field<E>) -> Value {
+    let mut j_siblings = Vec::new();
+    for val in path.siblings() {
+        j_siblings.push(json!(format!("{}", val)));
+    }
+
+    json!({
+        "type": "MerklePath",
+        "root": format!("{}", root),
+        "leaf_index": *path.leaf_index(),
+        "siblings": j_siblings,
+    })
+}
+
+/// Returns the Merkle paths from each transition in the given transaction to the transaction root, keyed by transition ID.
+pub fn transition_paths_to_json<N: Network>(transaction: &Transaction<N>) -> Result<Value> {
+    let root = transaction.to_root()?;
+
+    let mut j_transitions = IndexMap::new();
+    for transition_id in transaction.transition_ids() {
+        let path = transaction.to_path(&transaction.to_leaf(&**transition_id)?)?;
+        j_transitions.insert(format!("{}", transition_id), merkle_path_to_json(&path, &root));
+    }
+
+    Ok(json!(j_transitions))
+}
+
+/// Converts the given block into its block JSON, where each transaction additionally holds `merkle_paths`:
+/// its path to the transactions root of the block, and the path of each of its transitions to its transaction root.
+pub fn block_to_json_with_paths<N: Network>(block: &Block<N>) -> Result<Value> {
+    let mut j_block = block.to_json();
+
+    // Construct the transactions tree once, rather than once per transaction.
+    let tree = block.transactions().to_tree()?;
+    for (index, transaction) in block.transactions().iter().enumerate() {
+        let path = tree.prove(index, &transaction.id().to_bits_le())?;
+        j_block["transactions"][index]["merkle_paths"] = json!({
+            "transactions_path": merkle_path_to_json(&path, tree.root()),
+            "transitions": transition_paths_to_json(transaction.transaction())?,
+        });
+    }
+
+    Ok(j_block)
+}
+
+/// Returns the Merkle tree of the given block header: its leaves, the hashes of each level, and its root.
+///
+/// Leaf `i` is the BHP hash of the `HeaderLeaf` of index `i` and the listed ID. The levels are listed from the root
+/// down to the leaf hashes, and the node at position `j` of a level is the hash of the nodes at positions `2j`
+/// and `2j + 1` of the level below.
+pub fn header_tree_to_json<N: Network>(header: &Header<N>) -> Result<Value> {
+    let tree = header.to_tree()?;
+
+    let ids = [
+        ("previous_state_root", *header.previous_state_root()),
+        ("transactions_root", header.transactions_root()),
+        ("finalize_root", header.finalize_root()),
+        ("ratifications_root", header.ratifications_root()),
+        ("solutions_root", header.solutions_root()),
+        ("subdag_root", header.subdag_root()),
+        ("unused", Field::zero()),
+        ("metadata_hash", header.metadata().to_hash()?),
+    ];
+    let mut j_leaves = Vec::new();
+    for ((index, (name, id)), hash) in ids.iter().enumerate().zip(tree.leaf_hashes()?) {
+        j_leaves.push(json!({
+            "index": index,
+            "name": name,
+            "id": format!("{}", id),
+            "hash": format!("{}", hash),
+        }));
+    }
+
+    // The nodes are stored level by level, from the root, so level `k` holds the `2^k` nodes after the first `2^k - 1`.
+    let mut j_levels = Vec::new();
+    for depth in 0..=HEADER_DEPTH as u32 {
+        let start = (1usize << depth) - 1;
+        let end = (1usize << (depth + 1)) - 1;
+        let j_level = tree.tree()[start..end].iter().map(|node| json!(format!("{}", node))).collect::<Vec<_>>();
+        j_levels.push(json!(j_level));
+    }
+
+    Ok(json!({
+        "type": "HeaderTree",
+        "depth": HEADER_DEPTH,
+        "root": format!("{}", tree.root()),
+        "leaves": j_leaves,
+        "levels": j_levels,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_block_to_json_with_paths() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let j_block = block_to_json_with_paths(&block).unwrap();
+
+        let transactions_root = format!("{}", block.transactions().to_transactions_root().unwrap());
+        for (index, transaction) in block.transactions().iter().enumerate() {
+            let j_paths = &j_block["transactions"][index]["merkle_paths"];
+            assert_eq!(j_paths["transactions_path"]["type"], "MerklePath");
+            assert_eq!(j_paths["transactions_path"]["root"], transactions_root);
+            assert_eq!(j_paths["transactions_path"]["leaf_index"], index);
+
+            // Ensure the exported path matches the one computed by the ledger.
+            let expected = block.transactions().to_path(transaction.id()).unwrap();
+            let siblings: Vec<String> = expected.siblings().iter().map(|sibling| format!("{}", sibling)).collect();
+            assert_eq!(j_paths["transactions_path"]["siblings"], json!(siblings));
+
+            let transaction_root = format!("{}", transaction.transaction().to_root().unwrap());
+            let j_transitions = j_paths["transitions"].as_object().unwrap();
+            assert_eq!(j_transitions.len(), transaction.transaction().transition_ids().count());
+            for j_path in j_transitions.values() {
+                assert_eq!(j_path["root"], transaction_root);
+            }
+        }
+    }
+
+    #[test]
+    fn test_header_tree_to_json() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let j_tree = header_tree_to_json(block.header()).unwrap();
+
+        assert_eq!(j_tree["type"], "HeaderTree");
+        assert_eq!(j_tree["root"], format!("{}", block.header().to_root().unwrap()));
+        assert_eq!(j_tree["leaves"].as_array().unwrap().len(), 8);
+        assert_eq!(j_tree["leaves"][1]["id"], format!("{}", block.header().transactions_root()));
+
+        // The levels double in size down to the leaf hashes, from the root.
+        let j_levels = j_tree["levels"].as_array().unwrap();
+        assert_eq!(j_levels.len(), HEADER_DEPTH as usize + 1);
+        assert_eq!(j_levels[0][0], j_tree["root"]);
+        for (index, j_leaf) in j_tree["leaves"].as_array().unwrap().iter().enumerate() {
+            assert_eq!(j_levels[HEADER_DEPTH as usize][index], j_leaf["hash"]);
+        }
+    }
+}
@@ -0,0 +1,630 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{check_constructs, diff_json, push_token, ConversionError, ConversionMode, FORMAT_VERSION},
+    console::{
+        network::{prelude::FromField, Testnet3},
+        types::{Address, Field},
+    },
+};
+
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+/// The kind of a change made while migrating a document.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MigrationKind {
+    /// A field was renamed, in place.
+    Renamed,
+    /// A field was moved to another location.
+    Moved,
+    /// A node was replaced by a node of another shape.
+    Replaced,
+    /// A field was added, with its value recovered from the document, or `null` if it cannot be recovered.
+    Added,
+}
+
+impl MigrationKind {
+    /// Returns the name of the migration kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Renamed => "renamed",
+            Self::Moved => "moved",
+            Self::Replaced => "replaced",
+            Self::Added => "added",
+        }
+    }
+}
+
+/// A single change made while migrating a document, located by its JSON Pointer in the migrated document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationChange {
+    /// The format version the change upgrades to.
+    pub version: u32,
+    /// The JSON Pointer (RFC 6901) of the changed value.
+    pub pointer: String,
+    /// The kind of change.
+    pub kind: MigrationKind,
+    /// The JSON Pointer of the value before the change, if it was renamed or moved.
+    pub from: Option<String>,
+}
+
+impl MigrationChange {
+    /// Returns the change as JSON.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "version": self.version,
+            "pointer": self.pointer,
+            "kind": self.kind.as_str(),
+            "from": self.from,
+        })
+    }
+}
+
+/// The changes made while upgrading a document from one format version to another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The format version of the original document.
+    pub from_version: u32,
+    /// The format version of the migrated document.
+    pub to_version: u32,
+    /// The changes, in order of application.
+    pub changes: Vec<MigrationChange>,
+}
+
+impl MigrationReport {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_changes = Vec::new();
+        for val in &self.changes {
+            j_changes.push(val.to_json());
+        }
+
+        json!({
+            "type": "MigrationReport",
+            "from_version": self.from_version,
+            "to_version": self.to_version,
+            "changes": j_changes,
+        })
+    }
+}
+
+/// A step upgrading a document from the previous format version to the given one, appending its changes.
+type Migration = fn(&mut Value, &mut Vec<MigrationChange>) -> Result<(), ConversionError>;
+
+/// The migrations, indexed by the format version they upgrade to, starting at version 2.
+///
+/// A migration must be added here whenever `FORMAT_VERSION` is bumped.
+const MIGRATIONS: &[(u32, Migration)] =
+    &[(2, migrate_to_v2), (3, migrate_to_v3), (4, migrate_to_v4), (5, migrate_to_v5), (6, migrate_to_v6)];
+
+/// Upgrades the given document, produced by an earlier format version, to the current format version,
+/// returning the migrated document, stamped with the current format version, and a report of the changes.
+///
+/// The original `snarkvm_version` is kept, as the document is not reconverted.
+pub fn migrate_document(mut document: Value) -> Result<(Value, MigrationReport), ConversionError> {
+    let from_version = match document.get("format_version").and_then(Value::as_u64) {
+        Some(version) => u32::try_from(version).map_err(|_| invalid_version(version))?,
+        None => return Err(ConversionError::Invalid("The document has no 'format_version'".to_string())),
+    };
+    if from_version == 0 || from_version > FORMAT_VERSION {
+        return Err(invalid_version(from_version as u64));
+    }
+
+    let mut changes = Vec::new();
+    for (version, migration) in MIGRATIONS.iter().filter(|(version, _)| *version > from_version) {
+        migration(&mut document, &mut changes)?;
+        document["format_version"] = json!(version);
+    }
+
+    Ok((document, MigrationReport { from_version, to_version: FORMAT_VERSION, changes }))
+}
+
+/// Returns the error for a format version this release cannot migrate from.
+fn invalid_version(version: u64) -> ConversionError {
+    ConversionError::Invalid(format!(
+        "Cannot migrate from format version {version} (expected a version from 1 to {FORMAT_VERSION})"
+    ))
+}
+
+/// Version 2 replaces the instructions and commands without a structured JSON representation
+/// with `"unsupported"` nodes.
+fn migrate_to_v2(document: &mut Value, changes: &mut Vec<MigrationChange>) -> Result<(), ConversionError> {
+    let migrated = check_constructs(document.clone(), ConversionMode::Permissive)?;
+    for diff in diff_json(document, &migrated) {
+        if diff.right.as_ref().and_then(Value::as_str) == Some("unsupported") {
+            if let Some(pointer) = diff.pointer.strip_suffix("/type") {
+                changes.push(MigrationChange {
+                    version: 2,
+                    pointer: pointer.to_string(),
+                    kind: MigrationKind::Replaced,
+                    from: None,
+                });
+            }
+        }
+    }
+    *document = migrated;
+    Ok(())
+}
+
+/// Version 3 adds the base register of each register, under `locator`. The locator of a plain register is its
+/// value; the locator of a register access (i.e. `r0.owner`) was not emitted, so it is `null`.
+/// It also replaces the index of each access (i.e. `"2u32"`) with its typed `U32` literal.
+fn migrate_to_v3(document: &mut Value, changes: &mut Vec<MigrationChange>) -> Result<(), ConversionError> {
+    add_register_locators(&mut String::new(), document, changes);
+    type_access_indices(&mut String::new(), document, changes)
+}
+
+/// Replaces the index of each access in the given value, located at `pointer`, with its typed literal,
+/// appending the changes.
+fn type_access_indices(
+    pointer: &mut String,
+    value: &mut Value,
+    changes: &mut Vec<MigrationChange>,
+) -> Result<(), ConversionError> {
+    match value {
+        Value::Object(object) => {
+            let is_index = object.get("type").and_then(Value::as_str) == Some("Access")
+                && object.get("vtype").and_then(Value::as_str) == Some("Index");
+            if let (true, Some(Value::String(index))) = (is_index, object.get("value")) {
+                let integer =
+                    index.strip_suffix("u32").and_then(|integer| integer.parse::<u32>().ok()).ok_or_else(|| {
+                        ConversionError::Invalid(format!("Invalid access index '{index}' at '{pointer}'"))
+                    })?;
+                let literal = json!({
+                    "type": "Literal",
+                    "vtype": "U32",
+                    "value": { "type": "Integer", "vtype": "u32", "integer": integer.to_string() },
+                });
+                object.insert("value".to_string(), literal);
+                let length = push_token(pointer, "value");
+                changes.push(MigrationChange {
+                    version: 3,
+                    pointer: pointer.clone(),
+                    kind: MigrationKind::Replaced,
+                    from: None,
+                });
+                pointer.truncate(length);
+            }
+            for (key, val) in object.iter_mut() {
+                let length = push_token(pointer, key);
+                type_access_indices(pointer, val, changes)?;
+                pointer.truncate(length);
+            }
+        }
+        Value::Array(array) => {
+            for (index, val) in array.iter_mut().enumerate() {
+                let length = push_token(pointer, &index.to_string());
+                type_access_indices(pointer, val, changes)?;
+                pointer.truncate(length);
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Adds the missing `locator` of each register in the given value, located at `pointer`, appending the changes.
+fn add_register_locators(pointer: &mut String, value: &mut Value, changes: &mut Vec<MigrationChange>) {
+    match value {
+        Value::Object(object) => {
+            if object.get("type").and_then(Value::as_str) == Some("Register") && !object.contains_key("locator") {
+                let locator = match object.get("vtype").and_then(Value::as_str) {
+                    Some("Locator") => object.get("value").cloned().unwrap_or(Value::Null),
+                    _ => Value::Null,
+                };
+                object.insert("locator".to_string(), locator);
+                let length = push_token(pointer, "locator");
+                changes.push(MigrationChange {
+                    version: 3,
+                    pointer: pointer.clone(),
+                    kind: MigrationKind::Added,
+                    from: None,
+                });
+                pointer.truncate(length);
+            }
+            for (key, val) in object.iter_mut() {
+                let length = push_token(pointer, key);
+                add_register_locators(pointer, val, changes);
+                pointer.truncate(length);
+            }
+        }
+        Value::Array(array) => {
+            for (index, val) in array.iter_mut().enumerate() {
+                let length = push_token(pointer, &index.to_string());
+                add_register_locators(pointer, val, changes);
+                pointer.truncate(length);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Version 4 adds the hex form, bit width, and signedness of each integer, under `hex`, `bits`, and `signed`,
+/// all of which are recovered from its decimal string and type.
+fn migrate_to_v4(document: &mut Value, changes: &mut Vec<MigrationChange>) -> Result<(), ConversionError> {
+    add_integer_forms(&mut String::new(), document, changes)
+}
+
+/// Adds the missing hex form, bit width, and signedness of each integer in the given value, located at `pointer`,
+/// appending the changes.
+fn add_integer_forms(
+    pointer: &mut String,
+    value: &mut Value,
+    changes: &mut Vec<MigrationChange>,
+) -> Result<(), ConversionError> {
+    match value {
+        Value::Object(object) => {
+            if object.get("type").and_then(Value::as_str) == Some("Integer") && !object.contains_key("hex") {
+                let vtype = object.get("vtype").and_then(Value::as_str).unwrap_or_default();
+                let decimal = object.get("integer").and_then(Value::as_str).unwrap_or_default();
+                let (hex, bits, signed) = integer_forms(vtype, decimal).ok_or_else(|| {
+                    ConversionError::Invalid(format!("Invalid integer '{decimal}' of type '{vtype}' at '{pointer}'"))
+                })?;
+                for (key, val) in [("hex", json!(hex)), ("bits", json!(bits)), ("signed", json!(signed))] {
+                    object.insert(key.to_string(), val);
+                    let length = push_token(pointer, key);
+                    changes.push(MigrationChange {
+                        version: 4,
+                        pointer: pointer.clone(),
+                        kind: MigrationKind::Added,
+                        from: None,
+                    });
+                    pointer.truncate(length);
+                }
+            }
+            for (key, val) in object.iter_mut() {
+                let length = push_token(pointer, key);
+                add_integer_forms(pointer, val, changes)?;
+                pointer.truncate(length);
+            }
+        }
+        Value::Array(array) => {
+            for (index, val) in array.iter_mut().enumerate() {
+                let length = push_token(pointer, &index.to_string());
+                add_integer_forms(pointer, val, changes)?;
+                pointer.truncate(length);
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Returns the two's complement hex form, bit width, and signedness of the given integer, i.e. `0xff`, 8, and `true`
+/// for `-1` of type `i8`, or `None` if the integer or its type is invalid.
+fn integer_forms(vtype: &str, decimal: &str) -> Option<(String, u32, bool)> {
+    let signed = match vtype.get(..1)? {
+        "i" => true,
+        "u" => false,
+        _ => return None,
+    };
+    let bits = match vtype[1..].parse::<u32>().ok()? {
+        bits @ (8 | 16 | 32 | 64 | 128) => bits,
+        _ => return None,
+    };
+    let mask = u128::MAX >> (128 - bits);
+    let value = match signed {
+        true => {
+            let value = decimal.parse::<i128>().ok()?;
+            if bits < 128 && (value < -(1i128 << (bits - 1)) || value >= 1i128 << (bits - 1)) {
+                return None;
+            }
+            value as u128
+        }
+        false => decimal.parse::<u128>().ok().filter(|value| *value <= mask)?,
+    };
+    Some((format!("0x{:0width$x}", value & mask, width = (bits / 4) as usize), bits, signed))
+}
+
+/// Version 5 adds the bech32 string of each address, under `bech32`, which is recovered from its x-coordinate.
+fn migrate_to_v5(document: &mut Value, changes: &mut Vec<MigrationChange>) -> Result<(), ConversionError> {
+    add_address_strings(&mut String::new(), document, changes)
+}
+
+/// Adds the missing bech32 string of each address in the given value, located at `pointer`, appending the changes.
+fn add_address_strings(
+    pointer: &mut String,
+    value: &mut Value,
+    changes: &mut Vec<MigrationChange>,
+) -> Result<(), ConversionError> {
+    match value {
+        Value::Object(object) => {
+            if object.get("type").and_then(Value::as_str) == Some("Address") && !object.contains_key("bech32") {
+                let x_coordinate = object.get("address").and_then(|group| group["group"].as_str()).unwrap_or_default();
+                let address = Field::<Testnet3>::from_str(&format!("{x_coordinate}field"))
+                    .and_then(|field| Address::<Testnet3>::from_field(&field))
+                    .map_err(|_| {
+                        ConversionError::Invalid(format!(
+                            "Invalid address x-coordinate '{x_coordinate}' at '{pointer}'"
+                        ))
+                    })?;
+                object.insert("bech32".to_string(), json!(address.to_string()));
+                let length = push_token(pointer, "bech32");
+                changes.push(MigrationChange {
+                    version: 5,
+                    pointer: pointer.clone(),
+                    kind: MigrationKind::Added,
+                    from: None,
+                });
+                pointer.truncate(length);
+            }
+            for (key, val) in object.iter_mut() {
+                let length = push_token(pointer, key);
+                add_address_strings(pointer, val, changes)?;
+                pointer.truncate(length);
+            }
+        }
+        Value::Array(array) => {
+            for (index, val) in array.iter_mut().enumerate() {
+                let length = push_token(pointer, &index.to_string());
+                add_address_strings(pointer, val, changes)?;
+                pointer.truncate(length);
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Version 6 numbers each instruction and finalize command by its index, under `index`, and adds the `labels`
+/// of each finalize, which are its `positions` in order of their command index.
+fn migrate_to_v6(document: &mut Value, changes: &mut Vec<MigrationChange>) -> Result<(), ConversionError> {
+    add_instruction_indices(&mut String::new(), document, changes);
+    Ok(())
+}
+
+/// Adds the missing indices of the instructions and commands, and the missing labels, in the given value,
+/// located at `pointer`, appending the changes.
+fn add_instruction_indices(pointer: &mut String, value: &mut Value, changes: &mut Vec<MigrationChange>) {
+    let mut added = |pointer: &mut String, key: &str| {
+        let length = push_token(pointer, key);
+        changes.push(MigrationChange { version: 6, pointer: pointer.clone(), kind: MigrationKind::Added, from: None });
+        pointer.truncate(length);
+    };
+    match value {
+        Value::Object(object) => {
+            let (list, has_labels) = match object.get("type").and_then(Value::as_str) {
+                Some("FunctionCore" | "ClosureCore") => ("instructions", true),
+                Some("FinalizeCore") => ("commands", object.contains_key("labels")),
+                _ => ("", true),
+            };
+            if let Some(Value::Array(array)) = object.get_mut(list) {
+                let length = push_token(pointer, list);
+                for (index, val) in array.iter_mut().enumerate() {
+                    let length = push_token(pointer, &index.to_string());
+                    if let Value::Object(j_node) = val {
+                        if !j_node.contains_key("index") {
+                            j_node.insert("index".to_string(), json!(index));
+                            added(pointer, "index");
+                        }
+                        // The instruction of an instruction command is numbered by the index of its command.
+                        if j_node.get("vtype").and_then(Value::as_str) == Some("Instruction") {
+                            if let Some(Value::Object(j_instruction)) = j_node.get_mut("value") {
+                                if !j_instruction.contains_key("index") {
+                                    j_instruction.insert("index".to_string(), json!(index));
+                                    let length = push_token(pointer, "value");
+                                    added(pointer, "index");
+                                    pointer.truncate(length);
+                                }
+                            }
+                        }
+                    }
+                    pointer.truncate(length);
+                }
+                pointer.truncate(length);
+            }
+            if !has_labels {
+                let mut positions: Vec<(String, u64)> = match object.get("positions") {
+                    Some(Value::Object(positions)) => {
+                        positions.iter().filter_map(|(name, index)| Some((name.clone(), index.as_u64()?))).collect()
+                    }
+                    _ => Vec::new(),
+                };
+                positions.sort_by_key(|(_, index)| *index);
+                let labels = positions.into_iter().map(|(name, index)| (name, json!(index))).collect();
+                object.insert("labels".to_string(), Value::Object(labels));
+                added(pointer, "labels");
+            }
+            for (key, val) in object.iter_mut() {
+                let length = push_token(pointer, key);
+                add_instruction_indices(pointer, val, changes);
+                pointer.truncate(length);
+            }
+        }
+        Value::Array(array) => {
+            for (index, val) in array.iter_mut().enumerate() {
+                let length = push_token(pointer, &index.to_string());
+                add_instruction_indices(pointer, val, changes);
+                pointer.truncate(length);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::program::Literal;
+
+    #[test]
+    fn test_migrate_v1() {
+        let document = json!({
+            "format_version": 1,
+            "snarkvm_version": "0.16.0",
+            "type": "Program",
+            "functions": {
+                "foo": {
+                    "type": "Function",
+                    "instructions": [
+                        { "type": "Instruction", "vtype": "Add", "str": "add r0 r0 into r1;", "value": {} },
+                        { "type": "Instruction", "vtype": "Frobnicate", "str": "frob r1 into r2;", "value": {} },
+                    ],
+                },
+            },
+        });
+
+        let (migrated, report) = migrate_document(document).unwrap();
+        assert_eq!(migrated["format_version"], FORMAT_VERSION);
+        assert_eq!(migrated["snarkvm_version"], "0.16.0");
+        assert_eq!(migrated["functions"]["foo"]["instructions"][0]["vtype"], "Add");
+        assert_eq!(migrated["functions"]["foo"]["instructions"][1]["type"], "unsupported");
+
+        assert_eq!((report.from_version, report.to_version), (1, FORMAT_VERSION));
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].pointer, "/functions/foo/instructions/1");
+        assert_eq!(report.to_json()["changes"][0]["kind"], "replaced");
+    }
+
+    #[test]
+    fn test_migrate_v2() {
+        let document = json!({
+            "format_version": 2,
+            "type": "Function",
+            "inputs": [
+                { "type": "Input", "register": { "type": "Register", "vtype": "Locator", "value": 0 } },
+                {
+                    "type": "Input",
+                    "register": {
+                        "type": "Register",
+                        "vtype": "Access",
+                        "value": [
+                            { "type": "Access", "vtype": "Member", "value": "owner" },
+                            { "type": "Access", "vtype": "Index", "value": "2u32" },
+                        ],
+                    },
+                },
+            ],
+        });
+
+        let (migrated, report) = migrate_document(document).unwrap();
+        assert_eq!(migrated["inputs"][0]["register"]["locator"], 0);
+        assert_eq!(migrated["inputs"][1]["register"]["locator"], Value::Null);
+        // The access index becomes a typed literal, which the later versions complete.
+        let literal = Literal::<Testnet3>::from_str("2u32").unwrap().to_json();
+        assert_eq!(migrated["inputs"][1]["register"]["value"][1]["value"], literal);
+
+        let changes = report.changes.iter().filter(|change| change.version == 3).collect::<Vec<_>>();
+        let pointers = changes.iter().map(|change| change.pointer.as_str()).collect::<Vec<_>>();
+        assert_eq!(pointers, [
+            "/inputs/0/register/locator",
+            "/inputs/1/register/locator",
+            "/inputs/1/register/value/1/value"
+        ]);
+        assert_eq!(changes[2].kind, MigrationKind::Replaced);
+
+        let document = json!({ "format_version": 2, "type": "Access", "vtype": "Index", "value": "2u8" });
+        assert!(migrate_document(document).is_err());
+    }
+
+    #[test]
+    fn test_migrate_v3() {
+        let document = json!({
+            "format_version": 3,
+            "type": "Literal",
+            "vtype": "I8",
+            "value": { "type": "Integer", "vtype": "i8", "integer": "-1" },
+        });
+
+        let (migrated, report) = migrate_document(document).unwrap();
+        assert_eq!(
+            migrated["value"],
+            json!({
+                "type": "Integer",
+                "vtype": "i8",
+                "integer": "-1",
+                "hex": "0xff",
+                "bits": 8,
+                "signed": true,
+            })
+        );
+        assert_eq!(report.changes.len(), 3);
+        assert_eq!(report.changes[0].pointer, "/value/hex");
+
+        assert_eq!(integer_forms("u128", &u128::MAX.to_string()), Some((format!("0x{}", "f".repeat(32)), 128, false)));
+        assert_eq!(integer_forms("i16", "-32768"), Some(("0x8000".to_string(), 16, true)));
+        assert_eq!(integer_forms("u7", "1"), None);
+        assert!(migrate_document(json!({ "format_version": 3, "type": "Integer", "vtype": "u8", "integer": "256" }))
+            .is_err());
+    }
+
+    #[test]
+    fn test_migrate_v4() {
+        use crate::console::network::prelude::{TestRng, Uniform};
+
+        let address = Address::<Testnet3>::rand(&mut TestRng::default());
+        let mut j_address = address.to_json();
+        j_address.as_object_mut().unwrap().remove("bech32");
+        let document = json!({ "format_version": 4, "type": "Literal", "vtype": "Address", "value": j_address });
+
+        let (migrated, report) = migrate_document(document).unwrap();
+        assert_eq!(migrated["value"], address.to_json());
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].pointer, "/value/bech32");
+
+        let document = json!({ "format_version": 4, "type": "Address", "address": { "type": "Group", "group": "x" } });
+        assert!(migrate_document(document).is_err());
+    }
+
+    #[test]
+    fn test_migrate_v5() {
+        let add = json!({ "type": "Instruction", "vtype": "Add", "value": {}, "str": "add r0 r1 into r2;" });
+        let document = json!({
+            "format_version": 5,
+            "type": "FunctionCore",
+            "instructions": [add, add],
+            "finalize_logic": {
+                "type": "FinalizeCore",
+                "commands": [
+                    { "type": "Command", "vtype": "Position", "value": {}, "str": "position end;" },
+                    { "type": "Command", "vtype": "Instruction", "value": add, "str": "add r0 r1 into r2;" },
+                ],
+                "positions": { "end": 0 },
+            },
+        });
+
+        let (migrated, report) = migrate_document(document).unwrap();
+        assert_eq!(migrated["instructions"][1]["index"], 1);
+        assert_eq!(migrated["finalize_logic"]["commands"][1]["index"], 1);
+        assert_eq!(migrated["finalize_logic"]["commands"][1]["value"]["index"], 1);
+        assert_eq!(migrated["finalize_logic"]["labels"], json!({ "end": 0 }));
+
+        let pointers = report.changes.iter().map(|change| change.pointer.as_str()).collect::<Vec<_>>();
+        assert_eq!(pointers, [
+            "/instructions/0/index",
+            "/instructions/1/index",
+            "/finalize_logic/commands/0/index",
+            "/finalize_logic/commands/1/index",
+            "/finalize_logic/commands/1/value/index",
+            "/finalize_logic/labels",
+        ]);
+    }
+
+    #[test]
+    fn test_migrate_current() {
+        let document = json!({ "format_version": FORMAT_VERSION, "type": "Block" });
+        let (migrated, report) = migrate_document(document.clone()).unwrap();
+        assert_eq!(migrated, document);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_invalid() {
+        assert!(migrate_document(json!({ "type": "Block" })).is_err());
+        assert!(migrate_document(json!({ "format_version": 0 })).is_err());
+        assert!(migrate_document(json!({ "format_version": FORMAT_VERSION + 1 })).is_err());
+    }
+}
@@ -12,5 +12,352 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub mod aleo2json;
-pub use aleo2json::*;
\ No newline at end of file
+//! Conversion of snarkVM objects into JSON, sliced by features:
+//! `aleo2json-console` for the JSON utilities (canonicalization, diffing, differential testing, schemas, and validation),
+//! `aleo2json-synthesizer` for programs, `aleo2json-ledger` for blocks and ledger exports,
+//! and `cli` for the command-line interface. `fuzz` adds proptest strategies for fuzzing conversions and the parser.
+
+#[cfg(feature = "aleo2json-ledger")]
+mod abi;
+#[cfg(feature = "aleo2json-ledger")]
+pub use abi::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod addresses;
+#[cfg(feature = "aleo2json-ledger")]
+pub use addresses::*;
+
+mod batch;
+pub use batch::*;
+
+mod cache;
+pub use cache::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod calls;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use calls::*;
+
+mod canonical;
+pub use canonical::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod checkpoint;
+#[cfg(feature = "aleo2json-ledger")]
+pub use checkpoint::*;
+
+#[cfg(feature = "cli")]
+mod cli;
+#[cfg(feature = "cli")]
+pub use cli::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod client;
+#[cfg(feature = "aleo2json-ledger")]
+pub use client::*;
+
+#[cfg(feature = "cli")]
+mod commands;
+#[cfg(feature = "cli")]
+pub use commands::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod convert;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use convert::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod corpus;
+#[cfg(feature = "aleo2json-ledger")]
+pub use corpus::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod datalog;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use datalog::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod decompile;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use decompile::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod dependencies;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use dependencies::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod deployments;
+#[cfg(feature = "aleo2json-ledger")]
+pub use deployments::*;
+
+mod diff;
+pub use diff::*;
+
+mod differential;
+pub use differential::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod economics;
+#[cfg(feature = "aleo2json-ledger")]
+pub use economics::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod encodings;
+#[cfg(feature = "aleo2json-ledger")]
+pub use encodings::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod entry;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use entry::*;
+
+mod error;
+pub use error::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod extract;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use extract::*;
+
+mod fingerprint;
+pub use fingerprint::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod flow;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use flow::*;
+
+#[cfg(feature = "fuzz")]
+mod fuzz;
+#[cfg(feature = "fuzz")]
+pub use fuzz::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod genesis;
+#[cfg(feature = "aleo2json-ledger")]
+pub use genesis::*;
+
+#[cfg(all(test, feature = "aleo2json-ledger"))]
+mod golden;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod grammar;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use grammar::*;
+
+mod ids;
+pub use ids::*;
+
+mod imports;
+pub use imports::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod index;
+#[cfg(feature = "aleo2json-ledger")]
+pub use index::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod interface;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use interface::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod journal;
+#[cfg(feature = "aleo2json-ledger")]
+pub use journal::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod keys;
+#[cfg(feature = "aleo2json-ledger")]
+pub use keys::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod ledger_export;
+#[cfg(feature = "aleo2json-ledger")]
+pub use ledger_export::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod lint;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use lint::*;
+
+mod manifest;
+pub use manifest::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod mapping_updates;
+#[cfg(feature = "aleo2json-ledger")]
+pub use mapping_updates::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod merkle;
+#[cfg(feature = "aleo2json-ledger")]
+pub use merkle::*;
+
+mod migrate;
+pub use migrate::*;
+
+mod naming;
+pub use naming::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod network;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use network::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod node_stream;
+#[cfg(feature = "aleo2json-ledger")]
+pub use node_stream::*;
+
+mod normalize;
+pub use normalize::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod opcodes;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use opcodes::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod operands;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use operands::*;
+
+mod options;
+pub use options::*;
+
+mod progress;
+pub use progress::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod puzzle_rewards;
+#[cfg(feature = "aleo2json-ledger")]
+pub use puzzle_rewards::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod r1cs;
+#[cfg(feature = "aleo2json-ledger")]
+pub use r1cs::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod range;
+#[cfg(feature = "aleo2json-ledger")]
+pub use range::*;
+
+mod raw;
+pub use raw::*;
+
+mod redact;
+pub use redact::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod rejections;
+#[cfg(feature = "aleo2json-ledger")]
+pub use rejections::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod risks;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use risks::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod rpc;
+#[cfg(feature = "aleo2json-ledger")]
+pub use rpc::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod records;
+#[cfg(feature = "aleo2json-ledger")]
+pub use records::*;
+
+mod schema;
+pub use schema::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod server;
+#[cfg(feature = "aleo2json-ledger")]
+pub use server::*;
+
+mod similarity;
+pub use similarity::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod simulate;
+#[cfg(feature = "aleo2json-ledger")]
+pub use simulate::*;
+
+mod sink;
+pub use sink::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod source_map;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use source_map::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod smt;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use smt::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod spans;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use spans::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod staking;
+#[cfg(feature = "aleo2json-ledger")]
+pub use staking::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod state_diff;
+#[cfg(feature = "aleo2json-ledger")]
+pub use state_diff::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod stats;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use stats::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod trace;
+#[cfg(feature = "aleo2json-ledger")]
+pub use trace::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod typed_values;
+#[cfg(feature = "aleo2json-ledger")]
+pub use typed_values::*;
+
+mod validate;
+pub use validate::*;
+
+mod strict;
+pub use strict::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod value_flow;
+#[cfg(feature = "aleo2json-ledger")]
+pub use value_flow::*;
+
+mod vanguard;
+pub use vanguard::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod verification;
+#[cfg(feature = "aleo2json-ledger")]
+pub use verification::*;
+
+mod version;
+pub use version::*;
+
+#[cfg(feature = "aleo2json-synthesizer")]
+mod visibility;
+#[cfg(feature = "aleo2json-synthesizer")]
+pub use visibility::*;
+
+#[cfg(feature = "aleo2json-ledger")]
+mod xrefs;
+#[cfg(feature = "aleo2json-ledger")]
+pub use xrefs::*;
@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Error, Result};
+use serde_json::{Map, Value};
+use std::{fmt, str::FromStr};
+
+/// The properties whose objects are keyed by data (i.e. function names, struct members, or addresses),
+/// rather than by field names, and whose keys are therefore never renamed.
+pub(crate) const KEYED_PROPERTIES: &[&str] = &[
+    "address_activity",
+    "closures",
+    "entries",
+    "functions",
+    "identifiers",
+    "imports",
+    "labels",
+    "mapping_updates",
+    "mappings",
+    "members",
+    "opcodes",
+    "positions",
+    "public_balances",
+    "record_flow",
+    "records",
+    "structs",
+    "transactions",
+    "transitions",
+];
+
+/// The convention for the field names in the JSON output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NamingConvention {
+    /// Field names as emitted by the `to_json()` helpers, i.e. `block_hash`.
+    #[default]
+    SnakeCase,
+    /// Field names in camel case, i.e. `blockHash`.
+    CamelCase,
+}
+
+impl NamingConvention {
+    /// Returns the name of the convention.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::SnakeCase => "snake_case",
+            Self::CamelCase => "camelCase",
+        }
+    }
+
+    /// Returns the given snake case field name in this convention.
+    pub fn apply(&self, key: &str) -> String {
+        match self {
+            Self::SnakeCase => key.to_string(),
+            Self::CamelCase => to_camel_case(key),
+        }
+    }
+}
+
+impl FromStr for NamingConvention {
+    type Err = Error;
+
+    /// Parses a naming convention from its name.
+    fn from_str(convention: &str) -> Result<Self> {
+        match convention {
+            "snake_case" => Ok(Self::SnakeCase),
+            "camelCase" => Ok(Self::CamelCase),
+            _ => bail!("Unknown naming convention '{convention}' (expected one of: snake_case, camelCase)"),
+        }
+    }
+}
+
+impl fmt::Display for NamingConvention {
+    /// Prints the name of the naming convention.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The names of the keys in the JSON output.
+///
+/// Note: A renamed document no longer matches the aleo2json JSON Schema, which describes the default names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyNaming {
+    /// The convention for the field names.
+    pub convention: NamingConvention,
+    /// The name of the `type` key of the envelope.
+    pub type_key: String,
+    /// The name of the `vtype` key of the envelope.
+    pub vtype_key: String,
+    /// The name of the `value` key of the envelope.
+    pub value_key: String,
+}
+
+impl Default for KeyNaming {
+    /// Returns the names emitted by the `to_json()` helpers.
+    fn default() -> Self {
+        Self::new(NamingConvention::default())
+    }
+}
+
+impl KeyNaming {
+    /// Initializes the key names for the given convention, with the default envelope keys.
+    pub fn new(convention: NamingConvention) -> Self {
+        Self {
+            convention,
+            type_key: "type".to_string(),
+            vtype_key: "vtype".to_string(),
+            value_key: "value".to_string(),
+        }
+    }
+
+    /// Returns `true` if every key keeps its default name.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the new name of the given key.
+    pub fn rename(&self, key: &str) -> String {
+        match key {
+            "type" => self.type_key.clone(),
+            "vtype" => self.vtype_key.clone(),
+            "value" => self.value_key.clone(),
+            _ => self.convention.apply(key),
+        }
+    }
+}
+
+/// Returns the given snake case field name in camel case, i.e. `last_coinbase_target` becomes `lastCoinbaseTarget`.
+pub fn to_camel_case(key: &str) -> String {
+    let mut output = String::with_capacity(key.len());
+    let mut uppercase = false;
+    for character in key.chars() {
+        match character {
+            // A leading underscore is kept, as it is not a word separator.
+            '_' if !output.is_empty() => uppercase = true,
+            _ if uppercase => {
+                output.extend(character.to_uppercase());
+                uppercase = false;
+            }
+            _ => output.push(character),
+        }
+    }
+    output
+}
+
+/// Renames the keys of the given document with the given key names.
+///
+/// The keys of objects keyed by data, such as the functions of a program or the members of a struct,
+/// are kept as-is, while the values they hold are renamed.
+pub fn rename_keys(document: Value, naming: &KeyNaming) -> Value {
+    match naming.is_default() {
+        true => document,
+        false => rename_value(document, naming, false),
+    }
+}
+
+/// Renames the keys of the given value, and of its children, unless the value is keyed by data.
+fn rename_value(value: Value, naming: &KeyNaming, is_keyed: bool) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut renamed = Map::with_capacity(object.len());
+            for (key, val) in object {
+                let val = rename_value(val, naming, !is_keyed && KEYED_PROPERTIES.contains(&key.as_str()));
+                match is_keyed {
+                    true => renamed.insert(key, val),
+                    false => renamed.insert(naming.rename(&key), val),
+                };
+            }
+            Value::Object(renamed)
+        }
+        Value::Array(array) => Value::Array(array.into_iter().map(|val| rename_value(val, naming, false)).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("type"), "type");
+        assert_eq!(to_camel_case("block_hash"), "blockHash");
+        assert_eq!(to_camel_case("last_coinbase_target"), "lastCoinbaseTarget");
+        assert_eq!(to_camel_case("_private"), "_private");
+    }
+
+    #[test]
+    fn test_rename_keys() {
+        let document = json!({
+            "type": "Program",
+            "format_version": 2,
+            "functions": {
+                "transfer_public": { "type": "Function", "input_types": [{ "value_type": "u64" }] },
+            },
+            "value": { "public_balances": { "aleo1abc": 5 } },
+        });
+
+        // The default names leave the document unchanged.
+        assert_eq!(rename_keys(document.clone(), &KeyNaming::default()), document);
+
+        let naming = KeyNaming { type_key: "kind".to_string(), ..KeyNaming::new(NamingConvention::CamelCase) };
+        let expected = json!({
+            "kind": "Program",
+            "formatVersion": 2,
+            "functions": {
+                "transfer_public": { "kind": "Function", "inputTypes": [{ "valueType": "u64" }] },
+            },
+            "value": { "publicBalances": { "aleo1abc": 5 } },
+        });
+        assert_eq!(rename_keys(document, &naming), expected);
+    }
+
+    #[test]
+    fn test_naming_convention_from_str() {
+        for convention in [NamingConvention::SnakeCase, NamingConvention::CamelCase] {
+            assert_eq!(NamingConvention::from_str(convention.as_str()).unwrap(), convention);
+        }
+        assert!(NamingConvention::from_str("kebab-case").is_err());
+    }
+}
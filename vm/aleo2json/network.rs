@@ -0,0 +1,171 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "aleo2json-ledger")]
+use crate::aleo2json::{convert_block_bytes, convert_block_json, convert_transaction_bytes, convert_transaction_json};
+use crate::{
+    aleo2json::{convert_program_file, convert_program_with_options, ConversionError, ConversionMode, JsonOptions},
+    console::network::Network,
+    prelude::Testnet3,
+};
+
+use anyhow::{bail, Error, Result};
+use serde_json::Value;
+use std::{fmt, path::Path, str::FromStr};
+
+/// Runs the given expression with `$N` bound to the network type of the given network ID.
+macro_rules! dispatch {
+    ($network:expr, $N:ident => $body:expr) => {
+        match $network {
+            NetworkId::Testnet3 => {
+                type $N = Testnet3;
+                $body
+            }
+        }
+    };
+}
+
+/// A network, selected at runtime, so callers supporting several networks can share their code across them.
+///
+/// Each conversion dispatches to the monomorphization of the generic function for the selected network.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NetworkId {
+    /// The Aleo Testnet 3.
+    #[default]
+    Testnet3,
+}
+
+impl NetworkId {
+    /// The supported networks.
+    pub const ALL: &'static [NetworkId] = &[NetworkId::Testnet3];
+
+    /// Returns the network with the given numeric ID, as in `Network::ID`.
+    pub fn from_id(id: u16) -> Result<Self> {
+        match Self::ALL.iter().find(|network| network.id() == id) {
+            Some(network) => Ok(*network),
+            None => bail!("Unknown network ID '{id}'"),
+        }
+    }
+
+    /// Returns the numeric ID of the network, as in `Network::ID`.
+    pub fn id(&self) -> u16 {
+        dispatch!(self, N => N::ID)
+    }
+
+    /// Returns the name of the network, as in `Network::NAME`.
+    pub fn name(&self) -> &'static str {
+        dispatch!(self, N => N::NAME)
+    }
+
+    /// Converts the given Aleo source into its program JSON on this network, handling unsupported constructs
+    /// according to the given mode, and configuring the output with the given options.
+    pub fn convert_program(
+        &self,
+        source: &str,
+        mode: ConversionMode,
+        options: &JsonOptions,
+    ) -> Result<Value, ConversionError> {
+        dispatch!(self, N => convert_program_with_options::<N>(source, mode, options))
+    }
+
+    /// Converts the Aleo source at the given path into its program JSON on this network, handling unsupported
+    /// constructs according to the given mode, and configuring the output with the given options.
+    pub fn convert_program_file(
+        &self,
+        path: &Path,
+        mode: ConversionMode,
+        options: &JsonOptions,
+    ) -> Result<Value, ConversionError> {
+        dispatch!(self, N => convert_program_file::<N>(path, mode, options))
+    }
+
+    /// Converts the given block, encoded as snarkVM JSON, into its block JSON on this network.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub fn convert_block_json(&self, block: &str) -> Result<Value, ConversionError> {
+        dispatch!(self, N => convert_block_json::<N>(block))
+    }
+
+    /// Converts the given block, encoded as little-endian bytes, into its block JSON on this network.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub fn convert_block_bytes(&self, block: &[u8]) -> Result<Value, ConversionError> {
+        dispatch!(self, N => convert_block_bytes::<N>(block))
+    }
+
+    /// Converts the given transaction, encoded as snarkVM JSON, into its transaction JSON on this network.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub fn convert_transaction_json(&self, transaction: &str) -> Result<Value, ConversionError> {
+        dispatch!(self, N => convert_transaction_json::<N>(transaction))
+    }
+
+    /// Converts the given transaction, encoded as little-endian bytes, into its transaction JSON on this network.
+    #[cfg(feature = "aleo2json-ledger")]
+    pub fn convert_transaction_bytes(&self, transaction: &[u8]) -> Result<Value, ConversionError> {
+        dispatch!(self, N => convert_transaction_bytes::<N>(transaction))
+    }
+}
+
+impl FromStr for NetworkId {
+    type Err = Error;
+
+    /// Parses a network from its name.
+    fn from_str(network: &str) -> Result<Self> {
+        match network {
+            "testnet3" => Ok(Self::Testnet3),
+            _ => bail!("Unknown network '{network}' (expected one of: testnet3)"),
+        }
+    }
+}
+
+impl fmt::Display for NetworkId {
+    /// Prints the name of the network, as parsed by `FromStr`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Testnet3 => write!(f, "testnet3"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::convert_program;
+
+    const SOURCE: &str = "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;";
+
+    #[test]
+    fn test_network_id() {
+        let network = NetworkId::from_str("testnet3").unwrap();
+        assert_eq!(network, NetworkId::default());
+        assert_eq!(network.to_string(), "testnet3");
+        assert_eq!(network.id(), Testnet3::ID);
+        assert_eq!(NetworkId::from_id(Testnet3::ID).unwrap(), network);
+        assert!(NetworkId::from_id(u16::MAX).is_err());
+        assert!(NetworkId::from_str("mainnet").is_err());
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let network = NetworkId::Testnet3;
+        let program = network.convert_program(SOURCE, ConversionMode::default(), &JsonOptions::default()).unwrap();
+        assert_eq!(program, convert_program::<Testnet3>(SOURCE).unwrap());
+    }
+
+    #[cfg(feature = "aleo2json-ledger")]
+    #[test]
+    fn test_dispatch_block() {
+        let network = NetworkId::Testnet3;
+        let block = network.convert_block_bytes(Testnet3::genesis_bytes()).unwrap();
+        assert_eq!(block, convert_block_bytes::<Testnet3>(Testnet3::genesis_bytes()).unwrap());
+    }
+}
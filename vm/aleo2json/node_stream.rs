@@ -0,0 +1,263 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        attach_transaction_bytes,
+        block_to_json_with_options,
+        with_provenance,
+        BlockOptions,
+        JsonOptions,
+        OutputSink,
+        RestClient,
+    },
+    console::network::{
+        prelude::{de, DeserializeOwned, Deserializer as _, SeqAccess, ToBytes, Visitor},
+        Network,
+    },
+    ledger::block::{Block, Transaction},
+};
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::{
+    fmt,
+    io::{BufRead, BufReader, Read},
+};
+
+/// The capacity of the buffer the response is read through.
+const READ_BUFFER_SIZE: usize = 1 << 20;
+
+/// The byte order mark a response may start with.
+const BYTE_ORDER_MARK: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
+/// A streaming reader of the block and transaction JSON served by a node, decoding one document at a time,
+/// so a response of hundreds of megabytes is never held in memory as a whole.
+///
+/// The response may be a single document, an array of documents (i.e. as returned for a range of blocks),
+/// or a sequence of documents separated by whitespace (i.e. newline-delimited JSON).
+pub struct NodeStreamReader<R: Read> {
+    /// The buffered response.
+    reader: BufReader<R>,
+    /// Whether documents that do not decode are skipped, instead of failing the stream.
+    tolerant: bool,
+}
+
+/// The outcome of reading a stream of documents.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamSummary {
+    /// The number of documents decoded.
+    pub num_documents: usize,
+    /// The positions of the documents skipped, with the reason each did not decode.
+    pub skipped: Vec<(usize, String)>,
+}
+
+impl NodeStreamReader<Box<dyn Read + Send + Sync>> {
+    /// Initializes a reader streaming the response of a GET request to the given URL, sent with the given client,
+    /// so its retries and rate limit apply.
+    pub fn from_url(client: &RestClient, url: &str) -> Result<Self> {
+        Ok(Self::new(client.get_url(url)?.into_reader()))
+    }
+}
+
+impl<R: Read> NodeStreamReader<R> {
+    /// Initializes a reader of the given response.
+    pub fn new(reader: R) -> Self {
+        Self { reader: BufReader::with_capacity(READ_BUFFER_SIZE, reader), tolerant: false }
+    }
+
+    /// Sets whether documents that do not decode are skipped, and recorded in the summary, instead of failing
+    /// the stream. Malformed JSON always fails the stream, as the position of the next document is lost.
+    pub fn tolerant(mut self, tolerant: bool) -> Self {
+        self.tolerant = tolerant;
+        self
+    }
+
+    /// Decodes each document in the stream, in order, passing it to the given callback.
+    pub fn for_each<T: DeserializeOwned, F: FnMut(T) -> Result<()>>(
+        mut self,
+        mut callback: F,
+    ) -> Result<StreamSummary> {
+        let tolerant = self.tolerant;
+        let mut summary = StreamSummary::default();
+        let mut decode = |position: usize, document: Value| -> Result<()> {
+            match serde_json::from_value::<T>(document) {
+                Ok(document) => {
+                    callback(document)?;
+                    summary.num_documents += 1;
+                }
+                Err(error) if tolerant => summary.skipped.push((position, error.to_string())),
+                Err(error) => bail!("Failed to decode the document at position {position}: {error}"),
+            }
+            Ok(())
+        };
+
+        match self.peek()? {
+            // The documents are the elements of an array, which is never collected.
+            Some(b'[') => {
+                let mut deserializer = serde_json::Deserializer::from_reader(self.reader);
+                deserializer.deserialize_seq(ElementVisitor { decode: &mut decode })?;
+                deserializer.end()?;
+            }
+            // The documents follow one another.
+            Some(_) => {
+                let documents = serde_json::Deserializer::from_reader(self.reader).into_iter::<Value>();
+                for (position, document) in documents.enumerate() {
+                    decode(position, document?)?;
+                }
+            }
+            None => (),
+        }
+        Ok(summary)
+    }
+
+    /// Skips the byte order mark and the whitespace at the start of the stream, returning its first byte, if any.
+    fn peek(&mut self) -> Result<Option<u8>> {
+        if self.reader.fill_buf()?.starts_with(&BYTE_ORDER_MARK) {
+            self.reader.consume(BYTE_ORDER_MARK.len());
+        }
+        loop {
+            match self.reader.fill_buf()?.first().copied() {
+                Some(byte) if byte.is_ascii_whitespace() => self.reader.consume(1),
+                byte => return Ok(byte),
+            }
+        }
+    }
+}
+
+/// Passes each element of an array to the decoder, as it is read.
+struct ElementVisitor<'a, F: FnMut(usize, Value) -> Result<()>> {
+    /// The decoder of each element, given its position.
+    decode: &'a mut F,
+}
+
+impl<'de, 'a, F: FnMut(usize, Value) -> Result<()>> Visitor<'de> for ElementVisitor<'a, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of documents")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut position = 0;
+        while let Some(element) = seq.next_element::<Value>()? {
+            (self.decode)(position, element).map_err(de::Error::custom)?;
+            position += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Converts each block in the given stream of snarkVM block JSON into its block JSON, stamped with the format
+/// version, writing it to the given sink as soon as it is read.
+pub fn convert_block_stream<N: Network, R: Read, S: OutputSink + ?Sized>(
+    reader: NodeStreamReader<R>,
+    options: &BlockOptions,
+    sink: &mut S,
+) -> Result<StreamSummary> {
+    let summary = reader.for_each(|block: Block<N>| {
+        let provenance = options.json.provenance::<N>(&block.to_bytes_le()?)?;
+        let document = options.json.finish(with_provenance(block_to_json_with_options(&block, options)?, &provenance));
+        sink.write_document(&document).map(|_| ())
+    })?;
+    sink.flush()?;
+    Ok(summary)
+}
+
+/// Converts each transaction in the given stream of snarkVM transaction JSON into its transaction JSON,
+/// stamped with the format version, writing it to the given sink as soon as it is read.
+pub fn convert_transaction_stream<N: Network, R: Read, S: OutputSink + ?Sized>(
+    reader: NodeStreamReader<R>,
+    options: &JsonOptions,
+    sink: &mut S,
+) -> Result<StreamSummary> {
+    let summary = reader.for_each(|transaction: Transaction<N>| {
+        let provenance = options.provenance::<N>(&transaction.to_bytes_le()?)?;
+        let mut j_transaction = transaction.to_json();
+        if options.include_raw_bytes {
+            attach_transaction_bytes(&transaction, &mut j_transaction)?;
+        }
+        sink.write_document(&options.finish(with_provenance(j_transaction, &provenance))).map(|_| ())
+    })?;
+    sink.flush()?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    fn genesis_json() -> String {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        serde_json::to_string(&genesis).unwrap()
+    }
+
+    fn read_heights(stream: &str, tolerant: bool) -> Result<(Vec<u32>, StreamSummary)> {
+        let mut heights = Vec::new();
+        let reader = NodeStreamReader::new(stream.as_bytes()).tolerant(tolerant);
+        let summary = reader.for_each(|block: Block<CurrentNetwork>| {
+            heights.push(block.height());
+            Ok(())
+        })?;
+        Ok((heights, summary))
+    }
+
+    #[test]
+    fn test_node_stream_shapes() {
+        let genesis = genesis_json();
+
+        // A single document.
+        assert_eq!(read_heights(&genesis, false).unwrap().0, vec![0]);
+        // An array of documents, after a byte order mark.
+        assert_eq!(read_heights(&format!("\u{feff} [{genesis}, {genesis}]\n"), false).unwrap().0, vec![0, 0]);
+        // Newline-delimited documents.
+        assert_eq!(read_heights(&format!("{genesis}\n{genesis}\n"), false).unwrap().0, vec![0, 0]);
+        // No documents.
+        assert_eq!(read_heights(" \n", false).unwrap().1, StreamSummary::default());
+        assert_eq!(read_heights("[]", false).unwrap().1, StreamSummary::default());
+    }
+
+    #[test]
+    fn test_node_stream_tolerant() {
+        let genesis = genesis_json();
+        let stream = format!("[{{}}, {genesis}]");
+
+        assert!(read_heights(&stream, false).is_err());
+        let (heights, summary) = read_heights(&stream, true).unwrap();
+        assert_eq!(heights, vec![0]);
+        assert_eq!(summary.num_documents, 1);
+        assert_eq!(summary.skipped.len(), 1);
+        assert_eq!(summary.skipped[0].0, 0);
+
+        // Malformed JSON fails the stream, even when tolerant.
+        assert!(read_heights(&format!("[{genesis}, {{"), true).is_err());
+    }
+
+    #[test]
+    fn test_convert_block_stream() {
+        let genesis = genesis_json();
+
+        let mut documents: Vec<Value> = Vec::new();
+        let stream = format!("[{genesis}]");
+        let reader = NodeStreamReader::new(stream.as_bytes());
+        let summary =
+            convert_block_stream::<CurrentNetwork, _, _>(reader, &BlockOptions::default(), &mut documents).unwrap();
+        assert_eq!(summary.num_documents, 1);
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0]["header"]["metadata"]["height"], 0);
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::KEYED_PROPERTIES;
+
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// The nodes that each hold a separate register scope, i.e. whose `r0` is unrelated to the `r0` of another.
+const REGISTER_SCOPES: &[&str] = &["ClosureCore", "FinalizeCore", "FunctionCore"];
+
+/// The properties holding source text, whose registers and literals are normalized.
+const TEXT_PROPERTIES: &[&str] = &["source", "str"];
+
+/// Normalizes the given document, so two semantically identical closures, functions, or finalizes,
+/// that differ only in register numbering or literal formatting, serialize identically.
+///
+/// The registers of each scope are renamed to `r0`, `r1`, and so on, in order of their first appearance,
+/// both in the register nodes and in the source text. The literals in the source text are written without
+/// digit separators or leading zeros, i.e. `0_100u64` as `100u64`, which is how their nodes encode them.
+pub fn normalize(document: &mut Value) {
+    normalize_at(document, &HashMap::new(), false);
+}
+
+/// Normalizes the given value, and its children, renaming the registers with the given numbering.
+fn normalize_at(value: &mut Value, registers: &HashMap<u64, u64>, is_keyed: bool) {
+    match value {
+        Value::Object(object) => {
+            let scope;
+            let registers = match !is_keyed && is_scope(object) {
+                true => {
+                    scope = scope_registers(object);
+                    &scope
+                }
+                false => registers,
+            };
+            if !is_keyed && object.get("type").and_then(Value::as_str) == Some("Register") {
+                if let Some(locator) = register_locator(object).and_then(|locator| registers.get(&locator)) {
+                    let locator = json!(locator);
+                    if object.get("vtype").and_then(Value::as_str) == Some("Locator") {
+                        object.insert("value".to_string(), locator.clone());
+                    }
+                    object.insert("locator".to_string(), locator);
+                }
+            }
+            for (key, val) in object.iter_mut() {
+                match val {
+                    Value::String(text) if !is_keyed && TEXT_PROPERTIES.contains(&key.as_str()) => {
+                        *text = normalize_text(text, registers);
+                    }
+                    val => normalize_at(val, registers, !is_keyed && KEYED_PROPERTIES.contains(&key.as_str())),
+                }
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(|val| normalize_at(val, registers, false)),
+        _ => (),
+    }
+}
+
+/// Returns `true` if the given object is a register scope.
+fn is_scope(object: &Map<String, Value>) -> bool {
+    object.get("type").and_then(Value::as_str).map_or(false, |construct| REGISTER_SCOPES.contains(&construct))
+}
+
+/// Returns the canonical numbering of the registers of the given scope, in order of their first appearance,
+/// excluding those of the scopes nested in it.
+fn scope_registers(scope: &Map<String, Value>) -> HashMap<u64, u64> {
+    fn collect(value: &Value, registers: &mut HashMap<u64, u64>) {
+        match value {
+            Value::Object(object) if is_scope(object) => (),
+            Value::Object(object) => {
+                if object.get("type").and_then(Value::as_str) == Some("Register") {
+                    if let Some(locator) = register_locator(object) {
+                        let next = registers.len() as u64;
+                        registers.entry(locator).or_insert(next);
+                    }
+                }
+                object.values().for_each(|val| collect(val, registers));
+            }
+            Value::Array(array) => array.iter().for_each(|val| collect(val, registers)),
+            _ => (),
+        }
+    }
+
+    let mut registers = HashMap::new();
+    scope.values().for_each(|val| collect(val, &mut registers));
+    registers
+}
+
+/// Returns the locator of the given register node, i.e. `0` for `r0` and `r0.owner`.
+fn register_locator(register: &Map<String, Value>) -> Option<u64> {
+    match register.get("vtype").and_then(Value::as_str) {
+        Some("Locator") => register.get("value").and_then(Value::as_u64),
+        _ => register.get("locator").and_then(Value::as_u64),
+    }
+}
+
+/// Returns the given source text with its registers renamed with the given numbering,
+/// and its literals written without digit separators or leading zeros.
+fn normalize_text(text: &str, registers: &HashMap<u64, u64>) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_word_char) {
+        let (before, word) = rest.split_at(start);
+        let end = word.find(|c: char| !is_word_char(c)).unwrap_or(word.len());
+        let (word, after) = word.split_at(end);
+        normalized.push_str(before);
+
+        // A word following a `.` is a member or a visibility, not a register or a literal.
+        match normalized.ends_with('.') {
+            true => normalized.push_str(word),
+            false => normalized.push_str(&normalize_word(word, registers)),
+        }
+        rest = after;
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+/// Returns the given word renamed, if it is a register, or normalized, if it is a literal.
+fn normalize_word(word: &str, registers: &HashMap<u64, u64>) -> String {
+    // A register, i.e. `r5`.
+    if let Some(locator) = word.strip_prefix('r').and_then(|digits| digits.parse::<u64>().ok()) {
+        return match registers.get(&locator) {
+            Some(locator) => format!("r{locator}"),
+            None => word.to_string(),
+        };
+    }
+    // A literal, i.e. `1_000u64`, whose digits are followed by its type.
+    let suffix = word.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(word.len());
+    let (digits, literal_type) = word.split_at(suffix);
+    match !digits.is_empty() && !literal_type.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || c == '_') {
+        true => {
+            let digits = digits.replace('_', "");
+            let digits = digits.trim_start_matches('0');
+            format!("{}{literal_type}", if digits.is_empty() { "0" } else { digits })
+        }
+        false => word.to_string(),
+    }
+}
+
+/// Returns `true` if the given character may be part of an identifier, a register, or a literal.
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a function JSON, as emitted by `to_json()`, adding its inputs into its output.
+    fn function(inputs: [u64; 2], output: u64, source: &str) -> Value {
+        let register =
+            |locator: u64| json!({ "type": "Register", "vtype": "Locator", "locator": locator, "value": locator });
+        let operand = |locator: u64| json!({ "type": "Operand", "vtype": "Register", "value": register(locator) });
+        let input = |locator: u64| {
+            let text = format!("input r{locator} as u64.public;");
+            json!({ "type": "Input", "register": register(locator), "str": text })
+        };
+        json!({
+            "type": "FunctionCore",
+            "name": "sum",
+            "inputs": [input(inputs[0]), input(inputs[1])],
+            "instructions": [{
+                "type": "Instruction",
+                "vtype": "Add",
+                "value": { "operands": [operand(inputs[1]), operand(inputs[0])], "destination": register(output) },
+                "str": source,
+                "index": 0,
+            }],
+            "outputs": [{
+                "type": "Output",
+                "operand": operand(output),
+                "str": format!("output r{output} as u64.public;"),
+            }],
+            "finalize_logic": null,
+        })
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut renumbered = function([3, 7], 9, "add r7 r3 into r9;");
+        let mut canonical = function([0, 1], 2, "add r1 r0 into r2;");
+        let expected = canonical.clone();
+
+        normalize(&mut renumbered);
+        normalize(&mut canonical);
+        assert_eq!(renumbered, expected);
+        assert_eq!(canonical, expected);
+    }
+
+    #[test]
+    fn test_normalize_scopes() {
+        let mut function = function([3, 7], 9, "add r7 r3 into r9;");
+        function["finalize_logic"] = json!({
+            "type": "FinalizeCore",
+            "inputs": [{
+                "type": "Input",
+                "register": { "type": "Register", "vtype": "Locator", "locator": 4, "value": 4 },
+            }],
+            "commands": [],
+        });
+        normalize(&mut function);
+        // The finalize is numbered on its own.
+        assert_eq!(function["inputs"][1]["register"]["value"], 1);
+        assert_eq!(function["finalize_logic"]["inputs"][0]["register"]["value"], 0);
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        let registers = HashMap::from([(4, 0), (2, 1)]);
+        assert_eq!(normalize_text("add r4.owner r2 into r9;", &registers), "add r0.owner r1 into r9;");
+        assert_eq!(normalize_text("add 0_1_000u64 00u64 into r2;", &registers), "add 1000u64 0u64 into r1;");
+        assert_eq!(normalize_text("cast r4 into r5 as token.r2;", &registers), "cast r0 into r5 as token.r2;");
+        assert_eq!(normalize_text("-007i8", &HashMap::new()), "-7i8");
+    }
+}
@@ -0,0 +1,540 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{COMMAND_VTYPES, INSTRUCTION_VTYPES},
+    console::{
+        network::Network,
+        program::{Literal, LiteralType},
+    },
+    synthesizer::{
+        program::{
+            AbsOperation,
+            AbsWrappedOperation,
+            AddOperation,
+            AddWrappedOperation,
+            AndOperation,
+            DivOperation,
+            DivWrappedOperation,
+            DoubleOperation,
+            GreaterThanOperation,
+            GreaterThanOrEqualOperation,
+            InvOperation,
+            LessThanOperation,
+            LessThanOrEqualOperation,
+            ModuloOperation,
+            MulOperation,
+            MulWrappedOperation,
+            NandOperation,
+            NegOperation,
+            NorOperation,
+            NotOperation,
+            Opcode,
+            Operation,
+            OrOperation,
+            PowOperation,
+            PowWrappedOperation,
+            RemOperation,
+            RemWrappedOperation,
+            ShlOperation,
+            ShlWrappedOperation,
+            ShrOperation,
+            ShrWrappedOperation,
+            SquareOperation,
+            SquareRootOperation,
+            SubOperation,
+            SubWrappedOperation,
+            TernaryOperation,
+            XorOperation,
+        },
+        Instruction,
+    },
+};
+
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// Every literal type, in the order of its variant.
+const LITERAL_TYPES: [LiteralType; 17] = [
+    LiteralType::Address,
+    LiteralType::Boolean,
+    LiteralType::Field,
+    LiteralType::Group,
+    LiteralType::I8,
+    LiteralType::I16,
+    LiteralType::I32,
+    LiteralType::I64,
+    LiteralType::I128,
+    LiteralType::U8,
+    LiteralType::U16,
+    LiteralType::U32,
+    LiteralType::U64,
+    LiteralType::U128,
+    LiteralType::Scalar,
+    LiteralType::Signature,
+    LiteralType::String,
+];
+
+/// The semantics of an instruction or command that snarkVM does not expose, and are therefore recorded here.
+struct Semantics {
+    /// The name of the variant, as it appears in the `vtype` field.
+    vtype: &'static str,
+    /// The number of operands, or `None` if it varies.
+    arity: Option<usize>,
+    /// How the type of the result is determined.
+    result: &'static str,
+    /// When the operation halts, or `None` if it never does.
+    halts: Option<&'static str>,
+    /// Whether the operands can be swapped without changing the result.
+    commutative: bool,
+}
+
+/// Returns the semantics of the given variant.
+const fn semantics(
+    vtype: &'static str,
+    arity: Option<usize>,
+    result: &'static str,
+    halts: Option<&'static str>,
+    commutative: bool,
+) -> Semantics {
+    Semantics { vtype, arity, result, halts, commutative }
+}
+
+/// The result rule of the operations whose types are given by their signatures.
+const BY_SIGNATURE: &str = "The output type of the signature matching the operand types";
+/// The result rule of the operations with a declared output type.
+const DECLARED: &str = "The type declared after `as`";
+/// The halting behavior of checked arithmetic.
+const ON_OVERFLOW: Option<&str> = Some("On integer overflow or underflow");
+/// The halting behavior of checked division.
+const ON_DIVISION: Option<&str> = Some("On division by zero, or on integer overflow");
+
+/// The semantics of each instruction, in the order of `INSTRUCTION_VTYPES`.
+const INSTRUCTION_SEMANTICS: &[Semantics] = &[
+    semantics("Abs", Some(1), BY_SIGNATURE, ON_OVERFLOW, false),
+    semantics("AbsWrapped", Some(1), BY_SIGNATURE, None, false),
+    semantics("Add", Some(2), BY_SIGNATURE, ON_OVERFLOW, true),
+    semantics("AddWrapped", Some(2), BY_SIGNATURE, None, true),
+    semantics("And", Some(2), BY_SIGNATURE, None, true),
+    semantics("AssertEq", Some(2), "None", Some("If the operands are not equal"), true),
+    semantics("AssertNeq", Some(2), "None", Some("If the operands are equal"), true),
+    semantics("Async", None, "A future", None, false),
+    semantics("Call", None, "The output types of the callee", Some("If the callee halts"), false),
+    semantics("Cast", None, DECLARED, Some("If the operands do not fit the declared type"), false),
+    semantics("CastLossy", Some(1), DECLARED, None, false),
+    semantics("CommitBHP256", Some(2), DECLARED, None, false),
+    semantics("CommitBHP512", Some(2), DECLARED, None, false),
+    semantics("CommitBHP768", Some(2), DECLARED, None, false),
+    semantics("CommitBHP1024", Some(2), DECLARED, None, false),
+    semantics("CommitPED64", Some(2), DECLARED, None, false),
+    semantics("CommitPED128", Some(2), DECLARED, None, false),
+    semantics("Div", Some(2), BY_SIGNATURE, ON_DIVISION, false),
+    semantics("DivWrapped", Some(2), BY_SIGNATURE, Some("On division by zero"), false),
+    semantics("Double", Some(1), BY_SIGNATURE, None, false),
+    semantics("GreaterThan", Some(2), BY_SIGNATURE, None, false),
+    semantics("GreaterThanOrEqual", Some(2), BY_SIGNATURE, None, false),
+    semantics("HashBHP256", Some(1), DECLARED, None, false),
+    semantics("HashBHP512", Some(1), DECLARED, None, false),
+    semantics("HashBHP768", Some(1), DECLARED, None, false),
+    semantics("HashBHP1024", Some(1), DECLARED, None, false),
+    semantics("HashKeccak256", Some(1), DECLARED, None, false),
+    semantics("HashKeccak384", Some(1), DECLARED, None, false),
+    semantics("HashKeccak512", Some(1), DECLARED, None, false),
+    semantics("HashPED64", Some(1), DECLARED, None, false),
+    semantics("HashPED128", Some(1), DECLARED, None, false),
+    semantics("HashPSD2", Some(1), DECLARED, None, false),
+    semantics("HashPSD4", Some(1), DECLARED, None, false),
+    semantics("HashPSD8", Some(1), DECLARED, None, false),
+    semantics("HashSha3_256", Some(1), DECLARED, None, false),
+    semantics("HashSha3_384", Some(1), DECLARED, None, false),
+    semantics("HashSha3_512", Some(1), DECLARED, None, false),
+    semantics("HashManyPSD2", Some(2), DECLARED, None, false),
+    semantics("HashManyPSD4", Some(2), DECLARED, None, false),
+    semantics("HashManyPSD8", Some(2), DECLARED, None, false),
+    semantics("Inv", Some(1), BY_SIGNATURE, Some("If the operand is zero"), false),
+    semantics("IsEq", Some(2), "boolean", None, true),
+    semantics("IsNeq", Some(2), "boolean", None, true),
+    semantics("LessThan", Some(2), BY_SIGNATURE, None, false),
+    semantics("LessThanOrEqual", Some(2), BY_SIGNATURE, None, false),
+    semantics("Modulo", Some(2), BY_SIGNATURE, Some("On division by zero"), false),
+    semantics("Mul", Some(2), BY_SIGNATURE, ON_OVERFLOW, true),
+    semantics("MulWrapped", Some(2), BY_SIGNATURE, None, true),
+    semantics("Nand", Some(2), BY_SIGNATURE, None, true),
+    semantics("Neg", Some(1), BY_SIGNATURE, ON_OVERFLOW, false),
+    semantics("Nor", Some(2), BY_SIGNATURE, None, true),
+    semantics("Not", Some(1), BY_SIGNATURE, None, false),
+    semantics("Or", Some(2), BY_SIGNATURE, None, true),
+    semantics("Pow", Some(2), BY_SIGNATURE, ON_OVERFLOW, false),
+    semantics("PowWrapped", Some(2), BY_SIGNATURE, None, false),
+    semantics("Rem", Some(2), BY_SIGNATURE, ON_DIVISION, false),
+    semantics("RemWrapped", Some(2), BY_SIGNATURE, Some("On division by zero"), false),
+    semantics("Shl", Some(2), BY_SIGNATURE, Some("If the shift is not less than the bit width"), false),
+    semantics("ShlWrapped", Some(2), BY_SIGNATURE, None, false),
+    semantics("Shr", Some(2), BY_SIGNATURE, Some("If the shift is not less than the bit width"), false),
+    semantics("ShrWrapped", Some(2), BY_SIGNATURE, None, false),
+    semantics("SignVerify", Some(3), "boolean", None, false),
+    semantics("Square", Some(1), BY_SIGNATURE, None, false),
+    semantics("SquareRoot", Some(1), BY_SIGNATURE, Some("If the operand is not a square"), false),
+    semantics("Sub", Some(2), BY_SIGNATURE, ON_OVERFLOW, false),
+    semantics("SubWrapped", Some(2), BY_SIGNATURE, None, false),
+    semantics("Ternary", Some(3), BY_SIGNATURE, None, false),
+    semantics("Xor", Some(2), BY_SIGNATURE, None, true),
+];
+
+/// The opcode and semantics of each command, except `Instruction`, in the order of `COMMAND_VTYPES`.
+const COMMAND_SEMANTICS: &[(&str, Semantics)] = &[
+    ("await", semantics("Await", Some(1), "None", Some("If the awaited future halts"), false)),
+    ("contains", semantics("Contains", Some(1), "boolean", None, false)),
+    ("get", semantics("Get", Some(1), "The value type of the mapping", Some("If the key is not present"), false)),
+    ("get.or_use", semantics("GetOrUse", Some(2), "The value type of the mapping", None, false)),
+    ("rand.chacha", semantics("RandChaCha", None, DECLARED, None, false)),
+    ("remove", semantics("Remove", Some(1), "None", None, false)),
+    ("set", semantics("Set", Some(2), "None", None, false)),
+    ("branch.eq", semantics("BranchEq", Some(2), "None", None, true)),
+    ("branch.neq", semantics("BranchNeq", Some(2), "None", None, true)),
+    ("position", semantics("Position", Some(0), "None", None, false)),
+];
+
+/// The types of a literal operation's operands, and the type of its result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperandSignature {
+    /// The types of the operands.
+    pub inputs: Vec<LiteralType>,
+    /// The type of the result.
+    pub output: LiteralType,
+}
+
+impl OperandSignature {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let j_inputs: Vec<&str> = self.inputs.iter().map(LiteralType::type_name).collect();
+        json!({ "inputs": j_inputs, "output": self.output.type_name() })
+    }
+}
+
+/// The description of an opcode: its operands, the type of its result, and its halting behavior.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpcodeInfo {
+    /// The opcode, i.e. `add.w`.
+    pub opcode: String,
+    /// The name of the variant, as it appears in the `vtype` field.
+    pub vtype: &'static str,
+    /// Whether the opcode is an `Instruction` or a `Command`, as in the `type` field.
+    pub construct: &'static str,
+    /// The category of the opcode, i.e. `literal`, `hash`, or `command`.
+    pub category: &'static str,
+    /// The number of operands, or `None` if it varies.
+    pub arity: Option<usize>,
+    /// The accepted operand types, and the type of the result, of a literal operation.
+    pub signatures: Option<Vec<OperandSignature>>,
+    /// How the type of the result is determined.
+    pub result: &'static str,
+    /// When the opcode halts, or `None` if it never does.
+    pub halts: Option<&'static str>,
+    /// Whether the operands can be swapped without changing the result.
+    pub commutative: bool,
+}
+
+impl OpcodeInfo {
+    /// Initializes the description of an opcode from its semantics.
+    fn new(
+        opcode: String,
+        construct: &'static str,
+        category: &'static str,
+        signatures: Option<Vec<OperandSignature>>,
+        semantics: &Semantics,
+    ) -> Self {
+        Self {
+            opcode,
+            vtype: semantics.vtype,
+            construct,
+            category,
+            arity: semantics.arity,
+            signatures,
+            result: semantics.result,
+            halts: semantics.halts,
+            commutative: semantics.commutative,
+        }
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let j_signatures = self.signatures.as_ref().map(|signatures| {
+            let mut j_signatures = Vec::new();
+            for val in signatures {
+                j_signatures.push(val.to_json());
+            }
+            j_signatures
+        });
+
+        json!({
+            "type": "OpcodeInfo",
+            "opcode": self.opcode,
+            "vtype": self.vtype,
+            "construct": self.construct,
+            "category": self.category,
+            "arity": self.arity,
+            "signatures": j_signatures,
+            "result": self.result,
+            "halts": self.halts,
+            "commutative": self.commutative,
+        })
+    }
+}
+
+/// Returns the description of every instruction and command opcode.
+///
+/// The opcodes, and the signatures of the literal operations, are taken from snarkVM,
+/// so the table follows the instruction set of the snarkVM release aleo2json is built against.
+pub fn opcode_table<N: Network>() -> Vec<OpcodeInfo> {
+    let mut table = Vec::new();
+    for (opcode, semantics) in Instruction::<N>::OPCODES.iter().zip(INSTRUCTION_SEMANTICS) {
+        let category = match opcode {
+            Opcode::Assert(..) => "assert",
+            Opcode::Async => "async",
+            Opcode::Call => "call",
+            Opcode::Cast(..) => "cast",
+            Opcode::Command(..) => "command",
+            Opcode::Commit(..) => "commit",
+            Opcode::Hash(..) => "hash",
+            Opcode::Is(..) => "is",
+            Opcode::Literal(..) => "literal",
+            Opcode::Sign => "sign",
+        };
+        let signatures = literal_signatures::<N>(semantics.vtype);
+        table.push(OpcodeInfo::new(opcode.to_string(), "Instruction", category, signatures, semantics));
+    }
+    for (opcode, semantics) in COMMAND_SEMANTICS {
+        table.push(OpcodeInfo::new(opcode.to_string(), "Command", "command", None, semantics));
+    }
+    table
+}
+
+/// Returns the table describing every opcode as JSON.
+pub fn opcode_table_to_json<N: Network>() -> Value {
+    let mut j_opcodes = Vec::new();
+    for val in opcode_table::<N>() {
+        j_opcodes.push(val.to_json());
+    }
+
+    json!({ "type": "OpcodeTable", "opcodes": j_opcodes })
+}
+
+/// Returns the descriptions of the opcodes used in the given program JSON, in the order of the opcode table.
+pub fn program_opcodes_to_json<N: Network>(program: &Value) -> Value {
+    let mut used = HashSet::new();
+    collect_vtypes(program, &mut used);
+
+    let mut j_opcodes = Vec::new();
+    for val in opcode_table::<N>() {
+        if used.contains(&(val.construct, val.vtype)) {
+            j_opcodes.push(val.to_json());
+        }
+    }
+    Value::Array(j_opcodes)
+}
+
+/// Annotates every instruction and command in the given value, and in its children, with whether it can halt,
+/// under `can_halt`.
+///
+/// An instruction command can halt if its instruction can. The unsupported nodes of strict mode are not annotated.
+pub fn attach_halting(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.values_mut().for_each(attach_halting);
+            let can_halt =
+                match (object.get("type").and_then(Value::as_str), object.get("vtype").and_then(Value::as_str)) {
+                    (Some("Command"), Some("Instruction")) => {
+                        object.get("value").and_then(|val| val.get("can_halt")).and_then(Value::as_bool)
+                    }
+                    (Some("Instruction"), Some(vtype)) => INSTRUCTION_SEMANTICS
+                        .iter()
+                        .find(|semantics| semantics.vtype == vtype)
+                        .map(|semantics| semantics.halts.is_some()),
+                    (Some("Command"), Some(vtype)) => COMMAND_SEMANTICS
+                        .iter()
+                        .find(|(_, semantics)| semantics.vtype == vtype)
+                        .map(|(_, semantics)| semantics.halts.is_some()),
+                    _ => None,
+                };
+            if let Some(can_halt) = can_halt {
+                object.insert("can_halt".to_string(), Value::Bool(can_halt));
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(attach_halting),
+        _ => (),
+    }
+}
+
+/// Collects the construct and variant of every instruction and command in the given value, and in its children.
+fn collect_vtypes<'a>(value: &'a Value, used: &mut HashSet<(&'a str, &'a str)>) {
+    match value {
+        Value::Object(object) => {
+            if let (Some(construct @ ("Instruction" | "Command")), Some(vtype)) =
+                (object.get("type").and_then(Value::as_str), object.get("vtype").and_then(Value::as_str))
+            {
+                used.insert((construct, vtype));
+            }
+            object.values().for_each(|val| collect_vtypes(val, used));
+        }
+        Value::Array(array) => array.iter().for_each(|val| collect_vtypes(val, used)),
+        _ => (),
+    }
+}
+
+/// Returns the signatures of the literal operation with the given variant, or `None` if it is not one.
+fn literal_signatures<N: Network>(vtype: &str) -> Option<Vec<OperandSignature>> {
+    Some(match vtype {
+        "Abs" => signatures::<N, AbsOperation<N>, 1>(),
+        "AbsWrapped" => signatures::<N, AbsWrappedOperation<N>, 1>(),
+        "Add" => signatures::<N, AddOperation<N>, 2>(),
+        "AddWrapped" => signatures::<N, AddWrappedOperation<N>, 2>(),
+        "And" => signatures::<N, AndOperation<N>, 2>(),
+        "Div" => signatures::<N, DivOperation<N>, 2>(),
+        "DivWrapped" => signatures::<N, DivWrappedOperation<N>, 2>(),
+        "Double" => signatures::<N, DoubleOperation<N>, 1>(),
+        "GreaterThan" => signatures::<N, GreaterThanOperation<N>, 2>(),
+        "GreaterThanOrEqual" => signatures::<N, GreaterThanOrEqualOperation<N>, 2>(),
+        "Inv" => signatures::<N, InvOperation<N>, 1>(),
+        "LessThan" => signatures::<N, LessThanOperation<N>, 2>(),
+        "LessThanOrEqual" => signatures::<N, LessThanOrEqualOperation<N>, 2>(),
+        "Modulo" => signatures::<N, ModuloOperation<N>, 2>(),
+        "Mul" => signatures::<N, MulOperation<N>, 2>(),
+        "MulWrapped" => signatures::<N, MulWrappedOperation<N>, 2>(),
+        "Nand" => signatures::<N, NandOperation<N>, 2>(),
+        "Neg" => signatures::<N, NegOperation<N>, 1>(),
+        "Nor" => signatures::<N, NorOperation<N>, 2>(),
+        "Not" => signatures::<N, NotOperation<N>, 1>(),
+        "Or" => signatures::<N, OrOperation<N>, 2>(),
+        "Pow" => signatures::<N, PowOperation<N>, 2>(),
+        "PowWrapped" => signatures::<N, PowWrappedOperation<N>, 2>(),
+        "Rem" => signatures::<N, RemOperation<N>, 2>(),
+        "RemWrapped" => signatures::<N, RemWrappedOperation<N>, 2>(),
+        "Shl" => signatures::<N, ShlOperation<N>, 2>(),
+        "ShlWrapped" => signatures::<N, ShlWrappedOperation<N>, 2>(),
+        "Shr" => signatures::<N, ShrOperation<N>, 2>(),
+        "ShrWrapped" => signatures::<N, ShrWrappedOperation<N>, 2>(),
+        "Square" => signatures::<N, SquareOperation<N>, 1>(),
+        "SquareRoot" => signatures::<N, SquareRootOperation<N>, 1>(),
+        "Sub" => signatures::<N, SubOperation<N>, 2>(),
+        "SubWrapped" => signatures::<N, SubWrappedOperation<N>, 2>(),
+        "Ternary" => signatures::<N, TernaryOperation<N>, 3>(),
+        "Xor" => signatures::<N, XorOperation<N>, 2>(),
+        _ => return None,
+    })
+}
+
+/// Returns the signatures of the given literal operation, by asking it for the output type of every combination
+/// of operand types.
+fn signatures<N: Network, O: Operation<N, Literal<N>, LiteralType, K>, const K: usize>() -> Vec<OperandSignature> {
+    let mut signatures = Vec::new();
+    for index in 0..LITERAL_TYPES.len().pow(K as u32) {
+        // Decode the index into the operand types, with the last operand varying fastest.
+        let mut inputs = [LiteralType::Address; K];
+        let mut rest = index;
+        for input in inputs.iter_mut().rev() {
+            *input = LITERAL_TYPES[rest % LITERAL_TYPES.len()];
+            rest /= LITERAL_TYPES.len();
+        }
+        if let Ok(output) = O::output_type(&inputs) {
+            signatures.push(OperandSignature { inputs: inputs.to_vec(), output });
+        }
+    }
+    signatures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_opcode_table_covers_schema() {
+        // The semantics must follow the instruction and command variants of the schema.
+        let instructions: Vec<&str> = INSTRUCTION_SEMANTICS.iter().map(|semantics| semantics.vtype).collect();
+        assert_eq!(instructions, INSTRUCTION_VTYPES);
+        assert_eq!(Instruction::<CurrentNetwork>::OPCODES.len(), INSTRUCTION_VTYPES.len());
+        let commands: Vec<&str> = COMMAND_SEMANTICS.iter().map(|(_, semantics)| semantics.vtype).collect();
+        assert_eq!(commands, COMMAND_VTYPES[1..]);
+
+        let table = opcode_table::<CurrentNetwork>();
+        assert_eq!(table.len(), INSTRUCTION_VTYPES.len() + COMMAND_VTYPES.len() - 1);
+        // Each literal operation has a signature, and the arity of each signature matches the opcode.
+        for info in table.iter().filter(|info| info.category == "literal") {
+            let signatures = info.signatures.as_ref().unwrap();
+            assert!(!signatures.is_empty(), "'{}' has no signatures", info.opcode);
+            assert!(signatures.iter().all(|signature| Some(signature.inputs.len()) == info.arity));
+        }
+    }
+
+    #[test]
+    fn test_attach_halting() {
+        let instruction = |vtype: &str| json!({ "type": "Instruction", "vtype": vtype, "value": {} });
+        let mut document = json!({
+            "instructions": [instruction("AddWrapped"), instruction("Div"), instruction("CastLossy")],
+            "commands": [
+                { "type": "Command", "vtype": "Instruction", "value": instruction("AssertEq") },
+                { "type": "Command", "vtype": "Instruction", "value": instruction("Ternary") },
+                { "type": "Command", "vtype": "Get", "value": {} },
+                { "type": "Command", "vtype": "GetOrUse", "value": {} },
+                { "type": "Unsupported", "vtype": "Get" },
+            ],
+        });
+        attach_halting(&mut document);
+
+        let can_halt = |values: &Value| {
+            values
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|val| val.get("can_halt").and_then(Value::as_bool))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(can_halt(&document["instructions"]), [Some(false), Some(true), Some(false)]);
+        assert_eq!(can_halt(&document["commands"]), [Some(true), Some(false), Some(true), Some(false), None]);
+        assert_eq!(document["commands"][0]["value"]["can_halt"], true);
+    }
+
+    #[test]
+    fn test_opcode_info() {
+        let table = opcode_table::<CurrentNetwork>();
+        let add = table.iter().find(|info| info.opcode == "add").unwrap();
+        assert_eq!(add.vtype, "Add");
+        assert!(add.commutative);
+        assert!(add.halts.is_some());
+        let signature = OperandSignature { inputs: vec![LiteralType::U8, LiteralType::U8], output: LiteralType::U8 };
+        assert!(add.signatures.as_ref().unwrap().contains(&signature));
+
+        let j_add = add.to_json();
+        assert_eq!(j_add["arity"], 2);
+        assert_eq!(j_add["signatures"][0]["inputs"], json!(["field", "field"]));
+
+        let get = table.iter().find(|info| info.opcode == "get").unwrap();
+        assert_eq!(get.construct, "Command");
+        assert_eq!(get.to_json()["signatures"], Value::Null);
+    }
+
+    #[test]
+    fn test_program_opcodes() {
+        let program = json!({
+            "type": "Program",
+            "functions": { "foo": { "instructions": [{ "type": "Instruction", "vtype": "Add" }] } },
+        });
+        let j_opcodes = program_opcodes_to_json::<CurrentNetwork>(&program);
+        assert_eq!(j_opcodes.as_array().unwrap().len(), 1);
+        assert_eq!(j_opcodes[0]["opcode"], "add");
+    }
+}
@@ -0,0 +1,242 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{opcode_table, typed_instructions, ConversionError, ConversionMode},
+    console::{network::Network, program::LiteralType},
+    synthesizer::{program::InstructionTrait, Program},
+};
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The opcodes whose two operands must have the same type.
+const SAME_OPERAND_TYPE_OPCODES: &[&str] = &["assert.eq", "assert.neq", "is.eq", "is.neq"];
+
+/// The kind of violation of an opcode's signature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The instruction has more or fewer operands than its opcode takes.
+    Arity,
+    /// The operand types match none of the signatures of the opcode.
+    Type,
+}
+
+impl ViolationKind {
+    /// Returns the name of the kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Arity => "arity",
+            Self::Type => "type",
+        }
+    }
+}
+
+/// An instruction whose operands violate the signature of its opcode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperandViolation {
+    /// The JSON pointer to the instruction, in the program JSON.
+    pub pointer: String,
+    /// The opcode of the instruction.
+    pub opcode: String,
+    /// The kind of violation.
+    pub kind: ViolationKind,
+    /// The literal type of each operand, if it could be inferred.
+    pub operand_types: Vec<Option<LiteralType>>,
+    /// The explanation of the violation.
+    pub message: String,
+}
+
+impl OperandViolation {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let j_operand_types =
+            self.operand_types.iter().map(|operand_type| operand_type.map(|operand_type| operand_type.type_name()));
+        json!({
+            "type": "OperandViolation",
+            "kind": self.kind.as_str(),
+            "opcode": self.opcode,
+            "pointer": self.pointer,
+            "operand_types": j_operand_types.collect::<Vec<_>>(),
+            "message": self.message,
+        })
+    }
+
+    /// Returns the strict-mode error of the violation.
+    pub fn to_error(&self) -> ConversionError {
+        ConversionError::InvalidOperands {
+            opcode: self.opcode.clone(),
+            pointer: self.pointer.clone(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+/// Returns the instructions of the given program whose operand count differs from the arity of their opcode,
+/// or whose operand types match none of its signatures, in order of their closure, function, and finalize block.
+///
+/// The operand types are inferred as for the arithmetic risks. An operand of unknown type (i.e. the output of a call)
+/// matches any type, so the check reports no false positives, but misses the violations involving such operands.
+pub fn operand_violations<N: Network>(program: &Program<N>) -> Vec<OperandViolation> {
+    let table = opcode_table::<N>().into_iter().map(|info| (info.opcode.clone(), info)).collect::<HashMap<_, _>>();
+    let type_names = |operand_types: &[Option<LiteralType>]| {
+        let names =
+            operand_types.iter().map(|operand_type| operand_type.map_or("?", |operand_type| operand_type.type_name()));
+        names.collect::<Vec<_>>().join(", ")
+    };
+
+    let mut violations = Vec::new();
+    for typed in typed_instructions(program) {
+        let opcode = typed.instruction.opcode().to_string();
+        let info = match table.get(&opcode) {
+            Some(info) => info,
+            None => continue,
+        };
+        let num_operands = typed.instruction.operands().len();
+        let (kind, message) = match (info.arity, &info.signatures) {
+            (Some(arity), _) if arity != num_operands => {
+                (ViolationKind::Arity, format!("'{opcode}' takes {arity} operand(s), but is given {num_operands}"))
+            }
+            (_, Some(signatures)) => {
+                let matches = |inputs: &[LiteralType]| {
+                    inputs.len() == num_operands
+                        && inputs
+                            .iter()
+                            .zip(&typed.operand_types)
+                            .all(|(input, found)| found.map_or(true, |found| found == *input))
+                };
+                if signatures.iter().any(|signature| matches(&signature.inputs)) {
+                    continue;
+                }
+                let message =
+                    format!("'{opcode}' is not defined for operands of types ({})", type_names(&typed.operand_types));
+                (ViolationKind::Type, message)
+            }
+            _ if SAME_OPERAND_TYPE_OPCODES.contains(&opcode.as_str()) => match typed.operand_types.as_slice() {
+                [Some(first), Some(second)] if first != second => {
+                    let message = format!(
+                        "'{opcode}' compares operands of different types ({})",
+                        type_names(&typed.operand_types)
+                    );
+                    (ViolationKind::Type, message)
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+        violations.push(OperandViolation {
+            pointer: typed.pointer,
+            opcode,
+            kind,
+            operand_types: typed.operand_types,
+            message,
+        });
+    }
+    violations
+}
+
+/// Checks the operands of the instructions of the given program against the signatures of their opcodes, handling
+/// the violations according to the given mode: failing on the first one in strict mode, or listing them in the given
+/// program JSON, under `warnings`, in permissive mode.
+pub fn check_operands<N: Network>(
+    program: &Program<N>,
+    document: &mut Value,
+    mode: ConversionMode,
+) -> Result<(), ConversionError> {
+    let violations = operand_violations(program);
+    match (mode, violations.first()) {
+        (_, None) => Ok(()),
+        (ConversionMode::Strict, Some(violation)) => Err(violation.to_error()),
+        (ConversionMode::Permissive, Some(_)) => {
+            document["warnings"] = json!(violations.iter().map(OperandViolation::to_json).collect::<Vec<_>>());
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{convert_program_with_mode, parse_program},
+        prelude::Testnet3,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"
+program checks.aleo;
+
+function mix:
+    input r0 as u8.public;
+    input r1 as u16.public;
+    input r2 as field.private;
+    add r0 r1 into r3;
+    is.eq r0 r2 into r4;
+    mul r2 r2 into r5;
+    ternary r4 r0 r0 into r6;
+    output r3 as u8.public;";
+
+    #[test]
+    fn test_operand_violations() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let violations = operand_violations(&program);
+        let summary = violations
+            .iter()
+            .map(|violation| (violation.pointer.as_str(), violation.opcode.as_str(), violation.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(summary, [
+            ("/functions/mix/instructions/0", "add", ViolationKind::Type),
+            ("/functions/mix/instructions/1", "is.eq", ViolationKind::Type),
+        ]);
+        assert_eq!(violations[0].message, "'add' is not defined for operands of types (u8, u16)");
+
+        // A well-typed program has no violations.
+        let program = parse_program::<CurrentNetwork>(
+            "program fine.aleo; function f: input r0 as u8.public; add r0 1u8 into r1; output r1 as u8.public;",
+        )
+        .unwrap();
+        assert!(operand_violations(&program).is_empty());
+    }
+
+    #[test]
+    fn test_operand_violations_mixed_signature() {
+        // The product of a scalar and a group is a group, so it can be added to a group.
+        let source = r"
+program mixed.aleo;
+
+function scale:
+    input r0 as scalar.private;
+    input r1 as group.private;
+    mul r0 r1 into r2;
+    add r2 r1 into r3;
+    output r3 as group.private;";
+        let program = parse_program::<CurrentNetwork>(source).unwrap();
+        assert!(operand_violations(&program).is_empty());
+        assert!(convert_program_with_mode::<CurrentNetwork>(source, ConversionMode::Strict).is_ok());
+    }
+
+    #[test]
+    fn test_check_operands() {
+        // The violations are warnings in permissive mode.
+        let document = convert_program_with_mode::<CurrentNetwork>(PROGRAM, ConversionMode::Permissive).unwrap();
+        assert_eq!(document["warnings"][0]["type"], "OperandViolation");
+        assert_eq!(document["warnings"][1]["pointer"], "/functions/mix/instructions/1");
+
+        // The first violation is an error in strict mode.
+        let error = convert_program_with_mode::<CurrentNetwork>(PROGRAM, ConversionMode::Strict).unwrap_err();
+        assert_eq!(error.kind(), "invalid_operands");
+        assert_eq!(error.to_json()["pointer"], "/functions/mix/instructions/0");
+    }
+}
@@ -0,0 +1,511 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        annotate_nodes,
+        normalize,
+        redact,
+        rename_keys,
+        ImportResolver,
+        KeyNaming,
+        Provenance,
+        Redaction,
+        ToJson,
+        KEYED_PROPERTIES,
+    },
+    console::{
+        network::{prelude::ToBytes, Network, Testnet3},
+        types::{Field, Group},
+    },
+};
+
+#[cfg(feature = "aleo2json-ledger")]
+use crate::aleo2json::attach_credits;
+
+use anyhow::{bail, Error, Result};
+use serde_json::{Map, Value};
+use std::{fmt, str::FromStr};
+
+/// How much of the JSON representation is emitted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Omits the fields that are `null`, or that hold an empty array or object.
+    Compact,
+    /// Emits every field.
+    #[default]
+    Normal,
+}
+
+/// How numbers, and the values of integers and field elements, are encoded in the JSON output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NumericEncoding {
+    /// Numbers are emitted as JSON numbers, and the values of integers and field elements as decimal strings.
+    #[default]
+    Native,
+    /// Numbers are emitted as decimal strings, for consumers that cannot represent 64-bit integers exactly.
+    String,
+    /// The values of the integers of up to 64 bits are emitted as JSON numbers, as are numbers, as long as they are
+    /// at most `2^53 - 1` in magnitude, so every consumer (i.e. JavaScript) reads them exactly. Larger numbers and
+    /// values, and the values of wider integers and of field elements, are emitted as decimal strings instead.
+    Number,
+    /// Numbers, and the values of integers and field elements, are emitted with both their decimal and hex forms,
+    /// i.e. `{ "dec": "255", "hex": "0xff" }`, so no consumer has to parse or lose precision on either form.
+    /// The hex form of a value is that of its bits (i.e. `0xff` for `-1i8`), and that of a negative number is signed
+    /// (i.e. `-0x1` for `-1`).
+    Both,
+}
+
+impl FromStr for NumericEncoding {
+    type Err = Error;
+
+    /// Parses a numeric encoding from its name.
+    fn from_str(encoding: &str) -> Result<Self> {
+        match encoding {
+            "native" => Ok(Self::Native),
+            "string" => Ok(Self::String),
+            "number" => Ok(Self::Number),
+            "both" => Ok(Self::Both),
+            _ => bail!("Unknown numeric encoding '{encoding}' (expected one of: native, string, number, both)"),
+        }
+    }
+}
+
+impl fmt::Display for NumericEncoding {
+    /// Prints the name of the numeric encoding.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Native => write!(f, "native"),
+            Self::String => write!(f, "string"),
+            Self::Number => write!(f, "number"),
+            Self::Both => write!(f, "both"),
+        }
+    }
+}
+
+/// The largest magnitude of the integers that JSON consumers representing numbers as doubles read exactly.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// The configuration of the JSON output, accepted by `ToJson::to_json_with` and the conversion helpers.
+///
+/// The default options emit exactly the output of the `to_json()` helpers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonOptions {
+    /// How much of the JSON representation is emitted.
+    pub verbosity: Verbosity,
+    /// Includes the source text of each node, under `str`.
+    pub include_text: bool,
+    /// Includes the little-endian byte encoding of the converted object, in hex, under `bytes`.
+    pub include_bytes: bool,
+    /// Includes the little-endian byte encoding of each block, transaction, and transition, in hex, under `bytes`,
+    /// next to its JSON, when converting blocks or transactions.
+    pub include_raw_bytes: bool,
+    /// Includes the analysis sections of the converted object (i.e. program statistics), under `analysis`.
+    pub include_analysis: bool,
+    /// Includes the source location of each node, under `span`, when converting from source text.
+    pub include_spans: bool,
+    /// Includes the original source text of each construct, under `source`, when converting from source text.
+    pub include_source: bool,
+    /// Includes the comments of the source text, under `comments`, on the node of the construct following each one,
+    /// or on the program, when converting from source text. With `lossless`, edits of the comments are applied back
+    /// to the original text with `SourceMap::apply`.
+    pub include_comments: bool,
+    /// Includes the lossless source map of the program, under `source_map`, and the original source text of each
+    /// construct, under `source`, when converting from source text, so edits of the `source` of the constructs can
+    /// be applied back to the original text with `SourceMap::apply`.
+    pub lossless: bool,
+    /// Assigns each node a deterministic ID, under `node_id`.
+    pub include_node_ids: bool,
+    /// Annotates each node with its JSON pointer, under `pointer`, and with the ID of its parent node, under `parent_id`.
+    pub include_paths: bool,
+    /// Includes the description of each opcode used by a program, under `opcodes`.
+    pub include_opcodes: bool,
+    /// Includes the name, input types, and output types of each function of a program, at the top of its document,
+    /// under `interface`.
+    pub include_interface: bool,
+    /// Includes the Leo-like pseudocode of each closure and function of a program, under `pseudocode`.
+    pub include_pseudocode: bool,
+    /// Includes the x-coordinate field element of each address, under `address`, next to its bech32 string.
+    pub include_address_fields: bool,
+    /// Includes the affine coordinates of each group element, under `x` and `y`, next to its x-coordinate.
+    pub include_group_coordinates: bool,
+    /// Includes the structural fingerprint of each program, and of each of its closures and functions,
+    /// under `fingerprint`.
+    pub include_fingerprints: bool,
+    /// Annotates each instruction and command with whether it can halt (i.e. asserts, checked arithmetic, and `get`),
+    /// under `can_halt`.
+    pub include_halting: bool,
+    /// Annotates each instruction susceptible to wrap or truncation concerns (i.e. wrapping arithmetic, shifts,
+    /// and lossy casts) with its operand types, under `risk`.
+    pub include_risks: bool,
+    /// Annotates each closure, struct, record, and mapping with whether it is reachable from the functions, under
+    /// `reachable`, and lists the unreachable ones under `dead_definitions`.
+    pub include_reachability: bool,
+    /// Pairs each amount in microcredits (i.e. rewards, genesis balances, fees, and transfers) with its decimal value
+    /// in credits, under `credits`, when converting ledger objects.
+    pub include_credits: bool,
+    /// Compares the source checksum of each deployed program with the one recomputed from its copy found by the import
+    /// resolver, under `checksum_check`, so mismatching copies are detected from the export.
+    pub verify_source_checksums: bool,
+    /// Renames the registers of each closure, function, and finalize to a canonical numbering, and normalizes
+    /// the literals of the source text, so code that differs only in register numbering serializes identically.
+    pub normalize: bool,
+    /// Records the time of the conversion in the `meta` block of each document converted from an input, under
+    /// `timestamp`. It is disabled by default, so identical conversions produce identical output.
+    pub include_timestamp: bool,
+    /// Resolves each import of a program with the given resolver, under `resolution`, if set.
+    pub imports: Option<ImportResolver>,
+    /// Replaces the sensitive values (i.e. private inputs, decrypted records, and serial numbers), if set.
+    pub redaction: Option<Redaction>,
+    /// How numbers are encoded.
+    pub numbers: NumericEncoding,
+    /// The names of the keys.
+    pub naming: KeyNaming,
+}
+
+impl Default for JsonOptions {
+    /// Returns the options that emit exactly the output of the `to_json()` helpers.
+    fn default() -> Self {
+        Self {
+            verbosity: Verbosity::default(),
+            include_text: true,
+            include_bytes: false,
+            include_raw_bytes: false,
+            include_analysis: false,
+            include_spans: false,
+            include_source: false,
+            include_comments: false,
+            lossless: false,
+            include_node_ids: false,
+            include_paths: false,
+            include_opcodes: false,
+            include_interface: false,
+            include_pseudocode: false,
+            include_address_fields: true,
+            include_group_coordinates: false,
+            include_fingerprints: false,
+            include_halting: false,
+            include_risks: false,
+            include_reachability: false,
+            include_credits: false,
+            verify_source_checksums: false,
+            normalize: false,
+            include_timestamp: false,
+            imports: None,
+            redaction: None,
+            numbers: NumericEncoding::default(),
+            naming: KeyNaming::default(),
+        }
+    }
+}
+
+impl JsonOptions {
+    /// Adds the byte encoding and the analysis sections of the given value to its document,
+    /// and annotates its nodes with their IDs and paths, if they are enabled.
+    pub fn extend<T: ToJson + ?Sized>(&self, value: &T, document: &mut Value) {
+        if let Value::Object(object) = document {
+            if self.include_bytes {
+                if let Some(bytes) = value.to_json_bytes() {
+                    object.insert("bytes".to_string(), Value::String(to_hex(&bytes)));
+                }
+            }
+            if self.include_analysis {
+                if let Some(analysis) = value.to_json_analysis() {
+                    object.insert("analysis".to_string(), analysis);
+                }
+            }
+        }
+        if self.include_node_ids || self.include_paths {
+            let root = value.to_json_id().unwrap_or_default();
+            annotate_nodes(document, &root, self.include_node_ids, self.include_paths);
+        }
+    }
+
+    /// Returns the provenance of a document converted from the given input, on the given network,
+    /// with the time of the conversion if it is included.
+    pub fn provenance<N: Network>(&self, input: &[u8]) -> Result<Provenance> {
+        Ok(Provenance::of::<N>(input)?.with_timestamp(self.include_timestamp))
+    }
+
+    /// Applies the redaction, normalization, verbosity, text, address, group, numeric encoding, and naming options
+    /// to the given document.
+    ///
+    /// This is the last step of a conversion, as the other passes (i.e. strict mode) expect the default names.
+    pub fn finish(&self, mut document: Value) -> Value {
+        if let Some(redaction) = self.redaction {
+            redact(&mut document, redaction);
+        }
+        if self.normalize {
+            normalize(&mut document);
+        }
+        #[cfg(feature = "aleo2json-ledger")]
+        if self.include_credits {
+            attach_credits(&mut document);
+        }
+        let document = match self.verbosity == Verbosity::Normal
+            && self.include_text
+            && self.include_address_fields
+            && !self.include_group_coordinates
+            && self.numbers == NumericEncoding::Native
+        {
+            true => document,
+            false => self.filter(document, false),
+        };
+        rename_keys(document, &self.naming)
+    }
+
+    /// Filters the fields and encodes the numbers of the given value, and of its children.
+    fn filter(&self, value: Value, is_keyed: bool) -> Value {
+        match value {
+            Value::Object(object) => {
+                let is_address = object.get("type").and_then(Value::as_str) == Some("Address");
+                let coordinates = match self.include_group_coordinates && !is_keyed {
+                    true => group_coordinates(&object),
+                    false => None,
+                };
+                // The decimal value of an integer, with its hex form and bit width, or of a field element, if any.
+                let decimal = match (is_keyed, object.get("type").and_then(Value::as_str)) {
+                    (false, Some("Integer")) => Some((
+                        "integer",
+                        Some((
+                            object.get("hex").cloned().unwrap_or_default(),
+                            object.get("bits").and_then(Value::as_u64).unwrap_or_default(),
+                        )),
+                    )),
+                    (false, Some("Field")) => Some(("field", None)),
+                    _ => None,
+                };
+                let mut filtered = Map::with_capacity(object.len());
+                for (key, val) in object {
+                    if !is_keyed && !self.include_text && key == "str" {
+                        continue;
+                    }
+                    if is_address && !self.include_address_fields && key == "address" {
+                        continue;
+                    }
+                    if let (Some((value_key, integer)), Value::String(value)) = (&decimal, &val) {
+                        if key == *value_key {
+                            let value = self.encode_decimal(value.clone(), integer.as_ref());
+                            filtered.insert(key, value);
+                            continue;
+                        }
+                    }
+                    let val = self.filter(val, !is_keyed && KEYED_PROPERTIES.contains(&key.as_str()));
+                    if !is_keyed && self.verbosity == Verbosity::Compact && is_empty(&val) {
+                        continue;
+                    }
+                    filtered.insert(key, val);
+                }
+                if let Some((x, y)) = coordinates {
+                    filtered.insert("x".to_string(), x);
+                    filtered.insert("y".to_string(), y);
+                }
+                Value::Object(filtered)
+            }
+            Value::Array(array) => Value::Array(array.into_iter().map(|val| self.filter(val, false)).collect()),
+            Value::Number(number) => match self.numbers {
+                NumericEncoding::Native => Value::Number(number),
+                NumericEncoding::Number => match is_safe_integer(&number) || number.is_f64() {
+                    true => Value::Number(number),
+                    false => Value::String(number.to_string()),
+                },
+                NumericEncoding::String => Value::String(number.to_string()),
+                NumericEncoding::Both => {
+                    let hex = match (number.as_u64(), number.as_i64()) {
+                        (Some(number), _) => Some(format!("0x{number:x}")),
+                        (None, Some(number)) => Some(format!("-0x{:x}", number.unsigned_abs())),
+                        (None, None) => None,
+                    };
+                    serde_json::json!({ "dec": number.to_string(), "hex": hex })
+                }
+            },
+            value => value,
+        }
+    }
+
+    /// Encodes the given decimal value of an integer, with its hex form and bit width, or of a field element.
+    fn encode_decimal(&self, decimal: String, integer: Option<&(Value, u64)>) -> Value {
+        match (self.numbers, integer) {
+            (NumericEncoding::Number, Some((_, bits))) if *bits <= 64 => {
+                match decimal.parse::<i64>().map(serde_json::Number::from) {
+                    Ok(number) if is_safe_integer(&number) => Value::Number(number),
+                    _ => Value::String(decimal),
+                }
+            }
+            (NumericEncoding::Both, Some((hex, _))) => serde_json::json!({ "dec": decimal, "hex": hex }),
+            (NumericEncoding::Both, None) => {
+                let hex = field_hex(&decimal);
+                serde_json::json!({ "dec": decimal, "hex": hex })
+            }
+            _ => Value::String(decimal),
+        }
+    }
+}
+
+/// Returns `true` if the given number is an integer of at most `MAX_SAFE_INTEGER` in magnitude.
+fn is_safe_integer(number: &serde_json::Number) -> bool {
+    match (number.as_u64(), number.as_i64()) {
+        (Some(number), _) => number <= MAX_SAFE_INTEGER,
+        (None, Some(number)) => number.unsigned_abs() <= MAX_SAFE_INTEGER,
+        (None, None) => false,
+    }
+}
+
+/// Returns `true` if the given value is `null`, an empty array, or an empty object.
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(array) => array.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+/// Returns the affine coordinates of the given group element JSON, recovered from its x-coordinate,
+/// or `None` if the object is not a group element.
+fn group_coordinates(object: &Map<String, Value>) -> Option<(Value, Value)> {
+    if object.get("type").and_then(Value::as_str) != Some("Group") {
+        return None;
+    }
+    let x_coordinate = object.get("group").and_then(Value::as_str)?;
+    let field = Field::<Testnet3>::from_str(&format!("{x_coordinate}field")).ok()?;
+    let (x, y) = Group::from_x_coordinate(field).ok()?.to_xy_coordinates();
+    Some((serde_json::json!(*x), serde_json::json!(*y)))
+}
+
+/// Returns the hex form of the given decimal field element, most significant byte first,
+/// or `None` if it is not a field element.
+fn field_hex(decimal: &str) -> Option<String> {
+    let field = Field::<Testnet3>::from_str(&format!("{decimal}field")).ok()?;
+    let mut bytes = field.to_bytes_le().ok()?;
+    bytes.reverse();
+    Some(format!("0x{}", to_hex(&bytes)))
+}
+
+/// Returns the given bytes as a lowercase hex string.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleo2json::NamingConvention;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_default_options() {
+        let document = json!({ "type": "Node", "str": "add r0 r1 into r2;", "operands": [], "index": 1 });
+        assert_eq!(JsonOptions::default().finish(document.clone()), document);
+    }
+
+    #[test]
+    fn test_finish() {
+        let document = json!({
+            "type": "Node",
+            "str": "add r0 r1 into r2;",
+            "operands": [],
+            "fee": null,
+            "index": 1,
+            "members": { "str": 2 },
+        });
+        let options = JsonOptions {
+            verbosity: Verbosity::Compact,
+            include_text: false,
+            numbers: NumericEncoding::String,
+            naming: KeyNaming::new(NamingConvention::CamelCase),
+            ..Default::default()
+        };
+        // The members are keyed by name, so the `str` member is kept.
+        assert_eq!(options.finish(document), json!({ "type": "Node", "index": "1", "members": { "str": "2" } }));
+    }
+
+    #[test]
+    fn test_numeric_encodings() {
+        let document = json!({
+            "type": "Node",
+            "height": 255,
+            "u64": { "type": "Integer", "vtype": "u64", "integer": "255", "hex": "0xff", "bits": 64 },
+            "i8": { "type": "Integer", "vtype": "i8", "integer": "-1", "hex": "0xff", "bits": 8 },
+            "u64_max": {
+                "type": "Integer",
+                "vtype": "u64",
+                "integer": "18446744073709551615",
+                "hex": "0xffffffffffffffff",
+                "bits": 64,
+            },
+            "large": 9007199254740992u64,
+            "negative": -1,
+            "u128": { "type": "Integer", "vtype": "u128", "integer": "255", "hex": "0xff", "bits": 128 },
+            "field": { "type": "Field", "field": "255" },
+        });
+        for encoding in
+            [NumericEncoding::Native, NumericEncoding::String, NumericEncoding::Number, NumericEncoding::Both]
+        {
+            assert_eq!(NumericEncoding::from_str(&encoding.to_string()).unwrap(), encoding);
+        }
+
+        // The values of the integers of up to 64 bits become numbers; wider values and field elements do not.
+        let options = JsonOptions { numbers: NumericEncoding::Number, ..Default::default() };
+        let encoded = options.finish(document.clone());
+        assert_eq!(encoded["height"], 255);
+        assert_eq!(encoded["u64"]["integer"], 255);
+        assert_eq!(encoded["i8"]["integer"], -1);
+        // Numbers and values beyond 53 bits are not exactly representable by every consumer.
+        assert_eq!(encoded["u64_max"]["integer"], "18446744073709551615");
+        assert_eq!(encoded["large"], "9007199254740992");
+        assert_eq!(encoded["negative"], -1);
+        assert_eq!(encoded["u128"]["integer"], "255");
+        assert_eq!(encoded["field"]["field"], "255");
+
+        // Both forms are emitted for every number and value.
+        let options = JsonOptions { numbers: NumericEncoding::Both, ..Default::default() };
+        let encoded = options.finish(document);
+        assert_eq!(encoded["height"], json!({ "dec": "255", "hex": "0xff" }));
+        assert_eq!(encoded["i8"]["integer"], json!({ "dec": "-1", "hex": "0xff" }));
+        assert_eq!(encoded["negative"], json!({ "dec": "-1", "hex": "-0x1" }));
+        assert_eq!(encoded["u128"]["integer"], json!({ "dec": "255", "hex": "0xff" }));
+        assert_eq!(encoded["field"]["field"], json!({ "dec": "255", "hex": format!("0x{}ff", "0".repeat(62)) }));
+    }
+
+    #[test]
+    fn test_address_fields() {
+        let document = json!({ "type": "Address", "address": { "type": "Group", "group": "1" }, "bech32": "aleo1" });
+        let options = JsonOptions { include_address_fields: false, ..Default::default() };
+        assert_eq!(options.finish(document), json!({ "type": "Address", "bech32": "aleo1" }));
+    }
+
+    #[test]
+    fn test_group_coordinates() {
+        use crate::console::network::prelude::{TestRng, Uniform, Zero};
+
+        let group = Group::<Testnet3>::rand(&mut TestRng::default());
+        let (x, y) = group.to_xy_coordinates();
+        let options = JsonOptions { include_group_coordinates: true, ..Default::default() };
+        let document = options.finish(json!({ "type": "Literal", "value": group.to_json() }));
+        assert_eq!(document["value"]["group"], group.to_json()["group"]);
+        assert_eq!((&document["value"]["x"], &document["value"]["y"]), (&json!(*x), &json!(*y)));
+
+        // The identity is the point (0, 1).
+        let document = options.finish(Group::<Testnet3>::zero().to_json());
+        assert_eq!((&document["x"], &document["y"]), (&json!("0"), &json!("1")));
+    }
+
+    #[test]
+    fn test_to_hex() {
+        assert_eq!(to_hex(&[]), "");
+        assert_eq!(to_hex(&[0, 15, 255]), "000fff");
+    }
+}
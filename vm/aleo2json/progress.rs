@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fmt,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+/// The width of a progress bar, in characters.
+const BAR_WIDTH: usize = 30;
+
+/// The progress of a long-running conversion, i.e. of a block range or a ledger export.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of items converted so far.
+    pub completed: usize,
+    /// The total number of items to convert.
+    pub total: usize,
+}
+
+impl Progress {
+    /// Returns the fraction of the items converted so far, between 0 and 1.
+    pub fn fraction(&self) -> f64 {
+        match self.total {
+            0 => 1.0,
+            total => self.completed as f64 / total as f64,
+        }
+    }
+
+    /// Returns the time remaining, extrapolated from the time taken to convert the items so far,
+    /// or `None` if no item was converted yet.
+    pub fn eta(&self, elapsed: Duration) -> Option<Duration> {
+        match self.completed {
+            0 => None,
+            completed => Some(elapsed.mul_f64(self.total.saturating_sub(completed) as f64 / completed as f64)),
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    /// Prints the progress as `{completed}/{total}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.completed, self.total)
+    }
+}
+
+/// A callback invoked with the progress of a long-running conversion, after each converted item or batch of items.
+pub type ProgressCallback = dyn Fn(Progress) + Send + Sync;
+
+/// A progress bar for a long-running conversion, redrawn in place on stderr.
+#[derive(Clone, Debug)]
+pub struct ProgressBar {
+    /// The kind of the items converted, i.e. `blocks`.
+    label: String,
+    /// The time the conversion started.
+    started: Instant,
+}
+
+impl ProgressBar {
+    /// Initializes a progress bar for the given kind of items, starting now.
+    pub fn new(label: &str) -> Self {
+        Self { label: label.to_string(), started: Instant::now() }
+    }
+
+    /// Renders the given progress, after the given time, i.e. `[=========      ] 45/100 blocks (45%), ETA 00:01:12`.
+    pub fn render(&self, progress: Progress, elapsed: Duration) -> String {
+        let filled = ((progress.fraction() * BAR_WIDTH as f64) as usize).min(BAR_WIDTH);
+        let eta = match progress.eta(elapsed) {
+            Some(eta) => format_duration(eta),
+            None => "--:--:--".to_string(),
+        };
+        format!(
+            "[{}{}] {progress} {} ({:.0}%), ETA {eta}",
+            "=".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            self.label,
+            progress.fraction() * 100.0
+        )
+    }
+
+    /// Redraws the progress bar with the given progress, ending the line once every item is converted.
+    pub fn report(&self, progress: Progress) {
+        let line = self.render(progress, self.started.elapsed());
+        let mut stderr = std::io::stderr().lock();
+        // The progress is best-effort, so a failure to write it is ignored.
+        let _ = match progress.completed >= progress.total {
+            true => writeln!(stderr, "\r{line}"),
+            false => write!(stderr, "\r{line}").and_then(|_| stderr.flush()),
+        };
+    }
+}
+
+/// Returns the given duration as `hh:mm:ss`.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let progress = Progress { completed: 3, total: 4 };
+        assert_eq!(progress.to_string(), "3/4");
+        assert_eq!(progress.fraction(), 0.75);
+        assert_eq!(Progress { completed: 0, total: 0 }.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_eta() {
+        let progress = Progress { completed: 1, total: 4 };
+        assert_eq!(progress.eta(Duration::from_secs(10)), Some(Duration::from_secs(30)));
+        assert_eq!(Progress { completed: 0, total: 4 }.eta(Duration::from_secs(10)), None);
+        assert_eq!(Progress { completed: 4, total: 4 }.eta(Duration::from_secs(10)), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        let bar = ProgressBar::new("blocks");
+        assert_eq!(
+            bar.render(Progress { completed: 1, total: 3 }, Duration::from_secs(3661)),
+            format!("[{}{}] 1/3 blocks (33%), ETA 02:02:02", "=".repeat(10), " ".repeat(20))
+        );
+        assert_eq!(
+            bar.render(Progress { completed: 0, total: 3 }, Duration::ZERO),
+            format!("[{}] 0/3 blocks (0%), ETA --:--:--", " ".repeat(30))
+        );
+    }
+}
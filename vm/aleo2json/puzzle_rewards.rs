@@ -0,0 +1,90 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::network::Network,
+    ledger::block::{Block, Ratify},
+    synthesizer::proving_rewards,
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// Returns the attribution of the puzzle reward of the given block to its prover solutions: for each solution,
+/// its puzzle commitment, the address of its prover, its proof target, and its share of the combined proof target,
+/// and for each prover, its reward, in microcredits, as computed by `proving_rewards` when the reward is ratified.
+///
+/// The puzzle reward is `null`, and no prover is rewarded, if the block does not ratify one.
+pub fn puzzle_reward_attribution_to_json<N: Network>(block: &Block<N>) -> Result<Value> {
+    let puzzle_reward = block.ratifications().iter().find_map(|ratify| match ratify {
+        Ratify::PuzzleReward(amount) => Some(*amount),
+        _ => None,
+    });
+
+    let solutions = match block.solutions() {
+        Some(solutions) => solutions.values().collect::<Vec<_>>(),
+        None => Vec::new(),
+    };
+    let proof_targets = solutions.iter().map(|solution| solution.to_target()).collect::<Result<Vec<_>>>()?;
+    let combined_proof_target = proof_targets.iter().map(|target| *target as u128).sum::<u128>();
+    let rewards = proving_rewards(
+        solutions.iter().map(|solution| solution.address()).zip(proof_targets.iter().copied()).collect(),
+        puzzle_reward.unwrap_or_default(),
+    );
+
+    let j_solutions = solutions
+        .iter()
+        .zip(&proof_targets)
+        .map(|(solution, proof_target)| {
+            json!({
+                "puzzle_commitment": solution.commitment().to_string(),
+                "address": solution.address().to_string(),
+                "proof_target": proof_target,
+                "target_share": *proof_target as f64 / combined_proof_target as f64,
+            })
+        })
+        .collect::<Vec<_>>();
+    let j_rewards = rewards
+        .iter()
+        .map(|(address, reward)| json!({ "address": address.to_string(), "reward": reward }))
+        .collect::<Vec<_>>();
+
+    Ok(json!({
+        "type": "PuzzleRewardAttribution",
+        "puzzle_reward": puzzle_reward,
+        "combined_proof_target": combined_proof_target.to_string(),
+        "solutions": j_solutions,
+        "rewards": j_rewards,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_genesis_puzzle_reward_attribution() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let j_attribution = puzzle_reward_attribution_to_json(&block).unwrap();
+        assert_eq!(j_attribution["type"], "PuzzleRewardAttribution");
+        // The genesis block has no solutions, and ratifies no puzzle reward.
+        assert_eq!(j_attribution["puzzle_reward"], Value::Null);
+        assert_eq!(j_attribution["combined_proof_target"], "0");
+        assert_eq!(j_attribution["solutions"], json!([]));
+        assert_eq!(j_attribution["rewards"], json!([]));
+    }
+}
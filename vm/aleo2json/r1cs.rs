@@ -0,0 +1,215 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    circuit::{
+        self,
+        environment::{AssignmentLC, AssignmentVariable},
+    },
+    console::{
+        account::{Address, PrivateKey},
+        network::Network,
+        program::{Identifier, Request, ValueType},
+    },
+    ledger::store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    synthesizer::{
+        process::{Assignments, CallStack, StackExecute},
+        program::StackProgram,
+        CallMetrics,
+        Program,
+        VM,
+    },
+};
+
+use anyhow::Result;
+use rand::{CryptoRng, Rng};
+use serde_json::json;
+use std::str::FromStr;
+
+/// The circuit synthesized for a call to a function: its call metrics and its R1CS assignment.
+#[derive(Clone, Debug)]
+pub struct FunctionCircuit<N: Network> {
+    /// The metrics of the call, splitting its constraints between the request, the function body, and the response.
+    pub metrics: CallMetrics<N>,
+    /// The assignment of the circuit, i.e. its variables and constraints.
+    pub assignment: circuit::Assignment<N::Field>,
+}
+
+impl<N: Network> FunctionCircuit<N> {
+    /// Returns the constraints of the circuit as JSON, one object per constraint `a * b = c`.
+    ///
+    /// Each linear combination is an object with its constant and its terms, where a term is a pair of a variable
+    /// and its coefficient. Public variables are named `x<index>`, private variables `w<index>`,
+    /// and constant variables are given by their value.
+    pub fn constraints_to_json(&self) -> serde_json::Value {
+        let lc_to_json = |lc: &AssignmentLC<N::Field>| {
+            let mut j_terms = Vec::new();
+            for (variable, coefficient) in lc.terms() {
+                let j_variable = match variable {
+                    AssignmentVariable::Constant(value) => format!("{}", value),
+                    AssignmentVariable::Public(index) => format!("x{}", index),
+                    AssignmentVariable::Private(index) => format!("w{}", index),
+                };
+                j_terms.push(json!([j_variable, format!("{}", coefficient)]));
+            }
+            json!({ "constant": format!("{}", lc.constant()), "terms": j_terms })
+        };
+
+        let mut j_constraints = Vec::new();
+        for (a, b, c) in self.assignment.constraints() {
+            j_constraints.push(json!({ "a": lc_to_json(a), "b": lc_to_json(b), "c": lc_to_json(c) }));
+        }
+        serde_json::Value::Array(j_constraints)
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self, include_constraints: bool) -> serde_json::Value {
+        let (num_nonzeros_a, num_nonzeros_b, num_nonzeros_c) = self.assignment.num_nonzeros();
+        let mut j_circuit = json!({
+            "type": "FunctionCircuit",
+            "program_id": format!("{}", self.metrics.program_id),
+            "function": format!("{}", self.metrics.function_name),
+            "num_instructions": self.metrics.num_instructions,
+            "num_public": self.assignment.num_public(),
+            "num_private": self.assignment.num_private(),
+            "num_constraints": self.assignment.num_constraints(),
+            "num_request_constraints": self.metrics.num_request_constraints,
+            "num_function_constraints": self.metrics.num_function_constraints,
+            "num_response_constraints": self.metrics.num_response_constraints,
+            "num_nonzeros": {
+                "a": num_nonzeros_a,
+                "b": num_nonzeros_b,
+                "c": num_nonzeros_c,
+            },
+        });
+        if include_constraints {
+            j_circuit["constraints"] = self.constraints_to_json();
+        }
+        j_circuit
+    }
+}
+
+/// Synthesizes the circuit of the given function of the program on sampled inputs, signed by a burner private key,
+/// as when a deployment is verified.
+///
+/// Returns the circuit of each call in the order they complete, so calls to other functions come first,
+/// and the circuit of the function itself is last. The program may only import `credits.aleo`.
+pub fn synthesize_function<N: Network, A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+    program: &Program<N>,
+    function_name: &str,
+    rng: &mut R,
+) -> Result<Vec<FunctionCircuit<N>>> {
+    let function = program.get_function(&Identifier::from_str(function_name)?)?;
+
+    // Initialize the VM on an empty, in-memory store, and add the program to it.
+    let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(None)?)?;
+    let process = vm.process();
+    if !process.read().contains_program(program.id()) {
+        process.write().add_program(program)?;
+    }
+    let process = process.read();
+    let stack = process.get_stack(*program.id())?;
+
+    // Sample the inputs for a burner private key.
+    let burner_private_key = PrivateKey::new(rng)?;
+    let burner_address = Address::try_from(&burner_private_key)?;
+    let input_types = function.input_types();
+    let inputs = input_types
+        .iter()
+        .map(|input_type| match input_type {
+            ValueType::ExternalRecord(locator) => stack.get_external_stack(locator.program_id())?.sample_value(
+                &burner_address,
+                &ValueType::Record(*locator.resource()),
+                rng,
+            ),
+            _ => stack.sample_value(&burner_address, input_type, rng),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let request =
+        Request::sign(&burner_private_key, *program.id(), *function.name(), inputs.into_iter(), &input_types, rng)?;
+
+    // Synthesize the circuit, collecting the assignment of each call.
+    let assignments = Assignments::<N>::default();
+    let call_stack = CallStack::CheckDeployment(vec![request], burner_private_key, assignments.clone());
+    stack.execute_function::<A, R>(call_stack, None, rng)?;
+
+    let circuits = assignments
+        .read()
+        .iter()
+        .map(|(assignment, metrics)| FunctionCircuit { metrics: *metrics, assignment: assignment.clone() })
+        .collect();
+    Ok(circuits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circuit::AleoV0, prelude::Testnet3};
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"
+program circuit.aleo;
+
+function double:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;
+
+function quadruple:
+    input r0 as u8.private;
+    call double r0 into r1;
+    call double r1 into r2;
+    output r2 as u8.private;
+";
+
+    fn synthesize(function_name: &str) -> Vec<FunctionCircuit<CurrentNetwork>> {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let program = Program::<CurrentNetwork>::from_str(PROGRAM).unwrap();
+        synthesize_function::<CurrentNetwork, AleoV0, _>(&program, function_name, rng).unwrap()
+    }
+
+    #[test]
+    fn test_synthesize_function() {
+        let circuits = synthesize("double");
+        assert_eq!(circuits.len(), 1);
+
+        let circuit = &circuits[0];
+        assert_eq!(circuit.metrics.num_instructions, 1);
+        assert!(circuit.metrics.num_function_constraints > 0);
+        assert_eq!(
+            circuit.assignment.num_constraints(),
+            circuit.metrics.num_request_constraints
+                + circuit.metrics.num_function_constraints
+                + circuit.metrics.num_response_constraints
+        );
+
+        let j_circuit = circuit.to_json(false);
+        assert_eq!(j_circuit["type"], "FunctionCircuit");
+        assert_eq!(j_circuit["function"], "double");
+        assert!(j_circuit.get("constraints").is_none());
+
+        let j_circuit = circuit.to_json(true);
+        assert_eq!(j_circuit["constraints"].as_array().unwrap().len() as u64, circuit.assignment.num_constraints());
+    }
+
+    #[test]
+    fn test_synthesize_function_with_calls() {
+        let circuits = synthesize("quadruple");
+        let functions = circuits.iter().map(|circuit| circuit.metrics.function_name.to_string()).collect::<Vec<_>>();
+        assert_eq!(functions, ["double", "double", "quadruple"]);
+    }
+}
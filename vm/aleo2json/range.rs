@@ -0,0 +1,499 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        block_deployments_to_json,
+        block_to_json_with_options,
+        with_provenance,
+        BlockOptions,
+        ConversionManifest,
+        ExportCheckpoint,
+        ExportIndex,
+        IndexLocation,
+        JsonOptions,
+        ManifestEntry,
+        OutputSink,
+        Progress,
+        ProgressCallback,
+        Provenance,
+        RestClient,
+        WriterSink,
+    },
+    console::network::{prelude::ToBytes, Network},
+    ledger::{
+        block::Block,
+        store::{BlockStorage, BlockStore},
+    },
+};
+
+use anyhow::{bail, ensure, Result};
+use rayon::prelude::*;
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Seek, SeekFrom},
+    ops::RangeInclusive,
+    path::Path,
+};
+use tracing::{debug, info_span};
+
+/// The number of blocks fetched per worker thread before the batch is written out.
+const BLOCKS_PER_WORKER: usize = 4;
+
+/// A source of blocks, either local storage or a node's REST API.
+#[derive(Clone)]
+pub enum BlockSource<N: Network, B: BlockStorage<N>> {
+    /// The local block store.
+    Storage(BlockStore<N, B>),
+    /// The client of the node.
+    REST(RestClient),
+}
+
+impl<N: Network, B: BlockStorage<N>> From<BlockStore<N, B>> for BlockSource<N, B> {
+    fn from(block_store: BlockStore<N, B>) -> Self {
+        Self::Storage(block_store)
+    }
+}
+
+impl<N: Network, B: BlockStorage<N>> From<RestClient> for BlockSource<N, B> {
+    fn from(client: RestClient) -> Self {
+        Self::REST(client)
+    }
+}
+
+impl<N: Network, B: BlockStorage<N>> From<String> for BlockSource<N, B> {
+    fn from(url: String) -> Self {
+        Self::REST(RestClient::new(&url))
+    }
+}
+
+impl<N: Network, B: BlockStorage<N>> From<&str> for BlockSource<N, B> {
+    fn from(url: &str) -> Self {
+        Self::REST(RestClient::new(url))
+    }
+}
+
+impl<N: Network, B: BlockStorage<N>> BlockSource<N, B> {
+    /// Returns the block at the given height.
+    pub fn get_block(&self, height: u32) -> Result<Block<N>> {
+        match self {
+            Self::Storage(block_store) => {
+                let block = match block_store.get_block_hash(height)? {
+                    Some(hash) => block_store.get_block(&hash)?,
+                    None => None,
+                };
+                match block {
+                    Some(block) => Ok(block),
+                    None => bail!("Missing block at height {height} in storage"),
+                }
+            }
+            Self::REST(client) => client.get_block(height),
+        }
+    }
+}
+
+/// Converts the blocks in the given range of heights, writing each block JSON to the given sink.
+///
+/// Blocks are fetched and converted by up to `concurrency` threads in parallel, and are always written
+/// in order of height. If an index is given, each transaction and transition is recorded in it, at the
+/// byte offset of its block's document, so the sink must report offsets.
+/// Each block includes the optional sections selected by the given options.
+/// Returns the number of blocks written.
+pub fn export_block_range<N: Network, B: BlockStorage<N>, S: OutputSink + ?Sized>(
+    source: &BlockSource<N, B>,
+    heights: RangeInclusive<u32>,
+    concurrency: usize,
+    options: &BlockOptions,
+    sink: &mut S,
+    index: Option<&mut ExportIndex>,
+) -> Result<usize> {
+    export_block_range_with_progress(source, heights, concurrency, options, sink, index, None, None)
+}
+
+/// Converts the blocks in the given range of heights, as `export_block_range` does,
+/// reporting the number of blocks written to the given callback after each batch.
+/// If a manifest is given, each block is recorded in it, with its hash, the checksum of its bytes,
+/// and the byte offset of its document, if the sink reports offsets.
+#[allow(clippy::too_many_arguments)]
+pub fn export_block_range_with_progress<N: Network, B: BlockStorage<N>, S: OutputSink + ?Sized>(
+    source: &BlockSource<N, B>,
+    heights: RangeInclusive<u32>,
+    concurrency: usize,
+    options: &BlockOptions,
+    sink: &mut S,
+    mut index: Option<&mut ExportIndex>,
+    mut manifest: Option<&mut ConversionManifest>,
+    progress: Option<&ProgressCallback>,
+) -> Result<usize> {
+    ensure!(!heights.is_empty(), "The starting height must not exceed the ending height");
+    ensure!(concurrency > 0, "The concurrency must be greater than zero");
+
+    let _span = info_span!("export_block_range", from = heights.start(), to = heights.end(), concurrency).entered();
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(concurrency).build()?;
+    let heights: Vec<u32> = heights.collect();
+
+    let mut num_blocks = 0;
+    for batch in heights.chunks(concurrency * BLOCKS_PER_WORKER) {
+        // Fetch and convert the batch in parallel; the collected documents retain the order of the heights.
+        let documents = pool.install(|| {
+            batch
+                .par_iter()
+                .map(|height| {
+                    let block = source.get_block(*height)?;
+                    let provenance = options.json.provenance::<N>(&block.to_bytes_le()?)?;
+                    let j_block = block_to_json_with_options(&block, options)?;
+                    let document = options.json.finish(with_provenance(j_block, &provenance));
+                    Ok((document, block, provenance.input_checksum))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        for (document, block, input_checksum) in documents {
+            let offset = sink.write_document(&document)?;
+            if let Some(index) = index.as_deref_mut() {
+                let offset = match offset {
+                    Some(offset) => offset,
+                    None => bail!("An index requires an output sink reporting byte offsets"),
+                };
+                let location = IndexLocation { block_height: block.height(), file: None, offset };
+                for transaction in block.transactions().iter() {
+                    index.insert(transaction, &location);
+                }
+            }
+            if let Some(manifest) = manifest.as_deref_mut() {
+                manifest.push(ManifestEntry {
+                    output: None,
+                    offset,
+                    input: format!("{}", block.hash()),
+                    // The checksum of the block bytes is the one recorded in its provenance.
+                    checksum: input_checksum.unwrap_or_default(),
+                    height: Some(block.height()),
+                    edition: None,
+                });
+            }
+        }
+        sink.flush()?;
+        num_blocks += batch.len();
+
+        debug!("Exported {num_blocks} of {} blocks", heights.len());
+        if let Some(progress) = progress {
+            progress(Progress { completed: num_blocks, total: heights.len() });
+        }
+    }
+    Ok(num_blocks)
+}
+
+/// Converts the blocks left in the given checkpoint's range into the given output file, as
+/// `export_block_range_with_progress` does, saving the checkpoint to the given path after every `interval` blocks.
+///
+/// If the checkpoint has progress, i.e. as read back after an interruption, the output file is truncated to
+/// the blocks the checkpoint covers, and the export resumes after them, extending the checkpoint's index and manifest.
+/// Returns the number of blocks written by this call.
+#[allow(clippy::too_many_arguments)]
+pub fn export_block_range_resumable<N: Network, B: BlockStorage<N>>(
+    source: &BlockSource<N, B>,
+    concurrency: usize,
+    options: &BlockOptions,
+    output: &Path,
+    checkpoint: &mut ExportCheckpoint,
+    checkpoint_path: &Path,
+    interval: u32,
+    progress: Option<&ProgressCallback>,
+) -> Result<usize> {
+    ensure!(interval > 0, "The checkpoint interval must be greater than zero");
+
+    // Discard the blocks written after the checkpoint was saved.
+    let mut file = match checkpoint.last_height {
+        Some(_) => {
+            let file = OpenOptions::new().write(true).open(output)?;
+            ensure!(
+                file.metadata()?.len() >= checkpoint.output_length,
+                "The output file is shorter than its checkpoint; restart the export without the checkpoint"
+            );
+            file.set_len(checkpoint.output_length)?;
+            file
+        }
+        None => File::create(output)?,
+    };
+    file.seek(SeekFrom::End(0))?;
+    let mut sink = WriterSink::with_offset(BufWriter::new(file), checkpoint.output_length);
+
+    let total = (checkpoint.to_height - checkpoint.from_height) as usize + 1;
+    let mut num_blocks = 0;
+    while let Some(remaining) = checkpoint.remaining() {
+        let start = *remaining.start();
+        let end = start.saturating_add(interval - 1).min(*remaining.end());
+        // Report the progress over the whole range, including the blocks written before resuming.
+        let completed = (start - checkpoint.from_height) as usize;
+        let report = |batch: Progress| {
+            if let Some(progress) = progress {
+                progress(Progress { completed: completed + batch.completed, total })
+            }
+        };
+        num_blocks += export_block_range_with_progress(
+            source,
+            start..=end,
+            concurrency,
+            options,
+            &mut sink,
+            checkpoint.index.as_mut(),
+            checkpoint.manifest.as_mut(),
+            Some(&report as &ProgressCallback),
+        )?;
+        checkpoint.last_height = Some(end);
+        checkpoint.output_length = sink.offset();
+        checkpoint.write(checkpoint_path)?;
+        debug!("Saved a checkpoint at height {end}");
+    }
+    Ok(num_blocks)
+}
+
+/// Lists the deployments accepted in the blocks in the given range of heights, writing each deployment listing
+/// to the given sink, so an on-chain program registry can be built without converting whole blocks.
+///
+/// Blocks are fetched by up to `concurrency` threads in parallel, and the deployments are always written
+/// in order of height, then of transaction. Each program includes the optional sections selected by the given options.
+/// Returns the number of deployments written.
+pub fn export_deployment_range<N: Network, B: BlockStorage<N>, S: OutputSink + ?Sized>(
+    source: &BlockSource<N, B>,
+    heights: RangeInclusive<u32>,
+    concurrency: usize,
+    options: &JsonOptions,
+    sink: &mut S,
+) -> Result<usize> {
+    ensure!(!heights.is_empty(), "The starting height must not exceed the ending height");
+    ensure!(concurrency > 0, "The concurrency must be greater than zero");
+
+    let _span =
+        info_span!("export_deployment_range", from = heights.start(), to = heights.end(), concurrency).entered();
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(concurrency).build()?;
+    let heights: Vec<u32> = heights.collect();
+
+    let mut num_deployments = 0;
+    for batch in heights.chunks(concurrency * BLOCKS_PER_WORKER) {
+        // Fetch and list the batch in parallel; the collected listings retain the order of the heights.
+        let listings = pool.install(|| {
+            batch
+                .par_iter()
+                .map(|height| block_deployments_to_json(&source.get_block(*height)?, options))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        for listing in listings.into_iter().flatten() {
+            let provenance = Provenance { network: Some(N::ID), ..Default::default() };
+            sink.write_document(&options.finish(with_provenance(listing, &provenance)))?;
+            num_deployments += 1;
+        }
+        sink.flush()?;
+        debug!("Listed {num_deployments} deployments in {} blocks", batch.len());
+    }
+    Ok(num_deployments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{checksum, WriterSink},
+        console::network::prelude::FromBytes,
+        ledger::store::helpers::memory::BlockMemory,
+        prelude::Testnet3,
+    };
+
+    use serde_json::Value;
+
+    type CurrentNetwork = Testnet3;
+
+    fn sample_source() -> BlockSource<CurrentNetwork, BlockMemory<CurrentNetwork>> {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let block_store = BlockStore::<CurrentNetwork, BlockMemory<_>>::open(None).unwrap();
+        block_store.insert(&genesis).unwrap();
+        BlockSource::from(block_store)
+    }
+
+    #[test]
+    fn test_export_block_range() {
+        let source = sample_source();
+
+        let mut output = Vec::new();
+        assert_eq!(
+            export_block_range(&source, 0..=0, 2, &BlockOptions::default(), &mut WriterSink::new(&mut output), None)
+                .unwrap(),
+            1
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        let blocks: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0]["header"]["metadata"]["height"], 0);
+    }
+
+    #[test]
+    fn test_export_deployment_range() {
+        let source = sample_source();
+
+        let mut output = Vec::new();
+        let num_deployments =
+            export_deployment_range(&source, 0..=0, 2, &JsonOptions::default(), &mut WriterSink::new(&mut output))
+                .unwrap();
+
+        let genesis = source.get_block(0).unwrap();
+        let listings = block_deployments_to_json(&genesis, &JsonOptions::default()).unwrap();
+        assert_eq!(num_deployments, listings.len());
+        assert_eq!(String::from_utf8(output).unwrap().lines().count(), listings.len());
+    }
+
+    #[test]
+    fn test_export_block_range_merkle_paths() {
+        let source = sample_source();
+
+        let mut output = Vec::new();
+        let options = BlockOptions { merkle_paths: true, ..Default::default() };
+        export_block_range(&source, 0..=0, 1, &options, &mut WriterSink::new(&mut output), None).unwrap();
+
+        let block: Value = serde_json::from_str(String::from_utf8(output).unwrap().trim_end()).unwrap();
+        for transaction in block["transactions"].as_array().unwrap() {
+            assert_eq!(transaction["merkle_paths"]["transactions_path"]["type"], "MerklePath");
+        }
+    }
+
+    #[test]
+    fn test_export_block_range_progress() {
+        let source = sample_source();
+        let reports = std::sync::Mutex::new(Vec::new());
+        let callback = |progress: Progress| reports.lock().unwrap().push(progress);
+
+        let mut output = Vec::new();
+        export_block_range_with_progress(
+            &source,
+            0..=0,
+            1,
+            &BlockOptions::default(),
+            &mut WriterSink::new(&mut output),
+            None,
+            None,
+            Some(&callback),
+        )
+        .unwrap();
+        assert_eq!(reports.into_inner().unwrap(), vec![Progress { completed: 1, total: 1 }]);
+    }
+
+    #[test]
+    fn test_export_block_range_index() {
+        let source = sample_source();
+
+        let mut output = Vec::new();
+        let mut index = ExportIndex::new();
+        export_block_range(
+            &source,
+            0..=0,
+            1,
+            &BlockOptions::default(),
+            &mut WriterSink::new(&mut output),
+            Some(&mut index),
+        )
+        .unwrap();
+
+        let genesis = source.get_block(0).unwrap();
+        assert_eq!(index.num_transactions(), genesis.transactions().len());
+        for transaction in genesis.transactions().iter() {
+            let location = index.get_transaction(&format!("{}", transaction.id())).unwrap();
+            assert_eq!(location.block_height, 0);
+            assert_eq!(location.offset, 0);
+        }
+    }
+
+    #[test]
+    fn test_export_block_range_manifest() {
+        let source = sample_source();
+
+        let mut output = Vec::new();
+        let mut manifest = ConversionManifest::new();
+        let options = BlockOptions::default();
+        let mut sink = WriterSink::new(&mut output);
+        export_block_range_with_progress(&source, 0..=0, 1, &options, &mut sink, None, Some(&mut manifest), None)
+            .unwrap();
+
+        let genesis = source.get_block(0).unwrap();
+        assert_eq!(manifest.entries(), [ManifestEntry {
+            output: None,
+            offset: Some(0),
+            input: format!("{}", genesis.hash()),
+            checksum: checksum(&genesis.to_bytes_le().unwrap()).unwrap(),
+            height: Some(0),
+            edition: None,
+        }]);
+    }
+
+    #[test]
+    fn test_export_block_range_sink() {
+        let source = sample_source();
+
+        let mut documents: Vec<Value> = Vec::new();
+        export_block_range(&source, 0..=0, 1, &BlockOptions::default(), &mut documents, None).unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0]["header"]["metadata"]["height"], 0);
+
+        // An index requires the byte offsets of the documents.
+        let mut index = ExportIndex::new();
+        assert!(
+            export_block_range(&source, 0..=0, 1, &BlockOptions::default(), &mut documents, Some(&mut index)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_export_block_range_resumable() {
+        let source = sample_source();
+        let directory = tempfile::tempdir().unwrap();
+        let (output, path) = (directory.path().join("blocks.jsonl"), directory.path().join("checkpoint.json"));
+
+        let mut checkpoint = ExportCheckpoint::new(&(0..=0), true, true);
+        let options = BlockOptions::default();
+        assert_eq!(
+            export_block_range_resumable(&source, 1, &options, &output, &mut checkpoint, &path, 1, None).unwrap(),
+            1
+        );
+        assert_eq!(checkpoint.remaining(), None);
+        assert_eq!(checkpoint.output_length, std::fs::metadata(&output).unwrap().len());
+        assert_eq!(ExportCheckpoint::read(&path).unwrap().to_json(), checkpoint.to_json());
+
+        // A block written after the checkpoint was saved is discarded on resumption.
+        let exported = std::fs::read_to_string(&output).unwrap();
+        std::fs::write(&output, format!("{exported}{{\"partial\"")).unwrap();
+        let mut checkpoint = ExportCheckpoint::read(&path).unwrap();
+        assert_eq!(
+            export_block_range_resumable(&source, 1, &options, &output, &mut checkpoint, &path, 1, None).unwrap(),
+            0
+        );
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), exported);
+        assert_eq!(checkpoint.manifest.unwrap().entries().len(), 1);
+    }
+
+    #[test]
+    fn test_export_block_range_errors() {
+        let source = sample_source();
+        // The block at height 1 does not exist.
+        assert!(
+            export_block_range(&source, 0..=1, 2, &BlockOptions::default(), &mut Vec::<Value>::new(), None).is_err()
+        );
+        // The range is empty.
+        #[allow(clippy::reversed_empty_ranges)]
+        let heights = 1..=0;
+        assert!(
+            export_block_range(&source, heights, 2, &BlockOptions::default(), &mut Vec::<Value>::new(), None).is_err()
+        );
+        // The concurrency is zero.
+        assert!(
+            export_block_range(&source, 0..=0, 0, &BlockOptions::default(), &mut Vec::<Value>::new(), None).is_err()
+        );
+    }
+}
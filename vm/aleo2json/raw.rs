@@ -0,0 +1,185 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::ToJson,
+    console::network::prelude::{
+        ser::{SerializeMap, SerializeSeq},
+        Serialize,
+        Serializer,
+    },
+};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::value::{to_raw_value, RawValue};
+
+/// Converts the given value into a raw JSON fragment, which can be embedded into larger documents without re-parsing.
+pub fn to_raw_json<T: ToJson + ?Sized>(value: &T) -> Result<Box<RawValue>> {
+    Ok(to_raw_value(&value.to_json())?)
+}
+
+/// A JSON object composed of already-serialized fragments.
+///
+/// Fragments are written verbatim when the document is serialized, so sub-objects that were
+/// converted earlier (e.g. the documents of a batch conversion, in `convert_files_to`) are never cloned into,
+/// or re-parsed from, a `Value` tree.
+#[derive(Clone, Debug, Default)]
+pub struct RawDocument {
+    /// The fields of the document, in insertion order.
+    fields: IndexMap<String, Box<RawValue>>,
+}
+
+impl RawDocument {
+    /// Initializes an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a field to an already-serialized value.
+    ///
+    /// A new field is appended; an existing field is replaced in place, so the document never repeats a key.
+    pub fn insert_raw(&mut self, key: impl Into<String>, value: Box<RawValue>) -> &mut Self {
+        self.fields.insert(key.into(), value);
+        self
+    }
+
+    /// Sets a field, as for `insert_raw`, serializing the given value once.
+    pub fn insert<T: Serialize + ?Sized>(&mut self, key: impl Into<String>, value: &T) -> Result<&mut Self> {
+        Ok(self.insert_raw(key, to_raw_value(value)?))
+    }
+
+    /// Sets a field, as for `insert_raw`, to the Vanguard JSON representation of the given value.
+    pub fn insert_json<T: ToJson + ?Sized>(&mut self, key: impl Into<String>, value: &T) -> Result<&mut Self> {
+        Ok(self.insert_raw(key, to_raw_json(value)?))
+    }
+
+    /// Returns the number of fields in the document.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns `true` if the document has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the document as a raw JSON fragment, for embedding into an enclosing document.
+    pub fn into_raw(self) -> Result<Box<RawValue>> {
+        Ok(to_raw_value(&self)?)
+    }
+}
+
+impl Serialize for RawDocument {
+    /// Serializes the document, writing each fragment verbatim.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for (key, value) in &self.fields {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// A JSON array composed of already-serialized fragments.
+#[derive(Clone, Debug, Default)]
+pub struct RawArray {
+    /// The elements of the array, in insertion order.
+    elements: Vec<Box<RawValue>>,
+}
+
+impl RawArray {
+    /// Initializes an empty array.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an already-serialized element.
+    pub fn push_raw(&mut self, element: Box<RawValue>) -> &mut Self {
+        self.elements.push(element);
+        self
+    }
+
+    /// Appends the Vanguard JSON representation of the given value.
+    pub fn push_json<T: ToJson + ?Sized>(&mut self, element: &T) -> Result<&mut Self> {
+        self.elements.push(to_raw_json(element)?);
+        Ok(self)
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns `true` if the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the array as a raw JSON fragment, for embedding into an enclosing document.
+    pub fn into_raw(self) -> Result<Box<RawValue>> {
+        Ok(to_raw_value(&self)?)
+    }
+}
+
+impl Serialize for RawArray {
+    /// Serializes the array, writing each fragment verbatim.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.elements.len()))?;
+        for element in &self.elements {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(all(test, feature = "aleo2json-synthesizer"))]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    use serde_json::json;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_raw_document() {
+        let program = parse_program::<CurrentNetwork>(
+            "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;",
+        )
+        .unwrap();
+        // Convert the program once, as a cache would.
+        let cached = to_raw_json(&program).unwrap();
+
+        let mut programs = RawArray::new();
+        programs.push_raw(cached.clone()).push_raw(cached);
+
+        let mut document = RawDocument::new();
+        document.insert("height", &7u32).unwrap().insert_raw("programs", programs.into_raw().unwrap());
+        assert_eq!(document.len(), 2);
+
+        let document: serde_json::Value = serde_json::from_str(document.into_raw().unwrap().get()).unwrap();
+        assert_eq!(document, json!({ "height": 7, "programs": [program.to_json(), program.to_json()] }));
+    }
+
+    #[test]
+    fn test_raw_document_duplicate_keys() {
+        let mut document = RawDocument::new();
+        document.insert("a", &1u8).unwrap().insert("b", &2u8).unwrap();
+        // Setting an existing key replaces its value, keeping its position.
+        document.insert("a", &3u8).unwrap().insert_raw("b", RawValue::from_string("[4]".to_string()).unwrap());
+        assert_eq!(document.len(), 2);
+        assert_eq!(document.into_raw().unwrap().get(), r#"{"a":3,"b":[4]}"#);
+    }
+}
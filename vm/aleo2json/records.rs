@@ -0,0 +1,414 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::VerificationCheck,
+    console::{
+        account::{PrivateKey, ViewKey},
+        network::Network,
+        program::{Ciphertext, Entry, Field, Identifier, Owner, Plaintext, Record, ValueType},
+    },
+    ledger::block::{Block, Output, Transition},
+    synthesizer::Program,
+};
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The recomputed commitment of a decrypted output record, and its serial number, if a private key owns it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordCheck {
+    /// The commitment of the output, checked against the one recomputed from the decrypted record.
+    pub commitment: VerificationCheck,
+    /// The serial number of the record, derived from the private key that owns it, if one was given.
+    /// It matches the serial number of the input that spends the record.
+    pub serial_number: Option<String>,
+}
+
+impl RecordCheck {
+    /// Returns `true` if the recomputed commitment matches the commitment of the output.
+    pub fn passed(&self) -> bool {
+        self.commitment.passed()
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "RecordCheck",
+            "commitment": self.commitment.to_json(),
+            "serial_number": self.serial_number,
+            "passed": self.passed(),
+        })
+    }
+}
+
+/// An output record of a transition, with its plaintext if one of the given view keys owns it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordOutput {
+    /// The commitment of the record, which is the ID of the output.
+    pub commitment: String,
+    /// The checksum of the record ciphertext.
+    pub checksum: String,
+    /// The nonce of the record.
+    pub nonce: String,
+    /// The record ciphertext.
+    pub ciphertext: String,
+    /// The decrypted record, if one of the view keys owns it.
+    pub plaintext: Option<Value>,
+}
+
+impl RecordOutput {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "RecordOutput",
+            "commitment": self.commitment,
+            "checksum": self.checksum,
+            "nonce": self.nonce,
+            "ciphertext": self.ciphertext,
+            "plaintext": self.plaintext,
+        })
+    }
+}
+
+/// Returns the given plaintext record as JSON: its owner and each of its entries, with their visibilities,
+/// and its nonce.
+pub fn record_plaintext_to_json<N: Network>(record: &Record<N, Plaintext<N>>) -> Value {
+    let j_data = record
+        .data()
+        .iter()
+        .map(|(name, entry)| {
+            let (j_visibility, plaintext) = match entry {
+                Entry::Constant(plaintext) => ("constant", plaintext),
+                Entry::Public(plaintext) => ("public", plaintext),
+                Entry::Private(plaintext) => ("private", plaintext),
+            };
+            (name.to_string(), json!({ "visibility": j_visibility, "value": plaintext.to_string() }))
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let j_owner_visibility = match record.owner() {
+        Owner::Public(..) => "public",
+        Owner::Private(..) => "private",
+    };
+    json!({
+        "owner": {
+            "visibility": j_owner_visibility,
+            "address": (**record.owner()).to_string(),
+        },
+        "data": j_data,
+        "nonce": record.nonce().to_string(),
+    })
+}
+
+/// Returns the output records of the given transition, with the index of each output, decrypting those owned by
+/// one of the given view keys.
+pub fn transition_record_outputs<N: Network>(
+    transition: &Transition<N>,
+    view_keys: &[ViewKey<N>],
+) -> Vec<(usize, RecordOutput)> {
+    let mut outputs = Vec::new();
+    for (index, output) in transition.outputs().iter().enumerate() {
+        if let Output::Record(commitment, checksum, Some(record)) = output {
+            outputs.push((index, RecordOutput {
+                commitment: commitment.to_string(),
+                checksum: checksum.to_string(),
+                nonce: record.nonce().to_string(),
+                ciphertext: record.to_string(),
+                plaintext: decrypt_record(record, view_keys).map(|(_, plaintext)| record_plaintext_to_json(&plaintext)),
+            }));
+        }
+    }
+    outputs
+}
+
+/// Decrypts the output records of the given transition that are owned by one of the given view keys,
+/// returning the index of each decrypted output, paired with its plaintext record.
+pub fn decrypt_transition_records<N: Network>(
+    transition: &Transition<N>,
+    view_keys: &[ViewKey<N>],
+) -> Vec<(usize, Record<N, Plaintext<N>>)> {
+    owned_records(transition, view_keys).into_iter().map(|(index, _, _, record)| (index, record)).collect()
+}
+
+/// Decrypts the output records of the given transition that are owned by one of the given keys, and recomputes
+/// their commitments, looking up the record names in the given programs. The serial number of a record is
+/// derived if one of the given private keys owns it.
+pub fn check_transition_records<N: Network>(
+    transition: &Transition<N>,
+    programs: &[Program<N>],
+    view_keys: &[ViewKey<N>],
+    private_keys: &[PrivateKey<N>],
+) -> Vec<(usize, RecordCheck)> {
+    // Each private key also decrypts the records it owns.
+    let mut keys = view_keys.to_vec();
+    let mut owners = vec![None; view_keys.len()];
+    for private_key in private_keys {
+        if let Ok(view_key) = ViewKey::try_from(private_key) {
+            keys.push(view_key);
+            owners.push(Some(*private_key));
+        }
+    }
+
+    let mut checks = Vec::new();
+    for (index, commitment, key_index, record) in owned_records(transition, &keys) {
+        let computed = record_name(programs, transition, index)
+            .and_then(|record_name| record.to_commitment(transition.program_id(), &record_name));
+        let serial_number = owners[key_index]
+            .and_then(|private_key| Record::<N, Plaintext<N>>::serial_number(private_key, commitment).ok())
+            .map(|serial_number| format!("{}", serial_number));
+        checks.push((index, RecordCheck {
+            commitment: VerificationCheck::new("commitment", commitment, computed),
+            serial_number,
+        }));
+    }
+    checks
+}
+
+/// Decrypts the output records of the given transition that are owned by one of the given view keys, returning
+/// the index of each decrypted output, its commitment, and the index of the view key that decrypted it.
+fn owned_records<N: Network>(
+    transition: &Transition<N>,
+    view_keys: &[ViewKey<N>],
+) -> Vec<(usize, Field<N>, usize, Record<N, Plaintext<N>>)> {
+    let mut records = Vec::new();
+    for (index, output) in transition.outputs().iter().enumerate() {
+        if let Some((commitment, record)) = output.record() {
+            // Decrypt the record with the first view key that owns it.
+            if let Some((key_index, plaintext)) = decrypt_record(record, view_keys) {
+                records.push((index, *commitment, key_index, plaintext));
+            }
+        }
+    }
+    records
+}
+
+/// Decrypts the given record with the first of the given view keys that owns it.
+fn decrypt_record<N: Network>(
+    record: &Record<N, Ciphertext<N>>,
+    view_keys: &[ViewKey<N>],
+) -> Option<(usize, Record<N, Plaintext<N>>)> {
+    view_keys
+        .iter()
+        .enumerate()
+        .filter(|(_, view_key)| record.is_owner(view_key))
+        .find_map(|(key_index, view_key)| record.decrypt(view_key).ok().map(|plaintext| (key_index, plaintext)))
+}
+
+/// Returns the name of the record type of the given output of the given transition, from the given programs.
+fn record_name<N: Network>(programs: &[Program<N>], transition: &Transition<N>, index: usize) -> Result<Identifier<N>> {
+    let program = programs
+        .iter()
+        .find(|program| program.id() == transition.program_id())
+        .ok_or_else(|| anyhow!("Program '{}' is not available", transition.program_id()))?;
+    let function = program.get_function(transition.function_name())?;
+    match function.outputs().get_index(index).map(|output| output.value_type()) {
+        Some(ValueType::Record(record_name)) => Ok(*record_name),
+        _ => bail!("Output {index} of '{}/{}' is not a record", transition.program_id(), transition.function_name()),
+    }
+}
+
+/// Adds the plaintext of each output record of the given transition that is owned by one of the given view keys
+/// to the given transition JSON, under `plaintext`, next to the `record` ciphertext of the output value.
+pub fn attach_transition_records<N: Network>(
+    transition: &Transition<N>,
+    view_keys: &[ViewKey<N>],
+    j_transition: &mut Value,
+) {
+    insert_output_values(j_transition, "plaintext", &plaintexts(transition, view_keys));
+}
+
+/// Adds the plaintext of each output record in the given block that is owned by one of the given view keys
+/// to the given block JSON, under `plaintext`, next to the `record` ciphertext of the output value.
+///
+/// Records that are not owned by any of the view keys are left as ciphertexts.
+pub fn attach_block_records<N: Network>(block: &Block<N>, view_keys: &[ViewKey<N>], j_block: &mut Value) {
+    if !view_keys.is_empty() {
+        attach_block_values(block, j_block, "plaintext", |transition| plaintexts(transition, view_keys));
+    }
+}
+
+/// Adds each output record in the given block to the given block JSON, under `record_output`, next to the `record`
+/// ciphertext of the output value: its commitment, checksum, nonce, and ciphertext, and its plaintext if one of the
+/// given view keys owns it.
+pub fn attach_block_record_outputs<N: Network>(block: &Block<N>, view_keys: &[ViewKey<N>], j_block: &mut Value) {
+    attach_block_values(block, j_block, "record_output", |transition| {
+        transition_record_outputs(transition, view_keys)
+            .into_iter()
+            .map(|(index, output)| (index, output.to_json()))
+            .collect()
+    });
+}
+
+/// Adds the recomputed commitment and serial number of each output record in the given block that is owned by one
+/// of the given keys to the given block JSON, under `check`, next to the `record` ciphertext of the output value.
+///
+/// The record names are looked up in `credits.aleo`, and in the programs deployed in the block.
+pub fn attach_block_record_checks<N: Network>(
+    block: &Block<N>,
+    view_keys: &[ViewKey<N>],
+    private_keys: &[PrivateKey<N>],
+    j_block: &mut Value,
+) -> Result<()> {
+    if view_keys.is_empty() && private_keys.is_empty() {
+        return Ok(());
+    }
+
+    let mut programs = vec![Program::credits()?];
+    for confirmed in block.transactions().iter() {
+        if let Some(deployment) = confirmed.transaction().deployment() {
+            programs.push(deployment.program().clone());
+        }
+    }
+
+    attach_block_values(block, j_block, "check", |transition| {
+        check_transition_records(transition, &programs, view_keys, private_keys)
+            .into_iter()
+            .map(|(index, check)| (index, check.to_json()))
+            .collect()
+    });
+    Ok(())
+}
+
+/// Returns the plaintexts of the output records of the given transition that are owned by one of the given view keys.
+fn plaintexts<N: Network>(transition: &Transition<N>, view_keys: &[ViewKey<N>]) -> Vec<(usize, Value)> {
+    decrypt_transition_records(transition, view_keys)
+        .into_iter()
+        .map(|(index, record)| (index, json!(format!("{}", record))))
+        .collect()
+}
+
+/// Adds the output values computed for each transition in the given block to the given block JSON, under `key`.
+fn attach_block_values<N: Network, F: Fn(&Transition<N>) -> Vec<(usize, Value)>>(
+    block: &Block<N>,
+    j_block: &mut Value,
+    key: &str,
+    compute: F,
+) {
+    // Collect the values, keyed by the ID of their transition.
+    let mut values = HashMap::new();
+    for confirmed in block.transactions().iter() {
+        for transition in confirmed.transaction().transitions() {
+            let computed = compute(transition);
+            if !computed.is_empty() {
+                values.insert(format!("{}", transition.id()), computed);
+            }
+        }
+    }
+
+    if !values.is_empty() {
+        attach_values(j_block, key, &values);
+    }
+}
+
+/// Adds the given output values to the transitions found in the given value, and in its children.
+fn attach_values(value: &mut Value, key: &str, values: &HashMap<String, Vec<(usize, Value)>>) {
+    let computed = match value.get("type").and_then(Value::as_str) {
+        Some("Transition") => value.get("id").and_then(Value::as_str).and_then(|id| values.get(id)),
+        _ => None,
+    };
+    match (computed, value) {
+        (Some(computed), j_transition) => insert_output_values(j_transition, key, computed),
+        (None, Value::Object(object)) => object.values_mut().for_each(|val| attach_values(val, key, values)),
+        (None, Value::Array(array)) => array.iter_mut().for_each(|val| attach_values(val, key, values)),
+        (None, _) => (),
+    }
+}
+
+/// Inserts the given values into the values of the outputs of the given transition JSON, under `key`.
+fn insert_output_values(j_transition: &mut Value, key: &str, values: &[(usize, Value)]) {
+    for (index, val) in values {
+        if let Some(j_value) = j_transition["outputs"].get_mut(*index).and_then(|j_output| j_output.get_mut("value")) {
+            j_value[key] = val.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::generate_genesis_block, prelude::Testnet3};
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_attach_block_records() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let block = generate_genesis_block(&private_key, rng).unwrap();
+
+        // The genesis block transfers credits to private records owned by the validator.
+        let transition = block.transactions().iter().flat_map(|tx| tx.transaction().transitions()).next().unwrap();
+        let decrypted = decrypt_transition_records(transition, &[view_key]);
+        assert!(!decrypted.is_empty());
+
+        let (index, record) = &decrypted[0];
+        let mut j_transition = transition.to_json();
+        attach_transition_records(transition, &[view_key], &mut j_transition);
+        assert_eq!(j_transition["outputs"][*index]["value"]["plaintext"], format!("{}", record));
+        assert!(j_transition["outputs"][*index]["value"]["record"].is_string());
+
+        let mut j_block = block.to_json();
+        attach_block_records(&block, &[view_key], &mut j_block);
+        assert_ne!(j_block, block.to_json());
+
+        // The commitments are recomputed from the decrypted records, and the serial numbers from the private key.
+        let programs = [Program::credits().unwrap()];
+        let checks = check_transition_records(transition, &programs, &[], &[private_key]);
+        assert_eq!(checks.len(), decrypted.len());
+        assert!(checks.iter().all(|(_, check)| check.passed() && check.serial_number.is_some()));
+
+        // Without the program, the record name is unknown, so the commitments cannot be recomputed.
+        let checks = check_transition_records(transition, &[], &[view_key], &[]);
+        assert!(checks.iter().all(|(_, check)| !check.passed() && check.serial_number.is_none()));
+
+        let mut j_block = block.to_json();
+        attach_block_record_checks(&block, &[view_key], &[], &mut j_block).unwrap();
+        assert!(j_block.to_string().contains("\"type\":\"RecordCheck\""));
+
+        // A view key that owns none of the records leaves the block JSON unchanged.
+        let other_view_key = ViewKey::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let mut j_other = block.to_json();
+        attach_block_records(&block, &[other_view_key], &mut j_other);
+        assert_eq!(j_other, block.to_json());
+    }
+
+    #[test]
+    fn test_attach_block_record_outputs() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let block = generate_genesis_block(&private_key, rng).unwrap();
+
+        let transition = block.transactions().iter().flat_map(|tx| tx.transaction().transitions()).next().unwrap();
+        let decrypted = decrypt_transition_records(transition, &[view_key]);
+        let (index, record) = &decrypted[0];
+
+        // Every output record is exported, with the plaintext of those owned by the view key.
+        let outputs = transition_record_outputs(transition, &[view_key]);
+        assert_eq!(outputs.len(), transition.outputs().iter().filter(|output| output.record().is_some()).count());
+        let (_, output) = outputs.iter().find(|(output_index, _)| output_index == index).unwrap();
+        assert_eq!(output.nonce, record.nonce().to_string());
+        assert_eq!(output.plaintext.as_ref().unwrap()["owner"]["address"], (**record.owner()).to_string());
+        let mut j_block = block.to_json();
+        attach_block_record_outputs(&block, &[view_key], &mut j_block);
+        assert!(j_block.to_string().contains("\"type\":\"RecordOutput\""));
+        let mut j_transition = transition.to_json();
+        insert_output_values(&mut j_transition, "record_output", &[(*index, output.to_json())]);
+        assert_eq!(j_transition["outputs"][*index]["value"]["record_output"]["commitment"], output.commitment);
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::KEYED_PROPERTIES,
+    console::{
+        algorithms::{Hash, Sha3_256},
+        network::prelude::ToBits,
+    },
+};
+
+use anyhow::{bail, Error, Result};
+use serde_json::Value;
+use std::{fmt, str::FromStr};
+
+/// The placeholder replacing a redacted value.
+pub const REDACTED: &str = "<redacted>";
+
+/// The properties holding data derived from a view key or a private key (i.e. decrypted records, and the serial
+/// numbers of the records a private key owns), redacted wherever they appear.
+pub const REDACTED_PROPERTIES: &[&str] = &["plaintext", "serial_number"];
+
+/// How sensitive values are replaced when redacting a document.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Redaction {
+    /// Replaces each sensitive value with the `<redacted>` placeholder.
+    #[default]
+    Placeholder,
+    /// Replaces each sensitive value with its SHA3-256 hash, in hex, prefixed by `sha3-256:`.
+    /// Equal values therefore remain linkable, without being revealed.
+    Hash,
+}
+
+impl Redaction {
+    /// Returns the replacement of the given sensitive value.
+    fn replace(&self, value: &Value) -> Value {
+        match self {
+            Self::Placeholder => Value::String(REDACTED.to_string()),
+            Self::Hash => {
+                let preimage = match value {
+                    Value::String(string) => string.clone(),
+                    value => value.to_string(),
+                };
+                match Sha3_256::default().hash(&preimage.to_bits_le()) {
+                    Ok(bits) => Value::String(format!("sha3-256:{}", to_hex_le(&bits))),
+                    // Hashing a string does not fail; should it, the placeholder is used, so the value is never leaked.
+                    Err(_) => Value::String(REDACTED.to_string()),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Redaction {
+    type Err = Error;
+
+    /// Parses a redaction from its name.
+    fn from_str(redaction: &str) -> Result<Self> {
+        match redaction {
+            "placeholder" => Ok(Self::Placeholder),
+            "hash" => Ok(Self::Hash),
+            _ => bail!("Unknown redaction '{redaction}' (expected one of: placeholder, hash)"),
+        }
+    }
+}
+
+impl fmt::Display for Redaction {
+    /// Prints the name of the redaction.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Placeholder => write!(f, "placeholder"),
+            Self::Hash => write!(f, "hash"),
+        }
+    }
+}
+
+/// Replaces the sensitive values of the given document, preserving its structure, so it can be shared externally.
+///
+/// The sensitive values are the values of the private inputs and outputs of transitions, and the data derived
+/// from a view key (i.e. the decrypted records, under `plaintext`). Values that are `null` are left as is.
+pub fn redact(document: &mut Value, redaction: Redaction) {
+    redact_value(document, redaction, false);
+}
+
+/// Redacts the given value, and its children.
+fn redact_value(value: &mut Value, redaction: Redaction, is_keyed: bool) {
+    match value {
+        Value::Object(object) => {
+            let is_private = !is_keyed
+                && matches!(object.get("type").and_then(Value::as_str), Some("Input" | "Output"))
+                && object.get("vtype").and_then(Value::as_str) == Some("Private");
+            for (key, val) in object.iter_mut() {
+                let is_sensitive =
+                    !is_keyed && ((is_private && key == "value") || REDACTED_PROPERTIES.contains(&key.as_str()));
+                match is_sensitive {
+                    true if !val.is_null() => *val = redaction.replace(val),
+                    _ => redact_value(val, redaction, !is_keyed && KEYED_PROPERTIES.contains(&key.as_str())),
+                }
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(|val| redact_value(val, redaction, false)),
+        _ => (),
+    }
+}
+
+/// Returns the given little-endian bits as a lowercase hex string.
+pub(crate) fn to_hex_le(bits: &[bool]) -> String {
+    bits.chunks(8)
+        .map(|chunk| chunk.iter().rev().fold(0u8, |byte, bit| (byte << 1) | *bit as u8))
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_redact() {
+        let document = json!({
+            "type": "Transition",
+            "inputs": [
+                { "type": "Input", "vtype": "Private", "id": "1field", "value": "ciphertext1abc" },
+                { "type": "Input", "vtype": "Public", "id": "2field", "value": "5u64" },
+            ],
+            "outputs": [
+                { "type": "Output", "vtype": "Private", "id": "3field", "value": null },
+                {
+                    "type": "Output",
+                    "vtype": "Record",
+                    "id": "4field",
+                    "value": { "checksum": "5field", "record": "record1abc", "plaintext": "{ owner: aleo1abc.private }" },
+                    "check": { "type": "RecordCheck", "serial_number": "6field", "passed": true },
+                },
+            ],
+        });
+
+        let mut redacted = document.clone();
+        redact(&mut redacted, Redaction::Placeholder);
+        assert_eq!(redacted["inputs"][0]["value"], REDACTED);
+        assert_eq!(redacted["inputs"][0]["id"], "1field");
+        assert_eq!(redacted["inputs"][1]["value"], "5u64");
+        assert_eq!(redacted["outputs"][0]["value"], Value::Null);
+        assert_eq!(redacted["outputs"][1]["value"]["record"], "record1abc");
+        assert_eq!(redacted["outputs"][1]["value"]["plaintext"], REDACTED);
+        assert_eq!(redacted["outputs"][1]["check"]["serial_number"], REDACTED);
+        assert_eq!(redacted["outputs"][1]["check"]["passed"], true);
+
+        let mut hashed = document.clone();
+        redact(&mut hashed, Redaction::Hash);
+        let digest = hashed["inputs"][0]["value"].as_str().unwrap();
+        assert!(digest.starts_with("sha3-256:"));
+        assert_eq!(digest.len(), "sha3-256:".len() + 64);
+        // Hashing is deterministic, so equal values remain linkable.
+        let mut rehashed = document;
+        redact(&mut rehashed, Redaction::Hash);
+        assert_eq!(hashed, rehashed);
+    }
+
+    #[test]
+    fn test_redaction_from_str() {
+        assert_eq!(Redaction::from_str("placeholder").unwrap(), Redaction::Placeholder);
+        assert_eq!(Redaction::from_str("hash").unwrap(), Redaction::Hash);
+        assert!(Redaction::from_str("mask").is_err());
+        assert_eq!(Redaction::Hash.to_string(), "hash");
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::network::Network,
+    ledger::block::{Block, ConfirmedTransaction, Output, Rejected},
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// Returns the locators of the transitions of the given rejected transaction that have finalize logic,
+/// i.e. that output a future; a rejected execution was rejected by the finalize logic of one of them.
+fn finalize_transitions<N: Network>(rejected: &Rejected<N>) -> Vec<String> {
+    match rejected.execution() {
+        Some(execution) => execution
+            .transitions()
+            .filter(|transition| transition.outputs().iter().any(|output| matches!(output, Output::Future(..))))
+            .map(|transition| format!("{}/{}", transition.program_id(), transition.function_name()))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Returns a human-readable reason for the rejection of the given transaction, where it can be derived from the
+/// rejected payload alone, as the ledger does not record why the finalize logic failed.
+pub fn rejection_reason<N: Network>(rejected: &Rejected<N>) -> Option<String> {
+    if let Some(deployment) = rejected.deployment() {
+        return Some(format!(
+            "The deployment of '{}' (edition {}) failed to finalize",
+            deployment.program_id(),
+            deployment.edition()
+        ));
+    }
+    match finalize_transitions(rejected).as_slice() {
+        [] => None,
+        [locator] => Some(format!("The finalize logic of '{locator}' failed")),
+        locators => Some(format!("The finalize logic of one of '{}' failed", locators.join("', '"))),
+    }
+}
+
+/// Returns the context of the rejection of the given confirmed transaction, or `None` if it was accepted:
+/// the rejected ID, the ID of the transaction prior to confirmation, the ID of the fee transaction it was
+/// confirmed as, the transitions with finalize logic, and a human-readable reason, where derivable.
+///
+/// The rejected deployment or execution itself is already included in the confirmed transaction, under `rejected`.
+pub fn rejection_to_json<N: Network>(confirmed: &ConfirmedTransaction<N>) -> Result<Option<Value>> {
+    let rejected = match confirmed.to_rejected() {
+        Some(rejected) => rejected,
+        None => return Ok(None),
+    };
+    let j_vtype = match rejected.is_deployment() {
+        true => "Deployment",
+        false => "Execution",
+    };
+    Ok(Some(json!({
+        "type": "Rejection",
+        "vtype": j_vtype,
+        "rejected_id": rejected.to_id()?.to_string(),
+        "unconfirmed_transaction_id": confirmed.to_unconfirmed_transaction_id()?.to_string(),
+        "fee_transaction_id": confirmed.transaction().id().to_string(),
+        "finalize_transitions": finalize_transitions(rejected),
+        "reason": rejection_reason(rejected),
+    })))
+}
+
+/// Adds the context of the rejection of each rejected transaction of the given block to its block JSON,
+/// under `rejection`.
+pub fn attach_block_rejections<N: Network>(block: &Block<N>, j_block: &mut Value) -> Result<()> {
+    for (index, confirmed) in block.transactions().iter().enumerate() {
+        if let Some(j_rejection) = rejection_to_json(confirmed)? {
+            j_block["transactions"][index]["rejection"] = j_rejection;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuit::AleoV0,
+        console::{account::PrivateKey, network::prelude::FromBytes, program::ProgramOwner},
+        prelude::Testnet3,
+        synthesizer::{Process, Program},
+    };
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_rejection_reason() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program rejected.aleo;
+
+function double:
+    input r0 as u8.private;
+    add r0 r0 into r1;
+    output r1 as u8.private;
+",
+        )
+        .unwrap();
+        let process = Process::<CurrentNetwork>::load().unwrap();
+        let deployment = process.deploy::<AleoV0, _>(&program, rng).unwrap();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let owner = ProgramOwner::new(&private_key, deployment.to_deployment_id().unwrap(), rng).unwrap();
+
+        let rejected = Rejected::new_deployment(owner, deployment);
+        assert!(finalize_transitions(&rejected).is_empty());
+        assert_eq!(
+            rejection_reason(&rejected).unwrap(),
+            "The deployment of 'rejected.aleo' (edition 0) failed to finalize"
+        );
+    }
+
+    #[test]
+    fn test_attach_block_rejections() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut j_block = genesis.to_json();
+        attach_block_rejections(&genesis, &mut j_block).unwrap();
+        let j_transactions = j_block["transactions"].as_array().unwrap();
+        for (confirmed, j_transaction) in genesis.transactions().iter().zip(j_transactions) {
+            assert_eq!(j_transaction.get("rejection").is_some(), confirmed.is_rejected());
+        }
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{opcode_table, OperandSignature},
+    console::{
+        network::Network,
+        program::{FinalizeType, LiteralType, PlaintextType, Register, RegisterType, ValueType},
+    },
+    synthesizer::{
+        program::{CastType, Command, Finalize, InstructionTrait, MappingLocator, Operand},
+        Instruction,
+        Program,
+    },
+};
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The opcodes whose result wraps around on overflow.
+const WRAPPING_OPCODES: &[&str] = &["abs.w", "add.w", "div.w", "mul.w", "pow.w", "rem.w", "sub.w"];
+
+/// The opcodes whose result is a boolean.
+const BOOLEAN_OPCODES: &[&str] = &["gt", "gte", "is.eq", "is.neq", "lt", "lte"];
+
+/// The kind of wrap or truncation concern of an instruction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RiskKind {
+    /// The arithmetic wraps around on overflow, instead of halting (i.e. `add.w`).
+    Wrapping,
+    /// The bits shifted out of the operand are discarded (i.e. `shl`).
+    Shift,
+    /// The cast truncates the operand to the bits of the destination type (i.e. `cast.lossy`).
+    LossyCast,
+}
+
+impl RiskKind {
+    /// Returns the kind of concern of the given opcode, if it has one.
+    pub fn of(opcode: &str) -> Option<Self> {
+        match opcode {
+            "shl" | "shl.w" | "shr" | "shr.w" => Some(Self::Shift),
+            "cast.lossy" => Some(Self::LossyCast),
+            opcode if WRAPPING_OPCODES.contains(&opcode) => Some(Self::Wrapping),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Wrapping => "wrapping",
+            Self::Shift => "shift",
+            Self::LossyCast => "lossy_cast",
+        }
+    }
+}
+
+/// An instruction susceptible to wrap or truncation concerns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArithmeticRisk {
+    /// The JSON pointer to the instruction, in the program JSON.
+    pub pointer: String,
+    /// The opcode of the instruction.
+    pub opcode: String,
+    /// The kind of concern.
+    pub kind: RiskKind,
+    /// The literal type of each operand, if it could be inferred.
+    pub operand_types: Vec<Option<LiteralType>>,
+    /// The literal type of the destination, if it could be inferred.
+    pub destination_type: Option<LiteralType>,
+}
+
+impl ArithmeticRisk {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let type_name = |literal_type: &Option<LiteralType>| match literal_type {
+            Some(literal_type) => json!(literal_type.type_name()),
+            None => Value::Null,
+        };
+        json!({
+            "type": "ArithmeticRisk",
+            "kind": self.kind.as_str(),
+            "opcode": self.opcode,
+            "pointer": self.pointer,
+            "operand_types": self.operand_types.iter().map(type_name).collect::<Vec<_>>(),
+            "destination_type": type_name(&self.destination_type),
+        })
+    }
+}
+
+/// An instruction of a program, with the literal types inferred for its operands and its destination.
+pub(crate) struct TypedInstruction<'a, N: Network> {
+    /// The JSON pointer to the instruction, in the program JSON.
+    pub(crate) pointer: String,
+    /// The instruction.
+    pub(crate) instruction: &'a Instruction<N>,
+    /// The literal type of each operand, if it could be inferred.
+    pub(crate) operand_types: Vec<Option<LiteralType>>,
+    /// The literal type of the destination, if it could be inferred.
+    pub(crate) destination_type: Option<LiteralType>,
+}
+
+/// Returns the instructions of the given program susceptible to wrap or truncation concerns (i.e. wrapping arithmetic,
+/// shifts, and lossy casts), in order of their closure, function, and finalize block.
+///
+/// The operand types are inferred from the input declarations, the literals, and the instructions before them.
+/// The types that cannot be inferred (i.e. the outputs of calls, or of operations whose operand types leave their
+/// result type ambiguous) are `None`.
+pub fn program_risks<N: Network>(program: &Program<N>) -> Vec<ArithmeticRisk> {
+    let mut risks = Vec::new();
+    for typed in typed_instructions(program) {
+        let opcode = *typed.instruction.opcode();
+        if let Some(kind) = RiskKind::of(opcode) {
+            risks.push(ArithmeticRisk {
+                pointer: typed.pointer,
+                opcode: opcode.to_string(),
+                kind,
+                operand_types: typed.operand_types,
+                destination_type: typed.destination_type,
+            });
+        }
+    }
+    risks
+}
+
+/// Returns the instructions of the given program, with the literal types inferred for their operands and
+/// destinations, in order of their closure, function, and finalize block.
+pub(crate) fn typed_instructions<N: Network>(program: &Program<N>) -> Vec<TypedInstruction<'_, N>> {
+    let signatures = opcode_table::<N>()
+        .into_iter()
+        .filter_map(|info| Some((info.opcode, info.signatures?)))
+        .collect::<HashMap<_, _>>();
+    let mut typed = Vec::new();
+    for (name, closure) in program.closures() {
+        let mut scope = Scope::new(format!("/closures/{name}"), &signatures);
+        for input in closure.inputs() {
+            if let RegisterType::Plaintext(plaintext_type) = input.register_type() {
+                scope.declare(input.register(), plaintext_type);
+            }
+        }
+        for (index, instruction) in closure.instructions().iter().enumerate() {
+            typed.push(scope.instruction(format!("instructions/{index}"), instruction));
+        }
+    }
+    for (name, function) in program.functions() {
+        let mut scope = Scope::new(format!("/functions/{name}"), &signatures);
+        for input in function.inputs() {
+            if let ValueType::Constant(plaintext_type)
+            | ValueType::Public(plaintext_type)
+            | ValueType::Private(plaintext_type) = input.value_type()
+            {
+                scope.declare(input.register(), plaintext_type);
+            }
+        }
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            typed.push(scope.instruction(format!("instructions/{index}"), instruction));
+        }
+
+        if let Some(finalize) = function.finalize_logic() {
+            let pointer = format!("/functions/{name}/finalize_logic");
+            finalize_types(program, &pointer, finalize, &signatures, &mut typed);
+        }
+    }
+    typed
+}
+
+/// Appends the instructions of the given finalize block, at the given JSON pointer, with their inferred types.
+fn finalize_types<'a, N: Network>(
+    program: &Program<N>,
+    pointer: &str,
+    finalize: &'a Finalize<N>,
+    signatures: &HashMap<String, Vec<OperandSignature>>,
+    typed: &mut Vec<TypedInstruction<'a, N>>,
+) {
+    let mut scope = Scope::new(pointer.to_string(), signatures);
+    for input in finalize.inputs() {
+        if let FinalizeType::Plaintext(plaintext_type) = input.finalize_type() {
+            scope.declare(input.register(), plaintext_type);
+        }
+    }
+    for (index, command) in finalize.commands().iter().enumerate() {
+        match command {
+            Command::Instruction(instruction) => {
+                typed.push(scope.instruction(format!("commands/{index}/value"), instruction))
+            }
+            Command::Contains(contains) => scope.define(contains.destination(), Some(LiteralType::Boolean)),
+            Command::Get(get) => {
+                let value_type = match get.mapping() {
+                    MappingLocator::Resource(mapping_name) => program
+                        .get_mapping(mapping_name)
+                        .ok()
+                        .and_then(|mapping| literal_type(mapping.value().plaintext_type())),
+                    MappingLocator::Locator(_) => None,
+                };
+                scope.define(get.destination(), value_type)
+            }
+            Command::GetOrUse(get_or_use) => {
+                let default_type = scope.operand_type(get_or_use.default());
+                scope.define(get_or_use.destination(), default_type)
+            }
+            Command::RandChaCha(rand_chacha) => {
+                scope.define(rand_chacha.destination(), Some(rand_chacha.destination_type()))
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Annotates each instruction of the given program JSON susceptible to wrap or truncation concerns,
+/// under `risk`.
+pub fn attach_risks<N: Network>(program: &Program<N>, document: &mut Value) {
+    for risk in program_risks(program) {
+        if let Some(Value::Object(j_instruction)) = document.pointer_mut(&risk.pointer) {
+            j_instruction.insert("risk".to_string(), risk.to_json());
+        }
+    }
+}
+
+/// Returns the result type of the given signatures matching the given operand types, an unknown operand type
+/// matching any type, or `None` if no signature matches, or if the matching ones disagree on the result type.
+fn signature_output(signatures: &[OperandSignature], operand_types: &[Option<LiteralType>]) -> Option<LiteralType> {
+    let matches = |signature: &&OperandSignature| {
+        signature.inputs.len() == operand_types.len()
+            && signature
+                .inputs
+                .iter()
+                .zip(operand_types)
+                .all(|(input, found)| found.map_or(true, |found| found == *input))
+    };
+    let mut outputs = signatures.iter().filter(matches).map(|signature| signature.output);
+    let output = outputs.next()?;
+    outputs.all(|other| other == output).then_some(output)
+}
+
+/// Returns the literal type of the given plaintext type, if it is a literal.
+fn literal_type<N: Network>(plaintext_type: &PlaintextType<N>) -> Option<LiteralType> {
+    match plaintext_type {
+        PlaintextType::Literal(literal_type) => Some(*literal_type),
+        _ => None,
+    }
+}
+
+/// Returns the literal type of the given cast type, if it is a literal.
+fn cast_literal_type<N: Network>(cast_type: &CastType<N>) -> Option<LiteralType> {
+    match cast_type {
+        CastType::Plaintext(plaintext_type) => literal_type(plaintext_type),
+        _ => None,
+    }
+}
+
+/// The literal types of the registers of a closure, function, or finalize block.
+struct Scope<'s> {
+    /// The JSON pointer to the closure, function, or finalize block.
+    pointer: String,
+    /// The literal type of each register inferred so far, by locator.
+    types: HashMap<u64, LiteralType>,
+    /// The signatures of the literal operations, by opcode.
+    signatures: &'s HashMap<String, Vec<OperandSignature>>,
+}
+
+impl<'s> Scope<'s> {
+    /// Initializes an empty scope at the given JSON pointer, inferring the results of the literal operations
+    /// from the given signatures.
+    fn new(pointer: String, signatures: &'s HashMap<String, Vec<OperandSignature>>) -> Self {
+        Self { pointer, types: HashMap::new(), signatures }
+    }
+
+    /// Records the type of the given input register.
+    fn declare<N: Network>(&mut self, register: &Register<N>, plaintext_type: &PlaintextType<N>) {
+        self.define(register, literal_type(plaintext_type));
+    }
+
+    /// Records the type of the given destination register, if it is known.
+    fn define<N: Network>(&mut self, register: &Register<N>, literal_type: Option<LiteralType>) {
+        if let (Register::Locator(locator), Some(literal_type)) = (register, literal_type) {
+            self.types.insert(*locator, literal_type);
+        }
+    }
+
+    /// Returns the literal type of the given operand, if it is known.
+    fn operand_type<N: Network>(&self, operand: &Operand<N>) -> Option<LiteralType> {
+        match operand {
+            Operand::Literal(literal) => Some(literal.to_type()),
+            Operand::Register(Register::Locator(locator)) => self.types.get(locator).copied(),
+            Operand::ProgramID(_) | Operand::Signer | Operand::Caller => Some(LiteralType::Address),
+            Operand::BlockHeight => Some(LiteralType::U32),
+            Operand::Register(Register::Access(..)) => None,
+        }
+    }
+
+    /// Infers the type of the destination of the given instruction at the given path, relative to the scope,
+    /// returning the instruction with its inferred types.
+    fn instruction<'a, N: Network>(
+        &mut self,
+        path: String,
+        instruction: &'a Instruction<N>,
+    ) -> TypedInstruction<'a, N> {
+        let opcode = *instruction.opcode();
+        let operand_types = instruction.operands().iter().map(|operand| self.operand_type(operand)).collect::<Vec<_>>();
+        let destination_type = match instruction {
+            Instruction::Cast(cast) => cast_literal_type(cast.cast_type()),
+            Instruction::CastLossy(cast) => cast_literal_type(cast.cast_type()),
+            _ if BOOLEAN_OPCODES.contains(&opcode) => Some(LiteralType::Boolean),
+            _ => self.signatures.get(opcode).and_then(|signatures| signature_output(signatures, &operand_types)),
+        };
+        if let [destination] = instruction.destinations().as_slice() {
+            self.define(destination, destination_type);
+        }
+        TypedInstruction { pointer: format!("{}/{path}", self.pointer), instruction, operand_types, destination_type }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"
+program token.aleo;
+
+mapping supply:
+    key as u8.public;
+    value as u64.public;
+
+closure scale:
+    input r0 as u16;
+    mul.w r0 3u16 into r1;
+    output r1 as u16;
+
+function mint:
+    input r0 as u64.public;
+    input r1 as u8.public;
+    add r0 r0 into r2;
+    shl r2 r1 into r3;
+    cast.lossy r3 into r4 as u8;
+    async mint r3 into r5;
+    output r5 as token.aleo/mint.future;
+
+finalize mint:
+    input r0 as u64.public;
+    get supply[0u8] into r1;
+    sub.w r1 r0 into r2;
+    set r2 into supply[0u8];";
+
+    #[test]
+    fn test_risk_kind() {
+        assert_eq!(RiskKind::of("add.w"), Some(RiskKind::Wrapping));
+        assert_eq!(RiskKind::of("shr"), Some(RiskKind::Shift));
+        assert_eq!(RiskKind::of("cast.lossy"), Some(RiskKind::LossyCast));
+        assert_eq!(RiskKind::of("add"), None);
+        assert_eq!(RiskKind::of("cast"), None);
+    }
+
+    #[test]
+    fn test_program_risks() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let risks = program_risks(&program);
+        let summary = risks
+            .iter()
+            .map(|risk| {
+                (risk.pointer.as_str(), risk.opcode.as_str(), risk.operand_types.clone(), risk.destination_type)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(summary, [
+            ("/closures/scale/instructions/0", "mul.w", vec![Some(LiteralType::U16); 2], Some(LiteralType::U16)),
+            (
+                "/functions/mint/instructions/1",
+                "shl",
+                vec![Some(LiteralType::U64), Some(LiteralType::U8)],
+                Some(LiteralType::U64)
+            ),
+            ("/functions/mint/instructions/2", "cast.lossy", vec![Some(LiteralType::U64)], Some(LiteralType::U8)),
+            // The type of the value read from the mapping is inferred from its declaration.
+            (
+                "/functions/mint/finalize_logic/commands/1/value",
+                "sub.w",
+                vec![Some(LiteralType::U64); 2],
+                Some(LiteralType::U64)
+            ),
+        ]);
+        assert_eq!(risks[2].kind, RiskKind::LossyCast);
+    }
+
+    #[test]
+    fn test_attach_risks() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let mut document = program.to_json();
+        attach_risks(&program, &mut document);
+
+        let j_instructions = &document["functions"]["mint"]["instructions"];
+        assert_eq!(j_instructions[0].get("risk"), None);
+        assert_eq!(
+            j_instructions[2]["risk"],
+            json!({
+                "type": "ArithmeticRisk",
+                "kind": "lossy_cast",
+                "opcode": "cast.lossy",
+                "pointer": "/functions/mint/instructions/2",
+                "operand_types": ["u64"],
+                "destination_type": "u8",
+            })
+        );
+        let j_command = &document["functions"]["mint"]["finalize_logic"]["commands"][1];
+        assert_eq!(j_command["value"]["risk"]["kind"], "wrapping");
+    }
+}
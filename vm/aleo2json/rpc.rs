@@ -0,0 +1,297 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{
+        convert_block_json,
+        convert_program,
+        diff_json,
+        parse_program,
+        with_version,
+        ConversionError,
+        ProgramStats,
+    },
+    console::network::Network,
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, Write},
+    marker::PhantomData,
+};
+
+/// The JSON-RPC error code for invalid JSON.
+const PARSE_ERROR: i64 = -32700;
+/// The JSON-RPC error code for a request that is not a valid request object.
+const INVALID_REQUEST: i64 = -32600;
+/// The JSON-RPC error code for an unknown method.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The JSON-RPC error code for invalid method parameters.
+const INVALID_PARAMS: i64 = -32602;
+/// The JSON-RPC error code for a failed conversion.
+const CONVERSION_ERROR: i64 = -32000;
+
+/// A JSON-RPC 2.0 endpoint over newline-delimited streams, such as stdio.
+///
+/// Each line of input is one request, or a batch of requests as an array, and each response, or the array of
+/// the responses to a batch, is written as one line of output.
+/// The supported methods are:
+///  - `convertProgram`, with params `{ "source": "<.aleo source>" }`,
+///  - `convertBlock`, with params `{ "block": <block JSON, as an object or a string> }`,
+///  - `diff`, with params `{ "left": .., "right": .. }`, where each side is either `.aleo` source or a JSON document,
+///  - `stats`, with params `{ "source": "<.aleo source>" }`.
+pub struct RpcServer<N: Network>(PhantomData<N>);
+
+impl<N: Network> RpcServer<N> {
+    /// Serves requests from the given reader until it is exhausted, writing each response to the given writer.
+    pub fn run<R: BufRead, W: Write>(reader: R, writer: &mut W) -> Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = Self::handle(&line) {
+                writeln!(writer, "{response}")?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a single line of input, returning the response, or `None` if there is none,
+    /// i.e. for a notification, or a batch of notifications.
+    pub fn handle(request: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(request) {
+            Ok(request) => request,
+            Err(error) => return Some(Self::error(Value::Null, RpcError::new(PARSE_ERROR, error))),
+        };
+        match request {
+            // A batch receives the array of the responses to its requests; an empty batch is an invalid request.
+            Value::Array(requests) if !requests.is_empty() => {
+                let responses: Vec<_> = requests.iter().filter_map(Self::handle_request).collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            request => Self::handle_request(&request),
+        }
+    }
+
+    /// Handles a single request, returning the response, or `None` if the request is a notification.
+    fn handle_request(request: &Value) -> Option<Value> {
+        // An invalid request always receives an error, without an ID, as its ID cannot be relied on.
+        let id = match Self::validate(request) {
+            Ok(id) => id,
+            Err(error) => return Some(Self::error(Value::Null, error)),
+        };
+        let method = request["method"].as_str().unwrap_or_default();
+        let result = Self::dispatch(method, request.get("params").unwrap_or(&Value::Null));
+
+        // A valid request without an ID is a notification, which does not receive a response.
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => Self::error(id, error),
+        })
+    }
+
+    /// Checks that the given value is a valid request object, returning its ID, if any.
+    fn validate(request: &Value) -> Result<Option<Value>, RpcError> {
+        let invalid = |message: &str| RpcError::new(INVALID_REQUEST, message);
+        let request = request.as_object().ok_or_else(|| invalid("The request must be an object"))?;
+        if request.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+            return Err(invalid("The request must have 'jsonrpc' set to '2.0'"));
+        }
+        if !matches!(request.get("method"), Some(Value::String(_))) {
+            return Err(invalid("The request is missing a method"));
+        }
+        if !matches!(request.get("params"), None | Some(Value::Array(_) | Value::Object(_))) {
+            return Err(invalid("The params must be an array or an object"));
+        }
+        match request.get("id") {
+            None => Ok(None),
+            Some(id @ (Value::Null | Value::String(_) | Value::Number(_))) => Ok(Some(id.clone())),
+            Some(_) => Err(invalid("The ID must be a string, a number, or null")),
+        }
+    }
+
+    /// Invokes the given method with the given parameters.
+    fn dispatch(method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "convertProgram" => {
+                convert_program::<N>(Self::string_param(params, "source")?).map_err(RpcError::conversion)
+            }
+            "convertBlock" => {
+                let block = match params.get("block") {
+                    Some(Value::String(block)) => block.clone(),
+                    Some(block @ Value::Object(_)) => block.to_string(),
+                    _ => return Err(RpcError::new(INVALID_PARAMS, "Expected a 'block' object or string")),
+                };
+                convert_block_json::<N>(&block).map_err(RpcError::conversion)
+            }
+            "diff" => {
+                let left = Self::document_param(params, "left")?;
+                let right = Self::document_param(params, "right")?;
+                Ok(Value::Array(diff_json(&left, &right).iter().map(|diff| diff.to_json()).collect()))
+            }
+            "stats" => {
+                let program =
+                    parse_program::<N>(Self::string_param(params, "source")?).map_err(RpcError::conversion)?;
+                Ok(with_version(ProgramStats::new(&program).to_json()))
+            }
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Unknown method '{method}'"))),
+        }
+    }
+
+    /// Returns the string parameter with the given name.
+    fn string_param<'a>(params: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+        params
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Expected a string parameter '{name}'")))
+    }
+
+    /// Returns the document parameter with the given name, converting it first if it is `.aleo` source.
+    fn document_param(params: &Value, name: &str) -> Result<Value, RpcError> {
+        match params.get(name) {
+            Some(Value::String(source)) => convert_program::<N>(source).map_err(RpcError::conversion),
+            Some(document) => Ok(document.clone()),
+            None => Err(RpcError::new(INVALID_PARAMS, format!("Expected a parameter '{name}'"))),
+        }
+    }
+
+    /// Returns the error response for the given request ID.
+    fn error(id: Value, error: RpcError) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": match error.data {
+                Some(data) => json!({ "code": error.code, "message": error.message, "data": data }),
+                None => json!({ "code": error.code, "message": error.message }),
+            },
+        })
+    }
+}
+
+/// A JSON-RPC error.
+struct RpcError {
+    /// The error code.
+    code: i64,
+    /// The error message.
+    message: String,
+    /// The structured error, for a failed conversion.
+    data: Option<Value>,
+}
+
+impl RpcError {
+    /// Initializes a new error with the given code and message.
+    fn new(code: i64, message: impl ToString) -> Self {
+        Self { code, message: message.to_string(), data: None }
+    }
+
+    /// Initializes a new error for a failed conversion, carrying the structured error as its data.
+    fn conversion(error: ConversionError) -> Self {
+        Self { data: Some(error.to_json()), ..Self::new(CONVERSION_ERROR, error) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const SOURCE: &str = "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;";
+
+    #[test]
+    fn test_convert_program() {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "convertProgram", "params": { "source": SOURCE } });
+        let response = RpcServer::<CurrentNetwork>::handle(&request.to_string()).unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["type"], "ProgramCore");
+    }
+
+    #[test]
+    fn test_errors() {
+        let response = RpcServer::<CurrentNetwork>::handle("{").unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        let request = json!({ "jsonrpc": "2.0", "id": 2, "method": "unknown" });
+        let response = RpcServer::<CurrentNetwork>::handle(&request.to_string()).unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let request = json!({ "jsonrpc": "2.0", "id": 3, "method": "stats", "params": {} });
+        let response = RpcServer::<CurrentNetwork>::handle(&request.to_string()).unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let request =
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "convertProgram", "params": { "source": "program" } });
+        let response = RpcServer::<CurrentNetwork>::handle(&request.to_string()).unwrap();
+        assert_eq!(response["error"]["code"], CONVERSION_ERROR);
+        assert_eq!(response["error"]["data"]["kind"], "parse");
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        // Each invalid request receives an Invalid Request error, without an ID, rather than no response.
+        for request in ["[]", "42", r#"{"id":1}"#, r#"{"id":1,"method":"stats"}"#, r#"{"jsonrpc":"2.0","id":[1]}"#] {
+            let response = RpcServer::<CurrentNetwork>::handle(request).unwrap();
+            assert_eq!(response["error"]["code"], INVALID_REQUEST, "{request}");
+            assert_eq!(response["id"], Value::Null);
+        }
+    }
+
+    #[test]
+    fn test_batch() {
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "stats", "params": { "source": SOURCE } },
+            { "jsonrpc": "2.0", "method": "stats", "params": { "source": SOURCE } },
+            42,
+        ]);
+        let response = RpcServer::<CurrentNetwork>::handle(&batch.to_string()).unwrap();
+        let responses = response.as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[1]["error"]["code"], INVALID_REQUEST);
+
+        // A batch of notifications receives no response.
+        let batch = json!([{ "jsonrpc": "2.0", "method": "stats", "params": { "source": SOURCE } }]);
+        assert!(RpcServer::<CurrentNetwork>::handle(&batch.to_string()).is_none());
+    }
+
+    #[test]
+    fn test_notification() {
+        let request = json!({ "jsonrpc": "2.0", "method": "stats", "params": { "source": SOURCE } });
+        assert!(RpcServer::<CurrentNetwork>::handle(&request.to_string()).is_none());
+    }
+
+    #[test]
+    fn test_run() {
+        let requests = [
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "stats", "params": { "source": SOURCE } }).to_string(),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "diff", "params": { "left": SOURCE, "right": SOURCE } })
+                .to_string(),
+        ]
+        .join("\n");
+
+        let mut output = Vec::new();
+        RpcServer::<CurrentNetwork>::run(requests.as_bytes(), &mut output).unwrap();
+
+        let responses = String::from_utf8(output).unwrap();
+        let responses: Vec<Value> = responses.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["result"]["num_functions"], 1);
+        assert_eq!(responses[1]["result"], json!([]));
+    }
+}
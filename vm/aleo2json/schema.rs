@@ -0,0 +1,458 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::FORMAT_VERSION;
+
+use anyhow::{bail, Error, Result};
+use serde_json::{json, Map, Value};
+use std::{fmt, str::FromStr};
+
+/// The JSON Schema dialect of the emitted schemas.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The names of the instruction variants, as they appear in the `vtype` field.
+pub(crate) const INSTRUCTION_VTYPES: &[&str] = &[
+    "Abs",
+    "AbsWrapped",
+    "Add",
+    "AddWrapped",
+    "And",
+    "AssertEq",
+    "AssertNeq",
+    "Async",
+    "Call",
+    "Cast",
+    "CastLossy",
+    "CommitBHP256",
+    "CommitBHP512",
+    "CommitBHP768",
+    "CommitBHP1024",
+    "CommitPED64",
+    "CommitPED128",
+    "Div",
+    "DivWrapped",
+    "Double",
+    "GreaterThan",
+    "GreaterThanOrEqual",
+    "HashBHP256",
+    "HashBHP512",
+    "HashBHP768",
+    "HashBHP1024",
+    "HashKeccak256",
+    "HashKeccak384",
+    "HashKeccak512",
+    "HashPED64",
+    "HashPED128",
+    "HashPSD2",
+    "HashPSD4",
+    "HashPSD8",
+    "HashSha3_256",
+    "HashSha3_384",
+    "HashSha3_512",
+    "HashManyPSD2",
+    "HashManyPSD4",
+    "HashManyPSD8",
+    "Inv",
+    "IsEq",
+    "IsNeq",
+    "LessThan",
+    "LessThanOrEqual",
+    "Modulo",
+    "Mul",
+    "MulWrapped",
+    "Nand",
+    "Neg",
+    "Nor",
+    "Not",
+    "Or",
+    "Pow",
+    "PowWrapped",
+    "Rem",
+    "RemWrapped",
+    "Shl",
+    "ShlWrapped",
+    "Shr",
+    "ShrWrapped",
+    "SignVerify",
+    "Square",
+    "SquareRoot",
+    "Sub",
+    "SubWrapped",
+    "Ternary",
+    "Xor",
+];
+
+/// The names of the command variants, as they appear in the `vtype` field.
+pub(crate) const COMMAND_VTYPES: &[&str] = &[
+    "Instruction",
+    "Await",
+    "Contains",
+    "Get",
+    "GetOrUse",
+    "RandChaCha",
+    "Remove",
+    "Set",
+    "BranchEq",
+    "BranchNeq",
+    "Position",
+];
+
+/// The kinds of documents a schema can be emitted for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SchemaKind {
+    Program,
+    Function,
+    Instruction,
+    Block,
+    Transaction,
+}
+
+impl SchemaKind {
+    /// Returns every schema kind.
+    pub const fn all() -> [Self; 5] {
+        [Self::Program, Self::Function, Self::Instruction, Self::Block, Self::Transaction]
+    }
+
+    /// Returns the name of the schema kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Program => "program",
+            Self::Function => "function",
+            Self::Instruction => "instruction",
+            Self::Block => "block",
+            Self::Transaction => "transaction",
+        }
+    }
+
+    /// Returns the name of the definition at the root of the schema.
+    const fn root(&self) -> &'static str {
+        match self {
+            Self::Program => "Program",
+            Self::Function => "Function",
+            Self::Instruction => "Instruction",
+            Self::Block => "Block",
+            Self::Transaction => "Transaction",
+        }
+    }
+}
+
+impl FromStr for SchemaKind {
+    type Err = Error;
+
+    /// Parses a schema kind from its name.
+    fn from_str(kind: &str) -> Result<Self> {
+        match kind {
+            "program" => Ok(Self::Program),
+            "function" => Ok(Self::Function),
+            "instruction" => Ok(Self::Instruction),
+            "block" => Ok(Self::Block),
+            "transaction" => Ok(Self::Transaction),
+            _ => bail!("Unknown schema '{kind}' (expected one of: program, function, instruction, block, transaction)"),
+        }
+    }
+}
+
+impl fmt::Display for SchemaKind {
+    /// Prints the name of the schema kind.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Returns the JSON Schema describing the `to_json()` output of the given kind.
+///
+/// Every schema carries the full set of definitions under `$defs`, so it can be consumed on its own.
+pub fn json_schema(kind: SchemaKind) -> Value {
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "$id": format!("urn:aleo2json:schema:{}", kind),
+        "title": kind.root(),
+        "format_version": FORMAT_VERSION,
+        // Top-level documents are stamped with the versions they were produced with, and with their provenance.
+        "properties": {
+            "format_version": { "const": FORMAT_VERSION },
+            "snarkvm_version": { "type": "string" },
+            "meta": {
+                "type": "object",
+                "properties": {
+                    "aleo2json_version": { "type": "string" },
+                    "snarkvm_version": { "type": "string" },
+                    "network": nullable(json!({ "type": "integer" })),
+                    "timestamp": nullable(json!({ "type": "integer" })),
+                    "input_checksum": nullable(json!({ "type": "string" })),
+                },
+            },
+        },
+        "$ref": format!("#/$defs/{}", kind.root()),
+        "$defs": definitions(),
+    })
+}
+
+/// Returns a reference to the definition with the given name.
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{name}") })
+}
+
+/// Returns a schema accepting either the given schema or `null`.
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+/// Returns a schema accepting either the given schema or an `"unsupported"` node in its place.
+fn supported(schema: Value) -> Value {
+    json!({ "anyOf": [schema, reference("Unsupported")] })
+}
+
+/// Returns a schema for an array of the given schema.
+fn array_of(schema: Value) -> Value {
+    json!({ "type": "array", "items": schema })
+}
+
+/// Returns a schema for an object keyed by name, with values of the given schema.
+fn map_of(schema: Value) -> Value {
+    json!({ "type": "object", "additionalProperties": schema })
+}
+
+/// Returns a schema for a node with the given `type` tag and properties, all of which are required.
+fn node(type_name: &str, properties: &[(&str, Value)]) -> Value {
+    let mut j_properties = Map::new();
+    j_properties.insert("type".to_string(), json!({ "const": type_name }));
+    for (key, val) in properties {
+        j_properties.insert(key.to_string(), val.clone());
+    }
+
+    let j_required: Vec<&String> = j_properties.keys().collect();
+
+    json!({
+        "type": "object",
+        "properties": j_properties,
+        "required": j_required,
+    })
+}
+
+/// Returns a schema for a tagged node, whose `vtype` names the variant and whose `value` holds its payload.
+fn tagged(type_name: &str, vtypes: &[&str], properties: &[(&str, Value)]) -> Value {
+    let mut j_properties = vec![("vtype", json!({ "type": "string", "enum": vtypes })), ("value", json!({}))];
+    j_properties.extend_from_slice(properties);
+    node(type_name, &j_properties)
+}
+
+/// Returns the definitions shared by every schema.
+fn definitions() -> Value {
+    let string = json!({ "type": "string" });
+    let integer = json!({ "type": "integer", "minimum": 0 });
+    // A node produced by a `to_json()` helper whose inner structure is not pinned down by the schema.
+    let any_node = reference("Node");
+
+    json!({
+        "Node": {
+            "type": "object",
+            "properties": { "type": string },
+            "required": ["type"],
+        },
+        "ProgramID": node("ProgramID", &[("name", string.clone()), ("network", string.clone())]),
+        "Register": tagged("Register", &["Locator", "Access"], &[
+            ("locator", nullable(integer.clone())),
+            ("value", json!({ "anyOf": [integer.clone(), array_of(reference("Access"))] })),
+        ]),
+        // A member access holds the member name, and an index access holds its typed `U32` literal.
+        "Access": {
+            "anyOf": [
+                node("Access", &[("vtype", json!({ "const": "Member" })), ("value", string.clone())]),
+                node("Access", &[
+                    ("vtype", json!({ "const": "Index" })),
+                    ("value", node("Literal", &[("vtype", json!({ "const": "U32" })), ("value", any_node.clone())])),
+                ]),
+            ],
+        },
+        "ValueType": tagged("ValueType", &["Constant", "Public", "Private", "Record", "ExternalRecord", "Future"], &[]),
+        "Parameter": {
+            "type": "object",
+            "properties": {
+                "type": { "enum": ["Input", "Output"] },
+                "str": string,
+            },
+            "required": ["type", "str"],
+        },
+        "Import": node("Import", &[("program_id", reference("ProgramID"))]),
+        "Mapping": node("Mapping", &[("name", string.clone()), ("key", any_node.clone()), ("value", any_node.clone())]),
+        "Instruction": tagged("Instruction", INSTRUCTION_VTYPES, &[
+            ("str", string.clone()),
+            ("index", integer.clone()),
+        ]),
+        "Command": tagged("Command", COMMAND_VTYPES, &[("str", string.clone()), ("index", integer.clone())]),
+        "Unsupported": node("unsupported", &[
+            ("construct", string.clone()),
+            ("vtype", string.clone()),
+            ("str", string.clone()),
+            ("index", integer.clone()),
+        ]),
+        "Closure": node("ClosureCore", &[
+            ("name", string.clone()),
+            ("inputs", array_of(reference("Parameter"))),
+            ("instructions", array_of(supported(reference("Instruction")))),
+            ("outputs", array_of(reference("Parameter"))),
+        ]),
+        "Finalize": node("FinalizeCore", &[
+            ("name", string.clone()),
+            ("inputs", array_of(reference("Parameter"))),
+            ("commands", array_of(supported(reference("Command")))),
+            ("num_writes", integer.clone()),
+            ("positions", map_of(integer.clone())),
+            ("labels", map_of(integer.clone())),
+        ]),
+        "Function": node("FunctionCore", &[
+            ("name", string.clone()),
+            ("inputs", array_of(reference("Parameter"))),
+            ("instructions", array_of(supported(reference("Instruction")))),
+            ("outputs", array_of(reference("Parameter"))),
+            ("finalize_logic", nullable(reference("Finalize"))),
+        ]),
+        "Program": node("ProgramCore", &[
+            ("id", reference("ProgramID")),
+            ("identifiers", map_of(any_node.clone())),
+            ("imports", map_of(reference("Import"))),
+            ("mappings", map_of(reference("Mapping"))),
+            ("structs", map_of(any_node.clone())),
+            ("records", map_of(any_node.clone())),
+            ("closures", map_of(reference("Closure"))),
+            ("functions", map_of(reference("Function"))),
+        ]),
+        "TransitionInput": tagged("Input", &["Constant", "Public", "Private", "Record", "ExternalRecord"], &[("id", any_node.clone())]),
+        "TransitionOutput": tagged("Output", &["Constant", "Public", "Private", "Record", "ExternalRecord", "Future"], &[("id", any_node.clone())]),
+        "Transition": node("Transition", &[
+            ("id", string.clone()),
+            ("program_id", reference("ProgramID")),
+            ("function_name", string.clone()),
+            ("inputs", array_of(reference("TransitionInput"))),
+            ("outputs", array_of(reference("TransitionOutput"))),
+            ("tpk", any_node.clone()),
+            ("tcm", any_node.clone()),
+        ]),
+        "Fee": node("Fee", &[
+            ("transition", reference("Transition")),
+            ("global_state_root", string.clone()),
+            ("proof", nullable(string.clone())),
+        ]),
+        "Execution": node("Execution", &[
+            ("transitions", array_of(reference("Transition"))),
+            ("global_state_root", string.clone()),
+            ("proof", nullable(string.clone())),
+        ]),
+        "Deployment": node("Deployment", &[
+            ("edition", integer.clone()),
+            ("program", reference("Program")),
+            ("verifying_keys", array_of(json!({
+                "type": "object",
+                "properties": {
+                    "function_name": string,
+                    "verifying_key": string,
+                    "certificate": string,
+                },
+                "required": ["function_name", "verifying_key", "certificate"],
+            }))),
+        ]),
+        "Transaction": node("Transaction", &[
+            ("vtype", json!({ "enum": ["Deploy", "Execute", "Fee"] })),
+            ("id", string.clone()),
+            ("value", json!({
+                "type": "object",
+                "properties": {
+                    "owner": string,
+                    "deployment": reference("Deployment"),
+                    "execution": reference("Execution"),
+                    "fee": nullable(reference("Fee")),
+                },
+                "required": ["fee"],
+            })),
+        ]),
+        "Rejected": tagged("Rejected", &["Deployment", "Execution"], &[]),
+        "ConfirmedTransaction": node("ConfirmedTransaction", &[
+            ("vtype", json!({ "enum": ["AcceptedDeploy", "AcceptedExecute", "RejectedDeploy", "RejectedExecute"] })),
+            ("index", integer.clone()),
+            ("transaction", reference("Transaction")),
+            ("rejected", nullable(reference("Rejected"))),
+            ("finalize", array_of(reference("Node"))),
+        ]),
+        "Ratify": tagged("Ratify", &["Genesis", "BlockReward", "PuzzleReward"], &[]),
+        "Metadata": node("Metadata", &[
+            ("network", integer.clone()),
+            ("round", integer.clone()),
+            ("height", integer.clone()),
+            ("cumulative_weight", string.clone()),
+            ("cumulative_proof_target", string.clone()),
+            ("coinbase_target", integer.clone()),
+            ("proof_target", integer.clone()),
+            ("last_coinbase_target", integer.clone()),
+            ("last_coinbase_timestamp", json!({ "type": "integer" })),
+            ("timestamp", json!({ "type": "integer" })),
+        ]),
+        "Header": node("Header", &[
+            ("previous_state_root", string.clone()),
+            ("transactions_root", any_node.clone()),
+            ("finalize_root", any_node.clone()),
+            ("ratifications_root", any_node.clone()),
+            ("solutions_root", any_node.clone()),
+            ("subdag_root", any_node.clone()),
+            ("metadata", reference("Metadata")),
+        ]),
+        "Block": node("Block", &[
+            ("block_hash", string.clone()),
+            ("previous_hash", string.clone()),
+            ("header", reference("Header")),
+            ("authority", string.clone()),
+            ("ratifications", array_of(reference("Ratify"))),
+            ("solutions", nullable(string.clone())),
+            ("transactions", array_of(reference("ConfirmedTransaction"))),
+            ("aborted_transaction_ids", array_of(string)),
+        ]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_kind() {
+        for kind in SchemaKind::all() {
+            assert_eq!(kind, SchemaKind::from_str(kind.as_str()).unwrap());
+        }
+        assert!(SchemaKind::from_str("mapping").is_err());
+    }
+
+    #[test]
+    fn test_schema_references() {
+        for kind in SchemaKind::all() {
+            let schema = json_schema(kind);
+            let definitions = schema["$defs"].as_object().unwrap();
+
+            // Ensure every reference resolves to a definition.
+            let mut pending = vec![&schema];
+            while let Some(value) = pending.pop() {
+                match value {
+                    Value::Object(object) => {
+                        if let Some(Value::String(reference)) = object.get("$ref") {
+                            let name = reference.strip_prefix("#/$defs/").unwrap();
+                            assert!(definitions.contains_key(name), "Missing definition '{name}'");
+                        }
+                        pending.extend(object.values());
+                    }
+                    Value::Array(array) => pending.extend(array),
+                    _ => (),
+                }
+            }
+        }
+    }
+}
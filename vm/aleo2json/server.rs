@@ -0,0 +1,343 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{convert_block_bytes, convert_block_json, convert_program, ConversionError},
+    console::network::Network,
+};
+
+use anyhow::Result;
+use parking_lot::{Condvar, Mutex};
+use serde_json::json;
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    marker::PhantomData,
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+/// The maximum amount of time to wait on a client while reading its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// The maximum size of the request line and headers, in bytes.
+const MAX_HEADER_SIZE: usize = 16 * 1024;
+
+/// The configuration for the conversion server.
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    /// The socket address to bind to.
+    pub address: SocketAddr,
+    /// The maximum size of a request body, in bytes.
+    pub max_body_size: usize,
+    /// The maximum number of requests handled concurrently.
+    pub max_concurrency: usize,
+}
+
+/// An HTTP server exposing the converters as endpoints.
+///
+/// The server accepts:
+///  - `POST /program`, with the `.aleo` source as the body,
+///  - `POST /block`, with the block as snarkVM JSON, or as bytes if sent as `application/octet-stream`,
+///  - `GET /health`, which reports that the server is up.
+pub struct Server<N: Network> {
+    /// The server configuration.
+    config: ServerConfig,
+    /// The limiter on the number of in-flight requests.
+    limiter: Arc<Limiter>,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> Server<N> {
+    /// Initializes a new server with the given configuration.
+    pub fn new(config: ServerConfig) -> Self {
+        let limiter = Arc::new(Limiter::new(config.max_concurrency.max(1)));
+        Self { config, limiter, _phantom: PhantomData }
+    }
+
+    /// Binds to the configured address and serves requests until the process is terminated.
+    pub fn run(&self) -> Result<()> {
+        let listener = TcpListener::bind(self.config.address)?;
+        eprintln!("aleo2json is listening on http://{}", listener.local_addr()?);
+
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(error) => {
+                    eprintln!("Failed to accept a connection: {error}");
+                    continue;
+                }
+            };
+            // Wait for a free slot before handling the connection.
+            let permit = Limiter::acquire(&self.limiter);
+            let max_body_size = self.config.max_body_size;
+            thread::spawn(move || {
+                let _permit = permit;
+                if let Err(error) = Self::handle_connection(stream, max_body_size) {
+                    eprintln!("Failed to respond to a request: {error}");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Reads a single request from the given stream, and writes back the response.
+    fn handle_connection(mut stream: TcpStream, max_body_size: usize) -> Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let response = match Request::read(&mut reader, max_body_size) {
+            Ok(request) => Self::route(&request),
+            Err(response) => response,
+        };
+        response.write(&mut stream)
+    }
+
+    /// Dispatches the given request to its endpoint.
+    fn route(request: &Request) -> Response {
+        match (request.method.as_str(), request.path()) {
+            ("POST", "/program") => Response::from_result(
+                std::str::from_utf8(&request.body).map_err(Into::into).and_then(convert_program::<N>),
+            ),
+            ("POST", "/block") => match request.header("content-type") {
+                Some(content_type) if content_type.starts_with("application/octet-stream") => {
+                    Response::from_result(convert_block_bytes::<N>(&request.body))
+                }
+                _ => Response::from_result(
+                    std::str::from_utf8(&request.body).map_err(Into::into).and_then(convert_block_json::<N>),
+                ),
+            },
+            ("GET", "/health") => Response::new(200, json!({ "status": "ok" })),
+            (_, "/program" | "/block" | "/health") => Response::error(405, "Method not allowed"),
+            _ => Response::error(404, "Not found"),
+        }
+    }
+}
+
+/// An HTTP request.
+struct Request {
+    /// The request method.
+    method: String,
+    /// The request target, including any query string.
+    target: String,
+    /// The request headers, with lowercase names.
+    headers: Vec<(String, String)>,
+    /// The request body.
+    body: Vec<u8>,
+}
+
+impl Request {
+    /// Reads a request from the given reader, rejecting bodies larger than `max_body_size`.
+    fn read<R: BufRead>(reader: &mut R, max_body_size: usize) -> Result<Self, Response> {
+        let mut remaining = MAX_HEADER_SIZE;
+
+        // Read the request line.
+        let request_line = Self::read_line(reader, &mut remaining)?;
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => {
+                (method.to_string(), target.to_string())
+            }
+            _ => return Err(Response::error(400, "Malformed request line")),
+        };
+
+        // Read the headers.
+        let mut headers = Vec::new();
+        loop {
+            let line = Self::read_line(reader, &mut remaining)?;
+            if line.is_empty() {
+                break;
+            }
+            match line.split_once(':') {
+                Some((name, value)) => headers.push((name.trim().to_lowercase(), value.trim().to_string())),
+                None => return Err(Response::error(400, "Malformed header")),
+            }
+        }
+
+        // Read the body.
+        let content_length = match headers.iter().find(|(name, _)| name == "content-length") {
+            Some((_, value)) => value.parse::<usize>().map_err(|_| Response::error(400, "Invalid content length"))?,
+            None => 0,
+        };
+        if content_length > max_body_size {
+            return Err(Response::error(413, format!("Request body exceeds the limit of {max_body_size} bytes")));
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).map_err(|error| Response::error(400, error))?;
+
+        Ok(Self { method, target, headers, body })
+    }
+
+    /// Reads a single CRLF-terminated line, charging its length against the remaining header budget.
+    fn read_line<R: BufRead>(reader: &mut R, remaining: &mut usize) -> Result<String, Response> {
+        let mut line = String::new();
+        let num_bytes = reader
+            .by_ref()
+            .take(*remaining as u64)
+            .read_line(&mut line)
+            .map_err(|error| Response::error(400, error))?;
+        if !line.ends_with('\n') {
+            return Err(Response::error(431, "Request headers are too large or incomplete"));
+        }
+        *remaining -= num_bytes;
+        Ok(line.trim_end_matches(|c| c == '\r' || c == '\n').to_string())
+    }
+
+    /// Returns the request path, without the query string.
+    fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+
+    /// Returns the value of the given (lowercase) header, if it is present.
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+/// An HTTP response with a JSON body.
+#[derive(Debug)]
+struct Response {
+    /// The status code.
+    status: u16,
+    /// The response body.
+    body: serde_json::Value,
+}
+
+impl Response {
+    /// Initializes a new response.
+    fn new(status: u16, body: serde_json::Value) -> Self {
+        Self { status, body }
+    }
+
+    /// Initializes a new error response with the given message.
+    fn error(status: u16, message: impl ToString) -> Self {
+        Self::new(status, json!({ "error": message.to_string() }))
+    }
+
+    /// Initializes a response from the result of a conversion, with the kind of error on failure.
+    fn from_result(result: Result<serde_json::Value, ConversionError>) -> Self {
+        match result {
+            Ok(body) => Self::new(200, body),
+            Err(error) => Self::new(400, json!({ "error": error.to_string(), "kind": error.kind() })),
+        }
+    }
+
+    /// Returns the reason phrase for the status code.
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+
+    /// Writes the response to the given writer.
+    fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let body = self.body.to_string();
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.status,
+            self.reason(),
+            body.len()
+        )?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A counting semaphore bounding the number of in-flight requests.
+pub(crate) struct Limiter {
+    /// The number of available slots.
+    available: Mutex<usize>,
+    /// The condition variable signalled when a slot is released.
+    condvar: Condvar,
+}
+
+impl Limiter {
+    /// Initializes a new limiter with the given number of slots.
+    pub(crate) fn new(slots: usize) -> Self {
+        Self { available: Mutex::new(slots), condvar: Condvar::new() }
+    }
+
+    /// Blocks until a slot is available, and returns a permit holding it.
+    pub(crate) fn acquire(limiter: &Arc<Self>) -> Permit {
+        let mut available = limiter.available.lock();
+        while *available == 0 {
+            limiter.condvar.wait(&mut available);
+        }
+        *available -= 1;
+        Permit(limiter.clone())
+    }
+}
+
+/// A held slot in the limiter, released on drop.
+pub(crate) struct Permit(Arc<Limiter>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        *self.0.available.lock() += 1;
+        self.0.condvar.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    fn request(raw: &str, max_body_size: usize) -> Result<Request, Response> {
+        Request::read(&mut raw.as_bytes(), max_body_size)
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request =
+            request("POST /program?pretty HTTP/1.1\r\nContent-Length: 5\r\nContent-Type: text/plain\r\n\r\nhello", 16)
+                .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path(), "/program");
+        assert_eq!(request.header("content-type"), Some("text/plain"));
+        assert_eq!(request.body, b"hello");
+    }
+
+    #[test]
+    fn test_reject_large_body() {
+        let response = request("POST /program HTTP/1.1\r\nContent-Length: 17\r\n\r\n", 16).err().unwrap();
+        assert_eq!(response.status, 413);
+    }
+
+    #[test]
+    fn test_route() {
+        let source = "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;";
+        let raw = format!("POST /program HTTP/1.1\r\nContent-Length: {}\r\n\r\n{source}", source.len());
+        let response = Server::<CurrentNetwork>::route(&request(&raw, 1024).unwrap());
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["type"], "ProgramCore");
+
+        let raw = "GET /unknown HTTP/1.1\r\n\r\n";
+        let response = Server::<CurrentNetwork>::route(&request(raw, 1024).unwrap());
+        assert_eq!(response.status, 404);
+
+        let raw = "GET /program HTTP/1.1\r\n\r\n";
+        let response = Server::<CurrentNetwork>::route(&request(raw, 1024).unwrap());
+        assert_eq!(response.status, 405);
+    }
+}
@@ -0,0 +1,350 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{normalize, to_canonical_string, KEYED_PROPERTIES};
+
+use serde_json::{json, Map, Value};
+
+/// The default similarity score from which two functions are reported as a near match.
+pub const NEAR_MATCH_THRESHOLD: f64 = 0.8;
+
+/// The properties annotating a node with how it was converted, which do not describe the code itself.
+const ANNOTATION_PROPERTIES: &[&str] = &["fingerprint", "node_id", "parent_id", "pointer", "source", "span"];
+
+/// The statement lists of closures, functions, and finalizes.
+const STATEMENT_SECTIONS: &[&str] = &["inputs", "instructions", "outputs", "commands"];
+
+/// How closely a function matches its most similar function in the other program.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The functions are identical, except for their names.
+    Exact,
+    /// The functions are identical, once their registers are renumbered canonically.
+    RenamedRegisters,
+    /// The statements of the functions are similar, with a score above the threshold.
+    Near,
+    /// No function is similar.
+    None,
+}
+
+impl MatchKind {
+    /// Returns the name of the match kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::RenamedRegisters => "renamed_registers",
+            Self::Near => "near",
+            Self::None => "none",
+        }
+    }
+}
+
+/// The most similar function in the right program, for a function of the left program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionMatch {
+    /// The name of the function in the left program.
+    pub function: String,
+    /// The name of the most similar function in the right program, unless there is no match.
+    pub matched: Option<String>,
+    /// How closely the functions match.
+    pub kind: MatchKind,
+    /// The similarity of the statements of the functions, from 0 to 1.
+    pub score: f64,
+}
+
+impl FunctionMatch {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "function": self.function,
+            "match": self.matched,
+            "kind": self.kind.as_str(),
+            "score": self.score,
+        })
+    }
+}
+
+/// The per-function similarity of two converted programs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimilarityReport {
+    /// The ID of the left program.
+    pub left: Option<String>,
+    /// The ID of the right program.
+    pub right: Option<String>,
+    /// The match of each function of the left program, in order.
+    pub functions: Vec<FunctionMatch>,
+    /// The functions of the right program that no function of the left program matches, in order.
+    pub unmatched: Vec<String>,
+}
+
+impl SimilarityReport {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "SimilarityReport",
+            "left": self.left,
+            "right": self.right,
+            "functions": self.functions.iter().map(FunctionMatch::to_json).collect::<Vec<_>>(),
+            "unmatched": self.unmatched,
+        })
+    }
+}
+
+/// The forms of a function compared for similarity.
+struct FunctionBody {
+    /// The canonical JSON of the function, without its name and annotations.
+    exact: String,
+    /// The canonical JSON of the function, once normalized.
+    normalized: String,
+    /// The canonical JSON of each statement of the function, once normalized, in order.
+    statements: Vec<String>,
+}
+
+impl FunctionBody {
+    /// Returns the forms of the given function JSON.
+    fn new(function: &Value) -> Self {
+        let mut j_body = function.clone();
+        strip(&mut j_body, false);
+        let exact = to_canonical_string(&j_body);
+        normalize(&mut j_body);
+        let normalized = to_canonical_string(&j_body);
+        let mut statements = Vec::new();
+        collect_statements(&j_body, &mut statements);
+        Self { exact, normalized, statements }
+    }
+
+    /// Returns how closely this function matches the given one, with the similarity of their statements.
+    fn compare(&self, other: &Self, threshold: f64) -> (MatchKind, f64) {
+        if self.exact == other.exact {
+            return (MatchKind::Exact, 1.0);
+        }
+        if self.normalized == other.normalized {
+            return (MatchKind::RenamedRegisters, 1.0);
+        }
+        let score = sequence_similarity(&self.statements, &other.statements);
+        match score >= threshold {
+            true => (MatchKind::Near, score),
+            false => (MatchKind::None, score),
+        }
+    }
+}
+
+/// Compares the functions of the two given converted programs, matching each function of the left program
+/// with its most similar function in the right program, whatever its name.
+///
+/// Functions are an exact match if they are identical except for their names, and a renamed-registers match
+/// if they are identical once their registers are renumbered. Otherwise, their score is the similarity of their
+/// statement sequences, from 0 to 1, and they are a near match if it is at least the given threshold.
+/// Of equally similar functions, the one with the same name is preferred, then the first one.
+pub fn compare_programs(left: &Value, right: &Value, threshold: f64) -> SimilarityReport {
+    let right_functions: Vec<(&String, FunctionBody)> =
+        functions(right).map(|(name, function)| (name, FunctionBody::new(function))).collect();
+
+    let mut matched_names = Vec::new();
+    let mut matches = Vec::new();
+    for (name, function) in functions(left) {
+        let body = FunctionBody::new(function);
+        let rank = |kind: MatchKind, score: f64, candidate: &str| (match_rank(kind), score, candidate == name.as_str());
+        let mut best: Option<(&String, MatchKind, f64)> = None;
+        for (other_name, other) in &right_functions {
+            let (kind, score) = body.compare(other, threshold);
+            let is_better = match best {
+                Some((best_name, best_kind, best_score)) => {
+                    rank(kind, score, other_name.as_str()) > rank(best_kind, best_score, best_name.as_str())
+                }
+                None => true,
+            };
+            if is_better {
+                best = Some((*other_name, kind, score));
+            }
+        }
+
+        let (matched, kind, score) = match best {
+            Some((_, MatchKind::None, score)) => (None, MatchKind::None, score),
+            Some((matched, kind, score)) => (Some(matched.clone()), kind, score),
+            None => (None, MatchKind::None, 0.0),
+        };
+        matched_names.extend(matched.clone());
+        matches.push(FunctionMatch { function: name.clone(), matched, kind, score });
+    }
+
+    let unmatched =
+        right_functions.iter().map(|(name, _)| (*name).clone()).filter(|name| !matched_names.contains(name)).collect();
+    SimilarityReport { left: program_id(left), right: program_id(right), functions: matches, unmatched }
+}
+
+/// Returns the rank of the given match kind, higher for a closer match.
+const fn match_rank(kind: MatchKind) -> u8 {
+    match kind {
+        MatchKind::Exact => 3,
+        MatchKind::RenamedRegisters => 2,
+        MatchKind::Near => 1,
+        MatchKind::None => 0,
+    }
+}
+
+/// Returns the functions of the given program JSON, by name, in order.
+fn functions(program: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    program.get("functions").and_then(Value::as_object).into_iter().flat_map(Map::iter)
+}
+
+/// Returns the ID of the given program JSON, i.e. `token.aleo`.
+fn program_id(program: &Value) -> Option<String> {
+    let id = program.get("id")?;
+    Some(format!("{}.{}", id.get("name")?.as_str()?, id.get("network")?.as_str()?))
+}
+
+/// Removes the names of the closures, functions, and finalizes in the given value, and the annotations of its nodes.
+fn strip(value: &mut Value, is_keyed: bool) {
+    match value {
+        Value::Object(object) if !is_keyed => {
+            if matches!(
+                object.get("type").and_then(Value::as_str),
+                Some("ClosureCore" | "FinalizeCore" | "FunctionCore")
+            ) {
+                object.remove("name");
+            }
+            object.retain(|key, _| !ANNOTATION_PROPERTIES.contains(&key.as_str()));
+            for (key, val) in object.iter_mut() {
+                strip(val, KEYED_PROPERTIES.contains(&key.as_str()));
+            }
+        }
+        Value::Object(object) => object.values_mut().for_each(|val| strip(val, false)),
+        Value::Array(array) => array.iter_mut().for_each(|val| strip(val, false)),
+        _ => (),
+    }
+}
+
+/// Appends the canonical JSON of each statement in the given value, in order.
+fn collect_statements(value: &Value, statements: &mut Vec<String>) {
+    if let Value::Object(object) = value {
+        for (key, val) in object {
+            match val {
+                Value::Array(array) if STATEMENT_SECTIONS.contains(&key.as_str()) => {
+                    statements.extend(array.iter().map(to_canonical_string))
+                }
+                Value::Object(_) => collect_statements(val, statements),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Returns the similarity of the two given sequences, from 0 to 1: twice the length of their longest common
+/// subsequence, over their total length.
+fn sequence_similarity(left: &[String], right: &[String]) -> f64 {
+    if left.is_empty() && right.is_empty() {
+        return 1.0;
+    }
+    // The length of the longest common subsequence, computed one row at a time.
+    let mut previous = vec![0usize; right.len() + 1];
+    for left_item in left {
+        let mut current = vec![0usize; right.len() + 1];
+        for (index, right_item) in right.iter().enumerate() {
+            current[index + 1] = match left_item == right_item {
+                true => previous[index] + 1,
+                false => current[index].max(previous[index + 1]),
+            };
+        }
+        previous = current;
+    }
+    (2 * previous[right.len()]) as f64 / (left.len() + right.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a function JSON with the given name, whose statements are the given instructions.
+    fn function(name: &str, instructions: &[(&str, u64)]) -> Value {
+        let j_instructions: Vec<_> = instructions
+            .iter()
+            .enumerate()
+            .map(|(index, (opcode, register))| {
+                json!({
+                    "type": "Instruction",
+                    "vtype": opcode,
+                    "value": { "type": "Register", "vtype": "Locator", "locator": register, "value": register },
+                    "index": index,
+                })
+            })
+            .collect();
+        json!({ "type": "FunctionCore", "name": name, "inputs": [], "instructions": j_instructions, "outputs": [] })
+    }
+
+    /// Returns a program JSON with the given functions.
+    fn program(functions: &[Value]) -> Value {
+        let j_functions: Map<String, Value> = functions
+            .iter()
+            .map(|function| (function["name"].as_str().unwrap().to_string(), function.clone()))
+            .collect();
+        json!({
+            "type": "ProgramCore",
+            "id": { "type": "ProgramID", "name": "token", "network": "aleo" },
+            "functions": j_functions,
+        })
+    }
+
+    #[test]
+    fn test_compare_programs() {
+        let left = program(&[
+            function("transfer", &[("Add", 0), ("Sub", 1)]),
+            function("mint", &[("Add", 0), ("Mul", 1)]),
+            function("burn", &[("Add", 0), ("Sub", 1), ("Mul", 2), ("Div", 3), ("Hash", 4)]),
+            function("vote", &[("Ternary", 0)]),
+        ]);
+        let right = program(&[
+            function("send", &[("Add", 0), ("Sub", 1)]),
+            function("mint", &[("Add", 5), ("Mul", 7)]),
+            function("destroy", &[("Add", 0), ("Sub", 1), ("Mul", 2), ("Div", 3), ("Hash", 4), ("Cast", 5)]),
+            function("stake", &[("Cast", 0)]),
+        ]);
+
+        let report = compare_programs(&left, &right, NEAR_MATCH_THRESHOLD);
+        assert_eq!((report.left.as_deref(), report.right.as_deref()), (Some("token.aleo"), Some("token.aleo")));
+        let matches: Vec<_> = report
+            .functions
+            .iter()
+            .map(|function_match| {
+                (function_match.function.as_str(), function_match.matched.as_deref(), function_match.kind)
+            })
+            .collect();
+        assert_eq!(matches, [
+            ("transfer", Some("send"), MatchKind::Exact),
+            ("mint", Some("mint"), MatchKind::RenamedRegisters),
+            ("burn", Some("destroy"), MatchKind::Near),
+            ("vote", None, MatchKind::None),
+        ]);
+        assert!((report.functions[2].score - 10.0 / 11.0).abs() < 1e-9);
+        assert_eq!(report.unmatched, ["stake"]);
+        assert_eq!(report.to_json()["functions"][1]["kind"], "renamed_registers");
+    }
+
+    #[test]
+    fn test_compare_programs_prefers_same_name() {
+        let left = program(&[function("send", &[("Add", 0)])]);
+        let right = program(&[function("transfer", &[("Add", 0)]), function("send", &[("Add", 0)])]);
+        let report = compare_programs(&left, &right, NEAR_MATCH_THRESHOLD);
+        assert_eq!(report.functions[0].matched.as_deref(), Some("send"));
+        assert_eq!(report.unmatched, ["transfer"]);
+    }
+
+    #[test]
+    fn test_sequence_similarity() {
+        let sequence = |items: &[&str]| items.iter().map(|item| item.to_string()).collect::<Vec<_>>();
+        assert_eq!(sequence_similarity(&[], &[]), 1.0);
+        assert_eq!(sequence_similarity(&sequence(&["a"]), &[]), 0.0);
+        assert_eq!(sequence_similarity(&sequence(&["a", "b", "c"]), &sequence(&["a", "c"])), 0.8);
+    }
+}
@@ -0,0 +1,453 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{
+        network::Network,
+        program::{Identifier, Plaintext, ProgramID, Value},
+    },
+    ledger::store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    synthesizer::{
+        process::{FinalizeRegisters, StackProgramTypes},
+        program::{
+            Contains,
+            FinalizeGlobalState,
+            FinalizeStoreTrait,
+            Get,
+            GetOrUse,
+            MappingLocator,
+            Operand,
+            RegistersLoad,
+            RegistersStore,
+            Remove,
+            Set,
+        },
+        Command,
+        Finalize,
+        Program,
+        Stack,
+        VM,
+    },
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use indexmap::IndexMap;
+use serde_json::json;
+use std::{panic, str::FromStr};
+
+/// The entries of the mappings of a program, by mapping name, as key-value pairs in insertion order.
+pub type MappingEntries<N> = IndexMap<Identifier<N>, Vec<(Plaintext<N>, Value<N>)>>;
+
+/// Parses mapping entries from a JSON object of mapping names to objects of keys to values,
+/// i.e. `{ "account": { "aleo1...": "100u64" } }`, where keys and values are in their Aleo syntax.
+pub fn mapping_entries_from_json<N: Network>(value: &serde_json::Value) -> Result<MappingEntries<N>> {
+    let mappings = value.as_object().ok_or_else(|| anyhow!("Expected an object of mappings"))?;
+    let mut entries = MappingEntries::new();
+    for (mapping_name, mapping) in mappings {
+        let mapping =
+            mapping.as_object().ok_or_else(|| anyhow!("Expected an object of entries for '{mapping_name}'"))?;
+        let mut pairs = Vec::new();
+        for (key, value) in mapping {
+            let value = value.as_str().ok_or_else(|| anyhow!("Expected a string value for '{mapping_name}[{key}]'"))?;
+            pairs.push((Plaintext::from_str(key)?, Value::from_str(value)?));
+        }
+        entries.insert(Identifier::from_str(mapping_name)?, pairs);
+    }
+    Ok(entries)
+}
+
+/// Returns the mapping entries as a JSON object of mapping names to objects of keys to values,
+/// the inverse of [`mapping_entries_from_json`].
+pub fn mapping_entries_to_json<N: Network>(entries: &MappingEntries<N>) -> serde_json::Value {
+    let mut j_entries = serde_json::Map::new();
+    for (mapping_name, pairs) in entries {
+        let mut j_pairs = serde_json::Map::new();
+        for (key, value) in pairs {
+            j_pairs.insert(format!("{}", key), json!(format!("{}", value)));
+        }
+        j_entries.insert(format!("{}", mapping_name), serde_json::Value::Object(j_pairs));
+    }
+    serde_json::Value::Object(j_entries)
+}
+
+/// An access of a mapping by a finalize command, with the value at the key before and after the command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappingAccess<N: Network> {
+    /// The index of the command in the finalize block.
+    pub index: usize,
+    /// The opcode of the command: `get`, `get.or_use`, `contains`, `set`, or `remove`.
+    pub opcode: &'static str,
+    /// The command.
+    pub command: Command<N>,
+    /// The program ID of the mapping.
+    pub program_id: ProgramID<N>,
+    /// The name of the mapping.
+    pub mapping: Identifier<N>,
+    /// The key accessed.
+    pub key: Plaintext<N>,
+    /// The value at the key before the command, if the key was present.
+    pub before: Option<Value<N>>,
+    /// The value at the key after the command, if the key is present.
+    pub after: Option<Value<N>>,
+}
+
+impl<N: Network> MappingAccess<N> {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "type": "MappingAccess",
+            "index": self.index,
+            "opcode": self.opcode,
+            "command": format!("{}", self.command),
+            "mapping": format!("{}/{}", self.program_id, self.mapping),
+            "key": format!("{}", self.key),
+            "before": self.before.as_ref().map(|value| format!("{}", value)),
+            "after": self.after.as_ref().map(|value| format!("{}", value)),
+        })
+    }
+}
+
+/// The transcript of a simulated finalize block: every mapping access with its before and after values,
+/// and the state of the mappings of the program before and after the block.
+///
+/// A finalize block that halts (i.e. on a failed `assert` or an overflow) is rejected, so its writes are reverted
+/// and its final state is its initial state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalizeTranscript<N: Network> {
+    /// The program ID, i.e. `token.aleo`.
+    pub program_id: ProgramID<N>,
+    /// The name of the function whose finalize block was simulated.
+    pub function: Identifier<N>,
+    /// The block height the finalize block was simulated at.
+    pub block_height: u32,
+    /// The inputs of the finalize block.
+    pub inputs: Vec<Value<N>>,
+    /// The indices of the commands evaluated, in order.
+    pub path: Vec<usize>,
+    /// The mapping accesses, in order.
+    pub accesses: Vec<MappingAccess<N>>,
+    /// The entries of the mappings of the program before the finalize block.
+    pub initial_state: MappingEntries<N>,
+    /// The entries of the mappings of the program after the finalize block.
+    pub final_state: MappingEntries<N>,
+    /// The error of the command that halted, if the finalize block halted.
+    pub error: Option<String>,
+}
+
+impl<N: Network> FinalizeTranscript<N> {
+    /// Returns `true` if the finalize block halted.
+    pub fn is_halted(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_inputs = Vec::new();
+        for val in &self.inputs {
+            j_inputs.push(format!("{}", val));
+        }
+        let mut j_accesses = Vec::new();
+        for val in &self.accesses {
+            j_accesses.push(val.to_json());
+        }
+
+        json!({
+            "type": "FinalizeTranscript",
+            "program_id": format!("{}", self.program_id),
+            "function": format!("{}", self.function),
+            "block_height": self.block_height,
+            "inputs": j_inputs,
+            "path": self.path,
+            "accesses": j_accesses,
+            "initial_state": mapping_entries_to_json(&self.initial_state),
+            "final_state": mapping_entries_to_json(&self.final_state),
+            "halted": self.is_halted(),
+            "error": self.error,
+        })
+    }
+}
+
+/// Simulates the finalize block of the given function of the program on the given inputs, at the given block
+/// height, against an in-memory store whose mappings of the program are seeded with the initial state.
+///
+/// Mappings of the program missing from the initial state start empty. Awaiting futures is not supported,
+/// so a finalize block that awaits halts.
+pub fn simulate_finalize<N: Network>(
+    program: &Program<N>,
+    function_name: &str,
+    inputs: &[Value<N>],
+    initial_state: &MappingEntries<N>,
+    block_height: u32,
+) -> Result<FinalizeTranscript<N>> {
+    let program_id = *program.id();
+    let function = program.get_function(&Identifier::from_str(function_name)?)?;
+    let finalize = match function.finalize_logic() {
+        Some(finalize) => finalize,
+        None => bail!("Function '{function_name}' of '{program_id}' has no finalize block"),
+    };
+    ensure!(
+        finalize.inputs().len() == inputs.len(),
+        "The finalize block of '{function_name}' expects {} inputs, but {} were provided",
+        finalize.inputs().len(),
+        inputs.len()
+    );
+
+    // Initialize the VM on an empty, in-memory store, and add the program to it.
+    let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(None)?)?;
+    let process = vm.process();
+    if !process.read().contains_program(&program_id) {
+        process.write().add_program(program)?;
+    }
+    let process = process.read();
+    let stack = &**process.get_stack(program_id)?;
+
+    // Initialize the mappings of the program, and seed them with the initial state.
+    let store = vm.finalize_store();
+    for mapping_name in program.mappings().keys() {
+        store.initialize_mapping(program_id, *mapping_name)?;
+    }
+    for (mapping_name, pairs) in initial_state {
+        ensure!(
+            program.mappings().contains_key(mapping_name),
+            "Mapping '{mapping_name}' does not exist in '{program_id}'"
+        );
+        for (key, value) in pairs {
+            store.insert_key_value(program_id, *mapping_name, key.clone(), value.clone())?;
+        }
+    }
+    let read_state = || -> Result<MappingEntries<N>> {
+        program.mappings().keys().map(|name| Ok((*name, store.get_mapping_speculative(program_id, *name)?))).collect()
+    };
+
+    // Initialize the registers, and store the inputs.
+    let mut registers = FinalizeRegisters::new(
+        FinalizeGlobalState::from(0, block_height, [0; 32]),
+        N::TransitionID::default(),
+        *function.name(),
+        stack.get_finalize_types(function.name())?.clone(),
+    );
+    for (input, value) in finalize.inputs().iter().zip(inputs) {
+        registers.store(stack, input.register(), value.clone())?;
+    }
+
+    let mut transcript = FinalizeTranscript {
+        program_id,
+        function: *function.name(),
+        block_height,
+        inputs: inputs.to_vec(),
+        path: Vec::new(),
+        accesses: Vec::new(),
+        initial_state: read_state()?,
+        final_state: MappingEntries::new(),
+        error: None,
+    };
+
+    let mut counter = 0;
+    while counter < finalize.commands().len() {
+        let command = &finalize.commands()[counter];
+        transcript.path.push(counter);
+
+        // Read the value at the accessed key, if any, before the command.
+        let access = match mapping_access(&program_id, command) {
+            Some((opcode, mapping_program_id, mapping, key)) => match registers.load_plaintext(stack, key) {
+                Ok(key) => {
+                    let before = store.get_value_speculative(mapping_program_id, mapping, &key)?;
+                    Some((opcode, mapping_program_id, mapping, key, before))
+                }
+                // The command fails to load the key as well, and halts below.
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        // Note: Halting operations (i.e. overflows) panic, so they are caught and reported as errors.
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| match command {
+            Command::BranchEq(branch) => branch_to(
+                counter,
+                true,
+                branch.first(),
+                branch.second(),
+                branch.position(),
+                finalize,
+                stack,
+                &registers,
+            ),
+            Command::BranchNeq(branch) => branch_to(
+                counter,
+                false,
+                branch.first(),
+                branch.second(),
+                branch.position(),
+                finalize,
+                stack,
+                &registers,
+            ),
+            Command::Await(..) => bail!("Awaiting a future is not supported in a simulation"),
+            _ => command.finalize(stack, store, &mut registers).map(|_| counter + 1),
+        }));
+        let result = result.unwrap_or_else(|payload| {
+            let message = match (payload.downcast_ref::<String>(), payload.downcast_ref::<&str>()) {
+                (Some(message), _) => message.clone(),
+                (None, Some(message)) => message.to_string(),
+                (None, None) => "Halted".to_string(),
+            };
+            Err(anyhow!(message))
+        });
+
+        match result {
+            Ok(next) => {
+                if let Some((opcode, program_id, mapping, key, before)) = access {
+                    let after = store.get_value_speculative(program_id, mapping, &key)?;
+                    transcript.accesses.push(MappingAccess {
+                        index: counter,
+                        opcode,
+                        command: command.clone(),
+                        program_id,
+                        mapping,
+                        key,
+                        before,
+                        after,
+                    });
+                }
+                counter = next;
+            }
+            Err(error) => {
+                transcript.error = Some(format!("'finalize' failed to evaluate command ({command}): {error}"));
+                break;
+            }
+        }
+    }
+
+    transcript.final_state = match transcript.is_halted() {
+        true => transcript.initial_state.clone(),
+        false => read_state()?,
+    };
+    Ok(transcript)
+}
+
+/// Returns the opcode, mapping, and key operand of the given command, if it accesses a mapping.
+fn mapping_access<'a, N: Network>(
+    program_id: &ProgramID<N>,
+    command: &'a Command<N>,
+) -> Option<(&'static str, ProgramID<N>, Identifier<N>, &'a Operand<N>)> {
+    let locate = |mapping: &MappingLocator<N>| match mapping {
+        MappingLocator::Locator(locator) => (*locator.program_id(), *locator.resource()),
+        MappingLocator::Resource(mapping_name) => (*program_id, *mapping_name),
+    };
+    match command {
+        Command::Get(get) => {
+            let (program_id, mapping) = locate(get.mapping());
+            Some((*Get::<N>::opcode(), program_id, mapping, get.key()))
+        }
+        Command::GetOrUse(get_or_use) => {
+            let (program_id, mapping) = locate(get_or_use.mapping());
+            Some((*GetOrUse::<N>::opcode(), program_id, mapping, get_or_use.key()))
+        }
+        Command::Contains(contains) => {
+            Some((*Contains::<N>::opcode(), *program_id, *contains.mapping_name(), contains.key()))
+        }
+        Command::Set(set) => Some((*Set::<N>::opcode(), *program_id, *set.mapping_name(), set.key())),
+        Command::Remove(remove) => Some((*Remove::<N>::opcode(), *program_id, *remove.mapping_name(), remove.key())),
+        _ => None,
+    }
+}
+
+/// Returns the index of the next command after a `branch.eq` (if `jump_if_equal`) or a `branch.neq`.
+#[allow(clippy::too_many_arguments)]
+fn branch_to<N: Network>(
+    counter: usize,
+    jump_if_equal: bool,
+    first: &Operand<N>,
+    second: &Operand<N>,
+    position: &Identifier<N>,
+    finalize: &Finalize<N>,
+    stack: &Stack<N>,
+    registers: &FinalizeRegisters<N>,
+) -> Result<usize> {
+    let is_equal = registers.load(stack, first)? == registers.load(stack, second)?;
+    match is_equal == jump_if_equal {
+        true => match finalize.positions().get(position) {
+            Some(index) if *index > counter => Ok(*index),
+            Some(_) => bail!("Cannot branch to an earlier position '{position}' in the program"),
+            None => bail!("The position '{position}' does not exist."),
+        },
+        false => Ok(counter + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"
+program counter.aleo;
+
+mapping counts:
+    key as u8.public;
+    value as u64.public;
+
+function bump:
+    input r0 as u8.public;
+    input r1 as u64.public;
+    async bump r0 r1 into r2;
+    output r2 as counter.aleo/bump.future;
+
+finalize bump:
+    input r0 as u8.public;
+    input r1 as u64.public;
+    get.or_use counts[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into counts[r0];
+    remove counts[0u8];
+";
+
+    fn simulate(inputs: &[&str], state: &serde_json::Value) -> FinalizeTranscript<CurrentNetwork> {
+        let program = Program::<CurrentNetwork>::from_str(PROGRAM).unwrap();
+        let inputs = inputs.iter().map(|input| Value::from_str(input).unwrap()).collect::<Vec<_>>();
+        let state = mapping_entries_from_json(state).unwrap();
+        simulate_finalize(&program, "bump", &inputs, &state, 10).unwrap()
+    }
+
+    #[test]
+    fn test_simulate_finalize() {
+        let transcript = simulate(&["1u8", "5u64"], &json!({ "counts": { "0u8": "7u64", "1u8": "2u64" } }));
+        assert!(!transcript.is_halted());
+        assert_eq!(transcript.path, vec![0, 1, 2, 3]);
+        assert_eq!(transcript.accesses.len(), 3);
+
+        let j_transcript = transcript.to_json();
+        assert_eq!(j_transcript["type"], "FinalizeTranscript");
+        assert_eq!(j_transcript["accesses"][0]["opcode"], "get.or_use");
+        assert_eq!(j_transcript["accesses"][0]["mapping"], "counter.aleo/counts");
+        assert_eq!(j_transcript["accesses"][1]["before"], "2u64");
+        assert_eq!(j_transcript["accesses"][1]["after"], "7u64");
+        assert_eq!(j_transcript["accesses"][2]["opcode"], "remove");
+        assert_eq!(j_transcript["accesses"][2]["before"], "7u64");
+        assert_eq!(j_transcript["accesses"][2]["after"], serde_json::Value::Null);
+        assert_eq!(j_transcript["initial_state"]["counts"]["0u8"], "7u64");
+        assert_eq!(j_transcript["final_state"], json!({ "counts": { "1u8": "7u64" } }));
+    }
+
+    #[test]
+    fn test_simulate_finalize_halts() {
+        let transcript = simulate(&["1u8", "1u64"], &json!({ "counts": { "1u8": "18446744073709551615u64" } }));
+        assert!(transcript.is_halted());
+        assert_eq!(transcript.path, vec![0, 1]);
+        assert_eq!(transcript.accesses.len(), 1);
+        assert_eq!(transcript.final_state, transcript.initial_state);
+    }
+}
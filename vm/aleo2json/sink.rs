@@ -0,0 +1,193 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use serde_json::{value::RawValue, Value};
+use std::{
+    io::{BufWriter, Write},
+    net::{TcpStream, ToSocketAddrs},
+};
+
+/// A destination for converted JSON documents, written one at a time, in order.
+///
+/// The exports of block ranges, the batch conversions (`convert_files_to`), and the ledger exports
+/// (`LedgerExport::export_to`) write to a sink, so integrators can stream the documents into their own systems
+/// (i.e. a message queue, or an object store such as S3) by implementing it, or with a `CallbackSink`, rather than
+/// going through temporary files.
+pub trait OutputSink {
+    /// Writes the given document, returning its byte offset in the output, if the output is a byte stream.
+    fn write_document(&mut self, document: &Value) -> Result<Option<u64>>;
+
+    /// Writes the given already-serialized document, i.e. a `RawDocument`, as for `write_document`.
+    ///
+    /// By default, the document is parsed into a `Value`; sinks writing bytes write it verbatim instead.
+    fn write_raw(&mut self, document: &RawValue) -> Result<Option<u64>> {
+        self.write_document(&serde_json::from_str(document.get())?)
+    }
+
+    /// Flushes the documents written so far, i.e. after each batch.
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: OutputSink + ?Sized> OutputSink for Box<S> {
+    fn write_document(&mut self, document: &Value) -> Result<Option<u64>> {
+        (**self).write_document(document)
+    }
+
+    fn write_raw(&mut self, document: &RawValue) -> Result<Option<u64>> {
+        (**self).write_raw(document)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+}
+
+/// Collects the documents in memory.
+impl OutputSink for Vec<Value> {
+    fn write_document(&mut self, document: &Value) -> Result<Option<u64>> {
+        self.push(document.clone());
+        Ok(None)
+    }
+}
+
+/// Writes the documents as newline-delimited JSON to a writer: a file, stdout, or a socket.
+pub struct WriterSink<W: Write> {
+    /// The writer.
+    writer: W,
+    /// The number of bytes written so far.
+    offset: u64,
+}
+
+impl<W: Write> WriterSink<W> {
+    /// Initializes a sink writing to the given writer.
+    pub fn new(writer: W) -> Self {
+        Self::with_offset(writer, 0)
+    }
+
+    /// Initializes a sink appending to the given writer, which already holds the given number of bytes.
+    pub fn with_offset(writer: W, offset: u64) -> Self {
+        Self { writer, offset }
+    }
+
+    /// Returns the number of bytes written, including those the writer held initially.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes the given line, returning its byte offset.
+    fn write_line(&mut self, line: &str) -> Result<Option<u64>> {
+        let offset = self.offset;
+        writeln!(self.writer, "{line}")?;
+        self.offset += line.len() as u64 + 1;
+        Ok(Some(offset))
+    }
+}
+
+impl WriterSink<BufWriter<TcpStream>> {
+    /// Initializes a sink streaming to the TCP socket at the given address.
+    pub fn connect<A: ToSocketAddrs>(address: A) -> Result<Self> {
+        Ok(Self::new(BufWriter::new(TcpStream::connect(address)?)))
+    }
+}
+
+impl<W: Write> OutputSink for WriterSink<W> {
+    fn write_document(&mut self, document: &Value) -> Result<Option<u64>> {
+        self.write_line(&document.to_string())
+    }
+
+    fn write_raw(&mut self, document: &RawValue) -> Result<Option<u64>> {
+        // A line break can only be whitespace in JSON, so a document without one is already a single line.
+        match document.get().contains('\n') {
+            true => self.write_document(&serde_json::from_str(document.get())?),
+            false => self.write_line(document.get()),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+/// Passes each document to a user-provided callback.
+pub struct CallbackSink<F: FnMut(&Value) -> Result<()>> {
+    /// The callback receiving each document.
+    callback: F,
+}
+
+impl<F: FnMut(&Value) -> Result<()>> CallbackSink<F> {
+    /// Initializes a sink passing each document to the given callback.
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<F: FnMut(&Value) -> Result<()>> OutputSink for CallbackSink<F> {
+    fn write_document(&mut self, document: &Value) -> Result<Option<u64>> {
+        (self.callback)(document)?;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_writer_sink() {
+        let mut sink = WriterSink::new(Vec::new());
+        assert_eq!(sink.write_document(&json!({ "a": 1 })).unwrap(), Some(0));
+        assert_eq!(sink.write_document(&json!([true])).unwrap(), Some(8));
+        sink.flush().unwrap();
+        assert_eq!(String::from_utf8(sink.into_inner()).unwrap(), "{\"a\":1}\n[true]\n");
+    }
+
+    #[test]
+    fn test_writer_sink_raw() {
+        let mut sink = WriterSink::new(Vec::new());
+        let compact = RawValue::from_string("{\"a\": [1]}".to_string()).unwrap();
+        let pretty = RawValue::from_string("{\n  \"b\": 2\n}".to_string()).unwrap();
+        assert_eq!(sink.write_raw(&compact).unwrap(), Some(0));
+        // The pretty-printed document is compacted, so it stays on one line.
+        assert_eq!(sink.write_raw(&pretty).unwrap(), Some(11));
+        assert_eq!(String::from_utf8(sink.into_inner()).unwrap(), "{\"a\": [1]}\n{\"b\":2}\n");
+
+        let mut documents = Vec::<Value>::new();
+        documents.write_raw(&compact).unwrap();
+        assert_eq!(documents, vec![json!({ "a": [1] })]);
+    }
+
+    #[test]
+    fn test_callback_sink() {
+        let mut documents = Vec::new();
+        let mut sink = CallbackSink::new(|document: &Value| {
+            documents.push(document.clone());
+            Ok(())
+        });
+        assert_eq!(sink.write_document(&json!(1)).unwrap(), None);
+        let mut boxed: Box<dyn OutputSink> = Box::new(Vec::<Value>::new());
+        assert_eq!(boxed.write_document(&json!(2)).unwrap(), None);
+        drop(sink);
+        assert_eq!(documents, vec![json!(1)]);
+    }
+}
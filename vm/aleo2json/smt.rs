@@ -0,0 +1,578 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{
+        network::Network,
+        program::{Identifier, LiteralType, PlaintextType, Register, ValueType},
+        types::Field,
+    },
+    synthesizer::{
+        program::{InstructionTrait, Operand},
+        Instruction,
+        Program,
+    },
+};
+
+use anyhow::{bail, Error, Result};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// The modeling of integers in the SMT-LIB encoding of a function.
+///
+/// Field elements are always modeled as integers reduced modulo the field modulus.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SmtModel {
+    /// Integers are fixed-width bitvectors, e.g. `u8` as `(_ BitVec 8)`.
+    #[default]
+    BitVector,
+    /// Integers are unbounded integers constrained to the range of their type.
+    Integer,
+}
+
+impl FromStr for SmtModel {
+    type Err = Error;
+
+    /// Parses a model from its name.
+    fn from_str(model: &str) -> Result<Self> {
+        match model {
+            "bitvector" => Ok(Self::BitVector),
+            "integer" => Ok(Self::Integer),
+            _ => bail!("Unknown SMT model '{model}' (expected one of: bitvector, integer)"),
+        }
+    }
+}
+
+impl fmt::Display for SmtModel {
+    /// Prints the name of the model.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BitVector => write!(f, "bitvector"),
+            Self::Integer => write!(f, "integer"),
+        }
+    }
+}
+
+/// The options configuring the SMT-LIB encoding of a function.
+#[derive(Clone, Debug, Default)]
+pub struct SmtOptions {
+    /// The modeling of integers.
+    pub model: SmtModel,
+    /// Whether to end the encoding with a query for inputs on which the function halts.
+    pub check_halts: bool,
+}
+
+/// Encodes the given function of the program as SMT-LIB2 (experimental).
+///
+/// Each input register is declared as a constant, and each instruction as the definition of its destination register.
+/// Checked operations additionally define `r<i>_halts`, the condition under which they halt (e.g. on an overflow),
+/// and `halts` is the disjunction of these conditions. Assertions constrain the inputs, so the function never halts
+/// on an overflow if `(assert halts)` is unsatisfiable.
+///
+/// Only boolean, field, and integer values are encoded. Other instructions, and the instructions depending on their
+/// results, are left as comments.
+pub fn function_to_smtlib<N: Network>(program: &Program<N>, function: &str, options: &SmtOptions) -> Result<String> {
+    let function = program.get_function(&Identifier::<N>::from_str(function)?)?;
+
+    let mut encoder = Encoder { model: options.model, sorts: HashMap::new(), lines: Vec::new(), halts: Vec::new() };
+    encoder.lines.push(format!("; {}/{} ({} model)", program.id(), function.name(), options.model));
+    encoder.lines.push("(set-logic ALL)".to_string());
+    encoder.lines.push(format!("(define-fun field_modulus () Int (+ {} 1))", field_modulus_minus_one::<N>()));
+    if options.model == SmtModel::Integer {
+        // Aleo integer division truncates towards zero, whereas SMT-LIB `div` rounds towards negative infinity.
+        encoder.lines.push(
+            "(define-fun tdiv ((a Int) (b Int)) Int (ite (= (>= a 0) (>= b 0)) (div (abs a) (abs b)) (- (div (abs a) (abs b)))))"
+                .to_string(),
+        );
+        encoder.lines.push("(define-fun trem ((a Int) (b Int)) Int (- a (* b (tdiv a b))))".to_string());
+    }
+
+    for input in function.inputs() {
+        let literal_type = match input.value_type() {
+            ValueType::Constant(PlaintextType::Literal(literal_type))
+            | ValueType::Public(PlaintextType::Literal(literal_type))
+            | ValueType::Private(PlaintextType::Literal(literal_type)) => Some(*literal_type),
+            _ => None,
+        };
+        match (input.register(), literal_type.and_then(Sort::of)) {
+            (Register::Locator(locator), Some(sort)) => encoder.input(*locator, sort),
+            _ => encoder.lines.push(format!("; unsupported: {input}")),
+        }
+    }
+    for instruction in function.instructions() {
+        encoder.instruction(instruction);
+    }
+
+    let halts = match encoder.halts.len() {
+        0 => "false".to_string(),
+        1 => encoder.halts[0].clone(),
+        _ => format!("(or {})", encoder.halts.join(" ")),
+    };
+    encoder.lines.push(format!("(define-fun halts () Bool {halts})"));
+    if options.check_halts {
+        encoder.lines.push("(assert halts)".to_string());
+        encoder.lines.push("(check-sat)".to_string());
+    }
+
+    Ok(encoder.lines.join("\n") + "\n")
+}
+
+/// Returns the largest field element in decimal, i.e. one less than the field modulus.
+fn field_modulus_minus_one<N: Network>() -> String {
+    let largest = (Field::<N>::from_u8(0) - Field::<N>::from_u8(1)).to_string();
+    largest.trim_end_matches("field").to_string()
+}
+
+/// Returns `2^bits` in decimal.
+fn power_of_two(bits: u32) -> String {
+    match bits < 128 {
+        true => (1u128 << bits).to_string(),
+        false => "340282366920938463463374607431768211456".to_string(),
+    }
+}
+
+/// The sort of an encoded value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Sort {
+    Boolean,
+    Field,
+    Integer { signed: bool, bits: u32 },
+}
+
+impl Sort {
+    /// Returns the sort of the given literal type, if it is encoded.
+    fn of(literal_type: LiteralType) -> Option<Self> {
+        let integer = |signed, bits| Some(Self::Integer { signed, bits });
+        match literal_type {
+            LiteralType::Boolean => Some(Self::Boolean),
+            LiteralType::Field => Some(Self::Field),
+            LiteralType::I8 => integer(true, 8),
+            LiteralType::I16 => integer(true, 16),
+            LiteralType::I32 => integer(true, 32),
+            LiteralType::I64 => integer(true, 64),
+            LiteralType::I128 => integer(true, 128),
+            LiteralType::U8 => integer(false, 8),
+            LiteralType::U16 => integer(false, 16),
+            LiteralType::U32 => integer(false, 32),
+            LiteralType::U64 => integer(false, 64),
+            LiteralType::U128 => integer(false, 128),
+            _ => None,
+        }
+    }
+}
+
+/// The encoding of an instruction.
+enum Encoding {
+    /// The instruction constrains its operands.
+    Assert(String),
+    /// The instruction defines a value, halting under the given condition.
+    Value { term: String, sort: Sort, halts: Option<String> },
+}
+
+/// The state of the encoding of a function.
+struct Encoder {
+    model: SmtModel,
+    /// The sort of each register encoded so far, by locator.
+    sorts: HashMap<u64, Sort>,
+    lines: Vec<String>,
+    /// The names of the halting conditions defined so far.
+    halts: Vec<String>,
+}
+
+impl Encoder {
+    /// Returns the SMT-LIB sort of the given sort.
+    fn sort_name(&self, sort: Sort) -> String {
+        match (sort, self.model) {
+            (Sort::Boolean, _) => "Bool".to_string(),
+            (Sort::Integer { bits, .. }, SmtModel::BitVector) => format!("(_ BitVec {bits})"),
+            (Sort::Field, _) | (Sort::Integer { .. }, SmtModel::Integer) => "Int".to_string(),
+        }
+    }
+
+    /// Returns the SMT-LIB constant for the given value, written in decimal.
+    fn constant(&self, value: &str, sort: Sort) -> Option<String> {
+        match (sort, self.model) {
+            (Sort::Boolean, _) => Some(value.to_string()),
+            (Sort::Integer { bits, .. }, SmtModel::BitVector) => {
+                // Negative values are written in two's complement.
+                let value = match value.strip_prefix('-') {
+                    Some(_) => value.parse::<i128>().ok()? as u128,
+                    None => value.parse::<u128>().ok()?,
+                };
+                let mask = match bits < 128 {
+                    true => (1u128 << bits) - 1,
+                    false => u128::MAX,
+                };
+                Some(format!("(_ bv{} {bits})", value & mask))
+            }
+            (Sort::Field, _) | (Sort::Integer { .. }, SmtModel::Integer) => match value.strip_prefix('-') {
+                Some(magnitude) => Some(format!("(- {magnitude})")),
+                None => Some(value.to_string()),
+            },
+        }
+    }
+
+    /// Returns the bounds of the given integer type, as SMT-LIB constants.
+    fn bounds(&self, signed: bool, bits: u32) -> (String, String) {
+        let sort = Sort::Integer { signed, bits };
+        let (min, max) = match signed {
+            true => {
+                let min = i128::MIN >> (128 - bits);
+                (min.to_string(), (!min).to_string())
+            }
+            false => ("0".to_string(), (u128::MAX >> (128 - bits)).to_string()),
+        };
+        (self.constant(&min, sort).unwrap_or_default(), self.constant(&max, sort).unwrap_or_default())
+    }
+
+    /// Declares the given input register.
+    fn input(&mut self, locator: u64, sort: Sort) {
+        self.lines.push(format!("(declare-const r{locator} {})", self.sort_name(sort)));
+        let range = match (sort, self.model) {
+            (Sort::Field, _) => Some(format!("(and (<= 0 r{locator}) (< r{locator} field_modulus))")),
+            (Sort::Integer { signed, bits }, SmtModel::Integer) => {
+                let (min, max) = self.bounds(signed, bits);
+                Some(format!("(and (<= {min} r{locator}) (<= r{locator} {max}))"))
+            }
+            _ => None,
+        };
+        if let Some(range) = range {
+            self.lines.push(format!("(assert {range})"));
+        }
+        self.sorts.insert(locator, sort);
+    }
+
+    /// Returns the term and sort of the given operand, if it is encoded.
+    fn operand<N: Network>(&self, operand: &Operand<N>) -> Option<(String, Sort)> {
+        match operand {
+            Operand::Literal(literal) => {
+                let literal_type = literal.to_type();
+                let sort = Sort::of(literal_type)?;
+                let literal = literal.to_string();
+                let value = literal.strip_suffix(literal_type.type_name()).unwrap_or(&literal);
+                Some((self.constant(value, sort)?, sort))
+            }
+            Operand::Register(Register::Locator(locator)) => {
+                self.sorts.get(locator).map(|sort| (format!("r{locator}"), *sort))
+            }
+            _ => None,
+        }
+    }
+
+    /// Encodes the given instruction, or leaves it as a comment if it is not supported.
+    fn instruction<N: Network>(&mut self, instruction: &Instruction<N>) {
+        let operands = instruction.operands().iter().map(|operand| self.operand(operand)).collect::<Option<Vec<_>>>();
+        let encoding = operands.and_then(|operands| self.encode(*instruction.opcode(), &operands));
+
+        match (encoding, instruction.destinations().as_slice()) {
+            (Some(Encoding::Assert(condition)), []) => self.lines.push(format!("(assert {condition})")),
+            (Some(Encoding::Value { term, sort, halts }), [Register::Locator(locator)]) => {
+                self.lines.push(format!("(define-fun r{locator} () {} {term})", self.sort_name(sort)));
+                if let Some(halts) = halts {
+                    self.lines.push(format!("(define-fun r{locator}_halts () Bool {halts})"));
+                    self.halts.push(format!("r{locator}_halts"));
+                }
+                self.sorts.insert(*locator, sort);
+            }
+            _ => self.lines.push(format!("; unsupported: {instruction}")),
+        }
+    }
+
+    /// Encodes the given opcode applied to the given operands.
+    fn encode(&self, opcode: &str, operands: &[(String, Sort)]) -> Option<Encoding> {
+        let value = |term: String, sort: Sort| Some(Encoding::Value { term, sort, halts: None });
+        match (opcode, operands) {
+            ("is.eq", [(a, _), (b, _)]) => value(format!("(= {a} {b})"), Sort::Boolean),
+            ("is.neq", [(a, _), (b, _)]) => value(format!("(distinct {a} {b})"), Sort::Boolean),
+            ("assert.eq", [(a, _), (b, _)]) => Some(Encoding::Assert(format!("(= {a} {b})"))),
+            ("assert.neq", [(a, _), (b, _)]) => Some(Encoding::Assert(format!("(distinct {a} {b})"))),
+            ("ternary", [(c, Sort::Boolean), (a, sort), (b, _)]) => value(format!("(ite {c} {a} {b})"), *sort),
+            (_, [(_, Sort::Boolean), ..]) => self.encode_boolean(opcode, operands),
+            (_, [(_, Sort::Field), ..]) => self.encode_field(opcode, operands),
+            (_, [(_, Sort::Integer { signed, bits }), ..]) => match self.model {
+                SmtModel::BitVector => self.encode_bitvector(opcode, operands, *signed, *bits),
+                SmtModel::Integer => self.encode_integer(opcode, operands, *signed, *bits),
+            },
+            _ => None,
+        }
+    }
+
+    /// Encodes a boolean operation.
+    fn encode_boolean(&self, opcode: &str, operands: &[(String, Sort)]) -> Option<Encoding> {
+        let term = match (opcode, operands) {
+            ("and", [(a, _), (b, _)]) => format!("(and {a} {b})"),
+            ("or", [(a, _), (b, _)]) => format!("(or {a} {b})"),
+            ("xor", [(a, _), (b, _)]) => format!("(xor {a} {b})"),
+            ("nand", [(a, _), (b, _)]) => format!("(not (and {a} {b}))"),
+            ("nor", [(a, _), (b, _)]) => format!("(not (or {a} {b}))"),
+            ("not", [(a, _)]) => format!("(not {a})"),
+            _ => return None,
+        };
+        Some(Encoding::Value { term, sort: Sort::Boolean, halts: None })
+    }
+
+    /// Encodes a field operation, modulo the field modulus.
+    fn encode_field(&self, opcode: &str, operands: &[(String, Sort)]) -> Option<Encoding> {
+        let (term, sort) = match (opcode, operands) {
+            ("add", [(a, _), (b, _)]) => (format!("(mod (+ {a} {b}) field_modulus)"), Sort::Field),
+            ("sub", [(a, _), (b, _)]) => (format!("(mod (- {a} {b}) field_modulus)"), Sort::Field),
+            ("mul", [(a, _), (b, _)]) => (format!("(mod (* {a} {b}) field_modulus)"), Sort::Field),
+            ("neg", [(a, _)]) => (format!("(mod (- {a}) field_modulus)"), Sort::Field),
+            ("double", [(a, _)]) => (format!("(mod (* 2 {a}) field_modulus)"), Sort::Field),
+            ("square", [(a, _)]) => (format!("(mod (* {a} {a}) field_modulus)"), Sort::Field),
+            ("gt", [(a, _), (b, _)]) => (format!("(> {a} {b})"), Sort::Boolean),
+            ("gte", [(a, _), (b, _)]) => (format!("(>= {a} {b})"), Sort::Boolean),
+            ("lt", [(a, _), (b, _)]) => (format!("(< {a} {b})"), Sort::Boolean),
+            ("lte", [(a, _), (b, _)]) => (format!("(<= {a} {b})"), Sort::Boolean),
+            _ => return None,
+        };
+        Some(Encoding::Value { term, sort, halts: None })
+    }
+
+    /// Encodes an integer operation on bitvectors.
+    fn encode_bitvector(&self, opcode: &str, operands: &[(String, Sort)], signed: bool, bits: u32) -> Option<Encoding> {
+        let sort = Sort::Integer { signed, bits };
+        let checked = !opcode.ends_with(".w");
+        let zero = format!("(_ bv0 {bits})");
+        let (min, _) = self.bounds(signed, bits);
+        // Extends a value to twice its width, so the result of an operation on two values cannot overflow.
+        let extend = |term: &str| match signed {
+            true => format!("((_ sign_extend {bits}) {term})"),
+            false => format!("((_ zero_extend {bits}) {term})"),
+        };
+        let pick = |signed_op: &'static str, unsigned_op: &'static str| match signed {
+            true => signed_op,
+            false => unsigned_op,
+        };
+
+        let (term, sort, halts) = match (opcode.trim_end_matches(".w"), operands) {
+            (op @ ("add" | "sub" | "mul"), [(a, _), (b, _)]) => {
+                let op = format!("bv{op}");
+                let term = format!("({op} {a} {b})");
+                let halts = format!("(distinct {} ({op} {} {}))", extend(&term), extend(a), extend(b));
+                (term, sort, checked.then_some(halts))
+            }
+            (op @ ("div" | "rem"), [(a, _), (b, _)]) => {
+                let op = match op {
+                    "div" => pick("bvsdiv", "bvudiv"),
+                    _ => pick("bvsrem", "bvurem"),
+                };
+                let by_zero = format!("(= {b} {zero})");
+                let halts = match checked && signed {
+                    true => format!("(or {by_zero} (and (= {a} {min}) (= {b} (bvnot {zero}))))"),
+                    false => by_zero,
+                };
+                (format!("({op} {a} {b})"), sort, Some(halts))
+            }
+            ("abs", [(a, _)]) => match signed {
+                true => (
+                    format!("(ite (bvslt {a} {zero}) (bvneg {a}) {a})"),
+                    sort,
+                    checked.then(|| format!("(= {a} {min})")),
+                ),
+                false => (a.clone(), sort, None),
+            },
+            ("neg", [(a, _)]) if signed && checked => (format!("(bvneg {a})"), sort, Some(format!("(= {a} {min})"))),
+            (op @ ("and" | "or" | "xor" | "nand" | "nor"), [(a, _), (b, _)]) if checked => {
+                (format!("(bv{op} {a} {b})"), sort, None)
+            }
+            ("not", [(a, _)]) if checked => (format!("(bvnot {a})"), sort, None),
+            ("gt", [(a, _), (b, _)]) if checked => {
+                (format!("({} {a} {b})", pick("bvsgt", "bvugt")), Sort::Boolean, None)
+            }
+            ("gte", [(a, _), (b, _)]) if checked => {
+                (format!("({} {a} {b})", pick("bvsge", "bvuge")), Sort::Boolean, None)
+            }
+            ("lt", [(a, _), (b, _)]) if checked => {
+                (format!("({} {a} {b})", pick("bvslt", "bvult")), Sort::Boolean, None)
+            }
+            ("lte", [(a, _), (b, _)]) if checked => {
+                (format!("({} {a} {b})", pick("bvsle", "bvule")), Sort::Boolean, None)
+            }
+            (op @ ("shl" | "shr"), [(a, _), (b, Sort::Integer { signed: false, bits: amount_bits })]) => {
+                // Wrapped shifts reduce the shift amount modulo the bit width, whereas checked shifts halt.
+                let amount = match checked {
+                    true => b.clone(),
+                    false => format!("(bvand {b} (_ bv{} {amount_bits}))", bits - 1),
+                };
+                let amount = match amount_bits.cmp(&bits) {
+                    std::cmp::Ordering::Less => format!("((_ zero_extend {}) {amount})", bits - amount_bits),
+                    std::cmp::Ordering::Equal => amount,
+                    std::cmp::Ordering::Greater => format!("((_ extract {} 0) {amount})", bits - 1),
+                };
+                let op = match op {
+                    "shl" => "bvshl",
+                    _ => pick("bvashr", "bvlshr"),
+                };
+                let halts = format!("(bvuge {b} (_ bv{bits} {amount_bits}))");
+                (format!("({op} {a} {amount})"), sort, checked.then_some(halts))
+            }
+            _ => return None,
+        };
+        Some(Encoding::Value { term, sort, halts })
+    }
+
+    /// Encodes an integer operation on unbounded integers.
+    fn encode_integer(&self, opcode: &str, operands: &[(String, Sort)], signed: bool, bits: u32) -> Option<Encoding> {
+        let sort = Sort::Integer { signed, bits };
+        let checked = !opcode.ends_with(".w");
+        let (min, max) = self.bounds(signed, bits);
+        let out_of_range = |term: &str| format!("(not (and (<= {min} {term}) (<= {term} {max})))");
+        // Reduces a value into the range of the type, as a wrapped operation does.
+        let wrap = |term: String| match signed {
+            true => {
+                let half = power_of_two(bits - 1);
+                format!("(- (mod (+ {term} {half}) {}) {half})", power_of_two(bits))
+            }
+            false => format!("(mod {term} {})", power_of_two(bits)),
+        };
+        // Returns the encoding of a value that halts if it is out of range, or wraps if the operation is wrapped.
+        let bounded = |term: String, halts: Option<String>| {
+            let halts = match (checked, halts) {
+                (true, Some(halts)) => Some(format!("(or {halts} {})", out_of_range(&term))),
+                (true, None) => Some(out_of_range(&term)),
+                (false, halts) => halts,
+            };
+            let term = match checked {
+                true => term,
+                false => wrap(term),
+            };
+            (term, sort, halts)
+        };
+
+        let (term, sort, halts) = match (opcode.trim_end_matches(".w"), operands) {
+            ("add", [(a, _), (b, _)]) => bounded(format!("(+ {a} {b})"), None),
+            ("sub", [(a, _), (b, _)]) => bounded(format!("(- {a} {b})"), None),
+            ("mul", [(a, _), (b, _)]) => bounded(format!("(* {a} {b})"), None),
+            ("div", [(a, _), (b, _)]) => {
+                let op = match signed {
+                    true => "tdiv",
+                    false => "div",
+                };
+                bounded(format!("({op} {a} {b})"), Some(format!("(= {b} 0)")))
+            }
+            ("rem", [(a, _), (b, _)]) => {
+                let by_zero = format!("(= {b} 0)");
+                let halts = match checked && signed {
+                    true => format!("(or {by_zero} (and (= {a} {min}) (= {b} (- 1))))"),
+                    false => by_zero,
+                };
+                let op = match signed {
+                    true => "trem",
+                    false => "mod",
+                };
+                (format!("({op} {a} {b})"), sort, Some(halts))
+            }
+            ("abs", [(a, _)]) => match signed {
+                true => bounded(format!("(abs {a})"), None),
+                false => (a.clone(), sort, None),
+            },
+            ("neg", [(a, _)]) if signed && checked => bounded(format!("(- {a})"), None),
+            ("gt", [(a, _), (b, _)]) if checked => (format!("(> {a} {b})"), Sort::Boolean, None),
+            ("gte", [(a, _), (b, _)]) if checked => (format!("(>= {a} {b})"), Sort::Boolean, None),
+            ("lt", [(a, _), (b, _)]) if checked => (format!("(< {a} {b})"), Sort::Boolean, None),
+            ("lte", [(a, _), (b, _)]) if checked => (format!("(<= {a} {b})"), Sort::Boolean, None),
+            _ => return None,
+        };
+        Some(Encoding::Value { term, sort, halts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"program smt.aleo;
+
+function compute:
+    input r0 as u8.public;
+    input r1 as u8.private;
+    input r2 as field.private;
+    add r0 r1 into r3;
+    add.w r0 r1 into r4;
+    div r0 r1 into r5;
+    gt r3 10u8 into r6;
+    ternary r6 r3 r4 into r7;
+    assert.neq r1 0u8;
+    hash.bhp256 r2 into r8 as field;
+    add r8 r2 into r9;
+    output r7 as u8.private;
+";
+
+    #[test]
+    fn test_smt_model() {
+        for model in [SmtModel::BitVector, SmtModel::Integer] {
+            assert_eq!(model.to_string().parse::<SmtModel>().unwrap(), model);
+        }
+        assert!("real".parse::<SmtModel>().is_err());
+    }
+
+    #[test]
+    fn test_function_to_smtlib_bitvector() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let options = SmtOptions { model: SmtModel::BitVector, check_halts: true };
+        let smt = function_to_smtlib(&program, "compute", &options).unwrap();
+
+        assert!(smt.contains("(declare-const r0 (_ BitVec 8))"));
+        assert!(smt.contains("(assert (and (<= 0 r2) (< r2 field_modulus)))"));
+        assert!(smt.contains("(define-fun r3 () (_ BitVec 8) (bvadd r0 r1))"));
+        assert!(smt.contains(
+            "(define-fun r3_halts () Bool (distinct ((_ zero_extend 8) (bvadd r0 r1)) (bvadd ((_ zero_extend 8) r0) ((_ zero_extend 8) r1))))"
+        ));
+        // Wrapped operations never halt on an overflow.
+        assert!(smt.contains("(define-fun r4 () (_ BitVec 8) (bvadd r0 r1))"));
+        assert!(!smt.contains("r4_halts"));
+        assert!(smt.contains("(define-fun r5_halts () Bool (= r1 (_ bv0 8)))"));
+        assert!(smt.contains("(define-fun r6 () Bool (bvugt r3 (_ bv10 8)))"));
+        assert!(smt.contains("(define-fun r7 () (_ BitVec 8) (ite r6 r3 r4))"));
+        assert!(smt.contains("(assert (distinct r1 (_ bv0 8)))"));
+        // The hash, and the instruction depending on it, are left as comments.
+        assert!(smt.contains("; unsupported: hash.bhp256 r2 into r8 as field;"));
+        assert!(smt.contains("; unsupported: add r8 r2 into r9;"));
+        assert!(smt.ends_with("(define-fun halts () Bool (or r3_halts r5_halts))\n(assert halts)\n(check-sat)\n"));
+    }
+
+    #[test]
+    fn test_function_to_smtlib_integer() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        let options = SmtOptions { model: SmtModel::Integer, check_halts: false };
+        let smt = function_to_smtlib(&program, "compute", &options).unwrap();
+
+        assert!(smt.contains("(declare-const r0 Int)\n(assert (and (<= 0 r0) (<= r0 255)))"));
+        assert!(smt.contains("(define-fun r3 () Int (+ r0 r1))"));
+        assert!(smt.contains("(define-fun r3_halts () Bool (not (and (<= 0 (+ r0 r1)) (<= (+ r0 r1) 255))))"));
+        assert!(smt.contains("(define-fun r4 () Int (mod (+ r0 r1) 256))"));
+        assert!(smt.contains("(define-fun r6 () Bool (> r3 10))"));
+        assert!(!smt.contains("(check-sat)"));
+    }
+
+    #[test]
+    fn test_signed_bounds() {
+        let encoder = Encoder { model: SmtModel::Integer, sorts: HashMap::new(), lines: Vec::new(), halts: Vec::new() };
+        assert_eq!(encoder.bounds(true, 8), ("(- 128)".to_string(), "127".to_string()));
+        assert_eq!(encoder.bounds(false, 128), ("0".to_string(), u128::MAX.to_string()));
+
+        let encoder = Encoder { model: SmtModel::BitVector, ..encoder };
+        assert_eq!(encoder.bounds(true, 8), ("(_ bv128 8)".to_string(), "(_ bv127 8)".to_string()));
+        assert_eq!(encoder.constant("-1", Sort::Integer { signed: true, bits: 16 }).unwrap(), "(_ bv65535 16)");
+    }
+
+    #[test]
+    fn test_unknown_function() {
+        let program = parse_program::<CurrentNetwork>(PROGRAM).unwrap();
+        assert!(function_to_smtlib(&program, "missing", &SmtOptions::default()).is_err());
+    }
+}
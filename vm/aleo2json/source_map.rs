@@ -0,0 +1,251 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{locate_comments, locate_spans, ConversionError},
+    console::network::Network,
+};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// A segment of the source text: the text of a construct, or the text between two constructs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceSegment {
+    /// The JSON pointer of the construct or comment (i.e. `/functions/mint/comments/0`), or `None` for the text
+    /// between them (i.e. whitespace, and the headers of the program, closures, functions, and finalize blocks).
+    pub pointer: Option<String>,
+    /// The text of the segment.
+    pub text: String,
+}
+
+/// The lossless map of a program source: the segments of its text, whose concatenation is the source.
+///
+/// The constructs are the innermost ones located by `locate_spans`: the imports, mappings, structs, and records,
+/// and the inputs, instructions, outputs, and commands. Every other character, including the trivia, belongs to
+/// the segments between them, so the source can be reconstructed exactly. Each comment between the constructs is
+/// a segment of its own, pointing to its entry in the `comments` of the node it is attached to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The segments, in order of the source.
+    pub segments: Vec<SourceSegment>,
+}
+
+impl SourceMap {
+    /// Initializes the source map of the given source, which is expected to hold a valid program.
+    pub fn new<N: Network>(source: &str) -> Result<Self, ConversionError> {
+        let spans = locate_spans::<N>(source)?;
+        let is_leaf = |pointer: &str| {
+            let prefix = format!("{pointer}/");
+            !pointer.is_empty() && !spans.iter().any(|located| located.pointer.starts_with(&prefix))
+        };
+        let mut leaves = spans.iter().filter(|located| is_leaf(&located.pointer)).collect::<Vec<_>>();
+        leaves.sort_by_key(|located| located.span.start.offset);
+
+        // The range and the JSON pointer of each comment, in the order they are attached to their nodes.
+        let located_comments = locate_comments::<N>(source)?;
+        let mut counts = HashMap::<&str, usize>::new();
+        let comments = located_comments
+            .iter()
+            .map(|comment| {
+                let count = counts.entry(comment.pointer.as_str()).or_default();
+                let pointer = format!("{}/comments/{count}", comment.pointer);
+                *count += 1;
+                let start = comment.span.start.offset;
+                (start, start + comment.text.len(), pointer)
+            })
+            .collect::<Vec<_>>();
+
+        let mut map = Self::default();
+        let mut offset = 0;
+        for located in leaves {
+            map.push_between(source, offset, located.span.start.offset, &comments);
+            map.push(Some(located.pointer.clone()), located.span.text(source));
+            offset = located.span.end.offset;
+        }
+        map.push_between(source, offset, source.len(), &comments);
+        Ok(map)
+    }
+
+    /// Returns the source text, as the concatenation of the segments.
+    pub fn render(&self) -> String {
+        self.segments.iter().map(|segment| segment.text.as_str()).collect()
+    }
+
+    /// Returns the source text with the edits of the given program JSON applied: the text of each construct is
+    /// replaced by the `source` of its node, if it has one, and the text of each comment by its entry in the
+    /// `comments` of its node, if the document includes the comments. The text of the constructs missing from the
+    /// document (i.e. pruned by a function or state selection) and the text between the constructs are kept, so the
+    /// result differs from the source only where it was edited.
+    ///
+    /// Note: The constructs are matched by JSON pointer, so removing an element of an array (i.e. an instruction)
+    /// shifts the text of the following elements. Inserted constructs have no segment, and are not applied.
+    pub fn apply(&self, document: &Value) -> String {
+        let mut source = String::new();
+        for segment in &self.segments {
+            let edited =
+                segment.pointer.as_ref().and_then(|pointer| document.pointer(pointer)).and_then(|node| match node {
+                    Value::String(comment) => Some(comment.as_str()),
+                    node => node.get("source").and_then(Value::as_str),
+                });
+            source.push_str(edited.unwrap_or(&segment.text));
+        }
+        source
+    }
+
+    /// Appends the segments of the text between the given offsets of the source, which holds no construct, splitting
+    /// out the given comments, as `(start, end, pointer)`, that lie within it.
+    fn push_between(&mut self, source: &str, mut start: usize, end: usize, comments: &[(usize, usize, String)]) {
+        for (comment_start, comment_end, pointer) in comments {
+            if *comment_start >= start && *comment_end <= end {
+                self.push(None, &source[start..*comment_start]);
+                self.push(Some(pointer.clone()), &source[*comment_start..*comment_end]);
+                start = *comment_end;
+            }
+        }
+        self.push(None, &source[start..end]);
+    }
+
+    /// Appends a segment with the given text, unless it is empty.
+    fn push(&mut self, pointer: Option<String>, text: &str) {
+        if !text.is_empty() {
+            self.segments.push(SourceSegment { pointer, text: text.to_string() });
+        }
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let j_segments = self
+            .segments
+            .iter()
+            .map(|segment| json!({ "pointer": segment.pointer, "text": segment.text }))
+            .collect::<Vec<_>>();
+        json!({
+            "type": "SourceMap",
+            "segments": j_segments,
+        })
+    }
+
+    /// Parses a source map from its JSON representation, as emitted by `to_json`.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let j_segments = value["segments"].as_array().ok_or_else(|| anyhow!("The source map has no 'segments'"))?;
+        let mut segments = Vec::new();
+        for j_segment in j_segments {
+            let text = j_segment["text"].as_str().ok_or_else(|| anyhow!("A source segment has no 'text'"))?;
+            let pointer = j_segment["pointer"].as_str().map(ToString::to_string);
+            segments.push(SourceSegment { pointer, text: text.to_string() });
+        }
+        Ok(Self { segments })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{
+            attach_source_locations,
+            convert_program,
+            convert_state_schema_with_options,
+            ConversionMode,
+            JsonOptions,
+        },
+        prelude::Testnet3,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    const SOURCE: &str = r"// The token program.
+program token.aleo;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+function mint:
+    input r0 as address.public;   // The recipient.
+    input r1 as u64.public;
+    async mint r0 r1 into r2;
+    output r2 as token.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into account[r0];
+";
+
+    #[test]
+    fn test_source_map() {
+        let map = SourceMap::new::<CurrentNetwork>(SOURCE).unwrap();
+        assert_eq!(map.render(), SOURCE);
+        assert_eq!(SourceMap::from_json(&map.to_json()).unwrap(), map);
+
+        let segment = |pointer: &str| map.segments.iter().find(|segment| segment.pointer.as_deref() == Some(pointer));
+        assert_eq!(
+            segment("/mappings/account").unwrap().text,
+            "mapping account:\n    key as address.public;\n    value as u64.public;"
+        );
+        assert_eq!(segment("/functions/mint/finalize_logic/commands/1").unwrap().text, "add r2 r1 into r3;");
+        // The enclosing constructs are split into their inner constructs.
+        assert!(segment("/functions/mint").is_none());
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let mut document = convert_program::<CurrentNetwork>(SOURCE).unwrap();
+        let options = JsonOptions { lossless: true, ..Default::default() };
+        attach_source_locations::<CurrentNetwork>(SOURCE, &mut document, &options).unwrap();
+        let map = SourceMap::from_json(&document["source_map"]).unwrap();
+
+        // Without edits, the source is reconstructed exactly, including the comments and the alignment.
+        assert_eq!(map.apply(&document), SOURCE);
+
+        // An edit of a construct only changes its text.
+        document["functions"]["mint"]["finalize_logic"]["commands"][1]["source"] = json!("add.w r2 r1 into r3;");
+        assert_eq!(map.apply(&document), SOURCE.replace("add r2 r1", "add.w r2 r1"));
+    }
+
+    #[test]
+    fn test_apply_comments() {
+        let mut document = convert_program::<CurrentNetwork>(SOURCE).unwrap();
+        let options = JsonOptions { include_comments: true, lossless: true, ..Default::default() };
+        attach_source_locations::<CurrentNetwork>(SOURCE, &mut document, &options).unwrap();
+        let map = SourceMap::from_json(&document["source_map"]).unwrap();
+        assert_eq!(map.apply(&document), SOURCE);
+
+        // The comments are rendered back from the document, so an edit of a comment changes its text.
+        assert_eq!(document["comments"][0], "// The token program.");
+        document["functions"]["mint"]["inputs"][1]["comments"][0] = json!("// The recipient, as audited.");
+        assert_eq!(map.apply(&document), SOURCE.replace("// The recipient.", "// The recipient, as audited."));
+    }
+
+    #[test]
+    fn test_apply_edits_to_selection() {
+        let options = JsonOptions { lossless: true, ..Default::default() };
+        let mut document =
+            convert_state_schema_with_options::<CurrentNetwork>(SOURCE, ConversionMode::Strict, &options).unwrap();
+        assert!(document["functions"].get("mint").is_none());
+        let map = SourceMap::from_json(&document["source_map"]).unwrap();
+
+        // The constructs outside of the selection keep their text.
+        assert_eq!(map.apply(&document), SOURCE);
+
+        let edited = "mapping account:\n    key as address.public;\n    value as u128.public;";
+        document["mappings"]["account"]["source"] = json!(edited);
+        assert_eq!(map.apply(&document), SOURCE.replace("value as u64.public;", "value as u128.public;"));
+    }
+}
@@ -0,0 +1,453 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{ConversionError, JsonOptions, SourceMap},
+    console::{
+        network::prelude::{Network, Parser, ParserResult, Sanitizer},
+        program::{RecordType, StructType},
+    },
+    synthesizer::program::{Closure, Command, Function, Import, Instruction, Mapping},
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// A position in the source text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SourcePosition {
+    /// The byte offset from the start of the source.
+    pub offset: usize,
+    /// The line number, starting at 1.
+    pub line: usize,
+    /// The column number in characters, starting at 1.
+    pub column: usize,
+}
+
+impl SourcePosition {
+    /// Returns the JSON representation of the position.
+    pub fn to_json(&self) -> Value {
+        json!({ "offset": self.offset, "line": self.line, "column": self.column })
+    }
+}
+
+/// A range of the source text, from the first character of a construct to the end of its last token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// The position of the first character.
+    pub start: SourcePosition,
+    /// The position just past the last character.
+    pub end: SourcePosition,
+}
+
+impl SourceSpan {
+    /// Returns the text of the span in the given source.
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start.offset..self.end.offset]
+    }
+
+    /// Returns the JSON representation of the span.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "SourceSpan",
+            "start": self.start.to_json(),
+            "end": self.end.to_json(),
+        })
+    }
+}
+
+/// The span of a construct, along with the JSON pointer of its node in the program JSON.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocatedSpan {
+    /// The JSON pointer of the node, i.e. `/functions/mint/instructions/0`.
+    pub pointer: String,
+    /// The span of the construct in the source text.
+    pub span: SourceSpan,
+}
+
+/// A comment of the source text, along with the JSON pointer of the node it annotates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocatedComment {
+    /// The JSON pointer of the node, i.e. `/functions/mint`.
+    pub pointer: String,
+    /// The text of the comment, with its delimiters, i.e. `// Mints new tokens.`.
+    pub text: String,
+    /// The span of the comment in the source text.
+    pub span: SourceSpan,
+}
+
+/// Returns the spans of the program, and of its imports, mappings, structs, records, closures, functions,
+/// and finalize blocks, along with the spans of their inputs, instructions, outputs, and commands.
+///
+/// The source is expected to hold a valid program, i.e. one accepted by `parse_program`.
+pub fn locate_spans<N: Network>(source: &str) -> Result<Vec<LocatedSpan>, ConversionError> {
+    Ok(locate::<N>(source)?.0)
+}
+
+/// Returns the comments of the source, in order, each attached to the nearest construct following it.
+///
+/// The comments before the program declaration (unless an import follows them), and those after the last construct,
+/// are attached to the program itself.
+pub fn locate_comments<N: Network>(source: &str) -> Result<Vec<LocatedComment>, ConversionError> {
+    let (spans, declaration) = locate::<N>(source)?;
+    let locator = Locator::new(source);
+
+    let mut comments = Vec::new();
+    let mut rest = source;
+    while let Some(index) = rest.find('/') {
+        let start = source.len() - rest.len() + index;
+        let length = match &rest[index..] {
+            comment if comment.starts_with("//") => comment.find('\n').unwrap_or(comment.len()),
+            comment if comment.starts_with("/*") => comment.find("*/").map_or(comment.len(), |end| end + 2),
+            _ => 1,
+        };
+        rest = &rest[index + length..];
+        if length == 1 {
+            continue;
+        }
+
+        let end = start + length;
+        let following = spans
+            .iter()
+            .filter(|located| !located.pointer.is_empty() && located.span.start.offset >= end)
+            .min_by_key(|located| located.span.start.offset);
+        let pointer = match following {
+            Some(located) if end > declaration || located.span.start.offset < declaration => located.pointer.clone(),
+            _ => String::new(),
+        };
+        let span = SourceSpan { start: locator.position(start), end: locator.position(end) };
+        comments.push(LocatedComment { pointer, text: source[start..end].trim_end().to_string(), span });
+    }
+    Ok(comments)
+}
+
+/// Returns the spans of the constructs of the source, and the byte offset of its program declaration.
+fn locate<N: Network>(source: &str) -> Result<(Vec<LocatedSpan>, usize), ConversionError> {
+    let mut locator = Locator::new(source);
+    let first = sanitize(source)?;
+
+    // Locate the imports.
+    let mut string = source;
+    while let Ok((rest, import)) = Import::<N>::parse(string) {
+        locator.push(format!("/imports/{}", import.program_id().to_key()), sanitize(string)?, rest);
+        string = rest;
+    }
+
+    // Skip the program declaration, which is closed by the first semicolon.
+    let declaration = sanitize(string)?;
+    string = skip_past(declaration, ';')?;
+
+    // Locate the components, in the order of the program parser.
+    loop {
+        let start = sanitize(string)?;
+        let rest = if let Ok((rest, mapping)) = Mapping::<N>::parse(string) {
+            locator.push(format!("/mappings/{}", mapping.name().to_key()), start, rest);
+            rest
+        } else if let Ok((rest, struct_)) = StructType::<N>::parse(string) {
+            locator.push(format!("/structs/{}", struct_.name().to_key()), start, rest);
+            rest
+        } else if let Ok((rest, record)) = RecordType::<N>::parse(string) {
+            locator.push(format!("/records/{}", record.name().to_key()), start, rest);
+            rest
+        } else if let Ok((rest, closure)) = Closure::<N>::parse(string) {
+            let pointer = format!("/closures/{}", closure.name().to_key());
+            locator.push(pointer.clone(), start, rest);
+            locator.push_body::<N>(&pointer, start, rest)?;
+            rest
+        } else if let Ok((rest, function)) = Function::<N>::parse(string) {
+            let pointer = format!("/functions/{}", function.name().to_key());
+            locator.push(pointer.clone(), start, rest);
+            locator.push_body::<N>(&pointer, start, rest)?;
+            rest
+        } else {
+            break;
+        };
+        string = rest;
+    }
+
+    // Locate the program, from its first import or declaration to the end of its last component.
+    locator.push(String::new(), first, string);
+
+    Ok((locator.spans, source.len() - declaration.len()))
+}
+
+/// Adds the source locations selected by the given options to the nodes of the given program JSON:
+/// the span of each construct under `span`, its original text under `source`, the text of the comments
+/// preceding it under `comments`, and the lossless source map of the program under `source_map`.
+///
+/// Note: The original text is not added to the program itself, as it is the entire source.
+pub fn attach_source_locations<N: Network>(
+    source: &str,
+    document: &mut Value,
+    options: &JsonOptions,
+) -> Result<(), ConversionError> {
+    if !options.include_spans && !options.include_source && !options.include_comments && !options.lossless {
+        return Ok(());
+    }
+    for located in locate_spans::<N>(source)? {
+        if let Some(Value::Object(node)) = document.pointer_mut(&located.pointer) {
+            if options.include_spans {
+                node.insert("span".to_string(), located.span.to_json());
+            }
+            if (options.include_source || options.lossless) && !located.pointer.is_empty() {
+                node.insert("source".to_string(), Value::String(located.span.text(source).to_string()));
+            }
+        }
+    }
+    if options.lossless {
+        document["source_map"] = SourceMap::new::<N>(source)?.to_json();
+    }
+    if options.include_comments {
+        for comment in locate_comments::<N>(source)? {
+            if let Some(Value::Object(node)) = document.pointer_mut(&comment.pointer) {
+                if let Value::Array(j_comments) = node.entry("comments").or_insert_with(|| json!([])) {
+                    j_comments.push(Value::String(comment.text));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A helper to collect the spans of the constructs in a source.
+struct Locator<'a> {
+    /// The source text.
+    source: &'a str,
+    /// The byte offsets of the start of each line.
+    lines: Vec<usize>,
+    /// The spans located so far.
+    spans: Vec<LocatedSpan>,
+}
+
+impl<'a> Locator<'a> {
+    /// Initializes a new locator for the given source.
+    fn new(source: &'a str) -> Self {
+        let lines = std::iter::once(0).chain(source.match_indices('\n').map(|(index, _)| index + 1)).collect();
+        Self { source, lines, spans: Vec::new() }
+    }
+
+    /// Returns the position of the given byte offset.
+    fn position(&self, offset: usize) -> SourcePosition {
+        // The first line starts at offset 0, so there is always at least one line before the offset.
+        let line = self.lines.partition_point(|start| *start <= offset);
+        let column = self.source[self.lines[line - 1]..offset].chars().count() + 1;
+        SourcePosition { offset, line, column }
+    }
+
+    /// Records the span from the start of `start` to the start of `end`, both being suffixes of the source.
+    fn push(&mut self, pointer: String, start: &str, end: &str) {
+        let span = SourceSpan {
+            start: self.position(self.source.len() - start.len()),
+            end: self.position(self.source.len() - end.len()),
+        };
+        self.spans.push(LocatedSpan { pointer, span });
+    }
+
+    /// Records the spans of the statements of the closure or function from `start` to `end`,
+    /// along with the span of its finalize block.
+    fn push_body<N: Network>(&mut self, pointer: &str, start: &'a str, end: &'a str) -> Result<(), ConversionError> {
+        // Skip the header, which is closed by the first colon.
+        let mut string = skip_past(start, ':')?;
+        let mut parent = pointer.to_string();
+        let mut is_finalize = false;
+        let (mut num_inputs, mut num_statements, mut num_outputs) = (0, 0, 0);
+
+        loop {
+            let start = sanitize(string)?;
+            if start.len() <= end.len() {
+                break;
+            }
+            let rest = if is_keyword(start, "input") {
+                let rest = skip_past(start, ';')?;
+                self.push(format!("{parent}/inputs/{num_inputs}"), start, rest);
+                num_inputs += 1;
+                rest
+            } else if is_keyword(start, "output") {
+                let rest = skip_past(start, ';')?;
+                self.push(format!("{parent}/outputs/{num_outputs}"), start, rest);
+                num_outputs += 1;
+                rest
+            } else if is_keyword(start, "finalize") {
+                // The finalize block closes the function.
+                parent = format!("{pointer}/finalize_logic");
+                self.push(parent.clone(), start, end);
+                is_finalize = true;
+                (num_inputs, num_statements) = (0, 0);
+                skip_past(start, ':')?
+            } else {
+                let (field, rest) = match is_finalize {
+                    true => ("commands", parsed(Command::<N>::parse(start))?.0),
+                    false => ("instructions", parsed(Instruction::<N>::parse(start))?.0),
+                };
+                self.push(format!("{parent}/{field}/{num_statements}"), start, rest);
+                num_statements += 1;
+                rest
+            };
+            string = rest;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the remainder of the given parser result, or an error if the parser failed.
+fn parsed<'a, T>(result: ParserResult<'a, T>) -> Result<(&'a str, T), ConversionError> {
+    result.map_err(|error| ConversionError::parse(format!("Failed to locate the source spans: {error}")))
+}
+
+/// Returns the given string without its leading whitespace and comments.
+fn sanitize(string: &str) -> Result<&str, ConversionError> {
+    Ok(parsed(Sanitizer::parse(string))?.0)
+}
+
+/// Returns the remainder of the given string after the first occurrence of the delimiter.
+fn skip_past(string: &str, delimiter: char) -> Result<&str, ConversionError> {
+    match string.find(delimiter) {
+        Some(index) => Ok(&string[index + delimiter.len_utf8()..]),
+        None => Err(ConversionError::parse(format!("Failed to locate the source spans: missing '{delimiter}'"))),
+    }
+}
+
+/// Returns `true` if the given string starts with the keyword, followed by whitespace.
+fn is_keyword(string: &str, keyword: &str) -> bool {
+    string.strip_prefix(keyword).map_or(false, |rest| rest.starts_with(char::is_whitespace))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::convert_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    const SOURCE: &str = r"import credits.aleo;
+program token.aleo;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+// Mints new tokens.
+function mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    async mint r0 r1 into r2;
+    output r2 as token.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into account[r0];
+";
+
+    #[test]
+    fn test_locate_spans() {
+        let spans = locate_spans::<CurrentNetwork>(SOURCE).unwrap();
+        let find = |pointer: &str| spans.iter().find(|located| located.pointer == pointer).unwrap().span;
+
+        let import = find("/imports/credits.aleo");
+        assert_eq!(import.text(SOURCE), "import credits.aleo;");
+        assert_eq!((import.start.line, import.start.column), (1, 1));
+
+        let function = find("/functions/mint");
+        assert!(function.text(SOURCE).starts_with("function mint:"));
+        assert!(function.text(SOURCE).ends_with("set r3 into account[r0];"));
+        assert_eq!((function.start.line, function.start.column), (9, 1));
+
+        let input = find("/functions/mint/inputs/1");
+        assert_eq!(input.text(SOURCE), "input r1 as u64.public;");
+        assert_eq!((input.start.line, input.start.column, input.end.column), (11, 5, 28));
+
+        assert_eq!(find("/functions/mint/instructions/0").text(SOURCE), "async mint r0 r1 into r2;");
+        assert_eq!(find("/functions/mint/outputs/0").text(SOURCE), "output r2 as token.aleo/mint.future;");
+        assert_eq!(find("/functions/mint/finalize_logic/commands/2").text(SOURCE), "set r3 into account[r0];");
+        assert_eq!(find("/mappings/account").start.line, 4);
+        assert_eq!(find("").start.offset, 0);
+    }
+
+    #[test]
+    fn test_attach_spans() {
+        let source = "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;";
+        let mut program = convert_program::<CurrentNetwork>(source).unwrap();
+        let options = JsonOptions { include_spans: true, ..Default::default() };
+        attach_source_locations::<CurrentNetwork>(source, &mut program, &options).unwrap();
+
+        assert_eq!(program["span"]["type"], "SourceSpan");
+        assert_eq!(program["functions"]["foo"]["span"]["start"]["column"], 21);
+        assert_eq!(program["functions"]["foo"]["inputs"][0]["span"]["start"]["offset"], 34);
+        assert_eq!(program["functions"]["foo"]["outputs"][0]["span"]["end"]["offset"], source.len());
+        assert!(program["functions"]["foo"].get("source").is_none());
+    }
+
+    #[test]
+    fn test_attach_source() {
+        let mut program = convert_program::<CurrentNetwork>(SOURCE).unwrap();
+        let options = JsonOptions { include_source: true, ..Default::default() };
+        attach_source_locations::<CurrentNetwork>(SOURCE, &mut program, &options).unwrap();
+
+        let function = &program["functions"]["mint"];
+        assert!(function["source"].as_str().unwrap().starts_with("function mint:\n    input r0 as address.public;"));
+        assert_eq!(function["instructions"][0]["source"], "async mint r0 r1 into r2;");
+        assert_eq!(function["finalize_logic"]["commands"][0]["source"], "get.or_use account[r0] 0u64 into r2;");
+        assert!(function.get("span").is_none());
+        assert!(program.get("source").is_none());
+    }
+
+    #[test]
+    fn test_locate_comments() {
+        let source = r"// Audited by the token team.
+program token.aleo;
+
+/* The balances,
+   by owner. */
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+function mint:
+    input r0 as address.public; // The recipient.
+    // The amount is checked in finalize.
+    input r1 as u64.public;
+    async mint r0 r1 into r2;
+    output r2 as token.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into account[r0];
+// End of program.
+";
+        let comments = locate_comments::<CurrentNetwork>(source).unwrap();
+        let summary =
+            comments.iter().map(|comment| (comment.pointer.as_str(), comment.text.as_str())).collect::<Vec<_>>();
+        assert_eq!(summary, [
+            ("", "// Audited by the token team."),
+            ("/mappings/account", "/* The balances,\n   by owner. */"),
+            ("/functions/mint/inputs/1", "// The recipient."),
+            ("/functions/mint/inputs/1", "// The amount is checked in finalize."),
+            ("", "// End of program."),
+        ]);
+        assert_eq!((comments[2].span.start.line, comments[2].span.start.column), (11, 33));
+
+        let mut program = convert_program::<CurrentNetwork>(source).unwrap();
+        let options = JsonOptions { include_comments: true, ..Default::default() };
+        attach_source_locations::<CurrentNetwork>(source, &mut program, &options).unwrap();
+        assert_eq!(program["comments"], json!(["// Audited by the token team.", "// End of program."]));
+        assert_eq!(program["functions"]["mint"]["inputs"][1]["comments"][1], "// The amount is checked in finalize.");
+        assert!(program["functions"]["mint"].get("comments").is_none());
+    }
+}
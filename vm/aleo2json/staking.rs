@@ -0,0 +1,172 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::with_version,
+    console::{
+        account::Address,
+        network::{prelude::FromStr, Network},
+        program::{Identifier, ProgramID},
+    },
+    ledger::{
+        committee::Committee,
+        store::{FinalizeStorage, FinalizeStore},
+    },
+    synthesizer::{bonded_map_into_stakers, committee_map_into_committee},
+};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+/// Returns the staking state of the given committee and stakers as structured JSON.
+///
+/// Each validator lists its total stake, whether it is open to stakers, its self-bond, and its delegators.
+/// Bonds to an address outside of the committee (i.e. a validator that has since left) are listed under
+/// `other_bonds`. The microcredits are in the same units as `credits.aleo`, which has no notion of commission.
+///
+/// The validators, their delegators, and the other bonds are each sorted by address, as the order of the
+/// mappings they are read from depends on the storage backend.
+pub fn staking_to_json<N: Network>(
+    committee: &Committee<N>,
+    stakers: &IndexMap<Address<N>, (Address<N>, u64)>,
+) -> Value {
+    let mut validators = IndexMap::new();
+    for (address, (stake, is_open)) in committee.members() {
+        validators.insert(*address, (*stake, *is_open, 0u64, Vec::new()));
+    }
+
+    let mut j_other_bonds = Vec::new();
+    for (staker, (validator, microcredits)) in stakers {
+        match validators.get_mut(validator) {
+            Some((_, _, self_bond, _)) if staker == validator => *self_bond = *microcredits,
+            Some((_, _, _, delegators)) => delegators.push(json!({
+                "address": format!("{}", staker),
+                "microcredits": microcredits,
+            })),
+            None => j_other_bonds.push(json!({
+                "address": format!("{}", staker),
+                "validator": format!("{}", validator),
+                "microcredits": microcredits,
+            })),
+        }
+    }
+
+    let mut j_members = Vec::new();
+    for (address, (stake, is_open, self_bond, mut delegators)) in validators {
+        sort_by_address(&mut delegators);
+        j_members.push(json!({
+            "address": format!("{}", address),
+            "stake": stake,
+            "is_open": is_open,
+            "self_bond": self_bond,
+            "delegators": delegators,
+        }));
+    }
+    sort_by_address(&mut j_members);
+    sort_by_address(&mut j_other_bonds);
+
+    json!({
+        "type": "StakingState",
+        "total_stake": committee.total_stake(),
+        "validators": j_members,
+        "other_bonds": j_other_bonds,
+    })
+}
+
+/// Sorts the given JSON objects by their `address` field.
+fn sort_by_address(entries: &mut [Value]) {
+    entries.sort_by(|a, b| a["address"].as_str().cmp(&b["address"].as_str()));
+}
+
+/// Reads the `committee` and `bonded` mappings of `credits.aleo` from the given finalize storage,
+/// and returns the staking state as structured JSON, stamped with the format version.
+pub fn export_staking<N: Network, F: FinalizeStorage<N>>(finalize_store: &FinalizeStore<N, F>) -> Result<Value> {
+    let program_id = ProgramID::<N>::from_str("credits.aleo")?;
+    let committee_map = finalize_store.get_mapping_confirmed(program_id, Identifier::from_str("committee")?)?;
+    let bonded_map = finalize_store.get_mapping_confirmed(program_id, Identifier::from_str("bonded")?)?;
+
+    // Note: The starting round is not part of the mapping state, and only the members are exported.
+    let committee = committee_map_into_committee(0, committee_map)?;
+    let stakers = bonded_map_into_stakers(bonded_map)?;
+
+    Ok(with_version(staking_to_json(&committee, &stakers)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        console::network::prelude::FromBytes,
+        ledger::{
+            block::Block,
+            store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        },
+        prelude::Testnet3,
+        synthesizer::VM,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_export_genesis_staking() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        vm.add_next_block(&genesis).unwrap();
+
+        let j_staking = export_staking(vm.finalize_store()).unwrap();
+        assert_eq!(j_staking["type"], "StakingState");
+
+        // In the genesis block, every validator is bonded only to itself.
+        let validators = j_staking["validators"].as_array().unwrap();
+        assert!(!validators.is_empty());
+        let mut total_stake = 0;
+        for validator in validators {
+            assert_eq!(validator["self_bond"], validator["stake"]);
+            assert_eq!(validator["delegators"], json!([]));
+            total_stake += validator["stake"].as_u64().unwrap();
+        }
+        assert_eq!(j_staking["total_stake"], total_stake);
+        assert_eq!(j_staking["other_bonds"], json!([]));
+    }
+
+    #[test]
+    fn test_staking_order_independent() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<_>>::open(None).unwrap()).unwrap();
+        vm.add_next_block(&genesis).unwrap();
+
+        let program_id = ProgramID::<CurrentNetwork>::from_str("credits.aleo").unwrap();
+        let committee_map =
+            vm.finalize_store().get_mapping_confirmed(program_id, Identifier::from_str("committee").unwrap()).unwrap();
+        let bonded_map =
+            vm.finalize_store().get_mapping_confirmed(program_id, Identifier::from_str("bonded").unwrap()).unwrap();
+        assert!(committee_map.len() > 1);
+
+        // Ensure reversing the order of the mappings does not change the output.
+        let expected = staking_to_json(
+            &committee_map_into_committee(0, committee_map.clone()).unwrap(),
+            &bonded_map_into_stakers(bonded_map.clone()).unwrap(),
+        );
+        let candidate = staking_to_json(
+            &committee_map_into_committee(0, committee_map.into_iter().rev().collect()).unwrap(),
+            &bonded_map_into_stakers(bonded_map.into_iter().rev().collect()).unwrap(),
+        );
+        assert_eq!(candidate, expected);
+
+        let addresses: Vec<_> =
+            expected["validators"].as_array().unwrap().iter().map(|j| j["address"].as_str().unwrap()).collect();
+        assert!(addresses.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::{mapping_id, with_version},
+    console::network::Network,
+    ledger::block::Block,
+    synthesizer::program::FinalizeOperation,
+};
+
+use anyhow::{ensure, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+/// The net change of one mapping between two consecutive blocks.
+///
+/// The keys and values are identified by their IDs, as in the finalize operations. A removed entry is only known by
+/// its index in the mapping, as the finalize operations do not record its key, so a removal is matched to the entries
+/// changed earlier in the block by the indices their updates recorded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MappingDelta {
+    /// Whether the mapping was initialized.
+    pub initialized: bool,
+    /// Whether the mapping was replaced, in which case its entries must be read again.
+    pub replaced: bool,
+    /// Whether the mapping was removed.
+    pub removed: bool,
+    /// The value ID of each added key ID, including the keys added then updated.
+    pub added: IndexMap<String, String>,
+    /// The index and value ID of each updated key ID.
+    pub updated: IndexMap<String, (u64, String)>,
+    /// The indices of the removed entries.
+    pub removed_indices: Vec<u64>,
+    /// The index of each added key ID, if an update of the key recorded it.
+    added_indices: IndexMap<String, u64>,
+    /// The index of each removed key ID, if an update of the key recorded it.
+    removed_keys: IndexMap<String, u64>,
+}
+
+impl MappingDelta {
+    /// Applies the given finalize operation of the mapping to the delta.
+    pub fn apply<N: Network>(&mut self, operation: &FinalizeOperation<N>) {
+        match operation {
+            FinalizeOperation::InitializeMapping(..) => self.initialized = true,
+            FinalizeOperation::InsertKeyValue(_, key_id, value_id) => {
+                let key_id = key_id.to_string();
+                match self.removed_keys.remove(&key_id) {
+                    // A key removed then inserted again is updated.
+                    Some(index) => {
+                        self.removed_indices.retain(|removed_index| *removed_index != index);
+                        self.updated.insert(key_id, (index, value_id.to_string()));
+                    }
+                    None => {
+                        self.added.insert(key_id, value_id.to_string());
+                    }
+                }
+            }
+            FinalizeOperation::UpdateKeyValue(_, index, key_id, value_id) => {
+                let key_id = key_id.to_string();
+                match self.added.get_mut(&key_id) {
+                    Some(added) => {
+                        *added = value_id.to_string();
+                        self.added_indices.insert(key_id, *index);
+                    }
+                    None => {
+                        self.updated.insert(key_id, (*index, value_id.to_string()));
+                    }
+                }
+            }
+            FinalizeOperation::RemoveKeyValue(_, index) => {
+                // A key added then removed is left unchanged.
+                if let Some(key_id) = find_key(&self.added_indices, *index) {
+                    self.added_indices.remove(&key_id);
+                    self.added.remove(&key_id);
+                    return;
+                }
+                if let Some((key_id, _)) = self.updated.iter().find(|(_, (updated_index, _))| updated_index == index) {
+                    self.removed_keys.insert(key_id.clone(), *index);
+                }
+                self.updated.retain(|_, (updated_index, _)| updated_index != index);
+                self.removed_indices.push(*index);
+            }
+            FinalizeOperation::ReplaceMapping(..) => {
+                *self = Self { initialized: self.initialized, replaced: true, ..Default::default() };
+            }
+            FinalizeOperation::RemoveMapping(..) => {
+                *self = Self { initialized: self.initialized, removed: true, ..Default::default() };
+            }
+        }
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "MappingDelta",
+            "initialized": self.initialized,
+            "replaced": self.replaced,
+            "removed": self.removed,
+            "added": self
+                .added
+                .iter()
+                .map(|(key_id, value_id)| json!({ "key_id": key_id, "value_id": value_id }))
+                .collect::<Vec<_>>(),
+            "updated": self
+                .updated
+                .iter()
+                .map(|(key_id, (index, value_id))| json!({ "index": index, "key_id": key_id, "value_id": value_id }))
+                .collect::<Vec<_>>(),
+            "removed_indices": self.removed_indices,
+        })
+    }
+}
+
+/// Returns the key ID recorded at the given index, if any.
+fn find_key(indices: &IndexMap<String, u64>, index: u64) -> Option<String> {
+    indices.iter().find(|(_, key_index)| **key_index == index).map(|(key_id, _)| key_id.clone())
+}
+
+/// The net mapping-state delta between two consecutive blocks, by mapping ID, in order of the first change.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateDiff {
+    /// The height of the earlier block.
+    pub from_height: u32,
+    /// The height of the later block.
+    pub to_height: u32,
+    /// The hash of the later block.
+    pub block_hash: String,
+    /// The delta of each changed mapping, by mapping ID.
+    pub mappings: IndexMap<String, MappingDelta>,
+}
+
+impl StateDiff {
+    /// Returns the net mapping-state delta between the given consecutive blocks, i.e. the finalize operations of
+    /// every confirmed transaction of the later block, folded by mapping.
+    ///
+    /// Fails if the later block does not directly follow the earlier one.
+    pub fn between<N: Network>(previous: &Block<N>, block: &Block<N>) -> Result<Self> {
+        ensure!(
+            block.previous_hash() == previous.hash() && block.height() == previous.height() + 1,
+            "Block {} does not directly follow block {}",
+            block.height(),
+            previous.height()
+        );
+
+        let mut mappings = IndexMap::<String, MappingDelta>::new();
+        for confirmed in block.transactions().iter() {
+            for operation in confirmed.finalize_operations().iter() {
+                mappings.entry(mapping_id(operation).to_string()).or_default().apply(operation);
+            }
+        }
+        Ok(Self {
+            from_height: previous.height(),
+            to_height: block.height(),
+            block_hash: block.hash().to_string(),
+            mappings,
+        })
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        with_version(json!({
+            "type": "StateDiff",
+            "from_height": self.from_height,
+            "to_height": self.to_height,
+            "block_hash": self.block_hash,
+            "mappings": self
+                .mappings
+                .iter()
+                .map(|(mapping_id, delta)| (mapping_id.clone(), delta.to_json()))
+                .collect::<serde_json::Map<_, _>>(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        console::{network::prelude::FromBytes, types::Field},
+        prelude::Testnet3,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_mapping_delta_apply() {
+        let field = |value: u64| Field::<CurrentNetwork>::from_u64(value);
+        let mapping = field(0);
+
+        let mut delta = MappingDelta::default();
+        delta.apply(&FinalizeOperation::InsertKeyValue(mapping, field(1), field(10)));
+        delta.apply(&FinalizeOperation::UpdateKeyValue(mapping, 0, field(1), field(11)));
+        delta.apply(&FinalizeOperation::UpdateKeyValue(mapping, 3, field(2), field(20)));
+        delta.apply(&FinalizeOperation::UpdateKeyValue(mapping, 4, field(3), field(30)));
+        delta.apply(&FinalizeOperation::RemoveKeyValue(mapping, 4));
+
+        // A key added then updated is added with its last value, and an updated entry that is removed is only removed.
+        assert_eq!(delta.added, [(field(1).to_string(), field(11).to_string())].into_iter().collect());
+        assert_eq!(delta.updated, [(field(2).to_string(), (3, field(20).to_string()))].into_iter().collect());
+        assert_eq!(delta.removed_indices, vec![4]);
+
+        delta.apply(&FinalizeOperation::<CurrentNetwork>::RemoveMapping(mapping));
+        assert_eq!(delta, MappingDelta { removed: true, ..Default::default() });
+        assert_eq!(delta.to_json()["type"], "MappingDelta");
+    }
+
+    #[test]
+    fn test_mapping_delta_insert_remove() {
+        let field = |value: u64| Field::<CurrentNetwork>::from_u64(value);
+        let mapping = field(0);
+
+        // A key inserted then removed is not added.
+        let mut delta = MappingDelta::default();
+        delta.apply(&FinalizeOperation::InsertKeyValue(mapping, field(1), field(10)));
+        delta.apply(&FinalizeOperation::UpdateKeyValue(mapping, 5, field(1), field(11)));
+        delta.apply(&FinalizeOperation::RemoveKeyValue(mapping, 5));
+        assert!(delta.added.is_empty());
+        assert!(delta.removed_indices.is_empty());
+
+        // A key removed then inserted again is updated.
+        let mut delta = MappingDelta::default();
+        delta.apply(&FinalizeOperation::UpdateKeyValue(mapping, 2, field(2), field(20)));
+        delta.apply(&FinalizeOperation::RemoveKeyValue(mapping, 2));
+        assert_eq!(delta.removed_indices, vec![2]);
+        delta.apply(&FinalizeOperation::InsertKeyValue(mapping, field(2), field(21)));
+        assert!(delta.added.is_empty());
+        assert!(delta.removed_indices.is_empty());
+        assert_eq!(delta.updated, [(field(2).to_string(), (2, field(21).to_string()))].into_iter().collect());
+    }
+
+    #[test]
+    fn test_state_diff_requires_consecutive_blocks() {
+        let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        assert!(StateDiff::between(&genesis, &genesis).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{console::network::Network, synthesizer::Program};
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Summary statistics for a program.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgramStats {
+    /// The number of imports.
+    pub num_imports: usize,
+    /// The number of mappings.
+    pub num_mappings: usize,
+    /// The number of structs.
+    pub num_structs: usize,
+    /// The number of records.
+    pub num_records: usize,
+    /// The number of closures.
+    pub num_closures: usize,
+    /// The number of functions.
+    pub num_functions: usize,
+    /// The number of instructions, across all closures and functions.
+    pub num_instructions: usize,
+    /// The number of finalize commands, across all functions.
+    pub num_commands: usize,
+    /// The number of occurrences of each opcode, across all instructions and commands.
+    pub opcodes: BTreeMap<String, usize>,
+}
+
+impl ProgramStats {
+    /// Computes the statistics for the given program.
+    pub fn new<N: Network>(program: &Program<N>) -> Self {
+        let mut stats = Self {
+            num_imports: program.imports().len(),
+            num_mappings: program.mappings().len(),
+            num_structs: program.structs().len(),
+            num_records: program.records().len(),
+            num_closures: program.closures().len(),
+            num_functions: program.functions().len(),
+            ..Default::default()
+        };
+
+        let instructions = program
+            .closures()
+            .values()
+            .flat_map(|closure| closure.instructions())
+            .chain(program.functions().values().flat_map(|function| function.instructions()));
+        for instruction in instructions {
+            stats.num_instructions += 1;
+            *stats.opcodes.entry(instruction.opcode().to_string()).or_default() += 1;
+        }
+
+        let commands = program
+            .functions()
+            .values()
+            .filter_map(|function| function.finalize_logic())
+            .flat_map(|finalize| finalize.commands());
+        for command in commands {
+            stats.num_commands += 1;
+            // The opcode of a command is the first token of its string representation.
+            let command = command.to_string();
+            let opcode = command.split_whitespace().next().unwrap_or_default();
+            *stats.opcodes.entry(opcode.to_string()).or_default() += 1;
+        }
+
+        stats
+    }
+
+    /// Returns the statistics as JSON.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "num_imports": self.num_imports,
+            "num_mappings": self.num_mappings,
+            "num_structs": self.num_structs,
+            "num_records": self.num_records,
+            "num_closures": self.num_closures,
+            "num_functions": self.num_functions,
+            "num_instructions": self.num_instructions,
+            "num_commands": self.num_commands,
+            "opcodes": self.opcodes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_program_stats() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+program token.aleo;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+function mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    add r1 r1 into r2;
+    async mint r0 r2 into r3;
+    output r3 as token.aleo/mint.future;
+
+finalize mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into account[r0];",
+        )
+        .unwrap();
+
+        let stats = ProgramStats::new(&program);
+        assert_eq!(stats.num_mappings, 1);
+        assert_eq!(stats.num_functions, 1);
+        assert_eq!(stats.num_instructions, 2);
+        assert_eq!(stats.num_commands, 3);
+        assert_eq!(stats.opcodes.get("add"), Some(&2));
+        assert_eq!(stats.opcodes.get("get.or_use"), Some(&1));
+    }
+}
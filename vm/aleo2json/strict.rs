@@ -0,0 +1,153 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{push_token, ConversionError, COMMAND_VTYPES, INSTRUCTION_VTYPES};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+/// How constructs without a structured JSON representation are handled during conversion.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ConversionMode {
+    /// Fail the conversion, identifying the offending construct.
+    Strict,
+    /// Replace the construct with a tagged `"unsupported"` node that carries its source text.
+    #[default]
+    Permissive,
+}
+
+/// Returns the known variants of the given tagged construct, or `None` if the construct is not checked.
+fn known_vtypes(construct: &str) -> Option<&'static [&'static str]> {
+    match construct {
+        "Instruction" => Some(INSTRUCTION_VTYPES),
+        "Command" => Some(COMMAND_VTYPES),
+        _ => None,
+    }
+}
+
+/// Checks the given document for constructs without a structured JSON representation, handling them
+/// according to the given mode.
+///
+/// A construct is unsupported if its variant is not one the aleo2json schema describes, which is the
+/// case for any instruction or command added to snarkVM before its JSON shape was reviewed.
+pub fn check_constructs(mut document: Value, mode: ConversionMode) -> Result<Value, ConversionError> {
+    check_at(&mut String::new(), &mut document, mode)?;
+    Ok(document)
+}
+
+/// Checks the value located at `pointer`, and its children.
+fn check_at(pointer: &mut String, value: &mut Value, mode: ConversionMode) -> Result<(), ConversionError> {
+    match value {
+        Value::Object(object) => {
+            let construct = object.get("type").and_then(Value::as_str).unwrap_or_default();
+            if let Some(known) = known_vtypes(construct) {
+                let vtype = object.get("vtype").and_then(Value::as_str).unwrap_or_default();
+                if !known.contains(&vtype) {
+                    let source = object.get("str").and_then(Value::as_str).unwrap_or_default();
+                    match mode {
+                        ConversionMode::Strict => {
+                            return Err(ConversionError::Unsupported {
+                                construct: construct.to_string(),
+                                vtype: vtype.to_string(),
+                                pointer: pointer.clone(),
+                                text: source.to_string(),
+                            });
+                        }
+                        ConversionMode::Permissive => {
+                            let index = object.get("index").cloned();
+                            *value = json!({
+                                "type": "unsupported",
+                                "construct": construct,
+                                "vtype": vtype,
+                                "str": source,
+                            });
+                            // The unsupported node keeps the index of the instruction or command it replaces.
+                            if let Some(index) = index {
+                                value["index"] = index;
+                            }
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            for (key, val) in object.iter_mut() {
+                let length = push_token(pointer, key);
+                check_at(pointer, val, mode)?;
+                pointer.truncate(length);
+            }
+        }
+        Value::Array(array) => {
+            for (index, val) in array.iter_mut().enumerate() {
+                let length = push_token(pointer, &index.to_string());
+                check_at(pointer, val, mode)?;
+                pointer.truncate(length);
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Value {
+        json!({
+            "type": "FunctionCore",
+            "instructions": [
+                { "type": "Instruction", "vtype": "Add", "value": {}, "str": "add r0 r1 into r2;", "index": 0 },
+                {
+                    "type": "Instruction",
+                    "vtype": "Frobnicate",
+                    "value": {},
+                    "str": "frobnicate r0 into r1;",
+                    "index": 1,
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn test_supported() {
+        let document = json!({ "type": "Command", "vtype": "Instruction", "value": sample()["instructions"][0] });
+        assert_eq!(check_constructs(document.clone(), ConversionMode::Strict).unwrap(), document);
+    }
+
+    #[test]
+    fn test_strict() {
+        let error = check_constructs(sample(), ConversionMode::Strict).unwrap_err();
+        assert_eq!(error.kind(), "unsupported");
+        assert_eq!(
+            error.to_string(),
+            "Unsupported Instruction 'Frobnicate' at '/instructions/1': frobnicate r0 into r1;"
+        );
+    }
+
+    #[test]
+    fn test_permissive() {
+        let document = check_constructs(sample(), ConversionMode::Permissive).unwrap();
+        assert_eq!(document["instructions"][0]["vtype"], "Add");
+        assert_eq!(
+            document["instructions"][1],
+            json!({
+                "type": "unsupported",
+                "construct": "Instruction",
+                "vtype": "Frobnicate",
+                "str": "frobnicate r0 into r1;",
+                "index": 1,
+            })
+        );
+    }
+}
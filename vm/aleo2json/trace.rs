@@ -0,0 +1,261 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    circuit,
+    console::{
+        account::PrivateKey,
+        network::Network,
+        program::{Identifier, Register, Value},
+    },
+    ledger::store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    synthesizer::{
+        process::{CallStack, CallTrait, Registers, StackProgramTypes},
+        program::{InstructionTrait, Operand, RegistersLoad, RegistersSigner, RegistersStore},
+        Instruction,
+        Program,
+        VM,
+    },
+};
+
+use anyhow::Result;
+use rand::{CryptoRng, Rng};
+use serde_json::json;
+use std::str::FromStr;
+
+/// A step of an execution trace: an instruction, with the values of its operands before it was evaluated,
+/// and the values of its destinations after.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceStep<N: Network> {
+    /// The index of the instruction in the function body.
+    pub index: usize,
+    /// The instruction.
+    pub instruction: Instruction<N>,
+    /// The values of the operands of the instruction.
+    pub operands: Vec<Value<N>>,
+    /// The destination registers of the instruction, with their values.
+    pub destinations: Vec<(Register<N>, Value<N>)>,
+}
+
+impl<N: Network> TraceStep<N> {
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_operands = Vec::new();
+        for (operand, value) in self.instruction.operands().iter().zip(&self.operands) {
+            j_operands.push(json!({ "operand": format!("{}", operand), "value": format!("{}", value) }));
+        }
+        let mut j_destinations = Vec::new();
+        for (register, value) in &self.destinations {
+            j_destinations.push(json!({ "register": format!("{}", register), "value": format!("{}", value) }));
+        }
+
+        json!({
+            "type": "TraceStep",
+            "index": self.index,
+            "opcode": *self.instruction.opcode(),
+            "instruction": format!("{}", self.instruction),
+            "operands": j_operands,
+            "destinations": j_destinations,
+        })
+    }
+}
+
+/// The trace of the evaluation of a function: its inputs, each instruction with the concrete values it read and
+/// wrote, and its outputs.
+///
+/// Evaluation stops at the first instruction that fails (i.e. a failed `assert`), whose error is recorded in place
+/// of the outputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionTrace<N: Network> {
+    /// The program ID, i.e. `token.aleo`.
+    pub program_id: String,
+    /// The name of the function.
+    pub function: String,
+    /// The inputs of the function.
+    pub inputs: Vec<Value<N>>,
+    /// The steps of the evaluation, in order.
+    pub steps: Vec<TraceStep<N>>,
+    /// The outputs of the function, if its evaluation succeeded.
+    pub outputs: Vec<Value<N>>,
+    /// The error of the failed instruction, if its evaluation halted.
+    pub error: Option<String>,
+}
+
+impl<N: Network> ExecutionTrace<N> {
+    /// Returns `true` if the evaluation of the function halted.
+    pub fn is_halted(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut j_inputs = Vec::new();
+        for val in &self.inputs {
+            j_inputs.push(format!("{}", val));
+        }
+        let mut j_steps = Vec::new();
+        for val in &self.steps {
+            j_steps.push(val.to_json());
+        }
+        let mut j_outputs = Vec::new();
+        for val in &self.outputs {
+            j_outputs.push(format!("{}", val));
+        }
+
+        json!({
+            "type": "ExecutionTrace",
+            "program_id": self.program_id,
+            "function": self.function,
+            "inputs": j_inputs,
+            "steps": j_steps,
+            "outputs": j_outputs,
+            "halted": self.is_halted(),
+            "error": self.error,
+        })
+    }
+}
+
+/// Authorizes a call to the given function of the program with the given inputs, signed by the private key,
+/// and evaluates it on an in-memory VM, recording the values read and written by each instruction.
+///
+/// The program may only import `credits.aleo`. Calls to other functions are evaluated as a single step,
+/// whose destinations are the outputs of the callee.
+pub fn trace_function<N: Network, A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+    program: &Program<N>,
+    private_key: &PrivateKey<N>,
+    function_name: &str,
+    inputs: &[Value<N>],
+    rng: &mut R,
+) -> Result<ExecutionTrace<N>> {
+    let function = program.get_function(&Identifier::from_str(function_name)?)?;
+
+    // Initialize the VM on an empty, in-memory store, and add the program to it.
+    let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(None)?)?;
+    let process = vm.process();
+    if !process.read().contains_program(program.id()) {
+        process.write().add_program(program)?;
+    }
+
+    // Authorize the call, and take its request as the top-level one.
+    let authorization = vm.authorize(private_key, *program.id(), function_name, inputs.iter().cloned(), rng)?;
+    let request = authorization.next()?;
+
+    let process = process.read();
+    let stack = &**process.get_stack(*program.id())?;
+
+    // Initialize the registers, as the top-level function of the call stack.
+    let mut registers =
+        Registers::<N, A>::new(CallStack::evaluate(authorization)?, stack.get_register_types(function.name())?.clone());
+    registers.set_signer(*request.signer());
+    registers.set_caller(*request.signer());
+    registers.set_tvk(*request.tvk());
+    for (input, value) in function.inputs().iter().zip(request.inputs()) {
+        registers.store(stack, input.register(), value.clone())?;
+    }
+
+    let mut trace = ExecutionTrace {
+        program_id: program.id().to_string(),
+        function: function_name.to_string(),
+        inputs: request.inputs().to_vec(),
+        steps: Vec::new(),
+        outputs: Vec::new(),
+        error: None,
+    };
+
+    for (index, instruction) in function.instructions().iter().enumerate() {
+        let operands =
+            instruction.operands().iter().map(|operand| registers.load(stack, operand)).collect::<Result<Vec<_>>>()?;
+
+        // Note: `call` is evaluated separately, as in the stack, to evaluate the callee.
+        let result = match instruction {
+            Instruction::Call(call) => CallTrait::evaluate(call, stack, &mut registers),
+            _ => instruction.evaluate(stack, &mut registers),
+        };
+        if let Err(error) = result {
+            trace.steps.push(TraceStep { index, instruction: instruction.clone(), operands, destinations: Vec::new() });
+            trace.error = Some(format!("Failed to evaluate instruction ({instruction}): {error}"));
+            return Ok(trace);
+        }
+
+        let destinations = instruction
+            .destinations()
+            .into_iter()
+            .map(|register| {
+                let value = registers.load(stack, &Operand::Register(register.clone()))?;
+                Ok((register, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        trace.steps.push(TraceStep { index, instruction: instruction.clone(), operands, destinations });
+    }
+
+    trace.outputs =
+        function.outputs().iter().map(|output| registers.load(stack, output.operand())).collect::<Result<Vec<_>>>()?;
+    Ok(trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circuit::AleoV0, prelude::Testnet3};
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type CurrentNetwork = Testnet3;
+
+    const PROGRAM: &str = r"
+program trace.aleo;
+
+function scale:
+    input r0 as u64.public;
+    input r1 as u64.private;
+    add r0 r1 into r2;
+    mul r2 2u64 into r3;
+    assert.neq r3 0u64;
+    output r3 as u64.private;
+";
+
+    fn trace(inputs: &[&str]) -> ExecutionTrace<CurrentNetwork> {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let program = Program::<CurrentNetwork>::from_str(PROGRAM).unwrap();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let inputs = inputs.iter().map(|input| Value::from_str(input).unwrap()).collect::<Vec<_>>();
+        trace_function::<CurrentNetwork, AleoV0, _>(&program, &private_key, "scale", &inputs, rng).unwrap()
+    }
+
+    #[test]
+    fn test_trace_function() {
+        let trace = trace(&["3u64", "4u64"]);
+        assert!(!trace.is_halted());
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(trace.outputs, vec![Value::from_str("14u64").unwrap()]);
+
+        let j_trace = trace.to_json();
+        assert_eq!(j_trace["type"], "ExecutionTrace");
+        assert_eq!(j_trace["steps"][0]["opcode"], "add");
+        assert_eq!(j_trace["steps"][0]["operands"][1]["value"], "4u64");
+        assert_eq!(j_trace["steps"][1]["destinations"][0]["register"], "r3");
+        assert_eq!(j_trace["steps"][1]["destinations"][0]["value"], "14u64");
+        assert_eq!(j_trace["outputs"][0], "14u64");
+    }
+
+    #[test]
+    fn test_trace_function_halts() {
+        let trace = trace(&["0u64", "0u64"]);
+        assert!(trace.is_halted());
+        assert_eq!(trace.steps.len(), 3);
+        assert!(trace.steps[2].destinations.is_empty());
+        assert!(trace.outputs.is_empty());
+        assert_eq!(trace.to_json()["halted"], true);
+    }
+}
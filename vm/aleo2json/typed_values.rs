@@ -0,0 +1,331 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::ImportResolver,
+    console::{
+        network::Network,
+        program::{Literal, Plaintext, PlaintextType, ProgramID, ValueType},
+    },
+    ledger::block::{Block, Input, Output, Transition},
+    synthesizer::Program,
+};
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use std::{collections::HashMap, str::FromStr};
+
+/// Returns the given literal as JSON: booleans as booleans, integers of up to 32 bits as numbers, strings as their
+/// contents, and the others (i.e. 64-bit and 128-bit integers, fields, and addresses) as strings without a type suffix.
+fn literal_to_json<N: Network>(literal: &Literal<N>) -> Value {
+    match literal {
+        Literal::Boolean(boolean) => json!(**boolean),
+        Literal::I8(integer) => json!(**integer),
+        Literal::I16(integer) => json!(**integer),
+        Literal::I32(integer) => json!(**integer),
+        Literal::U8(integer) => json!(**integer),
+        Literal::U16(integer) => json!(**integer),
+        Literal::U32(integer) => json!(**integer),
+        Literal::String(string) => json!(&**string),
+        literal => {
+            let string = literal.to_string();
+            let suffix = literal.to_type().to_string();
+            json!(string.strip_suffix(&suffix).unwrap_or(&string))
+        }
+    }
+}
+
+/// Returns the given plaintext as typed JSON, decoded against the given plaintext type, with the structs looked up
+/// in the given program: a literal with its literal type, a struct with its name and typed members, and an array with
+/// its element type and typed elements.
+///
+/// Fails if the plaintext does not match the type.
+pub fn typed_plaintext_to_json<N: Network>(
+    program: &Program<N>,
+    plaintext_type: &PlaintextType<N>,
+    plaintext: &Plaintext<N>,
+) -> Result<Value> {
+    match (plaintext_type, plaintext) {
+        (PlaintextType::Literal(literal_type), Plaintext::Literal(literal, _)) => {
+            if literal.to_type() != *literal_type {
+                bail!("Expected a '{literal_type}', found '{literal}'");
+            }
+            Ok(json!({
+                "type": "Literal",
+                "literal_type": literal_type.to_string(),
+                "value": literal_to_json(literal),
+            }))
+        }
+        (PlaintextType::Struct(struct_name), Plaintext::Struct(members, _)) => {
+            let struct_type = program.get_struct(struct_name)?;
+            if struct_type.members().len() != members.len() {
+                bail!("Expected {} members in '{struct_name}', found {}", struct_type.members().len(), members.len());
+            }
+            let mut j_members = serde_json::Map::new();
+            for (member_name, member_type) in struct_type.members() {
+                let member = match members.get(member_name) {
+                    Some(member) => member,
+                    None => bail!("Missing member '{member_name}' of '{struct_name}'"),
+                };
+                j_members.insert(member_name.to_string(), typed_plaintext_to_json(program, member_type, member)?);
+            }
+            Ok(json!({
+                "type": "Struct",
+                "struct_name": struct_name.to_string(),
+                "members": j_members,
+            }))
+        }
+        (PlaintextType::Array(array_type), Plaintext::Array(elements, _)) => {
+            if **array_type.length() as usize != elements.len() {
+                bail!("Expected {} elements in '{array_type}', found {}", array_type.length(), elements.len());
+            }
+            let element_type = array_type.next_element_type();
+            Ok(json!({
+                "type": "Array",
+                "element_type": element_type.to_string(),
+                "elements": elements
+                    .iter()
+                    .map(|element| typed_plaintext_to_json(program, element_type, element))
+                    .collect::<Result<Vec<_>>>()?,
+            }))
+        }
+        (plaintext_type, plaintext) => bail!("Expected a '{plaintext_type}', found '{plaintext}'"),
+    }
+}
+
+/// Returns the plaintext type of the given value type, if its values are public (i.e. constant or public).
+fn public_plaintext_type<N: Network>(value_type: &ValueType<N>) -> Option<&PlaintextType<N>> {
+    match value_type {
+        ValueType::Constant(plaintext_type) | ValueType::Public(plaintext_type) => Some(plaintext_type),
+        _ => None,
+    }
+}
+
+/// Returns the typed JSON of each constant or public input and output of the given transition, with its index,
+/// decoded against the value types declared by its function in the given program.
+///
+/// Fails if the function is not in the program, or if a value does not match its declared type.
+pub fn typed_transition_values<N: Network>(
+    program: &Program<N>,
+    transition: &Transition<N>,
+) -> Result<(Vec<(usize, Value)>, Vec<(usize, Value)>)> {
+    let function = program.get_function_ref(transition.function_name())?;
+
+    let mut inputs = Vec::new();
+    for (index, (input, declared)) in transition.inputs().iter().zip(function.inputs()).enumerate() {
+        if let (Input::Constant(_, Some(plaintext)) | Input::Public(_, Some(plaintext)), Some(plaintext_type)) =
+            (input, public_plaintext_type(declared.value_type()))
+        {
+            inputs.push((index, typed_plaintext_to_json(program, plaintext_type, plaintext)?));
+        }
+    }
+    let mut outputs = Vec::new();
+    for (index, (output, declared)) in transition.outputs().iter().zip(function.outputs()).enumerate() {
+        if let (Output::Constant(_, Some(plaintext)) | Output::Public(_, Some(plaintext)), Some(plaintext_type)) =
+            (output, public_plaintext_type(declared.value_type()))
+        {
+            outputs.push((index, typed_plaintext_to_json(program, plaintext_type, plaintext)?));
+        }
+    }
+    Ok((inputs, outputs))
+}
+
+/// The programs that transitions are decoded against: `credits.aleo`, the programs added to it, and the programs
+/// resolved with an import resolver, locally or from a node. Each program is resolved at most once.
+pub struct ProgramCatalog<N: Network> {
+    /// The programs by ID, or `None` if the program could not be resolved.
+    programs: HashMap<ProgramID<N>, Option<Program<N>>>,
+    /// The resolver of the programs that were not added.
+    resolver: Option<ImportResolver>,
+}
+
+impl<N: Network> ProgramCatalog<N> {
+    /// Initializes a catalog holding `credits.aleo`, which resolves other programs with the given resolver, if any.
+    pub fn new(resolver: Option<ImportResolver>) -> Result<Self> {
+        let credits = Program::credits()?;
+        let programs = HashMap::from([(*credits.id(), Some(credits))]);
+        Ok(Self { programs, resolver })
+    }
+
+    /// Adds the given program to the catalog.
+    pub fn insert(&mut self, program: Program<N>) {
+        self.programs.insert(*program.id(), Some(program));
+    }
+
+    /// Adds the programs deployed in the given block to the catalog.
+    pub fn insert_deployments(&mut self, block: &Block<N>) {
+        for confirmed in block.transactions().iter() {
+            if let Some(deployment) = confirmed.transaction().deployment() {
+                self.insert(deployment.program().clone());
+            }
+        }
+    }
+
+    /// Returns the program with the given ID, resolving it if needed, or `None` if it cannot be resolved.
+    pub fn get(&mut self, program_id: &ProgramID<N>) -> Option<&Program<N>> {
+        if !self.programs.contains_key(program_id) {
+            let program = self.resolve(program_id).ok().flatten();
+            self.programs.insert(*program_id, program);
+        }
+        self.programs.get(program_id).and_then(Option::as_ref)
+    }
+
+    /// Resolves the program with the given ID with the resolver, rejecting a resolved program with another ID.
+    fn resolve(&self, program_id: &ProgramID<N>) -> Result<Option<Program<N>>> {
+        let resolver = match &self.resolver {
+            Some(resolver) => resolver,
+            None => return Ok(None),
+        };
+        match resolver.resolve(program_id)? {
+            Some(resolution) => match resolver.source(program_id, &resolution)? {
+                Some(source) => {
+                    let program = Program::from_str(&source)?;
+                    if program.id() != program_id {
+                        bail!("The program resolved for '{program_id}' is '{}'", program.id());
+                    }
+                    Ok(Some(program))
+                }
+                None => Ok(None),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/// Adds the typed JSON of each constant or public input and output of the transitions in the given block to its
+/// block JSON, under `typed_value`, next to `value`.
+///
+/// The transitions are decoded against the programs deployed in the block, and those in the given catalog;
+/// the values of a transition whose program is not available, or does not match, are left as they are.
+pub fn attach_block_typed_values<N: Network>(block: &Block<N>, catalog: &mut ProgramCatalog<N>, j_block: &mut Value) {
+    catalog.insert_deployments(block);
+
+    // Collect the values, keyed by the ID of their transition.
+    let mut values = HashMap::new();
+    for confirmed in block.transactions().iter() {
+        for transition in confirmed.transaction().transitions() {
+            if let Some(program) = catalog.get(transition.program_id()) {
+                if let Ok(typed) = typed_transition_values(program, transition) {
+                    values.insert(transition.id().to_string(), typed);
+                }
+            }
+        }
+    }
+    attach_typed_values(j_block, &values);
+}
+
+/// Adds the given typed values to the transitions found in the given value, and in its children.
+fn attach_typed_values(value: &mut Value, values: &HashMap<String, (Vec<(usize, Value)>, Vec<(usize, Value)>)>) {
+    let typed = match value.get("type").and_then(Value::as_str) {
+        Some("Transition") => value.get("id").and_then(Value::as_str).and_then(|id| values.get(id)),
+        _ => None,
+    };
+    match (typed, value) {
+        (Some((inputs, outputs)), j_transition) => {
+            for (key, typed_values) in [("inputs", inputs), ("outputs", outputs)] {
+                for (index, typed_value) in typed_values {
+                    if let Some(j_value) = j_transition[key].get_mut(*index) {
+                        j_value["typed_value"] = typed_value.clone();
+                    }
+                }
+            }
+        }
+        (None, Value::Object(object)) => object.values_mut().for_each(|val| attach_typed_values(val, values)),
+        (None, Value::Array(array)) => array.iter_mut().for_each(|val| attach_typed_values(val, values)),
+        (None, _) => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_typed_plaintext_to_json() {
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program typed.aleo;
+
+struct point:
+    x as i32;
+    y as u128;
+
+function origin:
+    input r0 as point.public;
+    output r0 as point.public;
+",
+        )
+        .unwrap();
+        let typed = |plaintext_type: &str, plaintext: &str| {
+            typed_plaintext_to_json(
+                &program,
+                &PlaintextType::from_str(plaintext_type).unwrap(),
+                &Plaintext::from_str(plaintext).unwrap(),
+            )
+        };
+
+        let j_point = typed("point", "{ x: -3i32, y: 5u128 }").unwrap();
+        assert_eq!(j_point["type"], "Struct");
+        assert_eq!(j_point["struct_name"], "point");
+        assert_eq!(j_point["members"]["x"], json!({ "type": "Literal", "literal_type": "i32", "value": -3 }));
+        assert_eq!(j_point["members"]["y"]["value"], "5");
+
+        let j_array = typed("[boolean; 2u32]", "[true, false]").unwrap();
+        assert_eq!(j_array["element_type"], "boolean");
+        assert_eq!(j_array["elements"][1]["value"], false);
+
+        assert_eq!(typed("field", "7field").unwrap()["value"], "7");
+        assert!(typed("u8", "7u16").is_err());
+        assert!(typed("point", "{ x: -3i32 }").is_err());
+        assert!(typed("[u8; 3u32]", "[1u8, 2u8]").is_err());
+    }
+
+    #[test]
+    fn test_attach_block_typed_values() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut catalog = ProgramCatalog::new(None).unwrap();
+        let mut j_block = block.to_json();
+        attach_block_typed_values(&block, &mut catalog, &mut j_block);
+
+        // The genesis block executes `credits.aleo`, whose constant and public values are all decoded.
+        let credits = Program::<CurrentNetwork>::credits().unwrap();
+        let mut num_typed = 0;
+        for transition in block.transactions().iter().flat_map(|tx| tx.transaction().transitions()) {
+            let (inputs, outputs) = typed_transition_values(&credits, transition).unwrap();
+            num_typed += inputs.len() + outputs.len();
+        }
+        assert_eq!(j_block.to_string().matches("\"typed_value\"").count(), num_typed);
+    }
+
+    #[test]
+    fn test_program_catalog_mismatched_program() {
+        let program =
+            "program token.aleo;\n\nfunction mint:\n    input r0 as u64.public;\n    output r0 as u64.public;\n";
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("token.aleo"), program).unwrap();
+        // The file resolved for `other.aleo` holds `token.aleo`.
+        std::fs::write(directory.path().join("other.aleo"), program).unwrap();
+        let resolver = ImportResolver { local: Some(directory.path().to_path_buf()), ..Default::default() };
+
+        let mut catalog = ProgramCatalog::<CurrentNetwork>::new(Some(resolver)).unwrap();
+        let token = ProgramID::from_str("token.aleo").unwrap();
+        assert!(catalog.get(&token).is_some());
+        let other = ProgramID::from_str("other.aleo").unwrap();
+        assert!(catalog.resolve(&other).unwrap_err().to_string().contains("token.aleo"));
+        assert!(catalog.get(&other).is_none());
+    }
+}
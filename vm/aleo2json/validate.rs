@@ -0,0 +1,261 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::aleo2json::{json_schema, push_token, SchemaKind};
+
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// A location in a JSON document that does not conform to the aleo2json schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// The JSON pointer (RFC 6901) to the offending value.
+    pub pointer: String,
+    /// A description of the violation.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    /// Prints the violation, i.e. `/functions/foo/name: expected a string, found a number`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.pointer.is_empty() {
+            true => write!(f, "(root): {}", self.message),
+            false => write!(f, "{}: {}", self.pointer, self.message),
+        }
+    }
+}
+
+/// Checks the given program JSON against the aleo2json program schema.
+pub fn validate_program_json(document: &Value) -> Result<(), Vec<SchemaViolation>> {
+    validate_json(SchemaKind::Program, document)
+}
+
+/// Checks the given JSON against the aleo2json schema of the given kind, returning every violation found.
+pub fn validate_json(kind: SchemaKind, document: &Value) -> Result<(), Vec<SchemaViolation>> {
+    let schema = json_schema(kind);
+    let mut validator = Validator { definitions: schema["$defs"].as_object(), violations: Vec::new() };
+    validator.validate(&mut String::new(), &schema, document);
+
+    match validator.violations.is_empty() {
+        true => Ok(()),
+        false => Err(validator.violations),
+    }
+}
+
+/// A validator for the subset of JSON Schema used by the aleo2json schemas.
+struct Validator<'a> {
+    /// The definitions that references resolve against.
+    definitions: Option<&'a Map<String, Value>>,
+    /// The violations found so far.
+    violations: Vec<SchemaViolation>,
+}
+
+impl<'a> Validator<'a> {
+    /// Checks the value located at `pointer` against the given schema.
+    fn validate(&mut self, pointer: &mut String, schema: &'a Value, value: &Value) {
+        let schema = match schema.as_object() {
+            Some(schema) => schema,
+            None => return,
+        };
+
+        if let Some(Value::String(reference)) = schema.get("$ref") {
+            let name = reference.strip_prefix("#/$defs/").unwrap_or(reference);
+            match self.definitions.and_then(|definitions| definitions.get(name)) {
+                Some(definition) => self.validate(pointer, definition, value),
+                None => self.violation(pointer, format!("unresolved schema reference '{reference}'")),
+            }
+        }
+
+        if let Some(Value::String(expected)) = schema.get("type") {
+            let matches = match expected.as_str() {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "boolean" => value.is_boolean(),
+                "null" => value.is_null(),
+                _ => true,
+            };
+            // Skip the remaining keywords, as they would only repeat the mismatch.
+            if !matches {
+                return self.violation(pointer, format!("expected {}, found {}", article(expected), kind_of(value)));
+            }
+        }
+
+        if let Some(expected) = schema.get("const") {
+            if value != expected {
+                self.violation(pointer, format!("expected {expected}, found {value}"));
+            }
+        }
+
+        if let Some(Value::Array(allowed)) = schema.get("enum") {
+            if !allowed.contains(value) {
+                self.violation(pointer, format!("{value} is not one of the allowed values"));
+            }
+        }
+
+        if let (Some(minimum), Some(number)) = (schema.get("minimum").and_then(Value::as_f64), value.as_f64()) {
+            if number < minimum {
+                self.violation(pointer, format!("{value} is less than the minimum of {minimum}"));
+            }
+        }
+
+        if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+            let num_violations = self.violations.len();
+            let mut matched = false;
+            for schema in schemas {
+                self.validate(pointer, schema, value);
+                matched |= self.violations.len() == num_violations;
+                self.violations.truncate(num_violations);
+                if matched {
+                    break;
+                }
+            }
+            if !matched {
+                self.violation(pointer, format!("{} does not match any of the allowed schemas", kind_of(value)));
+            }
+        }
+
+        if let Value::Object(object) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(key) {
+                        self.violation(pointer, format!("missing required property '{key}'"));
+                    }
+                }
+            }
+
+            for (key, val) in object {
+                let schema = match properties.and_then(|properties| properties.get(key)) {
+                    Some(schema) => schema,
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            self.violation(pointer, format!("unexpected property '{key}'"));
+                            continue;
+                        }
+                        Some(schema) => schema,
+                        None => continue,
+                    },
+                };
+                let length = push_token(pointer, key);
+                self.validate(pointer, schema, val);
+                pointer.truncate(length);
+            }
+        }
+
+        if let (Value::Array(array), Some(schema)) = (value, schema.get("items")) {
+            for (index, val) in array.iter().enumerate() {
+                let length = push_token(pointer, &index.to_string());
+                self.validate(pointer, schema, val);
+                pointer.truncate(length);
+            }
+        }
+    }
+
+    /// Records a violation at the given pointer.
+    fn violation(&mut self, pointer: &str, message: String) {
+        self.violations.push(SchemaViolation { pointer: pointer.to_string(), message });
+    }
+}
+
+/// Returns a description of the kind of the given value, i.e. `a string`.
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Returns the given JSON Schema type name with its indefinite article, i.e. `an integer`.
+fn article(type_name: &str) -> String {
+    match type_name {
+        "null" => type_name.to_string(),
+        "object" | "array" | "integer" => format!("an {type_name}"),
+        _ => format!("a {type_name}"),
+    }
+}
+
+#[cfg(all(test, feature = "aleo2json-ledger"))]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{convert_block_bytes, convert_program},
+        prelude::Testnet3,
+    };
+
+    use serde_json::json;
+
+    type CurrentNetwork = Testnet3;
+
+    const SOURCE: &str = r"
+program token.aleo;
+
+mapping balances:
+    key as address.public;
+    value as u64.public;
+
+function transfer:
+    input r0 as address.public;
+    input r1 as u64.public;
+    async transfer r0 r1 into r2;
+    output r2 as token.aleo/transfer.future;
+
+finalize transfer:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use balances[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into balances[r0];";
+
+    #[test]
+    fn test_validate_program_json() {
+        let program = convert_program::<CurrentNetwork>(SOURCE).unwrap();
+        assert_eq!(validate_program_json(&program), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_genesis_block() {
+        let block = convert_block_bytes::<CurrentNetwork>(CurrentNetwork::genesis_bytes()).unwrap();
+        assert_eq!(validate_json(SchemaKind::Block, &block), Ok(()));
+    }
+
+    #[test]
+    fn test_violations() {
+        let mut program = convert_program::<CurrentNetwork>(SOURCE).unwrap();
+        program["functions"]["transfer"]["name"] = json!(7);
+        program["functions"]["transfer"]["instructions"][0].as_object_mut().unwrap().remove("vtype");
+        program["format_version"] = json!(0);
+
+        let violations = validate_program_json(&program).unwrap_err();
+        let pointers: Vec<&str> = violations.iter().map(|violation| violation.pointer.as_str()).collect();
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert!(pointers.contains(&"/format_version"));
+        assert!(pointers.contains(&"/functions/transfer/name"));
+        assert!(pointers.contains(&"/functions/transfer/instructions/0"));
+    }
+
+    #[test]
+    fn test_violation_display() {
+        let violations = validate_program_json(&json!([])).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].to_string(), "(root): expected an object, found an array");
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{
+        network::Network,
+        program::{Literal, Plaintext},
+    },
+    ledger::block::{Block, Input, Transaction, Transition},
+};
+
+use serde_json::{json, Map, Value};
+
+/// The category of a `credits.aleo` function in a value-flow summary.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum FlowCategory {
+    Fee,
+    Transfer,
+    Staking,
+}
+
+impl FlowCategory {
+    /// Returns the key of the category in the summary.
+    const fn key(&self) -> &'static str {
+        match self {
+            Self::Fee => "fees",
+            Self::Transfer => "transfers",
+            Self::Staking => "staking",
+        }
+    }
+}
+
+/// The `credits.aleo` functions moving or staking credits, each with its category and the name of each input.
+const CREDITS_FUNCTIONS: &[(&str, FlowCategory, &[&str])] = &[
+    ("bond_public", FlowCategory::Staking, &["validator", "amount"]),
+    ("unbond_public", FlowCategory::Staking, &["amount"]),
+    ("unbond_delegator_as_validator", FlowCategory::Staking, &["delegator"]),
+    ("claim_unbond_public", FlowCategory::Staking, &[]),
+    ("set_validator_state", FlowCategory::Staking, &["is_open"]),
+    ("transfer_public", FlowCategory::Transfer, &["recipient", "amount"]),
+    ("transfer_private", FlowCategory::Transfer, &["record", "recipient", "amount"]),
+    ("transfer_private_to_public", FlowCategory::Transfer, &["record", "recipient", "amount"]),
+    ("transfer_public_to_private", FlowCategory::Transfer, &["recipient", "amount"]),
+    ("fee_private", FlowCategory::Fee, &["record", "base_fee", "priority_fee", "id"]),
+    ("fee_public", FlowCategory::Fee, &["base_fee", "priority_fee", "id"]),
+];
+
+/// Returns the summary of the given `credits.aleo` transition, with its decoded public inputs by name, and its
+/// category, or `None` if the transition does not move or stake credits.
+///
+/// The amounts are in microcredits. The private inputs and records are not decoded, and are omitted.
+pub fn credits_transition_to_json<N: Network>(transition: &Transition<N>) -> Option<(&'static str, Value)> {
+    if transition.program_id().to_string() != "credits.aleo" {
+        return None;
+    }
+    let function_name = transition.function_name().to_string();
+    let (_, category, names) = CREDITS_FUNCTIONS.iter().find(|(name, ..)| *name == function_name)?;
+
+    let mut j_flow = Map::new();
+    j_flow.insert("transition_id".to_string(), json!(transition.id().to_string()));
+    j_flow.insert("function".to_string(), json!(function_name));
+    for (name, input) in names.iter().zip(transition.inputs()) {
+        if let Input::Public(_, Some(plaintext)) = input {
+            j_flow.insert(name.to_string(), plaintext_to_json(plaintext));
+        }
+    }
+    Some((category.key(), Value::Object(j_flow)))
+}
+
+/// Returns the value-flow summary of the given transaction: its fees, public transfers, and staking operations,
+/// decoded from the public inputs of its `credits.aleo` transitions, in order of the transitions.
+pub fn value_flow_to_json<N: Network>(transaction: &Transaction<N>) -> Value {
+    let mut j_categories = Map::new();
+    for (_, category, _) in CREDITS_FUNCTIONS {
+        j_categories.entry(category.key()).or_insert_with(|| json!([]));
+    }
+    for transition in transaction.transitions() {
+        if let Some((key, j_flow)) = credits_transition_to_json(transition) {
+            if let Some(Value::Array(j_flows)) = j_categories.get_mut(key) {
+                j_flows.push(j_flow);
+            }
+        }
+    }
+
+    let mut j_value_flow = Map::new();
+    j_value_flow.insert("type".to_string(), json!("ValueFlow"));
+    j_value_flow.extend(j_categories);
+    Value::Object(j_value_flow)
+}
+
+/// Adds the value-flow summary of each confirmed transaction in the given block to the given block JSON,
+/// under `value_flow`.
+pub fn attach_block_value_flows<N: Network>(block: &Block<N>, j_block: &mut Value) {
+    for (index, confirmed) in block.transactions().iter().enumerate() {
+        j_block["transactions"][index]["value_flow"] = value_flow_to_json(confirmed.transaction());
+    }
+}
+
+/// Returns the given plaintext input as JSON: amounts as numbers, booleans as booleans, and others as strings.
+fn plaintext_to_json<N: Network>(plaintext: &Plaintext<N>) -> Value {
+    match plaintext {
+        Plaintext::Literal(Literal::U64(amount), _) => json!(**amount),
+        Plaintext::Literal(Literal::Boolean(boolean), _) => json!(**boolean),
+        plaintext => json!(plaintext.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_plaintext_to_json() {
+        let plaintext = |plaintext: &str| plaintext_to_json(&Plaintext::<CurrentNetwork>::from_str(plaintext).unwrap());
+        assert_eq!(plaintext("5000u64"), json!(5000));
+        assert_eq!(plaintext("true"), json!(true));
+        assert_eq!(plaintext("1field"), json!("1field"));
+    }
+
+    #[test]
+    fn test_attach_block_value_flows() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut j_block = block.to_json();
+        attach_block_value_flows(&block, &mut j_block);
+
+        for (index, confirmed) in block.transactions().iter().enumerate() {
+            let j_value_flow = &j_block["transactions"][index]["value_flow"];
+            assert_eq!(j_value_flow["type"], "ValueFlow");
+            // Every transition moving or staking credits is listed once, under its category.
+            for transition in confirmed.transaction().transitions() {
+                if let Some((key, j_flow)) = credits_transition_to_json(transition) {
+                    assert_eq!(j_flow["function"], transition.function_name().to_string());
+                    assert!(j_value_flow[key].as_array().unwrap().contains(&j_flow));
+                }
+            }
+        }
+    }
+}
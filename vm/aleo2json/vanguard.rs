@@ -0,0 +1,326 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::JsonOptions,
+    console::{
+        network::{
+            prelude::{Serialize, Serializer, ToBytes},
+            Network,
+        },
+        program::{Identifier, Literal, Locator, ProgramID, Register, ValueType},
+    },
+};
+
+#[cfg(feature = "aleo2json-synthesizer")]
+use crate::aleo2json::{external_calls, program_record_flows_to_json, program_risks, ProgramStats, VisibilityProfile};
+#[cfg(feature = "aleo2json-ledger")]
+use crate::aleo2json::{header_economics_to_json, Verification, VerifyingKeyMetadata};
+#[cfg(feature = "aleo2json-ledger")]
+use crate::ledger::block::{
+    Block,
+    ConfirmedTransaction,
+    Deployment,
+    Execution,
+    Fee,
+    Header,
+    Metadata,
+    Ratifications,
+    Ratify,
+    Transaction,
+    Transactions,
+    Transition,
+};
+#[cfg(feature = "aleo2json-synthesizer")]
+use crate::synthesizer::program::{
+    Closure,
+    Command,
+    CommandTrait,
+    Finalize,
+    Function,
+    Instruction,
+    InstructionTrait,
+    Program,
+};
+
+use serde_json::{json, Value};
+
+/// A type with a Vanguard JSON representation, i.e. the tagged `{ type, vtype, value }` structure.
+pub trait ToJson {
+    /// Returns the Vanguard JSON representation.
+    fn to_json(&self) -> Value;
+
+    /// Returns the little-endian byte encoding, if the type has one.
+    fn to_json_bytes(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Returns the analysis sections, if the type has any.
+    fn to_json_analysis(&self) -> Option<Value> {
+        None
+    }
+
+    /// Returns the ID that prefixes the node IDs of the document (i.e. the program ID), if the type has one.
+    fn to_json_id(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the Vanguard JSON representation, configured by the given options.
+    fn to_json_with(&self, options: &JsonOptions) -> Value {
+        let mut document = self.to_json();
+        options.extend(self, &mut document);
+        options.finish(document)
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> Value {
+        (**self).to_json()
+    }
+
+    fn to_json_bytes(&self) -> Option<Vec<u8>> {
+        (**self).to_json_bytes()
+    }
+
+    fn to_json_analysis(&self) -> Option<Value> {
+        (**self).to_json_analysis()
+    }
+
+    fn to_json_id(&self) -> Option<String> {
+        (**self).to_json_id()
+    }
+}
+
+/// Implements `ToJson` by delegating to the inherent `to_json()` helper and the byte encoding of each type.
+macro_rules! impl_to_json {
+    ($($type:ident),+ $(,)?) => {
+        $(
+            impl<N: Network> ToJson for $type<N> {
+                fn to_json(&self) -> Value {
+                    $type::<N>::to_json(self)
+                }
+
+                fn to_json_bytes(&self) -> Option<Vec<u8>> {
+                    self.to_bytes_le().ok()
+                }
+            }
+        )+
+    };
+}
+
+impl_to_json!(Identifier, Literal, Locator, ProgramID, Register, ValueType);
+#[cfg(feature = "aleo2json-synthesizer")]
+impl_to_json!(Function, Closure, Finalize);
+#[cfg(feature = "aleo2json-ledger")]
+impl_to_json!(
+    Header,
+    Metadata,
+    Ratifications,
+    Ratify,
+    Transactions,
+    ConfirmedTransaction,
+    Transaction,
+    Execution,
+    Fee,
+    Transition
+);
+
+#[cfg(feature = "aleo2json-synthesizer")]
+impl<N: Network> ToJson for Program<N> {
+    fn to_json(&self) -> Value {
+        Program::<N>::to_json(self)
+    }
+
+    fn to_json_bytes(&self) -> Option<Vec<u8>> {
+        self.to_bytes_le().ok()
+    }
+
+    /// Returns the program statistics, the records consumed and produced by each function, the functions of other
+    /// programs it calls, the instructions susceptible to wrap or truncation concerns, and its visibility profile.
+    fn to_json_analysis(&self) -> Option<Value> {
+        let j_calls = external_calls(self).iter().map(|call| call.to_json()).collect::<Vec<_>>();
+        let j_risks = program_risks(self).iter().map(|risk| risk.to_json()).collect::<Vec<_>>();
+        Some(json!({
+            "stats": ProgramStats::new(self).to_json(),
+            "record_flow": program_record_flows_to_json(self),
+            "external_calls": j_calls,
+            "risks": j_risks,
+            "visibility": VisibilityProfile::new(self).to_json(),
+        }))
+    }
+
+    /// Returns the program ID.
+    fn to_json_id(&self) -> Option<String> {
+        Some(self.id().to_string())
+    }
+}
+
+#[cfg(feature = "aleo2json-ledger")]
+impl<N: Network> ToJson for Block<N> {
+    fn to_json(&self) -> Value {
+        Block::<N>::to_json(self)
+    }
+
+    fn to_json_bytes(&self) -> Option<Vec<u8>> {
+        self.to_bytes_le().ok()
+    }
+
+    /// Returns the recomputed hashes and IDs, and the economics of the block.
+    fn to_json_analysis(&self) -> Option<Value> {
+        Some(json!({
+            "verification": Verification::of_block(self).to_json(),
+            "economics": header_economics_to_json(self),
+        }))
+    }
+
+    /// Returns the block hash.
+    fn to_json_id(&self) -> Option<String> {
+        Some(self.hash().to_string())
+    }
+}
+
+#[cfg(feature = "aleo2json-ledger")]
+impl<N: Network> ToJson for Deployment<N> {
+    fn to_json(&self) -> Value {
+        Deployment::<N>::to_json(self)
+    }
+
+    fn to_json_bytes(&self) -> Option<Vec<u8>> {
+        self.to_bytes_le().ok()
+    }
+
+    /// Returns the metadata of each verifying key and its certificate, by function name.
+    fn to_json_analysis(&self) -> Option<Value> {
+        let mut j_metadata = serde_json::Map::new();
+        for (function_name, (verifying_key, certificate)) in self.verifying_keys() {
+            let metadata = VerifyingKeyMetadata::new(verifying_key, certificate).ok()?;
+            j_metadata.insert(function_name.to_string(), metadata.to_json());
+        }
+        Some(json!({ "verifying_keys": j_metadata }))
+    }
+}
+
+#[cfg(feature = "aleo2json-synthesizer")]
+impl<N: Network> ToJson for Instruction<N> {
+    fn to_json(&self) -> Value {
+        InstructionTrait::to_json(self)
+    }
+
+    fn to_json_bytes(&self) -> Option<Vec<u8>> {
+        self.to_bytes_le().ok()
+    }
+}
+
+#[cfg(feature = "aleo2json-synthesizer")]
+impl<N: Network> ToJson for Command<N> {
+    fn to_json(&self) -> Value {
+        CommandTrait::to_json(self)
+    }
+
+    fn to_json_bytes(&self) -> Option<Vec<u8>> {
+        self.to_bytes_le().ok()
+    }
+}
+
+/// A wrapper that unifies the serde output of a value with its Vanguard JSON representation.
+///
+/// Human-readable serializers receive the `to_json()` structure, while binary serializers
+/// receive the value's own (byte) serialization, so the wrapper can be embedded anywhere
+/// the wrapped value could be.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Vanguard<T>(pub T);
+
+impl<T: ToJson + Serialize> Serialize for Vanguard<T> {
+    /// Serializes the value to its Vanguard JSON representation or its bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_vanguard(&self.0, serializer)
+    }
+}
+
+/// Serializes the given value through its Vanguard JSON representation, if the serializer is human-readable.
+///
+/// This is intended for use as `#[serde(serialize_with = "serialize_vanguard")]` on fields of snarkVM types.
+pub fn serialize_vanguard<T: ToJson + Serialize, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    match serializer.is_human_readable() {
+        true => value.to_json().serialize(serializer),
+        false => value.serialize(serializer),
+    }
+}
+
+#[cfg(all(test, feature = "aleo2json-ledger"))]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::parse_program,
+        console::network::prelude::{FromBytes, ToBytes},
+        prelude::Testnet3,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_vanguard_program() {
+        let program = parse_program::<CurrentNetwork>(
+            "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;",
+        )
+        .unwrap();
+
+        // The human-readable serialization matches the Vanguard helper.
+        assert_eq!(serde_json::to_value(Vanguard(&program)).unwrap(), program.to_json());
+
+        // The binary serialization matches the program's own serialization.
+        let expected = bincode::serialize(&program).unwrap();
+        assert_eq!(bincode::serialize(&Vanguard(&program)).unwrap(), expected);
+        assert_eq!(&expected[8..], &program.to_bytes_le().unwrap()[..]);
+    }
+
+    #[test]
+    fn test_to_json_with() {
+        let program = parse_program::<CurrentNetwork>(
+            "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;",
+        )
+        .unwrap();
+
+        // The default options emit the Vanguard helper output.
+        assert_eq!(program.to_json_with(&JsonOptions::default()), program.to_json());
+
+        let options = JsonOptions { include_bytes: true, include_analysis: true, ..Default::default() };
+        let document = program.to_json_with(&options);
+        let bytes: String = program.to_bytes_le().unwrap().iter().map(|byte| format!("{byte:02x}")).collect();
+        assert_eq!(document["bytes"], bytes);
+        assert_eq!(document["analysis"]["stats"], ProgramStats::new(&program).to_json());
+
+        let options = JsonOptions { include_node_ids: true, ..Default::default() };
+        let document = program.to_json_with(&options);
+        assert_eq!(document["functions"]["foo"]["node_id"], "token.aleo#/functions/foo");
+    }
+
+    #[test]
+    fn test_to_json_with_visibility() {
+        let program = parse_program::<CurrentNetwork>(
+            "program token.aleo; function foo: input r0 as u8.public; output r0 as u8.public;",
+        )
+        .unwrap();
+
+        // The analysis counts the public input of the function.
+        let document = program.to_json_with(&JsonOptions { include_analysis: true, ..Default::default() });
+        assert_eq!(document["analysis"]["visibility"]["inputs"]["public"], 1);
+    }
+
+    #[test]
+    fn test_vanguard_genesis_block() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        assert_eq!(serde_json::to_value(Vanguard(&block)).unwrap(), block.to_json());
+    }
+}
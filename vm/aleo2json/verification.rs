@@ -0,0 +1,183 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::network::{prelude::ToBits, Network},
+    ledger::block::{Block, Transaction, Transition},
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::fmt::Display;
+
+/// A recomputed hash or ID, compared against the one found in the converted object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationCheck {
+    /// The name of the check, i.e. `block_hash`.
+    pub name: &'static str,
+    /// The value found in the object.
+    pub found: String,
+    /// The recomputed value, or the error raised while recomputing it.
+    pub computed: Result<String, String>,
+}
+
+impl VerificationCheck {
+    /// Initializes a check from the found value and the result of recomputing it.
+    pub(crate) fn new<F: Display, T: Display>(name: &'static str, found: F, computed: Result<T>) -> Self {
+        Self {
+            name,
+            found: format!("{}", found),
+            computed: computed.map(|val| format!("{}", val)).map_err(|error| format!("{}", error)),
+        }
+    }
+
+    /// Returns `true` if the recomputed value matches the found value.
+    pub fn passed(&self) -> bool {
+        self.computed.as_ref() == Ok(&self.found)
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let (j_computed, j_error) = match &self.computed {
+            Ok(computed) => (json!(computed), Value::Null),
+            Err(error) => (Value::Null, json!(error)),
+        };
+
+        json!({
+            "type": "VerificationCheck",
+            "name": self.name,
+            "found": self.found,
+            "computed": j_computed,
+            "error": j_error,
+            "passed": self.passed(),
+        })
+    }
+}
+
+/// The checks recomputing the block hash, the block roots, and the ID of every transaction and transition in a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Verification {
+    checks: Vec<VerificationCheck>,
+}
+
+impl Verification {
+    /// Recomputes the hashes and IDs in the given block.
+    pub fn of_block<N: Network>(block: &Block<N>) -> Self {
+        let mut checks = Vec::new();
+
+        let block_hash = block.header().to_root().and_then(|header_root| {
+            let preimage = [block.previous_hash().to_bits_le(), header_root.to_bits_le()].concat();
+            Ok(N::BlockHash::from(N::hash_bhp1024(&preimage)?))
+        });
+        checks.push(VerificationCheck::new("block_hash", block.hash(), block_hash));
+        checks.push(VerificationCheck::new(
+            "transactions_root",
+            block.transactions_root(),
+            block.transactions().to_transactions_root(),
+        ));
+        checks.push(VerificationCheck::new(
+            "ratifications_root",
+            block.ratifications_root(),
+            block.ratifications().to_ratifications_root(),
+        ));
+
+        for confirmed in block.transactions().iter() {
+            checks.extend(Self::of_transaction(confirmed.transaction()).checks);
+        }
+
+        Self { checks }
+    }
+
+    /// Recomputes the ID of the given transaction, and of each of its transitions.
+    pub fn of_transaction<N: Network>(transaction: &Transaction<N>) -> Self {
+        let mut checks = Vec::new();
+
+        let transaction_id = transaction.to_root().map(N::TransactionID::from);
+        checks.push(VerificationCheck::new("transaction_id", transaction.id(), transaction_id));
+
+        for transition in transaction.transitions() {
+            checks.push(Self::check_transition_id(transition));
+        }
+
+        Self { checks }
+    }
+
+    /// Recomputes the ID of the given transition from its inputs, outputs, and commitment.
+    fn check_transition_id<N: Network>(transition: &Transition<N>) -> VerificationCheck {
+        let transition_id = transition
+            .to_root()
+            .and_then(|root| Ok(N::TransitionID::from(N::hash_bhp512(&(root, *transition.tcm()).to_bits_le())?)));
+        VerificationCheck::new("transition_id", transition.id(), transition_id)
+    }
+
+    /// Returns the checks.
+    pub fn checks(&self) -> &[VerificationCheck] {
+        &self.checks
+    }
+
+    /// Returns `true` if every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(VerificationCheck::passed)
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_checks = Vec::new();
+        for val in &self.checks {
+            j_checks.push(val.to_json());
+        }
+
+        json!({
+            "type": "Verification",
+            "passed": self.passed(),
+            "checks": j_checks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{console::network::prelude::FromBytes, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_verify_genesis_block() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let verification = Verification::of_block(&block);
+        assert!(verification.passed());
+
+        // The block hash, the transactions root, and the ratifications root are checked, along with every ID.
+        let num_transitions: usize = block.transactions().iter().map(|tx| tx.transaction().transitions().count()).sum();
+        assert_eq!(verification.checks().len(), 3 + block.transactions().len() + num_transitions);
+
+        let j_verification = verification.to_json();
+        assert_eq!(j_verification["passed"], true);
+        assert_eq!(j_verification["checks"][0]["name"], "block_hash");
+        assert_eq!(j_verification["checks"][0]["computed"], format!("{}", block.hash()));
+    }
+
+    #[test]
+    fn test_verification_check_mismatch() {
+        let check = VerificationCheck::new("block_hash", "ab1", Ok("ab2"));
+        assert!(!check.passed());
+        assert_eq!(check.to_json()["passed"], false);
+
+        let check = VerificationCheck::new::<_, &str>("block_hash", "ab1", Err(anyhow::anyhow!("Failed")));
+        assert!(!check.passed());
+        assert_eq!(check.to_json()["error"], "Failed");
+        assert_eq!(check.to_json()["computed"], Value::Null);
+    }
+}
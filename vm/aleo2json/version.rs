@@ -0,0 +1,238 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{aleo2json::checksum, console::network::Network};
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The version of the JSON format emitted by aleo2json.
+///
+/// This must be bumped whenever the shape of an emitted document changes, i.e. when a field is added,
+/// removed, renamed, or changes type. The shape is pinned by `test_format_shape`, which fails until the
+/// recorded shape and this version are updated together.
+pub const FORMAT_VERSION: u32 = 6;
+
+/// The version of the snarkVM grammar the documents are produced from.
+pub const SNARKVM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The version of aleo2json the documents are produced by, which is released with snarkVM.
+pub const ALEO2JSON_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The provenance of a top-level document, recorded under its `meta` key, so it can be traced back to its input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// The ID of the network of the input, if it is known.
+    pub network: Option<u16>,
+    /// The time of the conversion, in seconds since the Unix epoch, if it is recorded.
+    pub timestamp: Option<u64>,
+    /// The checksum of the input, if it is known.
+    pub input_checksum: Option<String>,
+}
+
+impl Provenance {
+    /// Returns the provenance of a document converted from the given input, on the given network.
+    pub fn of<N: Network>(input: &[u8]) -> Result<Self> {
+        Ok(Self { network: Some(N::ID), timestamp: None, input_checksum: Some(checksum(input)?) })
+    }
+
+    /// Records the current time as the time of the conversion, if enabled.
+    ///
+    /// The timestamp is opt-in, as it makes the output of two identical conversions differ.
+    pub fn with_timestamp(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.timestamp = Some(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs());
+        }
+        self
+    }
+
+    /// Returns the `meta` block of the provenance, with the aleo2json and snarkVM versions.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "aleo2json_version": ALEO2JSON_VERSION,
+            "snarkvm_version": SNARKVM_VERSION,
+            "network": self.network,
+            "timestamp": self.timestamp,
+            "input_checksum": self.input_checksum,
+        })
+    }
+}
+
+/// Stamps the given top-level document with the format and snarkVM versions, and with a `meta` block
+/// whose network and input are unknown.
+///
+/// The version fields are placed first; documents that are not JSON objects are returned unchanged.
+pub fn with_version(document: Value) -> Value {
+    with_provenance(document, &Provenance::default())
+}
+
+/// Stamps the given top-level document with the format and snarkVM versions, and with the given provenance,
+/// under `meta`.
+///
+/// The version fields and the `meta` block are placed first; documents that are not JSON objects are returned
+/// unchanged. Like the version fields, the `meta` block is part of the envelope of a document, not of its shape.
+pub fn with_provenance(document: Value, provenance: &Provenance) -> Value {
+    match document {
+        Value::Object(fields) => {
+            let mut j_document = Map::new();
+            j_document.insert("format_version".to_string(), Value::from(FORMAT_VERSION));
+            j_document.insert("snarkvm_version".to_string(), Value::from(SNARKVM_VERSION));
+            j_document.insert("meta".to_string(), provenance.to_json());
+            j_document.extend(fields);
+            Value::Object(j_document)
+        }
+        document => document,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::{json_schema, SchemaKind},
+        console::network::Testnet3,
+    };
+
+    use serde_json::json;
+
+    /// The properties of every schema definition, as of `FORMAT_VERSION`.
+    ///
+    /// If this test fails, the output shape has changed: update this table *and* bump `FORMAT_VERSION`.
+    const FORMAT_SHAPE: (u32, &[(&str, &[&str])]) = (6, &[
+        ("Node", &["type"]),
+        ("ProgramID", &["name", "network", "type"]),
+        ("Register", &["locator", "type", "value", "vtype"]),
+        ("ValueType", &["type", "value", "vtype"]),
+        ("Parameter", &["str", "type"]),
+        ("Import", &["program_id", "type"]),
+        ("Mapping", &["key", "name", "type", "value"]),
+        ("Instruction", &["index", "str", "type", "value", "vtype"]),
+        ("Command", &["index", "str", "type", "value", "vtype"]),
+        ("Unsupported", &["construct", "index", "str", "type", "vtype"]),
+        ("Closure", &["inputs", "instructions", "name", "outputs", "type"]),
+        ("Finalize", &["commands", "inputs", "labels", "name", "num_writes", "positions", "type"]),
+        ("Function", &["finalize_logic", "inputs", "instructions", "name", "outputs", "type"]),
+        ("Program", &[
+            "closures",
+            "functions",
+            "id",
+            "identifiers",
+            "imports",
+            "mappings",
+            "records",
+            "structs",
+            "type",
+        ]),
+        ("TransitionInput", &["id", "type", "value", "vtype"]),
+        ("TransitionOutput", &["id", "type", "value", "vtype"]),
+        ("Transition", &["function_name", "id", "inputs", "outputs", "program_id", "tcm", "tpk", "type"]),
+        ("Fee", &["global_state_root", "proof", "transition", "type"]),
+        ("Execution", &["global_state_root", "proof", "transitions", "type"]),
+        ("Deployment", &["edition", "program", "type", "verifying_keys"]),
+        ("Transaction", &["id", "type", "value", "vtype"]),
+        ("Rejected", &["type", "value", "vtype"]),
+        ("ConfirmedTransaction", &["finalize", "index", "rejected", "transaction", "type", "vtype"]),
+        ("Ratify", &["type", "value", "vtype"]),
+        ("Metadata", &[
+            "coinbase_target",
+            "cumulative_proof_target",
+            "cumulative_weight",
+            "height",
+            "last_coinbase_target",
+            "last_coinbase_timestamp",
+            "network",
+            "proof_target",
+            "round",
+            "timestamp",
+            "type",
+        ]),
+        ("Header", &[
+            "finalize_root",
+            "metadata",
+            "previous_state_root",
+            "ratifications_root",
+            "solutions_root",
+            "subdag_root",
+            "transactions_root",
+            "type",
+        ]),
+        ("Block", &[
+            "aborted_transaction_ids",
+            "authority",
+            "block_hash",
+            "header",
+            "previous_hash",
+            "ratifications",
+            "solutions",
+            "transactions",
+            "type",
+        ]),
+    ]);
+
+    #[test]
+    fn test_format_shape() {
+        let (version, shape) = FORMAT_SHAPE;
+        assert_eq!(version, FORMAT_VERSION, "The recorded shape belongs to a different format version");
+
+        let schema = json_schema(SchemaKind::Program);
+        let definitions = schema["$defs"].as_object().unwrap();
+        assert_eq!(definitions.len(), shape.len(), "A definition was added or removed; bump FORMAT_VERSION");
+
+        for (name, expected) in shape {
+            let properties = definitions[*name]["properties"].as_object().unwrap();
+            let mut candidate: Vec<&str> = properties.keys().map(String::as_str).collect();
+            candidate.sort_unstable();
+            assert_eq!(&candidate, expected, "The shape of '{name}' changed; bump FORMAT_VERSION");
+        }
+    }
+
+    #[test]
+    fn test_with_version() {
+        let document = with_version(json!({ "type": "Block" }));
+        assert_eq!(
+            document,
+            json!({
+                "format_version": FORMAT_VERSION,
+                "snarkvm_version": SNARKVM_VERSION,
+                "meta": {
+                    "aleo2json_version": ALEO2JSON_VERSION,
+                    "snarkvm_version": SNARKVM_VERSION,
+                    "network": null,
+                    "timestamp": null,
+                    "input_checksum": null,
+                },
+                "type": "Block",
+            })
+        );
+        // The version fields come first.
+        assert_eq!(document.as_object().unwrap().keys().next().unwrap(), "format_version");
+
+        assert_eq!(with_version(json!([1, 2])), json!([1, 2]));
+    }
+
+    #[test]
+    fn test_with_provenance() {
+        let provenance = Provenance::of::<Testnet3>(b"program hello.aleo;").unwrap();
+        let document = with_provenance(json!({ "type": "Program" }), &provenance);
+        assert_eq!(document["meta"]["network"], Testnet3::ID);
+        assert_eq!(document["meta"]["input_checksum"], checksum(b"program hello.aleo;").unwrap());
+        assert_eq!(document["meta"]["timestamp"], Value::Null);
+        assert_eq!(document.as_object().unwrap().keys().nth(2).unwrap(), "meta");
+
+        // The timestamp is only recorded when enabled.
+        assert_eq!(provenance.clone().with_timestamp(false), provenance);
+        assert!(provenance.with_timestamp(true).to_json()["timestamp"].as_u64().unwrap() > 0);
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    console::{
+        network::Network,
+        program::{EntryType, ValueType},
+    },
+    synthesizer::Program,
+};
+
+use serde_json::{json, Map, Value};
+
+/// The number of values of each visibility, among a set of inputs, outputs, or record entries.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VisibilityCounts {
+    /// The number of constant values.
+    pub constant: usize,
+    /// The number of public values.
+    pub public: usize,
+    /// The number of private values.
+    pub private: usize,
+    /// The number of records, which are encrypted to their owner.
+    pub record: usize,
+    /// The number of futures.
+    pub future: usize,
+}
+
+impl VisibilityCounts {
+    /// Counts the given input or output type.
+    fn add_value_type<N: Network>(&mut self, value_type: &ValueType<N>) {
+        match value_type {
+            ValueType::Constant(_) => self.constant += 1,
+            ValueType::Public(_) => self.public += 1,
+            ValueType::Private(_) => self.private += 1,
+            ValueType::Record(_) | ValueType::ExternalRecord(_) => self.record += 1,
+            ValueType::Future(_) => self.future += 1,
+        }
+    }
+
+    /// Counts the given record entry type.
+    fn add_entry_type<N: Network>(&mut self, entry_type: &EntryType<N>) {
+        match entry_type {
+            EntryType::Constant(_) => self.constant += 1,
+            EntryType::Public(_) => self.public += 1,
+            EntryType::Private(_) => self.private += 1,
+        }
+    }
+
+    /// Adds the given counts to these counts.
+    fn add(&mut self, other: &Self) {
+        self.constant += other.constant;
+        self.public += other.public;
+        self.private += other.private;
+        self.record += other.record;
+        self.future += other.future;
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        json!({
+            "constant": self.constant,
+            "public": self.public,
+            "private": self.private,
+            "record": self.record,
+            "future": self.future,
+        })
+    }
+}
+
+/// The visibility of the inputs and outputs of a function.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FunctionVisibility {
+    /// The name of the function.
+    pub function: String,
+    /// The visibility counts of the inputs.
+    pub inputs: VisibilityCounts,
+    /// The visibility counts of the outputs.
+    pub outputs: VisibilityCounts,
+}
+
+/// The visibility profile of a program: how many of its inputs, outputs, and record entries are public or private.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VisibilityProfile {
+    /// The visibility of each function, in declaration order.
+    pub functions: Vec<FunctionVisibility>,
+    /// The visibility counts of the inputs, across all functions.
+    pub inputs: VisibilityCounts,
+    /// The visibility counts of the outputs, across all functions.
+    pub outputs: VisibilityCounts,
+    /// The visibility counts of the entries of the records declared by the program, including their owners.
+    pub record_entries: VisibilityCounts,
+}
+
+impl VisibilityProfile {
+    /// Computes the visibility profile of the given program.
+    pub fn new<N: Network>(program: &Program<N>) -> Self {
+        let mut profile = Self::default();
+        for (name, function) in program.functions() {
+            let mut visibility = FunctionVisibility { function: name.to_string(), ..Default::default() };
+            function.inputs().iter().for_each(|input| visibility.inputs.add_value_type(input.value_type()));
+            function.outputs().iter().for_each(|output| visibility.outputs.add_value_type(output.value_type()));
+            profile.inputs.add(&visibility.inputs);
+            profile.outputs.add(&visibility.outputs);
+            profile.functions.push(visibility);
+        }
+        for record in program.records().values() {
+            match record.owner().is_public() {
+                true => profile.record_entries.public += 1,
+                false => profile.record_entries.private += 1,
+            }
+            record.entries().values().for_each(|entry_type| profile.record_entries.add_entry_type(entry_type));
+        }
+        profile
+    }
+
+    /// ** Vanguard JSON serialization helper ** ///
+    pub fn to_json(&self) -> Value {
+        let mut j_functions = Map::new();
+        for visibility in &self.functions {
+            j_functions.insert(
+                visibility.function.clone(),
+                json!({ "inputs": visibility.inputs.to_json(), "outputs": visibility.outputs.to_json() }),
+            );
+        }
+        json!({
+            "inputs": self.inputs.to_json(),
+            "outputs": self.outputs.to_json(),
+            "record_entries": self.record_entries.to_json(),
+            "functions": j_functions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aleo2json::parse_program, prelude::Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_visibility_profile() {
+        let program = parse_program::<CurrentNetwork>(
+            r"
+import credits.aleo;
+
+program swap.aleo;
+
+record ticket:
+    owner as address.private;
+    amount as u64.private;
+    seat as u32.public;
+
+function buy:
+    input r0 as credits.aleo/credits.record;
+    input r1 as u64.public;
+    input r2 as u32.constant;
+    cast r0.owner r1 r2 into r3 as ticket.record;
+    output r3 as ticket.record;
+    output r1 as u64.private;
+
+function quote:
+    input r0 as u64.public;
+    output r0 as u64.public;",
+        )
+        .unwrap();
+
+        let profile = VisibilityProfile::new(&program);
+        assert_eq!(profile.inputs, VisibilityCounts { constant: 1, public: 2, record: 1, ..Default::default() });
+        assert_eq!(profile.outputs, VisibilityCounts { public: 1, private: 1, record: 1, ..Default::default() });
+        assert_eq!(profile.record_entries, VisibilityCounts { public: 1, private: 2, ..Default::default() });
+        assert_eq!(profile.functions[1].inputs, VisibilityCounts { public: 1, ..Default::default() });
+
+        let j_profile = profile.to_json();
+        assert_eq!(j_profile["functions"]["buy"]["outputs"]["private"], 1);
+        assert_eq!(j_profile["record_entries"]["private"], 2);
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    aleo2json::ProgramCatalog,
+    console::{
+        network::Network,
+        program::{Identifier, ProgramID},
+    },
+    ledger::block::{Block, Transition},
+};
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Returns the node ID of the given function in the document of the given program, as assigned by `assign_node_ids`.
+pub fn function_node_id<N: Network>(program_id: &ProgramID<N>, function_name: &Identifier<N>) -> String {
+    format!("{program_id}#/functions/{function_name}")
+}
+
+/// The node IDs that a transition refers to: its function, and the declaration of each of its inputs and outputs.
+struct TransitionRefs {
+    /// The node ID of the function.
+    function: String,
+    /// The number of inputs of the function.
+    num_inputs: usize,
+    /// The number of outputs of the function.
+    num_outputs: usize,
+}
+
+/// Returns the node IDs that the given transition refers to, if the program of the transition is in the given catalog
+/// and declares its function, with as many inputs and outputs as the transition.
+fn transition_refs<N: Network>(catalog: &mut ProgramCatalog<N>, transition: &Transition<N>) -> Option<TransitionRefs> {
+    let program = catalog.get(transition.program_id())?;
+    let function = program.get_function_ref(transition.function_name()).ok()?;
+    let is_declared =
+        function.inputs().len() == transition.inputs().len() && function.outputs().len() == transition.outputs().len();
+    match is_declared {
+        true => Some(TransitionRefs {
+            function: function_node_id(transition.program_id(), transition.function_name()),
+            num_inputs: transition.inputs().len(),
+            num_outputs: transition.outputs().len(),
+        }),
+        false => None,
+    }
+}
+
+/// Adds, to each transition in the given block JSON, the node ID of its function in the converted program document,
+/// under `function_node_id`, and the node ID of the declaration of each of its inputs and outputs, under
+/// `declaration_node_id`, so the transitions can be joined with programs converted with node IDs.
+///
+/// The programs are those deployed in the block, and those in the given catalog; the transitions whose programs are
+/// not available, or do not declare their functions, are left as they are.
+pub fn attach_block_function_refs<N: Network>(block: &Block<N>, catalog: &mut ProgramCatalog<N>, j_block: &mut Value) {
+    catalog.insert_deployments(block);
+
+    // Collect the references, keyed by the ID of their transition.
+    let mut refs = HashMap::new();
+    for confirmed in block.transactions().iter() {
+        for transition in confirmed.transaction().transitions() {
+            if let Some(transition_refs) = transition_refs(catalog, transition) {
+                refs.insert(transition.id().to_string(), transition_refs);
+            }
+        }
+    }
+    attach_refs(j_block, &refs);
+}
+
+/// Adds the given references to the transitions found in the given value, and in its children.
+fn attach_refs(value: &mut Value, refs: &HashMap<String, TransitionRefs>) {
+    let transition_refs = match value.get("type").and_then(Value::as_str) {
+        Some("Transition") => value.get("id").and_then(Value::as_str).and_then(|id| refs.get(id)),
+        _ => None,
+    };
+    match (transition_refs, value) {
+        (Some(transition_refs), j_transition) => {
+            for (key, length) in [("inputs", transition_refs.num_inputs), ("outputs", transition_refs.num_outputs)] {
+                for index in 0..length {
+                    if let Some(j_value) = j_transition[key].get_mut(index) {
+                        j_value["declaration_node_id"] = format!("{}/{key}/{index}", transition_refs.function).into();
+                    }
+                }
+            }
+            j_transition["function_node_id"] = transition_refs.function.clone().into();
+        }
+        (None, Value::Object(object)) => object.values_mut().for_each(|val| attach_refs(val, refs)),
+        (None, Value::Array(array)) => array.iter_mut().for_each(|val| attach_refs(val, refs)),
+        (None, _) => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aleo2json::assign_node_ids,
+        console::network::prelude::FromBytes,
+        prelude::Testnet3,
+        synthesizer::Program,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_attach_block_function_refs() {
+        let block = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut catalog = ProgramCatalog::new(None).unwrap();
+        let mut j_block = block.to_json();
+        attach_block_function_refs(&block, &mut catalog, &mut j_block);
+
+        // The genesis block executes `credits.aleo`, so each of its transitions refers to a node of `credits.aleo`.
+        let credits = Program::<CurrentNetwork>::credits().unwrap();
+        let mut j_credits = credits.to_json();
+        assign_node_ids(&mut j_credits, &credits.id().to_string());
+        let transition = block.transactions().iter().flat_map(|tx| tx.transaction().transitions()).next().unwrap();
+        let node_id = function_node_id(transition.program_id(), transition.function_name());
+        assert_eq!(j_credits["functions"][transition.function_name().to_string()]["node_id"], node_id);
+        assert!(j_block.to_string().contains(&format!("\"function_node_id\":\"{node_id}\"")));
+        if !transition.inputs().is_empty() {
+            let pointer = format!("/functions/{}/inputs/0/node_id", transition.function_name());
+            assert_eq!(j_credits.pointer(&pointer).unwrap(), &Value::from(format!("{node_id}/inputs/0")));
+        }
+    }
+}
@@ -16,13 +16,17 @@
 #![allow(clippy::module_inception)]
 #![cfg_attr(test, allow(clippy::assertions_on_result_states))]
 
-#[cfg(feature = "cli")]
+#[cfg(feature = "aleo2json-console")]
 #[macro_use]
 extern crate thiserror;
 
+#[cfg(feature = "aleo2json-console")]
+pub mod aleo2json;
 #[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(all(feature = "ledger", feature = "synthesizer"))]
 pub mod file;
+#[cfg(all(feature = "ledger", feature = "synthesizer"))]
 pub mod package;
 
 #[cfg(feature = "algorithms")]